@@ -0,0 +1,321 @@
+# Descriptions of the commands and their options, which Discord shows in place of the English ones.
+# Each message is a command, with subcommands written as `command_subcommand`. Its `.description`
+# describes the command, and `.option-description` each option. A message value, or an `.option`
+# attribute, would rename the command or option too, but the names are kept in English as replies
+# and examples refer to them.
+
+## Locations
+
+add-location =
+    .description = Fügt einen Beobachtungsort hinzu.
+    .name-description = Name
+    .latitude-description = Breitengrad
+    .longitude-description = Längengrad
+    .altitude-description = Höhe in Metern
+list-locations =
+    .description = Listet alle Beobachtungsorte auf.
+remove-location =
+    .description = Entfernt einen Beobachtungsort.
+    .name-description = Name
+
+## Passes
+
+get-upcoming-passes =
+    .description = Zeigt alle kommenden Überflüge eines Satelliten.
+    .satellite_id-description = die NORAD-ID des Satelliten oder ein Alias dafür
+    .min_max_elevation-description = die Mindesthöhe der Überflüge (standardmäßig 20°, sofern nicht eingestellt)
+    .location-description = der Ort für die Überflüge, falls nicht dein Standardort
+    .days-description = die Anzahl der Tage im Voraus (max. 10, Standard 3)
+    .sort_by-description = wonach die Überflüge sortiert werden, beste zuerst (Standard Zeit)
+    .limit-description = die meisten aufzulistenden Überflüge (max. 25)
+    .lighting-description = ob nur Überflüge bei Tag oder bei Nacht aufgelistet werden
+    .kind-description = ob Funk- oder sichtbare Überflüge aufgelistet werden (Standard Funk)
+    .brighter_than-description = nur sichtbare Überflüge heller als diese Helligkeit, etwa 2
+    .detailed-description = ob jeder Überflug als Tabelle von Aufgang, Höhepunkt und Untergang erscheint
+    .raw-description = ob auch die Antwort von N2YO als JSON für eigene Skripte angehängt wird
+get-upcoming-noaa-passes =
+    .description = Zeigt die kommenden Überflüge der Wettersatelliten NOAA 15, 18 und 19 sowie Meteor-M2.
+    .min_max_elevation-description = die Mindesthöhe der Überflüge (standardmäßig 20°, sofern nicht eingestellt)
+    .location-description = der Ort für die Überflüge, falls nicht dein Standardort
+    .days-description = die Anzahl der Tage im Voraus (max. 10, Standard 3)
+    .sort_by-description = wonach die Überflüge sortiert werden, beste zuerst (Standard Zeit)
+    .limit-description = die meisten aufzulistenden Überflüge (max. 25)
+    .lighting-description = ob nur Überflüge bei Tag oder bei Nacht aufgelistet werden
+    .detailed-description = ob jeder Überflug als Tabelle von Aufgang, Höhepunkt und Untergang erscheint
+    .raw-description = ob auch die Antwort von N2YO als JSON für eigene Skripte angehängt wird
+get-upcoming-meteor-passes =
+    .description = Zeigt die kommenden Überflüge der Meteor-M-Satelliten mit der Frequenz ihrer LRPT-Bilder.
+    .min_max_elevation-description = die Mindesthöhe der Überflüge (standardmäßig 20°, sofern nicht eingestellt)
+    .location-description = der Ort für die Überflüge, falls nicht dein Standardort
+    .days-description = die Anzahl der Tage im Voraus (max. 10, Standard 3)
+    .sort_by-description = wonach die Überflüge sortiert werden, beste zuerst (Standard Zeit)
+    .limit-description = die meisten aufzulistenden Überflüge (max. 25)
+    .lighting-description = ob nur Überflüge bei Tag oder bei Nacht aufgelistet werden
+    .detailed-description = ob jeder Überflug als Tabelle von Aufgang, Höhepunkt und Untergang erscheint
+    .raw-description = ob auch die Antwort von N2YO als JSON für eigene Skripte angehängt wird
+get-upcoming-passes-multi =
+    .description = Zeigt die kommenden Überflüge mehrerer Satelliten, jeweils in einem eigenen Embed.
+    .satellites-description = durch Kommas getrennte NORAD-IDs oder ein Gruppenname wie noaa oder favorites
+    .min_max_elevation-description = die Mindesthöhe der Überflüge (standardmäßig 20°, sofern nicht eingestellt)
+    .location-description = der Ort für die Überflüge, falls nicht dein Standardort
+    .days-description = die Anzahl der Tage im Voraus (max. 10, Standard 3)
+    .sort_by-description = wonach die Überflüge sortiert werden, beste zuerst (Standard Zeit)
+    .limit-description = die meisten aufzulistenden Überflüge (max. 25)
+    .lighting-description = ob nur Überflüge bei Tag oder bei Nacht aufgelistet werden
+    .detailed-description = ob jeder Überflug als Tabelle von Aufgang, Höhepunkt und Untergang erscheint
+    .raw-description = ob auch die Antwort von N2YO als JSON für eigene Skripte angehängt wird
+best-pass =
+    .description = Findet den besten Überflug eines Satelliten in der nächsten Woche, mit Kalender-Button.
+    .satellite_id-description = die NORAD-ID des Satelliten oder ein Alias dafür
+    .location-description = der Ort für den Überflug, falls nicht dein Standardort
+    .by-description = was einen Überflug am besten macht (Standard Höhe)
+    .kind-description = ob nach Funk- oder sichtbaren Überflügen gesucht wird (Standard Funk)
+    .min_max_elevation-description = die Mindesthöhe der Überflüge (standardmäßig 20°, sofern nicht eingestellt)
+next-pass =
+    .description = Sagt, wann der nächste Überflug eines Satelliten beginnt, mit Countdown.
+    .satellite_id-description = die NORAD-ID des Satelliten oder ein Alias dafür
+    .location-description = der Ort für den Überflug, falls nicht dein Standardort
+    .min_max_elevation-description = die Mindesthöhe des Überflugs (standardmäßig 20°, sofern nicht eingestellt)
+pass-info =
+    .description = Ruft einen hier gemeldeten Überflug per ID ab, mit Teilnehmern und Beobachtungen.
+    .id-description = die ID des Überflugs aus der Fußzeile seiner Benachrichtigung
+plan-session =
+    .description = Plant eine Beobachtungssitzung mit Überflügen, die sich nicht überschneiden, beste zuerst.
+    .satellites-description = durch Kommas getrennte NORAD-IDs oder ein Gruppenname wie noaa oder favorites
+    .location-description = der Ort für die Sitzung, falls nicht dein Standardort
+    .starts_in-description = in wie vielen Stunden die Sitzung beginnt (Standard 0)
+    .hours-description = wie lange die Sitzung dauert, in Stunden (max. 72, Standard 12)
+    .min_max_elevation-description = die Mindesthöhe der Überflüge (standardmäßig 20°, sofern nicht eingestellt)
+    .kind-description = ob Funk- oder sichtbare Überflüge geplant werden (Standard Funk)
+    .file-description = eine Datei des Plans zum Anhängen
+compare-passes =
+    .description = Vergleicht die nächsten Überflüge eines Satelliten an zwei Orten nebeneinander.
+    .satellite_id-description = die NORAD-ID des Satelliten oder ein Alias dafür
+    .first_location-description = der erste zu vergleichende Ort
+    .second_location-description = der zweite zu vergleichende Ort
+    .count-description = wie viele Überflüge je Ort verglichen werden (max. 10, Standard 5)
+    .min_max_elevation-description = die Mindesthöhe der Überflüge (standardmäßig 20°, sofern nicht eingestellt)
+    .days-description = die Anzahl der Tage im Voraus (max. 10, Standard 3)
+export-rotator-schedule =
+    .description = Exportiert Azimut und Höhe eines Überflugs als CSV-Datei für Rotorsteuerungen.
+    .satellite_id-description = die NORAD-ID des Satelliten oder ein Alias dafür
+    .location-description = der Ort, von dem aus verfolgt wird, falls nicht dein Standardort
+    .pass-description = welcher kommende Überflug exportiert wird, 1 für den nächsten (Standard 1)
+    .step-description = die Sekunden zwischen den Zeilen, von 1 bis 60 (Standard 1)
+    .min_max_elevation-description = die Mindesthöhe der gezählten Überflüge (standardmäßig 20°)
+export-pass =
+    .description = Exportiert einen Überflug für Stationssoftware wie Gpredict oder rotctld von Hamlib.
+    .satellite_id-description = die NORAD-ID des Satelliten oder ein Alias dafür
+    .format-description = die Software, für die exportiert wird
+    .location-description = der Ort, von dem aus verfolgt wird, falls nicht dein Standardort
+    .pass-description = welcher kommende Überflug exportiert wird, 1 für den nächsten (Standard 1)
+    .min_max_elevation-description = die Mindesthöhe der gezählten Überflüge (standardmäßig 20°)
+starlink-train =
+    .description = Sagt voraus, wann kürzlich gestartete Starlink-Ketten von einem Ort aus sichtbar sind.
+    .location-description = der Ort für die Vorhersage, falls nicht dein Standardort
+    .days-description = wie viele Tage im Voraus gesucht wird (max. 7, Standard 3)
+
+## Watches
+
+watch-satellite =
+    .description = Beobachtet einen Satelliten und meldet passende Überflüge.
+    .satellite_id-description = die NORAD-ID des Satelliten oder ein Alias dafür
+    .min_max_elevation-description = die Mindesthöhe der gemeldeten Überflüge (standardmäßig 20°)
+    .location-description = der Ort für die Meldungen, falls nicht dein Standardort
+    .days_ahead-description = wie viele Tage im Voraus Überflüge gemeldet werden (max. 10, Standard 1)
+    .notify_within_hours-description = nur Überflüge melden, die innerhalb so vieler Stunden beginnen
+    .timezone-description = die Zeitzone des Beobachters, etwa Europe/Berlin, für zusätzliche Ortszeiten
+    .lighting-description = ob nur Überflüge bei Tag oder bei Nacht gemeldet werden
+    .min_quality-description = die niedrigste gemeldete Qualität von 0 bis 100, statt einer Höhe
+    .show_transmitter-description = ob der Hauptsender des Satelliten aus der SatNOGS DB angegeben wird
+    .kind-description = ob Funk- oder sichtbare Überflüge gemeldet werden (Standard Funk)
+    .brighter_than-description = nur sichtbare Überflüge heller als diese Helligkeit melden, etwa 2
+    .thread_per_pass-description = ob zu jeder Meldung ein Thread für Berichte zum Überflug erstellt wird
+    .title_template-description = eine Vorlage für den Titel der Meldungen, etwa {"{"}name{"}"} über {"{"}location{"}"}
+    .description_template-description = eine Vorlage für die Beschreibung der Meldungen, etwa {"{"}start{"}"}: {"{"}max_el{"}"}°
+    .tags-description = durch Kommas getrennte Tags wie wetter, um Beobachtungen aufzulisten oder zu pausieren
+list-watched-satellites =
+    .description = Listet alle beobachteten Satelliten auf, oder nur die mit einem Tag.
+    .tag-description = nur Beobachtungen mit diesem Tag auflisten
+unwatch-satellite =
+    .description = Entfernt einen beobachteten Satelliten.
+    .satellite_id-description = die NORAD-ID des Satelliten oder ein Alias dafür
+    .channel-description = der Kanal, in dem der Satellit beobachtet wird
+    .location-description = der Ort, von dem aus der Satellit beobachtet wird
+edit-watch =
+    .description = Bearbeitet einen beobachteten Satelliten und aktiviert ihn wieder, falls er deaktiviert war.
+    .satellite_id-description = die NORAD-ID des Satelliten oder ein Alias dafür
+    .location-description = der Ort, von dem aus der Satellit beobachtet wird
+    .channel-description = der Kanal, in dem der Satellit beobachtet wird, falls du mehrere hast
+    .new_channel-description = der Kanal, in den die Meldungen stattdessen gesendet werden
+    .min_max_elevation-description = die Mindesthöhe der gemeldeten Überflüge
+    .days_ahead-description = wie viele Tage im Voraus Überflüge gemeldet werden (max. 10)
+    .notify_within_hours-description = nur Überflüge melden, die innerhalb so vieler Stunden beginnen (0 aus)
+    .timezone-description = die Zeitzone des Beobachters, etwa Europe/Berlin (none zum Entfernen)
+    .lighting-description = ob nur Überflüge bei Tag oder bei Nacht gemeldet werden
+    .min_quality-description = die niedrigste gemeldete Qualität von 0 bis 100 (0 aus)
+    .show_transmitter-description = ob der Hauptsender des Satelliten aus der SatNOGS DB angegeben wird
+    .kind-description = ob Funk- oder sichtbare Überflüge gemeldet werden
+    .brighter_than-description = nur sichtbare Überflüge heller als diese Helligkeit melden
+    .thread_per_pass-description = ob zu jeder Meldung ein Thread für Berichte zum Überflug erstellt wird
+    .title_template-description = eine Vorlage für den Titel der Meldungen (none zum Entfernen)
+    .description_template-description = eine Vorlage für die Beschreibung der Meldungen (none zum Entfernen)
+    .tags-description = durch Kommas getrennte Tags, die die bisherigen ersetzen (none zum Entfernen)
+clone-watch =
+    .description = Kopiert eine deiner Beobachtungen mit allen Einstellungen in einen anderen Kanal oder Ort.
+    .satellite_id-description = die NORAD-ID des Satelliten oder ein Alias dafür
+    .location-description = der Ort, von dem aus der Satellit beobachtet wird
+    .channel-description = der Kanal, in dem der Satellit beobachtet wird, falls du mehrere hast
+    .new_channel-description = der Kanal für die Meldungen der Kopie, falls nicht dieser
+    .new_location-description = der Ort für die Meldungen der Kopie, falls nicht derselbe
+move-watch =
+    .description = Verschiebt die Beobachtungen eines Kanals in einen anderen, etwa beim Archivieren.
+    .from-description = der Kanal, aus dem Beobachtungen verschoben werden
+    .to-description = der Kanal, in den sie verschoben werden
+mute-notifications =
+    .description = Hält alle Beobachtungsmeldungen in diesem Kanal eine Weile zurück.
+    .duration-description = wie lange stummgeschaltet wird, etwa 90m, 12h oder 3d, oder off zum Aufheben
+vacation =
+    .description = Pausiert alle deine Beobachtungen, während du weg bist, und setzt sie danach fort.
+    .until-description = das Datum der Fortsetzung als JJJJ-MM-TT in UTC, eine Dauer wie 2w, oder off
+pause-watch =
+    .description = Pausiert die Meldungen all deiner Beobachtungen mit einem Tag, bis sie fortgesetzt werden.
+    .tag-description = das Tag der zu pausierenden Beobachtungen
+resume-watch =
+    .description = Setzt die Meldungen all deiner Beobachtungen mit einem Tag fort.
+    .tag-description = das Tag der fortzusetzenden Beobachtungen
+update-watched-satellites =
+    .description = Aktualisiert die beobachteten Satelliten.
+
+## Settings
+
+settings =
+    .description = Zeigt und ändert deine persönlichen Einstellungen.
+settings_show =
+    .description = Zeigt deine aktuellen Einstellungen.
+settings_default-location =
+    .description = Legt den Ort fest, der verwendet wird, wenn ein Befehl keinen erhält.
+    .location-description = der standardmäßig verwendete Ort
+settings_units =
+    .description = Legt die Einheiten fest, in denen Längen wie Höhen angegeben werden.
+    .units-description = die Einheiten, oder keine, um deiner Discord-Sprache zu folgen
+settings_time-format =
+    .description = Legt fest, wie Zeiten und Daten geschrieben werden, wo Discord sie nicht anzeigen kann.
+    .clock-description = ob eine 12- oder 24-Stunden-Uhr verwendet wird
+    .date-description = die Reihenfolge, in der Daten geschrieben werden
+settings_ephemeral =
+    .description = Legt fest, ob Abfragen wie kommende Überflüge nur dir angezeigt werden.
+    .enabled-description = ob nur du die Antworten siehst
+    .category-description = die Befehlskategorie, für die es gilt, falls nicht für alle Befehle
+settings_direct-messages =
+    .description = Legt fest, ob der Bot dir Direktnachrichten senden darf, etwa wenn eine Beobachtung endet.
+    .enabled-description = ob der Bot dir schreiben darf
+settings_reset =
+    .description = Setzt alle deine Einstellungen zurück und behält deine Lieblingssatelliten.
+server-settings =
+    .description = Zeigt und ändert die Einstellungen für den ganzen Server.
+server-settings_show =
+    .description = Zeigt die aktuellen Einstellungen des Servers.
+server-settings_default-elevation =
+    .description = Legt die Mindesthöhe fest, die verwendet wird, wenn ein Befehl keine erhält.
+    .elevation-description = die Mindesthöhe, zwischen 0 und 90
+server-settings_max-watches =
+    .description = Legt fest, wie viele Beobachtungen jedes Mitglied auf dem Server haben kann.
+    .limit-description = die meisten Beobachtungen pro Mitglied, zwischen 1 und 1000
+server-settings_cooldowns =
+    .description = Legt fest, wie lange zwischen Befehlen gewartet wird, die Überflüge von N2YO abfragen.
+    .user_seconds-description = die Sekunden, die jedes Mitglied zwischen Befehlen wartet, bis 3600
+    .channel_seconds-description = die Sekunden, die jeder Kanal zwischen Befehlen wartet, bis 3600
+server-settings_admin-role =
+    .description = Erlaubt oder verbietet Mitgliedern mit einer Rolle die Admin-Befehle, wie diesen.
+    .role-description = die zu erlaubende oder zu verbietende Rolle
+    .allowed-description = ob Mitglieder mit der Rolle die Admin-Befehle verwenden können
+
+## Conditions
+
+space-weather =
+    .description = Zeigt die geomagnetischen Bedingungen und die Chance, Polarlichter zu sehen.
+    .location-description = der Ort für die Polarlichtvorhersage, falls nicht dein Standardort
+solar =
+    .description = Zeigt die Sonnenindizes, mit denen die Funkausbreitung beurteilt wird.
+moon =
+    .description = Zeigt die Mondphase, wo der Mond am Himmel steht und wann er als Nächstes auf- und untergeht.
+    .location-description = der Ort, von dem aus der Mond gezeigt wird, falls nicht dein Standardort
+
+## Satellites
+
+frequencies =
+    .description = Listet die Funkfrequenzen eines häufig gehörten Satelliten auf.
+    .satellite_id-description = die NORAD-ID des Satelliten oder ein Alias dafür
+transmitters =
+    .description = Listet die aktiven Sender eines beliebigen Satelliten aus der SatNOGS DB auf.
+    .satellite_id-description = die NORAD-ID des Satelliten oder ein Alias dafür
+geo-pointing =
+    .description = Zeigt, wohin eine Antenne für einen geostationären Satelliten wie QO-100 oder GOES zeigt.
+    .satellite_id-description = die NORAD-ID des Satelliten oder ein Alias dafür
+    .location-description = der Ort, von dem aus gezielt wird, falls nicht dein Standardort
+satellite-info =
+    .description = Zeigt, was über einen Satelliten bekannt ist: Start, Umlaufbahn und Alter seines TLE.
+    .satellite_id-description = die NORAD-ID des Satelliten oder ein Alias dafür
+satcat-search =
+    .description = Durchsucht den CelesTrak-Katalog aller verfolgten Objekte nach Name, NORAD-ID oder Kennung.
+    .query-description = ein Teil eines Namens, eine NORAD-ID oder der Anfang einer internationalen Kennung
+    .launch_year-description = nur Objekte, die in diesem Jahr gestartet sind
+    .owner-description = nur Objekte dieses Landes oder dieser Organisation, etwa US, PRC oder CIS
+    .object_type-description = nur Objekte dieses Typs
+recent-observations =
+    .description = Listet die neuesten guten Beobachtungen eines Satelliten im SatNOGS Network auf.
+    .satellite_id-description = die NORAD-ID des Satelliten oder ein Alias dafür
+upcoming-launches =
+    .description = Listet die nächsten Starts aus Launch Library 2 auf.
+    .category-description = die Art der aufzulistenden Starts (Standard alle)
+launch-alerts =
+    .description = Postet in diesem Kanal, wenn Starts geplant oder verschoben werden.
+launch-alerts_subscribe =
+    .description = Postet in diesem Kanal, wenn Starts einer Art geplant oder verschoben werden.
+    .category-description = die Art der Starts, über die gepostet wird
+launch-alerts_unsubscribe =
+    .description = Beendet das Posten über Starts in diesem Kanal.
+favorite-satellite =
+    .description = Führt eine Auswahlliste deiner Lieblingssatelliten.
+favorite-satellite_add =
+    .description = Fügt einen Satelliten zu deinen Favoriten hinzu.
+    .satellite_id-description = die NORAD-ID des Satelliten oder ein Alias dafür
+favorite-satellite_remove =
+    .description = Entfernt einen Satelliten aus deinen Favoriten.
+    .satellite_id-description = die NORAD-ID des Satelliten oder ein Alias dafür
+favorite-satellite_list =
+    .description = Listet deine Lieblingssatelliten auf.
+satellite-alias =
+    .description = Benennt Satelliten oder Gruppen davon für den ganzen Server.
+satellite-alias_set =
+    .description = Benennt einen Satelliten oder eine Gruppe und ersetzt einen Alias gleichen Namens.
+    .name-description = der Alias, der keine Zahl sein und keine Kommas enthalten darf
+    .satellites-description = durch Kommas getrennte NORAD-IDs oder ein Gruppenname wie noaa
+satellite-alias_remove =
+    .description = Entfernt einen Alias.
+    .name-description = der zu entfernende Alias
+satellite-alias_list =
+    .description = Listet die Aliase des Servers auf.
+
+## Observations
+
+log-observation =
+    .description = Hält fest, dass du einen Überflug empfangen oder gesehen hast, optional mit Notizen und Bild.
+    .satellite_id-description = die NORAD-ID des Satelliten oder ein Alias dafür
+    .pass-description = der beobachtete Überflug, falls nicht der zuletzt hier gemeldete
+    .notes-description = Notizen zur Beobachtung, etwa Signalqualität oder Ausrüstung
+    .image-description = ein Bild der Beobachtung, etwa ein dekodiertes Bild
+gallery =
+    .description = Blättert durch die Bilder der Beobachtungen auf diesem Server, neueste zuerst.
+    .satellite_id-description = die NORAD-ID des Satelliten, dessen Beobachtungen gezeigt werden, oder ein Alias
+    .location-description = der Ort, dessen Beobachtungen gezeigt werden
+
+## Other
+
+stats =
+    .description = Zeigt Nutzungsstatistiken des Bots.
+sync-commands =
+    .description = Registriert die Befehle des Bots erneut bei Discord, etwa wenn welche fehlen.
+help =
+    .description = Listet alle Befehle auf, mit Details zu ihrer Verwendung.
@@ -0,0 +1,321 @@
+# Descriptions of the commands and their options, which Discord shows in place of the English ones.
+# Each message is a command, with subcommands written as `command_subcommand`. Its `.description`
+# describes the command, and `.option-description` each option. A message value, or an `.option`
+# attribute, would rename the command or option too, but the names are kept in English as replies
+# and examples refer to them.
+
+## Locations
+
+add-location =
+    .description = Ajoute un lieu d'observation.
+    .name-description = nom
+    .latitude-description = latitude
+    .longitude-description = longitude
+    .altitude-description = altitude en mètres
+list-locations =
+    .description = Liste tous les lieux d'observation.
+remove-location =
+    .description = Supprime un lieu d'observation.
+    .name-description = nom
+
+## Passes
+
+get-upcoming-passes =
+    .description = Donne tous les prochains passages d'un satellite.
+    .satellite_id-description = l'identifiant NORAD du satellite, ou un alias
+    .min_max_elevation-description = l'élévation minimale des passages (20° par défaut sauf configuration)
+    .location-description = le lieu des passages, s'il ne s'agit pas de votre lieu par défaut
+    .days-description = le nombre de jours à venir (10 au maximum, 3 par défaut)
+    .sort_by-description = le critère de tri des passages, meilleurs en premier (heure par défaut)
+    .limit-description = le nombre maximal de passages à lister (25 au maximum)
+    .lighting-description = s'il faut lister uniquement les passages de jour ou de nuit
+    .kind-description = s'il faut lister les passages radio ou visibles (radio par défaut)
+    .brighter_than-description = uniquement les passages visibles plus brillants que cette magnitude, comme 2
+    .detailed-description = s'il faut présenter chaque passage en tableau de lever, culmination et coucher
+    .raw-description = s'il faut joindre la réponse de N2YO en JSON, pour vos propres scripts
+get-upcoming-noaa-passes =
+    .description = Donne les prochains passages des satellites météo : NOAA 15, 18 et 19, et Meteor-M2.
+    .min_max_elevation-description = l'élévation minimale des passages (20° par défaut sauf configuration)
+    .location-description = le lieu des passages, s'il ne s'agit pas de votre lieu par défaut
+    .days-description = le nombre de jours à venir (10 au maximum, 3 par défaut)
+    .sort_by-description = le critère de tri des passages, meilleurs en premier (heure par défaut)
+    .limit-description = le nombre maximal de passages à lister (25 au maximum)
+    .lighting-description = s'il faut lister uniquement les passages de jour ou de nuit
+    .detailed-description = s'il faut présenter chaque passage en tableau de lever, culmination et coucher
+    .raw-description = s'il faut joindre la réponse de N2YO en JSON, pour vos propres scripts
+get-upcoming-meteor-passes =
+    .description = Donne les prochains passages des satellites Meteor-M, avec la fréquence de leurs images LRPT.
+    .min_max_elevation-description = l'élévation minimale des passages (20° par défaut sauf configuration)
+    .location-description = le lieu des passages, s'il ne s'agit pas de votre lieu par défaut
+    .days-description = le nombre de jours à venir (10 au maximum, 3 par défaut)
+    .sort_by-description = le critère de tri des passages, meilleurs en premier (heure par défaut)
+    .limit-description = le nombre maximal de passages à lister (25 au maximum)
+    .lighting-description = s'il faut lister uniquement les passages de jour ou de nuit
+    .detailed-description = s'il faut présenter chaque passage en tableau de lever, culmination et coucher
+    .raw-description = s'il faut joindre la réponse de N2YO en JSON, pour vos propres scripts
+get-upcoming-passes-multi =
+    .description = Donne les prochains passages de plusieurs satellites, un embed chacun.
+    .satellites-description = des identifiants NORAD séparés par des virgules, ou un groupe comme noaa ou favorites
+    .min_max_elevation-description = l'élévation minimale des passages (20° par défaut sauf configuration)
+    .location-description = le lieu des passages, s'il ne s'agit pas de votre lieu par défaut
+    .days-description = le nombre de jours à venir (10 au maximum, 3 par défaut)
+    .sort_by-description = le critère de tri des passages, meilleurs en premier (heure par défaut)
+    .limit-description = le nombre maximal de passages à lister (25 au maximum)
+    .lighting-description = s'il faut lister uniquement les passages de jour ou de nuit
+    .detailed-description = s'il faut présenter chaque passage en tableau de lever, culmination et coucher
+    .raw-description = s'il faut joindre la réponse de N2YO en JSON, pour vos propres scripts
+best-pass =
+    .description = Trouve le meilleur passage d'un satellite dans la semaine, avec un bouton pour l'agenda.
+    .satellite_id-description = l'identifiant NORAD du satellite, ou un alias
+    .location-description = le lieu du passage, s'il ne s'agit pas de votre lieu par défaut
+    .by-description = ce qui fait le meilleur passage (élévation par défaut)
+    .kind-description = s'il faut chercher des passages radio ou visibles (radio par défaut)
+    .min_max_elevation-description = l'élévation minimale des passages (20° par défaut sauf configuration)
+next-pass =
+    .description = Indique quand commence le prochain passage d'un satellite, avec un compte à rebours.
+    .satellite_id-description = l'identifiant NORAD du satellite, ou un alias
+    .location-description = le lieu du passage, s'il ne s'agit pas de votre lieu par défaut
+    .min_max_elevation-description = l'élévation minimale du passage (20° par défaut sauf configuration)
+pass-info =
+    .description = Retrouve un passage notifié ici par son identifiant, avec les participants et observations.
+    .id-description = l'identifiant du passage, en pied de sa notification
+plan-session =
+    .description = Planifie une séance d'observation avec des passages qui ne se chevauchent pas.
+    .satellites-description = des identifiants NORAD séparés par des virgules, ou un groupe comme noaa ou favorites
+    .location-description = le lieu de la séance, s'il ne s'agit pas de votre lieu par défaut
+    .starts_in-description = dans combien d'heures la séance commence (0 par défaut)
+    .hours-description = la durée de la séance, en heures (72 au maximum, 12 par défaut)
+    .min_max_elevation-description = l'élévation minimale des passages (20° par défaut sauf configuration)
+    .kind-description = s'il faut planifier des passages radio ou visibles (radio par défaut)
+    .file-description = un fichier du plan à joindre
+compare-passes =
+    .description = Compare côte à côte les prochains passages d'un satellite en deux lieux.
+    .satellite_id-description = l'identifiant NORAD du satellite, ou un alias
+    .first_location-description = le premier lieu à comparer
+    .second_location-description = le second lieu à comparer
+    .count-description = combien de passages comparer en chaque lieu (10 au maximum, 5 par défaut)
+    .min_max_elevation-description = l'élévation minimale des passages (20° par défaut sauf configuration)
+    .days-description = le nombre de jours à venir (10 au maximum, 3 par défaut)
+export-rotator-schedule =
+    .description = Exporte l'azimut et l'élévation d'un passage en CSV, pour les contrôleurs de rotor.
+    .satellite_id-description = l'identifiant NORAD du satellite, ou un alias
+    .location-description = le lieu de suivi, s'il ne s'agit pas de votre lieu par défaut
+    .pass-description = le passage à venir à exporter, 1 pour le prochain (1 par défaut)
+    .step-description = les secondes entre chaque ligne, de 1 à 60 (1 par défaut)
+    .min_max_elevation-description = l'élévation minimale des passages comptés (20° par défaut)
+export-pass =
+    .description = Exporte un passage pour des logiciels de station comme Gpredict ou rotctld de Hamlib.
+    .satellite_id-description = l'identifiant NORAD du satellite, ou un alias
+    .format-description = le logiciel pour lequel exporter
+    .location-description = le lieu de suivi, s'il ne s'agit pas de votre lieu par défaut
+    .pass-description = le passage à venir à exporter, 1 pour le prochain (1 par défaut)
+    .min_max_elevation-description = l'élévation minimale des passages comptés (20° par défaut)
+starlink-train =
+    .description = Prédit quand les trains Starlink récemment lancés sont visibles depuis un lieu.
+    .location-description = le lieu de la prédiction, s'il ne s'agit pas de votre lieu par défaut
+    .days-description = combien de jours à l'avance chercher (7 au maximum, 3 par défaut)
+
+## Watches
+
+watch-satellite =
+    .description = Suit un satellite, en envoyant une notification pour chaque passage adapté.
+    .satellite_id-description = l'identifiant NORAD du satellite, ou un alias
+    .min_max_elevation-description = l'élévation minimale des passages notifiés (20° par défaut)
+    .location-description = le lieu des notifications, s'il ne s'agit pas de votre lieu par défaut
+    .days_ahead-description = combien de jours à l'avance notifier les passages (10 au maximum, 1 par défaut)
+    .notify_within_hours-description = notifier uniquement les passages commençant dans ce nombre d'heures
+    .timezone-description = le fuseau horaire de l'observateur, comme Europe/Paris, pour les heures locales
+    .lighting-description = s'il faut notifier uniquement les passages de jour ou de nuit
+    .min_quality-description = la qualité minimale des passages notifiés, de 0 à 100, au lieu d'une élévation
+    .show_transmitter-description = s'il faut indiquer l'émetteur principal du satellite depuis SatNOGS DB
+    .kind-description = s'il faut notifier les passages radio ou visibles (radio par défaut)
+    .brighter_than-description = notifier uniquement les passages visibles plus brillants que cette magnitude
+    .thread_per_pass-description = s'il faut ouvrir un fil sur chaque notification pour les comptes rendus
+    .title_template-description = un modèle pour le titre des notifications, comme {"{"}name{"}"} sur {"{"}location{"}"}
+    .description_template-description = un modèle pour la description des notifications, comme {"{"}start{"}"} : {"{"}max_el{"}"}°
+    .tags-description = des étiquettes séparées par des virgules, comme meteo, pour lister ou suspendre
+list-watched-satellites =
+    .description = Liste tous les satellites suivis, ou seulement ceux ayant une étiquette.
+    .tag-description = lister uniquement les suivis ayant cette étiquette
+unwatch-satellite =
+    .description = Supprime un satellite suivi.
+    .satellite_id-description = l'identifiant NORAD du satellite, ou un alias
+    .channel-description = le salon où le satellite est suivi
+    .location-description = le lieu depuis lequel le satellite est suivi
+edit-watch =
+    .description = Modifie un satellite suivi, en le réactivant s'il était désactivé.
+    .satellite_id-description = l'identifiant NORAD du satellite, ou un alias
+    .location-description = le lieu depuis lequel le satellite est suivi
+    .channel-description = le salon où le satellite est suivi, si vous en avez plusieurs
+    .new_channel-description = le salon où envoyer les notifications à la place
+    .min_max_elevation-description = l'élévation minimale des passages notifiés
+    .days_ahead-description = combien de jours à l'avance notifier les passages (10 au maximum)
+    .notify_within_hours-description = notifier uniquement les passages commençant dans ce nombre d'heures (0 désactive)
+    .timezone-description = le fuseau horaire de l'observateur, comme Europe/Paris (none pour le retirer)
+    .lighting-description = s'il faut notifier uniquement les passages de jour ou de nuit
+    .min_quality-description = la qualité minimale des passages notifiés, de 0 à 100 (0 désactive)
+    .show_transmitter-description = s'il faut indiquer l'émetteur principal du satellite depuis SatNOGS DB
+    .kind-description = s'il faut notifier les passages radio ou visibles
+    .brighter_than-description = notifier uniquement les passages visibles plus brillants que cette magnitude
+    .thread_per_pass-description = s'il faut ouvrir un fil sur chaque notification pour les comptes rendus
+    .title_template-description = un modèle pour le titre des notifications (none pour le retirer)
+    .description_template-description = un modèle pour la description des notifications (none pour le retirer)
+    .tags-description = des étiquettes séparées par des virgules, remplaçant les actuelles (none pour les retirer)
+clone-watch =
+    .description = Copie l'un de vos suivis, avec tous ses réglages, vers un autre salon ou lieu.
+    .satellite_id-description = l'identifiant NORAD du satellite, ou un alias
+    .location-description = le lieu depuis lequel le satellite est suivi
+    .channel-description = le salon où le satellite est suivi, si vous en avez plusieurs
+    .new_channel-description = le salon des notifications de la copie, si ce n'est pas celui-ci
+    .new_location-description = le lieu des notifications de la copie, si ce n'est pas le même
+move-watch =
+    .description = Déplace les suivis d'un salon vers un autre, par exemple quand un salon est archivé.
+    .from-description = le salon d'où déplacer les suivis
+    .to-description = le salon où les déplacer
+mute-notifications =
+    .description = Retient toutes les notifications de suivi de ce salon pendant un temps.
+    .duration-description = la durée de la mise en sourdine, comme 90m, 12h ou 3d, ou off pour la lever
+vacation =
+    .description = Suspend tous vos suivis pendant votre absence, puis les reprend automatiquement.
+    .until-description = la date de reprise au format AAAA-MM-JJ en UTC, une durée comme 2w, ou off
+pause-watch =
+    .description = Suspend les notifications de tous vos suivis ayant une étiquette, jusqu'à leur reprise.
+    .tag-description = l'étiquette des suivis à suspendre
+resume-watch =
+    .description = Reprend les notifications de tous vos suivis ayant une étiquette.
+    .tag-description = l'étiquette des suivis à reprendre
+update-watched-satellites =
+    .description = Met à jour les satellites suivis.
+
+## Settings
+
+settings =
+    .description = Affiche et modifie vos paramètres personnels.
+settings_show =
+    .description = Affiche vos paramètres actuels.
+settings_default-location =
+    .description = Définit le lieu utilisé quand une commande n'en reçoit pas.
+    .location-description = le lieu à utiliser par défaut
+settings_units =
+    .description = Définit les unités des longueurs, comme les altitudes.
+    .units-description = les unités à utiliser, ou aucune pour suivre votre langue Discord
+settings_time-format =
+    .description = Définit l'écriture des heures et dates là où Discord ne peut pas les afficher.
+    .clock-description = s'il faut utiliser une horloge de 12 ou 24 heures
+    .date-description = l'ordre d'écriture des dates
+settings_ephemeral =
+    .description = Définit si les recherches, comme les prochains passages, ne sont visibles que par vous.
+    .enabled-description = si vous seul voyez les réponses
+    .category-description = la catégorie de commandes concernée, si ce ne sont pas toutes les commandes
+settings_direct-messages =
+    .description = Définit si le bot peut vous écrire en privé, par exemple quand un suivi est désactivé.
+    .enabled-description = si le bot peut vous écrire
+settings_reset =
+    .description = Réinitialise tous vos paramètres, en gardant vos satellites favoris.
+server-settings =
+    .description = Affiche et modifie les paramètres de tout le serveur.
+server-settings_show =
+    .description = Affiche les paramètres actuels du serveur.
+server-settings_default-elevation =
+    .description = Définit l'élévation minimale utilisée quand une commande n'en reçoit pas.
+    .elevation-description = l'élévation minimale, entre 0 et 90
+server-settings_max-watches =
+    .description = Définit le nombre maximal de suivis par membre sur le serveur.
+    .limit-description = le nombre maximal de suivis par membre, entre 1 et 1000
+server-settings_cooldowns =
+    .description = Définit l'attente entre les commandes qui demandent des passages à N2YO.
+    .user_seconds-description = les secondes d'attente de chaque membre entre deux commandes, jusqu'à 3600
+    .channel_seconds-description = les secondes d'attente de chaque salon entre deux commandes, jusqu'à 3600
+server-settings_admin-role =
+    .description = Autorise ou non les membres ayant un rôle à utiliser les commandes d'administration.
+    .role-description = le rôle à autoriser ou non
+    .allowed-description = si les membres ayant le rôle peuvent utiliser les commandes d'administration
+
+## Conditions
+
+space-weather =
+    .description = Affiche les conditions géomagnétiques et la probabilité de voir une aurore.
+    .location-description = le lieu de la prévision d'aurore, s'il ne s'agit pas de votre lieu par défaut
+solar =
+    .description = Affiche les indices solaires utilisés pour juger la propagation radio.
+moon =
+    .description = Affiche la phase de la Lune, sa position dans le ciel, et ses prochains lever et coucher.
+    .location-description = le lieu d'où voir la Lune, s'il ne s'agit pas de votre lieu par défaut
+
+## Satellites
+
+frequencies =
+    .description = Liste les fréquences radio d'un satellite couramment écouté.
+    .satellite_id-description = l'identifiant NORAD du satellite, ou un alias
+transmitters =
+    .description = Liste les émetteurs actifs de n'importe quel satellite, depuis SatNOGS DB.
+    .satellite_id-description = l'identifiant NORAD du satellite, ou un alias
+geo-pointing =
+    .description = Indique où pointer une antenne vers un satellite géostationnaire, comme QO-100 ou GOES.
+    .satellite_id-description = l'identifiant NORAD du satellite, ou un alias
+    .location-description = le lieu de pointage, s'il ne s'agit pas de votre lieu par défaut
+satellite-info =
+    .description = Affiche ce que l'on sait d'un satellite : son lancement, son orbite et l'âge de son TLE.
+    .satellite_id-description = l'identifiant NORAD du satellite, ou un alias
+satcat-search =
+    .description = Cherche dans le catalogue CelesTrak par nom, identifiant NORAD ou désignation.
+    .query-description = une partie d'un nom, un identifiant NORAD, ou le début d'une désignation internationale
+    .launch_year-description = uniquement les objets lancés cette année-là
+    .owner-description = uniquement les objets de ce pays ou de cette organisation, comme US, PRC ou CIS
+    .object_type-description = uniquement les objets de ce type
+recent-observations =
+    .description = Liste les dernières bonnes observations d'un satellite sur SatNOGS Network.
+    .satellite_id-description = l'identifiant NORAD du satellite, ou un alias
+upcoming-launches =
+    .description = Liste les prochains lancements, depuis Launch Library 2.
+    .category-description = le type de lancement à lister (tous par défaut)
+launch-alerts =
+    .description = Publie dans ce salon quand des lancements sont programmés ou reportés.
+launch-alerts_subscribe =
+    .description = Publie dans ce salon quand des lancements d'un type sont programmés ou reportés.
+    .category-description = le type de lancement à suivre
+launch-alerts_unsubscribe =
+    .description = Arrête de publier au sujet des lancements dans ce salon.
+favorite-satellite =
+    .description = Tient une courte liste de vos satellites favoris.
+favorite-satellite_add =
+    .description = Ajoute un satellite à vos favoris.
+    .satellite_id-description = l'identifiant NORAD du satellite, ou un alias
+favorite-satellite_remove =
+    .description = Retire un satellite de vos favoris.
+    .satellite_id-description = l'identifiant NORAD du satellite, ou un alias
+favorite-satellite_list =
+    .description = Liste vos satellites favoris.
+satellite-alias =
+    .description = Nomme des satellites, ou des groupes de satellites, pour tout le serveur.
+satellite-alias_set =
+    .description = Nomme un satellite ou un groupe, en remplaçant tout alias du même nom.
+    .name-description = l'alias, qui ne peut pas être un nombre ni contenir de virgules
+    .satellites-description = des identifiants NORAD séparés par des virgules, ou un groupe comme noaa
+satellite-alias_remove =
+    .description = Supprime un alias.
+    .name-description = l'alias à supprimer
+satellite-alias_list =
+    .description = Liste les alias du serveur.
+
+## Observations
+
+log-observation =
+    .description = Consigne un passage reçu ou vu, avec des notes et une image si vous le souhaitez.
+    .satellite_id-description = l'identifiant NORAD du satellite, ou un alias
+    .pass-description = le passage observé, si ce n'est pas le dernier notifié ici
+    .notes-description = des notes sur l'observation, comme la qualité du signal ou le matériel
+    .image-description = une image de l'observation, comme une image décodée
+gallery =
+    .description = Parcourt les images des observations consignées sur ce serveur, les plus récentes d'abord.
+    .satellite_id-description = l'identifiant NORAD du satellite dont montrer les observations, ou un alias
+    .location-description = le lieu dont montrer les observations
+
+## Other
+
+stats =
+    .description = Affiche les statistiques d'utilisation du bot.
+sync-commands =
+    .description = Enregistre de nouveau les commandes du bot auprès de Discord, par exemple s'il en manque.
+help =
+    .description = Liste toutes les commandes, avec le détail de leur utilisation.
@@ -0,0 +1,401 @@
+## Errors
+
+error-title = Ein Fehler ist aufgetreten
+error-n2yo = Daten von N2YO sind gerade nicht verfügbar, bitte versuche es später erneut ({ $details })
+error-database = Beim Zugriff auf gespeicherte Daten ist ein Fehler aufgetreten, bitte informiere den Betreiber des Bots
+error-discord = Discord hat die Anfrage abgelehnt: { $details }
+error-unknown = Fehler: { $details }
+error-days-range = { $parameter } muss zwischen 1 und 10 liegen
+error-elevation-range = min_max_elevation muss zwischen 0 und 90 liegen
+error-quality-range = min_quality muss zwischen 0 und 100 liegen
+error-count-range = { $parameter } muss zwischen 1 und { $max } liegen
+error-no-such-location = Diesen Standort gibt es nicht
+error-no-such-satellite = Es gibt keinen Satelliten mit der NORAD-ID { $satellite_id }
+error-no-such-pass = So weit im Voraus gibt es keinen Überflug. Versuche einen früheren oder eine niedrigere Elevation.
+error-step-range = Der Schritt muss zwischen 1 und { $max } Sekunden liegen.
+error-unknown-frequencies = Die Frequenzen dieses Satelliten kenne ich nicht. Versuche `/transmitters` für andere.
+error-no-transmitters = SatNOGS DB führt keine aktiven Sender auf diesem Satelliten.
+error-no-observations = SatNOGS Network hat keine aktuellen guten Beobachtungen dieses Satelliten.
+error-no-launches = Es sind keine bevorstehenden Starts dieser Art gelistet.
+error-not-subscribed-to-launches = Dieser Kanal hat keine Startmeldungen abonniert.
+error-no-starlink-trains = Es wurden zuletzt keine Starlink-Satelliten gestartet, die noch einen Zug bilden.
+error-unknown-pass = Dieser Überflug kann nicht mehr verfolgt werden.
+error-pass-over = Dieser Überflug ist bereits vorbei.
+error-pass-too-early = Die Live-Verfolgung kann bis zu { $minutes } Minuten vor dem Überflug beginnen.
+error-already-tracking = Dieser Überflug wird in diesem Kanal bereits verfolgt.
+error-tracking-unavailable = Dieser Überflug kann gerade nicht verfolgt werden, bitte versuche es später erneut.
+error-invalid-satellite = `{ $satellite }` ist weder eine NORAD-ID noch eine Gruppe. Gruppen: { $groups }.
+error-no-satellites = Gib mindestens eine NORAD-ID oder einen Gruppennamen an.
+error-too-many-satellites = Gib höchstens { $max } Satelliten auf einmal an.
+error-no-favorites = Du hast keine Lieblingssatelliten. Füge welche mit `/favorite-satellite add` hinzu.
+error-too-many-favorites = Du kannst höchstens { $max } Lieblingssatelliten haben.
+error-invalid-satellite-id = `{ $satellite }` ist weder eine NORAD-ID noch ein Alias auf diesem Server.
+error-alias-is-group = `{ $alias }` steht für mehrere Satelliten. Gib eine NORAD-ID an.
+error-invalid-alias = Aliase müssen 1 bis { $max } Zeichen lang sein, dürfen keine Zahl sein und keine Kommas enthalten.
+error-reserved-alias = `{ $alias }` ist bereits der Name einer Gruppe.
+error-not-geostationary = { $satellite } ist nicht geostationär und bewegt sich über den Himmel. Versuche stattdessen `/get-upcoming-passes`.
+error-unknown-satellite = Weder N2YO noch CelesTrak kennen einen Satelliten mit dieser NORAD-ID.
+error-satcat-not-loaded = Der SATCAT wird noch heruntergeladen. Versuche es in einer Minute erneut.
+error-no-satcat-results = Nichts im SATCAT passt zu dieser Suche.
+error-too-many-aliases = Ein Server kann höchstens { $max } Aliase haben.
+error-no-such-alias = Es gibt keinen Alias mit diesem Namen.
+error-no-aliases = Dieser Server hat keine Aliase. Füge welche mit `/satellite-alias set` hinzu.
+error-already-favorite = Dieser Satellit ist bereits einer deiner Favoriten.
+error-not-favorite = Dieser Satellit ist keiner deiner Favoriten.
+error-empty-session = Keiner dieser Satelliten fliegt während der Sitzung hoch genug über.
+error-session-too-late = Die Sitzung muss innerhalb von 10 Tagen enden.
+error-notes-too-long = Notizen dürfen höchstens { $max } Zeichen lang sein.
+error-not-an-image = Der Anhang muss ein Bild sein.
+error-unknown-pass-time = Über diesen Überflug wurde in diesem Kanal nicht benachrichtigt. Wähle einen aus der Liste oder lass es weg.
+error-unknown-pass-id = Über keinen Überflug mit dieser ID wurde hier benachrichtigt. Überflug-IDs stehen in der Fußzeile der Benachrichtigungen und werden { $days } Tage nach dem Überflug aufbewahrt.
+error-template-too-long = Vorlagen dürfen höchstens { $max } Zeichen lang sein.
+error-template-braces = Diese Vorlage enthält eine nicht geschlossene Klammer. Schreibe Platzhalter wie { $placeholders } in einfachen Klammern und Klammern selbst als {"{{"} und {"}}"}.
+error-unknown-placeholder = Es gibt keinen Platzhalter {"{"}{ $placeholder }{"}"}. Vorlagen können { $placeholders } verwenden.
+error-empty-gallery = Hier wurden noch keine Beobachtungen mit Bildern eingetragen. Nutze `/log-observation`, um eine hinzuzufügen.
+error-no-location-given = kein Standort angegeben und kein Standardstandort festgelegt, siehe `/settings default-location`
+error-location-exists = Dieser Standort existiert bereits
+error-location-not-creator = Der Standort kann nur von seinem Ersteller entfernt werden
+error-already-watched = Dieser Satellit wird in diesem Kanal bereits mit diesen Parametern beobachtet
+error-no-such-watch = Diese Beobachtung gibt es nicht
+error-no-such-watch-of-yours = Du hast keine solche Beobachtung
+error-invalid-tag = Tags dürfen nicht leer oder länger als { $max } Zeichen sein.
+error-too-many-tags = Eine Beobachtung kann höchstens { $max } Tags haben.
+error-no-watches-tagged = Keine Beobachtungen haben das Tag { $tag }.
+error-no-watches-of-yours-tagged = Keine deiner Beobachtungen hat das Tag { $tag }.
+error-same-channel = Beobachtungen können nicht in den Kanal verschoben werden, in dem sie bereits sind.
+error-no-watches-to-move = In diesem Kanal gibt es keine Beobachtungen, die du verschieben kannst.
+error-too-many-watches = Du hast bereits { $max } Beobachtungen auf diesem Server, das erlaubte Maximum. Entferne zuerst eine mit `/unwatch-satellite`.
+error-max-watches-range = Das Beobachtungslimit muss zwischen 1 und { $max } liegen.
+error-cooldown = Bitte warte { $seconds ->
+    [one] eine Sekunde
+   *[other] { $seconds } Sekunden
+}, bevor du es erneut versuchst.
+error-cooldown-range = Abklingzeiten müssen zwischen 0 und { $max } Sekunden liegen.
+error-not-admin = Nur Mitglieder, die den Server verwalten können oder eine seiner Admin-Rollen haben, können diesen Befehl verwenden.
+error-manage-guild-only = Nur Mitglieder, die den Server verwalten können, können die Admin-Rollen ändern.
+error-invalid-duration = Das ist keine Dauer. Gib eine wie 90m, 12h, 3d oder 1w an.
+error-mute-too-long = Kanäle können höchstens { $days } Tage stummgeschaltet werden.
+error-not-muted = Dieser Kanal ist nicht stummgeschaltet.
+error-invalid-vacation-end = Gib das Datum der Fortsetzung als JJJJ-MM-TT oder eine Dauer wie 2w an.
+error-vacation-range = Urlaube müssen in der Zukunft und innerhalb von { $days } Tagen enden.
+error-not-on-vacation = Du bist nicht im Urlaub.
+error-not-watcher = Die Beobachtung kann nur von ihrem Ersteller entfernt werden
+error-ambiguous-watch = Mehrere Beobachtungen passen, bitte gib den Kanal an
+error-unknown-timezone = unbekannte Zeitzone { $timezone }, verwende einen Namen wie Europe/Berlin
+error-guild-only = dieser Befehl kann nur auf einem Server verwendet werden
+
+## Locations
+
+location-added = Standort hinzugefügt
+location-added-description = { $name } ({ $author })
+locations-title = Standorte
+location-removed = Standort entfernt
+location-removed-description = { $name }, erstellt von { $author }
+
+## Passes
+
+no-passes = Keine Überflüge gefunden
+note-days-clamped = { $days ->
+    [one] { $days } Tag wird
+   *[other] { $days } Tage werden
+} angezeigt, der nächste unterstützte Wert.
+passes-title = Überflüge von { $satellite } { $days ->
+    [one] am nächsten Tag
+   *[other] in den nächsten { $days } Tagen
+}
+passes-downlink = Stelle { $frequency } für { $description } ein, in { $mode }.
+pass-details = Maximale Elevation: { $elevation }° · Qualität: { $quality }/100
+pass-path = AOS { $aos } → max. { $elevation }° → LOS { $los }
+pass-table-time = Zeit
+pass-table-azimuth = Azimut
+pass-table-elevation = Elevation
+pass-table-duration = Dauer: { $duration }
+pass-visible = 👁 Mit bloßem Auge sichtbar
+pass-radio-only = 📡 Nur per Funk
+pass-near-moon = 🌙 Zieht bis auf { $separation }° am Mond vorbei ({ $illumination } % beleuchtet)
+pass-cloud-cover = ☁️ Bewölkung: { $cover } %
+pass-magnitude = Helligkeit { $magnitude } mag
+export-pass = Überflug von { $satellite } bei { $location }, { $time }
+data-source = Datenquelle: { $source }
+raw-unavailable = Für { $satellites } gibt es keine Rohantwort, da N2YO nicht erreichbar war und die Überflüge lokal berechnet wurden.
+compare-title = Überflüge von { $satellite } an { $first } und { $second }
+compare-better = { $location } hat im Durchschnitt die besseren Überflüge.
+compare-even = Keiner der Standorte hat im Durchschnitt die besseren Überflüge.
+compare-pass = { $time } · { $elevation }° · { $quality }/100
+compare-average = **Durchschnitt**: { $elevation }° · { $quality }/100
+best-pass-title = Bester Überflug von { $satellite } in den nächsten { $days } Tagen
+best-pass-rise = Aufgang
+best-pass-highest = Höchster Punkt
+best-pass-set = Untergang
+best-pass-calendar = Zum Kalender hinzufügen
+calendar-event-title = Überflug von { $satellite }
+calendar-event-details = Überflug über { $location } mit bis zu { $elevation }° Elevation.
+next-pass = Der nächste Überflug von **{ $satellite }** beginnt { $countdown } um { $time } und erreicht { $elevation }° von { $from } nach { $to }.
+next-pass-none = **{ $satellite }** hat in den nächsten { $days } Tagen keine ausreichend hohen Überflüge.
+pass-info-title = Überflug { $id } von { $satellite }
+pass-info-attendees = Dabei
+pass-info-nobody = Niemand hat sich angemeldet.
+pass-info-observations = Beobachtungen
+pass-info-image = Bild
+session-title = Beobachtungssitzung in { $location }
+session-description = { $count } Überflüge von { $start } bis { $end }, mit Zeit zum Drehen der Antenne dazwischen.
+session-pass = { $time } · { $elevation }° · { $quality }/100
+session-conflicts = { $count } überlappende Überflüge wurden zugunsten besserer ausgelassen.
+logbook-logged = Beobachtung von { $satellite } eingetragen
+logbook-notified-pass = Überflug { $time } in { $location }
+logbook-time = Beobachtet { $time }
+logbook-notes = Notizen
+gallery-observer = Beobachtet von { $observer } { $time }
+gallery-location = Aus { $location }
+page-number = Seite { $page } von { $pages }
+tracking-title = { $satellite } live über { $location }
+tracking-rises = Geht { $time } auf
+tracking-sets = Geht { $time } unter
+tracking-over = Der Überflug ist vorbei, der Satellit ging um { $time } unter.
+tracking-azimuth = Azimut
+tracking-elevation = Elevation
+tracking-range = Entfernung
+
+## Watches
+
+watch-added = Satellit wird beobachtet
+watch-added-description = { $satellite } mit einer Mindestelevation von { $elevation }° bei { $location }, { $days ->
+    [one] 1 Tag
+   *[other] { $days } Tage
+} im Voraus, von { $author }
+watches-title = Beobachtete Satelliten
+watch-details =
+    Kanal: { $channel }
+    Standort: { $location }
+    Mindestelevation: { $elevation }°
+    Vorschau: { $days ->
+        [one] 1 Tag
+       *[other] { $days } Tage
+    }
+watch-notify-within = Benachrichtigen innerhalb von: { $hours } h
+watch-timezone = Zeitzone: { $timezone }
+watch-min-quality = Mindestqualität: { $quality }/100
+watch-visual = Sichtbare Überflüge
+watch-brighter-than = Sichtbare Überflüge heller als Magnitude { $magnitude }
+watch-threads = Startet einen Thread für jeden Überflug
+watch-templated = Eigene Benachrichtigungsvorlage
+watch-tags = Tags: { $tags }
+watch-paused = **Pausiert**
+watch-tag-choice = { $tag } ({ $count ->
+    [one] 1 Beobachtung
+   *[other] { $count } Beobachtungen
+})
+watches-paused = { $count ->
+    [one] 1 Beobachtung
+   *[other] { $count } Beobachtungen
+} mit dem Tag { $tag } pausiert.
+watches-resumed = { $count ->
+    [one] 1 Beobachtung
+   *[other] { $count } Beobachtungen
+} mit dem Tag { $tag } fortgesetzt.
+mute-started = Benachrichtigungen in diesem Kanal sind bis { $time } stummgeschaltet. Die inzwischen gefundenen Überflüge werden danach aufgelistet.
+mute-ended = Benachrichtigungen in diesem Kanal sind nicht mehr stummgeschaltet.
+mute-lifted-title = Benachrichtigungen wieder aktiv
+mute-lifted-pass = **{ $satellite }** von { $location }: { $time }, { $elevation }° (`{ $id }`)
+mute-lifted-more = …und { $count } weitere.
+mute-lifted-none = Während der Stummschaltung wurden keine bevorstehenden Überflüge gefunden.
+vacation-started = Deine Beobachtungen sind bis { $time } pausiert. Du bekommst eine Direktnachricht, wenn sie fortgesetzt werden.
+vacation-ended = Dein Urlaub ist vorbei, und deine Beobachtungen werden beim nächsten Benachrichtigungslauf fortgesetzt.
+vacation-over = Willkommen zurück! Deine Beobachtungen laufen wieder.
+vacation-over-passes = Die besten bevorstehenden Überflüge, mit einer Qualität von mindestens { $quality }:
+vacation-over-pass = **{ $satellite }**: { $time }, { $elevation }°, Qualität { $quality }
+watch-disabled = **Deaktiviert**: { $reason }
+watch-removed = Beobachtung entfernt
+watch-updated = Beobachtung aktualisiert
+watch-cloned = Beobachtung kopiert
+watches-moved = { $count ->
+    [one] 1 Beobachtung
+   *[other] { $count } Beobachtungen
+} von { $from } nach { $to } verschoben.
+watch-description = { $satellite } ({ $author })
+watches-updated = Beobachtete Satelliten aktualisiert
+
+## Settings
+
+settings-title = Einstellungen
+settings-default-location = Standardstandort
+settings-units = Einheiten
+settings-time-format = Zeitformat
+settings-ephemeral = Private Antworten
+settings-direct-messages = Direktnachrichten
+settings-on = An
+settings-off = Aus
+settings-none = Keiner
+server-settings-title = Servereinstellungen
+server-settings-default-elevation = Standard-Mindesthöhe
+server-settings-max-watches = Beobachtungen pro Mitglied
+server-settings-cooldowns = Abklingzeiten für Überflugabfragen
+server-settings-cooldowns-value = { $user } s pro Mitglied, { $channel } s pro Kanal
+server-settings-admin-roles = Admin-Rollen
+server-settings-no-admin-roles = Keine, nur Mitglieder, die den Server verwalten können
+favorites-title = Lieblingssatelliten
+favorites-footer = Gib `favorites` bei /plan-session oder /get-upcoming-passes-multi an, um alle abzufragen.
+favorites-added = { $satellite } wurde zu deinen Favoriten hinzugefügt.
+favorites-removed = { $satellite } wurde aus deinen Favoriten entfernt.
+aliases-title = Satelliten-Aliase
+alias-set = `{ $alias }` steht jetzt für { $satellites }.
+alias-removed = Der Alias `{ $alias }` wurde entfernt.
+
+## Notifications
+
+notification-title = Bevorstehender Überflug von { $satellite } bei { $location }
+notification-frequency = Downlink
+notification-sstv = SSTV
+notification-sstv-active = 📡 SSTV aktiv auf { $frequency } ({ $mode })
+notification-track-live = Live verfolgen
+notification-track-pass = Überflug { $number } verfolgen
+notification-pass-id = Überflug { $id }
+notification-rsvp = Ich bin dabei
+notification-rsvp-pass = Dabei: Überflug { $number }
+rsvp-going = ✋ { $count } dabei: { $names }
+rsvp-more = { " " }und { $count } weitere
+rsvp-reminder = ⏰ Der Überflug von { $satellite }, bei dem du dabei bist, beginnt { $time }, siehe <#{ $channel }>.
+notification-watch-disabled = Deine Beobachtung von { $satellite } bei { $location } wurde deaktiviert, da ich nicht mehr in <#{ $channel }> schreiben kann. Verwende `/edit-watch` mit einem neuen Kanal, um sie wieder zu aktivieren.
+decay-reentry-title = { $satellite } wird voraussichtlich bald wiedereintreten
+decay-reentry = Die Umlaufbahn von { $satellite } ist auf { $perigee } × { $apogee } km gesunken, ein Wiedereintritt ist innerhalb von { $days } Tagen möglich. Danach gibt es keine Überflüge mehr.
+decay-stale-title = { $satellite } hat veraltete Bahnelemente
+decay-stale = Das neueste TLE für { $satellite } ist { $days } Tage alt, daher können Überflüge ungenau vorhergesagt werden. Der Satellit ist eventuell wiedereingetreten oder wird nicht mehr verfolgt.
+decay-new-tle-title = { $satellite } hat neue Bahnelemente
+decay-new-tle = { $satellite } hat nach { $days } Tagen ohne TLE ein neues, die Umlaufbahn beträgt jetzt { $perigee } × { $apogee } km. Bisher gemeldete Überflüge können falsch sein.
+decay-manoeuvre = { $satellite } hat anscheinend manövriert, die Umlaufbahn hat sich von { $previous_perigee } × { $previous_apogee } km auf { $perigee } × { $apogee } km geändert. Bisher gemeldete Überflüge können falsch sein.
+
+## Space weather
+
+space-weather-title = Weltraumwetter
+space-weather-current = Aktueller Kp-Index
+space-weather-predicted = Vorhergesagter Kp-Index (nächste 24 Stunden)
+space-weather-no-prediction = Keine Vorhersage verfügbar
+space-weather-kp = Kp { $kp }
+space-weather-kp-storm = Kp { $kp } (Sturm G{ $level })
+space-weather-aurora = Polarlicht bei { $location }
+space-weather-aurora-chance = { $chance } % Wahrscheinlichkeit im Zenit
+space-weather-source = Daten von NOAA SWPC
+solar-title = Sonnenindizes
+solar-flux = Solarer Flussindex (SFI)
+solar-sunspot-number = Sonnenfleckenzahl
+solar-a-index = A-Index
+solar-k-index = K-Index
+solar-unavailable = Nicht verfügbar
+
+## Moon
+
+moon-title = Der Mond von { $location } aus
+moon-phase = Phase
+moon-illumination = Beleuchtet
+moon-position = Position
+moon-position-up = { $azimuth }° Azimut, { $elevation }° Elevation
+moon-position-down = Unter dem Horizont
+moon-rise = Nächster Mondaufgang
+moon-set = Nächster Monduntergang
+moon-not-soon = Nicht in den nächsten 2 Tagen
+moon-phase-new = Neumond
+moon-phase-waxing-crescent = Zunehmende Sichel
+moon-phase-first-quarter = Erstes Viertel
+moon-phase-waxing-gibbous = Zunehmender Mond
+moon-phase-full = Vollmond
+moon-phase-waning-gibbous = Abnehmender Mond
+moon-phase-last-quarter = Letztes Viertel
+moon-phase-waning-crescent = Abnehmende Sichel
+geo-title = Ausrichtung auf { $satellite } von { $location }
+geo-above-horizon = Er bleibt an derselben Stelle am Himmel, die Antenne muss also nur einmal ausgerichtet werden.
+geo-below-horizon = Er steht hier unter dem Horizont und ist von diesem Ort aus nicht erreichbar.
+geo-azimuth = Azimut
+geo-elevation = Elevation
+geo-range = Entfernung
+geo-longitude = Über
+geo-east = { $longitude }° O
+geo-west = { $longitude }° W
+geo-tle-epoch = Aus dem TLE vom { $time }
+satellite-info-id = NORAD-ID
+satellite-info-designator = Internationale Kennung
+satellite-info-launched = Gestartet
+satellite-info-type = Typ
+satellite-info-period = Umlaufzeit
+satellite-info-minutes = { $minutes } Minuten
+satellite-info-inclination = Inklination
+satellite-info-orbit = Perigäum × Apogäum
+satellite-info-tle-age = Alter des TLE
+satellite-info-days = { $days ->
+    [one] { $days } Tag
+   *[other] { $days } Tage
+}
+satellite-info-no-tle = Kein TLE
+satellite-info-unknown = Unbekannt
+satellite-info-source = Aus dem neuesten TLE und dem SATCAT von CelesTrak
+satcat-search-title = SATCAT-Ergebnisse für "{ $query }"
+satcat-search-truncated = Nur die ersten { $max } Ergebnisse werden angezeigt. Füge Filter hinzu, um die Suche einzugrenzen.
+satcat-search-result = { $designator } · { $kind } · { $owner } · gestartet { $launched }
+satcat-search-decayed = {" "}· wiedereingetreten { $date }
+satcat-search-watch = Beobachten
+satcat-search-watch-hint = Führe `/watch-satellite satellite_id:{ $id }` im Kanal aus, um über seine Überflüge benachrichtigt zu werden.
+
+## Frequencies
+
+frequencies-title = Frequenzen von { $satellite }
+frequencies-downlink = Downlink: { $frequency }
+frequencies-uplink = Uplink: { $frequency }
+frequencies-mode = Betriebsart: { $mode }
+frequencies-tone = Ton: { $tone } Hz
+transmitters-title = Aktive Sender von { $satellite_id }
+transmitters-baud = Baud: { $baud }
+transmitters-more = Und { $count } weitere in SatNOGS DB
+observation-title = Beobachtung { $id } von { $station }
+
+## Launches
+
+launches-title = Bevorstehende Starts
+launches-source = Daten von Launch Library 2 von The Space Devs
+launch-net-unknown = Datum noch nicht bestätigt
+launch-alert-title = Neuigkeiten zu einem Start
+launch-alert-footer = Beobachte neue Satelliten, sobald ihre TLEs erscheinen
+launch-scheduled = { $launch } wurde angesetzt
+launch-slipped = { $launch } wurde von { $previous } verschoben
+launch-alerts-subscribed = Dieser Kanal wird über Starts der Art { $categories } informiert.
+launch-alerts-unsubscribed = Dieser Kanal wird nicht mehr über Starts informiert.
+
+## Starlink
+
+starlink-title = Starlink-Züge von { $location } aus
+starlink-description = Die Zeiten gelten für den ersten Satelliten jedes Zuges, die übrigen folgen in den nächsten Minuten.
+starlink-batch = Start { $launch } · { $count } Satelliten auf etwa { $altitude } km
+starlink-pass = { $time }, bis { $elevation }°, von { $from } nach { $to }
+starlink-no-passes = Keine sichtbaren Überflüge
+starlink-source = Bahnen von CelesTrak, lokal berechnet
+
+## Statistics
+
+stats-title = Statistiken
+stats-started = Gestartet
+stats-locations = Standorte
+stats-watches = Beobachtete Satelliten
+stats-notifications-sent = Gesendete Benachrichtigungen (seit Start)
+stats-last-notify-run = Letzte Benachrichtigungsrunde
+stats-notify-restarts = Neustarts der Benachrichtigungen
+stats-never = Nie
+stats-notifications-this-week = Benachrichtigungen diese Woche
+stats-commands-this-week = Befehle diese Woche
+stats-top-satellites = Am häufigsten gemeldete Satelliten
+stats-top-commands = Am häufigsten genutzte Befehle
+stats-n2yo-hourly = N2YO-Transaktionen (letzte Stunde)
+stats-n2yo-today = N2YO-Transaktionen (heute, UTC)
+stats-n2yo-total = N2YO-Transaktionen (seit Start)
+stats-n2yo-keys = N2YO-Schlüssel
+commands-synced = { $count } Befehle bei Discord registriert. Es kann eine Minute dauern, bis sie erscheinen.
+
+## Help
+
+help-title = Befehle
+help-description = Wähle unten einen Befehl aus, um Details und Beispiele zu sehen.
+help-placeholder = Befehl für Details auswählen
+help-placeholder-more = Weitere Befehle
+help-overview = Übersicht
+help-overview-description = Alle Befehle nach Kategorie
+help-usage = Verwendung
+help-parameters = Parameter
+help-details = Details
+help-optional = (optional)
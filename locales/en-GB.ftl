@@ -0,0 +1,401 @@
+## Errors
+
+error-title = An error occurred
+error-n2yo = Couldn't get data from N2YO right now, please try again later ({ $details })
+error-database = Something went wrong while accessing saved data, please let the bot's operator know
+error-discord = Discord rejected the request: { $details }
+error-unknown = Error: { $details }
+error-days-range = { $parameter } must be between 1 and 10
+error-elevation-range = min_max_elevation must be between 0 and 90
+error-quality-range = min_quality must be between 0 and 100
+error-count-range = { $parameter } must be between 1 and { $max }
+error-no-such-location = no such location
+error-no-such-satellite = no satellite with NORAD ID { $satellite_id }
+error-no-such-pass = There isn't a pass that far ahead. Try an earlier one or a lower elevation.
+error-step-range = The step must be between 1 and { $max } seconds.
+error-unknown-frequencies = I don't know the frequencies of that satellite. Try `/transmitters` for others.
+error-no-transmitters = SatNOGS DB doesn't list any active transmitters on that satellite.
+error-no-observations = SatNOGS Network has no recent good observations of that satellite.
+error-no-launches = No upcoming launches of that kind are listed.
+error-not-subscribed-to-launches = This channel is not subscribed to launch alerts.
+error-no-starlink-trains = No Starlink batches have been launched recently enough to still form a train.
+error-unknown-pass = That pass can no longer be tracked.
+error-pass-over = That pass is already over.
+error-pass-too-early = Live tracking can start up to { $minutes } minutes before the pass.
+error-already-tracking = That pass is already being tracked in this channel.
+error-tracking-unavailable = That pass can't be tracked right now, please try again later.
+error-invalid-satellite = `{ $satellite }` is not a NORAD ID or a group. Groups: { $groups }.
+error-no-satellites = Give at least one NORAD ID or a group name.
+error-too-many-satellites = Give at most { $max } satellites at once.
+error-no-favorites = You have no favorite satellites. Add some with `/favorite-satellite add`.
+error-too-many-favorites = You can have at most { $max } favorite satellites.
+error-invalid-satellite-id = `{ $satellite }` is not a NORAD ID or an alias in this server.
+error-alias-is-group = `{ $alias }` stands for several satellites. Give one NORAD ID.
+error-invalid-alias = Aliases must be 1 to { $max } characters long, cannot be a number, and cannot contain commas.
+error-reserved-alias = `{ $alias }` is already the name of a group.
+error-not-geostationary = { $satellite } is not geostationary, so it moves across the sky. Try `/get-upcoming-passes` instead.
+error-unknown-satellite = Neither N2YO nor CelesTrak know of a satellite with that NORAD ID.
+error-satcat-not-loaded = The SATCAT is still being downloaded. Try again in a minute.
+error-no-satcat-results = Nothing in the SATCAT matches that search.
+error-too-many-aliases = A server can have at most { $max } aliases.
+error-no-such-alias = There is no alias with that name.
+error-no-aliases = This server has no aliases. Add some with `/satellite-alias set`.
+error-already-favorite = That satellite is already one of your favorites.
+error-not-favorite = That satellite is not one of your favorites.
+error-empty-session = None of those satellites pass high enough during the session.
+error-session-too-late = The session must end within 10 days.
+error-notes-too-long = Notes can be at most { $max } characters long.
+error-not-an-image = The attachment must be an image.
+error-unknown-pass-time = That isn't a pass notified of in this channel. Pick one from the list, or leave it out.
+error-unknown-pass-id = No pass with that ID was notified of here. Pass IDs are in the footer of notifications, and are kept for { $days } days after the pass.
+error-template-too-long = Templates can be at most { $max } characters long.
+error-template-braces = That template has an unmatched brace. Write placeholders such as { $placeholders } in single braces, and braces themselves as {"{{"} and {"}}"}.
+error-unknown-placeholder = There is no placeholder {"{"}{ $placeholder }{"}"}. Templates can use { $placeholders }.
+error-empty-gallery = No observations with images have been logged here yet. Use `/log-observation` to add one.
+error-no-location-given = no location given and no default location set, see `/settings default-location`
+error-location-exists = location already exists
+error-location-not-creator = location must be removed by its creator
+error-already-watched = satellite already being watched in this channel with these parameters
+error-no-such-watch = no such watched satellite
+error-no-such-watch-of-yours = no such watched satellite of yours
+error-invalid-tag = Tags cannot be empty or longer than { $max } characters.
+error-too-many-tags = A watch can have at most { $max } tags.
+error-no-watches-tagged = No watches are tagged { $tag }.
+error-no-watches-of-yours-tagged = None of your watches are tagged { $tag }.
+error-same-channel = Watches cannot be moved to the channel they are already in.
+error-no-watches-to-move = There are no watches in that channel that you can move.
+error-too-many-watches = You already have { $max } watches in this server, the most allowed. Remove one with `/unwatch-satellite` first.
+error-max-watches-range = The watch limit must be between 1 and { $max }.
+error-cooldown = Please wait { $seconds ->
+    [one] a second
+   *[other] { $seconds } seconds
+} before trying again.
+error-cooldown-range = Cooldowns must be between 0 and { $max } seconds.
+error-not-admin = Only members who can manage the server, or have one of its admin roles, can use this command.
+error-manage-guild-only = Only members who can manage the server can change the admin roles.
+error-invalid-duration = That is not a duration. Give one such as 90m, 12h, 3d, or 1w.
+error-mute-too-long = Channels can be muted for at most { $days } days.
+error-not-muted = This channel is not muted.
+error-invalid-vacation-end = Give the date to resume on as YYYY-MM-DD, or a duration such as 2w.
+error-vacation-range = Vacations must end in the future, and within { $days } days.
+error-not-on-vacation = You are not on vacation.
+error-not-watcher = watched satellite must be removed by its watcher
+error-ambiguous-watch = you have several matching watches, specify the channel
+error-unknown-timezone = unknown timezone { $timezone }, use a name such as Europe/London
+error-guild-only = this command can only be used in a server
+
+## Locations
+
+location-added = Location added
+location-added-description = { $name } ({ $author })
+locations-title = Locations
+location-removed = Location removed
+location-removed-description = { $name }, created by { $author }
+
+## Passes
+
+no-passes = No passes found
+note-days-clamped = Showing { $days ->
+    [one] { $days } day
+   *[other] { $days } days
+}, the closest supported value.
+passes-title = Upcoming passes for { $satellite } in the next { $days ->
+    [one] day
+   *[other] { $days } days
+}
+passes-downlink = Tune to { $frequency } for { $description }, in { $mode }.
+pass-details = Max Elevation: { $elevation }° · Quality: { $quality }/100
+pass-path = AOS { $aos } → max { $elevation }° → LOS { $los }
+pass-table-time = Time
+pass-table-azimuth = Azimuth
+pass-table-elevation = Elevation
+pass-table-duration = Duration: { $duration }
+pass-visible = 👁 Visible to the naked eye
+pass-radio-only = 📡 Radio only
+pass-near-moon = 🌙 Passes within { $separation }° of the Moon ({ $illumination }% lit)
+pass-cloud-cover = ☁️ Cloud cover: { $cover }%
+pass-magnitude = Magnitude { $magnitude }
+export-pass = { $satellite } pass at { $location }, { $time }
+data-source = Data source: { $source }
+raw-unavailable = There is no raw response for { $satellites }, as N2YO was unavailable and the passes were predicted locally.
+compare-title = { $satellite } passes at { $first } and { $second }
+compare-better = { $location } has the better passes on average.
+compare-even = Neither location has the better passes on average.
+compare-pass = { $time } · { $elevation }° · { $quality }/100
+compare-average = **Average**: { $elevation }° · { $quality }/100
+best-pass-title = Best pass of { $satellite } in the next { $days } days
+best-pass-rise = Rises
+best-pass-highest = Highest
+best-pass-set = Sets
+best-pass-calendar = Add to calendar
+calendar-event-title = { $satellite } pass
+calendar-event-details = Pass over { $location }, reaching { $elevation }° elevation.
+next-pass = The next pass of **{ $satellite }** starts { $countdown }, at { $time }, reaching { $elevation }° from { $from } to { $to }.
+next-pass-none = **{ $satellite }** has no passes high enough in the next { $days } days.
+pass-info-title = Pass { $id } of { $satellite }
+pass-info-attendees = Going
+pass-info-nobody = Nobody said they were going.
+pass-info-observations = Observations
+pass-info-image = image
+session-title = Observing session at { $location }
+session-description = { $count } passes from { $start } to { $end }, with time to turn the antenna between each.
+session-pass = { $time } · { $elevation }° · { $quality }/100
+session-conflicts = { $count } overlapping passes were left out for better ones.
+logbook-logged = Logged an observation of { $satellite }
+logbook-notified-pass = Pass of { $time } at { $location }
+logbook-time = Observed { $time }
+logbook-notes = Notes
+gallery-observer = Observed by { $observer } { $time }
+gallery-location = From { $location }
+page-number = Page { $page } of { $pages }
+tracking-title = { $satellite } live over { $location }
+tracking-rises = Rises { $time }
+tracking-sets = Sets { $time }
+tracking-over = The pass is over, the satellite set at { $time }.
+tracking-azimuth = Azimuth
+tracking-elevation = Elevation
+tracking-range = Range
+
+## Watches
+
+watch-added = Satellite watched
+watch-added-description = { $satellite } with a minimum elevation of { $elevation }° at { $location }, { $days ->
+    [one] 1 day
+   *[other] { $days } days
+} ahead, by { $author }
+watches-title = Watched satellites
+watch-details =
+    Channel: { $channel }
+    Location: { $location }
+    Minimum Elevation: { $elevation }°
+    Look-ahead: { $days ->
+        [one] 1 day
+       *[other] { $days } days
+    }
+watch-notify-within = Notify within: { $hours }h
+watch-timezone = Timezone: { $timezone }
+watch-min-quality = Minimum quality: { $quality }/100
+watch-visual = Visual passes
+watch-brighter-than = Visual passes brighter than magnitude { $magnitude }
+watch-threads = Starts a thread for each pass
+watch-templated = Custom notification template
+watch-tags = Tags: { $tags }
+watch-paused = **Paused**
+watch-tag-choice = { $tag } ({ $count ->
+    [one] 1 watch
+   *[other] { $count } watches
+})
+watches-paused = Paused { $count ->
+    [one] 1 watch
+   *[other] { $count } watches
+} tagged { $tag }.
+watches-resumed = Resumed { $count ->
+    [one] 1 watch
+   *[other] { $count } watches
+} tagged { $tag }.
+mute-started = Notifications in this channel are muted until { $time }. The passes found meanwhile will be listed when the mute lifts.
+mute-ended = Notifications in this channel are unmuted.
+mute-lifted-title = Notifications unmuted
+mute-lifted-pass = **{ $satellite }** from { $location }: { $time }, { $elevation }° (`{ $id }`)
+mute-lifted-more = …and { $count } more.
+mute-lifted-none = No upcoming passes were found while muted.
+vacation-started = Your watches are paused until { $time }. You will get a direct message when they resume.
+vacation-ended = Your vacation is over, and your watches resume at the next notification run.
+vacation-over = Welcome back! Your watches have resumed.
+vacation-over-passes = The best passes coming up, with a quality of at least { $quality }:
+vacation-over-pass = **{ $satellite }**: { $time }, { $elevation }°, quality { $quality }
+watch-disabled = **Disabled**: { $reason }
+watch-removed = Watched satellite removed
+watch-updated = Watched satellite updated
+watch-cloned = Watch copied
+watches-moved = Moved { $count ->
+    [one] 1 watch
+   *[other] { $count } watches
+} from { $from } to { $to }.
+watch-description = { $satellite } ({ $author })
+watches-updated = Updated watched satellites
+
+## Settings
+
+settings-title = Settings
+settings-default-location = Default location
+settings-units = Units
+settings-time-format = Time format
+settings-ephemeral = Private replies
+settings-direct-messages = Direct messages
+settings-on = On
+settings-off = Off
+settings-none = None
+server-settings-title = Server settings
+server-settings-default-elevation = Default minimum elevation
+server-settings-max-watches = Watches per member
+server-settings-cooldowns = Pass request cooldowns
+server-settings-cooldowns-value = { $user }s per member, { $channel }s per channel
+server-settings-admin-roles = Admin roles
+server-settings-no-admin-roles = None, only members who can manage the server
+favorites-title = Favorite satellites
+favorites-footer = Give `favorites` to /plan-session or /get-upcoming-passes-multi to look them all up.
+favorites-added = Added { $satellite } to your favorites.
+favorites-removed = Removed { $satellite } from your favorites.
+aliases-title = Satellite aliases
+alias-set = `{ $alias }` now stands for { $satellites }.
+alias-removed = Removed the alias `{ $alias }`.
+
+## Notifications
+
+notification-title = Upcoming pass for { $satellite } at { $location }
+notification-frequency = Downlink
+notification-sstv = SSTV
+notification-sstv-active = 📡 SSTV active on { $frequency } ({ $mode })
+notification-track-live = Track live
+notification-track-pass = Track pass { $number }
+notification-pass-id = Pass { $id }
+notification-rsvp = I'm in
+notification-rsvp-pass = I'm in: pass { $number }
+rsvp-going = ✋ { $count } going: { $names }
+rsvp-more = { " " }and { $count } more
+rsvp-reminder = ⏰ The pass of { $satellite } you're going to starts { $time }, see <#{ $channel }>.
+notification-watch-disabled = Your watch of { $satellite } at { $location } has been disabled because I can no longer post in <#{ $channel }>. Use `/edit-watch` with a new channel to re-enable it.
+decay-reentry-title = { $satellite } is expected to reenter soon
+decay-reentry = { $satellite }'s orbit has decayed to { $perigee } × { $apogee } km, and it could reenter within { $days } days. Passes will stop once it does.
+decay-stale-title = { $satellite } has out of date orbital elements
+decay-stale = The newest TLE for { $satellite } is { $days } days old, so its passes may be predicted inaccurately. It may have reentered or stopped being tracked.
+decay-new-tle-title = { $satellite } has new orbital elements
+decay-new-tle = { $satellite } has a new TLE after { $days } days without one, and its orbit is now { $perigee } × { $apogee } km. Passes notified before now may be wrong.
+decay-manoeuvre = { $satellite } appears to have manoeuvred, changing its orbit from { $previous_perigee } × { $previous_apogee } km to { $perigee } × { $apogee } km. Passes notified before now may be wrong.
+
+## Space weather
+
+space-weather-title = Space weather
+space-weather-current = Current Kp index
+space-weather-predicted = Predicted Kp index (next 24 hours)
+space-weather-no-prediction = No prediction available
+space-weather-kp = Kp { $kp }
+space-weather-kp-storm = Kp { $kp } (G{ $level } storm)
+space-weather-aurora = Aurora at { $location }
+space-weather-aurora-chance = { $chance }% chance overhead
+space-weather-source = Data from NOAA SWPC
+solar-title = Solar indices
+solar-flux = Solar flux index (SFI)
+solar-sunspot-number = Sunspot number
+solar-a-index = A index
+solar-k-index = K index
+solar-unavailable = Unavailable
+
+## Moon
+
+moon-title = The Moon from { $location }
+moon-phase = Phase
+moon-illumination = Illuminated
+moon-position = Position
+moon-position-up = { $azimuth }° azimuth, { $elevation }° elevation
+moon-position-down = Below the horizon
+moon-rise = Next moonrise
+moon-set = Next moonset
+moon-not-soon = Not in the next 2 days
+moon-phase-new = New Moon
+moon-phase-waxing-crescent = Waxing crescent
+moon-phase-first-quarter = First quarter
+moon-phase-waxing-gibbous = Waxing gibbous
+moon-phase-full = Full Moon
+moon-phase-waning-gibbous = Waning gibbous
+moon-phase-last-quarter = Last quarter
+moon-phase-waning-crescent = Waning crescent
+geo-title = Pointing at { $satellite } from { $location }
+geo-above-horizon = It stays in the same place in the sky, so the antenna can be pointed once and left.
+geo-below-horizon = It is below the horizon here, so it can't be reached from this location.
+geo-azimuth = Azimuth
+geo-elevation = Elevation
+geo-range = Range
+geo-longitude = Over
+geo-east = { $longitude }° E
+geo-west = { $longitude }° W
+geo-tle-epoch = From the TLE of { $time }
+satellite-info-id = NORAD ID
+satellite-info-designator = International designator
+satellite-info-launched = Launched
+satellite-info-type = Type
+satellite-info-period = Period
+satellite-info-minutes = { $minutes } minutes
+satellite-info-inclination = Inclination
+satellite-info-orbit = Perigee × apogee
+satellite-info-tle-age = TLE age
+satellite-info-days = { $days ->
+    [one] { $days } day
+   *[other] { $days } days
+}
+satellite-info-no-tle = No TLE
+satellite-info-unknown = Unknown
+satellite-info-source = From the latest TLE and CelesTrak SATCAT
+satcat-search-title = SATCAT results for "{ $query }"
+satcat-search-truncated = Only the first { $max } results are shown. Add filters to narrow the search.
+satcat-search-result = { $designator } · { $kind } · { $owner } · launched { $launched }
+satcat-search-decayed = {" "}· reentered { $date }
+satcat-search-watch = Watch
+satcat-search-watch-hint = Run `/watch-satellite satellite_id:{ $id }` in the channel to notify of its passes.
+
+## Frequencies
+
+frequencies-title = { $satellite } frequencies
+frequencies-downlink = Downlink: { $frequency }
+frequencies-uplink = Uplink: { $frequency }
+frequencies-mode = Mode: { $mode }
+frequencies-tone = Tone: { $tone } Hz
+transmitters-title = Active transmitters of { $satellite_id }
+transmitters-baud = Baud: { $baud }
+transmitters-more = And { $count } more on SatNOGS DB
+observation-title = Observation { $id } from { $station }
+
+## Launches
+
+launches-title = Upcoming launches
+launches-source = Data from Launch Library 2 by The Space Devs
+launch-net-unknown = Date to be confirmed
+launch-alert-title = Launch update
+launch-alert-footer = Watch new satellites once their TLEs appear
+launch-scheduled = { $launch } has been scheduled
+launch-slipped = { $launch } has slipped from { $previous }
+launch-alerts-subscribed = This channel will be told about { $categories } launches.
+launch-alerts-unsubscribed = This channel will no longer be told about launches.
+
+## Starlink
+
+starlink-title = Starlink trains from { $location }
+starlink-description = Times are for the first satellite of each train, with the rest following over the next few minutes.
+starlink-batch = Launch { $launch } · { $count } satellites at around { $altitude } km
+starlink-pass = { $time }, up to { $elevation }°, { $from } to { $to }
+starlink-no-passes = No visible passes
+starlink-source = Orbits from CelesTrak, predicted locally
+
+## Statistics
+
+stats-title = Statistics
+stats-started = Started
+stats-locations = Locations
+stats-watches = Watched satellites
+stats-notifications-sent = Notifications sent (since start)
+stats-last-notify-run = Last notification run
+stats-notify-restarts = Notification task restarts
+stats-never = Never
+stats-notifications-this-week = Notifications this week
+stats-commands-this-week = Commands this week
+stats-top-satellites = Most notified satellites
+stats-top-commands = Most used commands
+stats-n2yo-hourly = N2YO transactions (last hour)
+stats-n2yo-today = N2YO transactions (today, UTC)
+stats-n2yo-total = N2YO transactions (since start)
+stats-n2yo-keys = N2YO keys
+commands-synced = Registered { $count } commands with Discord. They may take a minute to appear.
+
+## Help
+
+help-title = Commands
+help-description = Choose a command below for usage details and examples.
+help-placeholder = Choose a command for details
+help-placeholder-more = More commands
+help-overview = Overview
+help-overview-description = All commands by category
+help-usage = Usage
+help-parameters = Parameters
+help-details = Details
+help-optional = (optional)
@@ -0,0 +1,401 @@
+## Errors
+
+error-title = Une erreur est survenue
+error-n2yo = Impossible d'obtenir les données de N2YO pour le moment, veuillez réessayer plus tard ({ $details })
+error-database = Un problème est survenu lors de l'accès aux données enregistrées, veuillez prévenir l'opérateur du bot
+error-discord = Discord a refusé la requête : { $details }
+error-unknown = Erreur : { $details }
+error-days-range = { $parameter } doit être compris entre 1 et 10
+error-elevation-range = min_max_elevation doit être compris entre 0 et 90
+error-quality-range = min_quality doit être compris entre 0 et 100
+error-count-range = { $parameter } doit être compris entre 1 et { $max }
+error-no-such-location = lieu inconnu
+error-no-such-satellite = aucun satellite avec l'identifiant NORAD { $satellite_id }
+error-no-such-pass = Il n'y a pas de passage aussi loin. Essayez un passage plus proche ou une élévation plus basse.
+error-step-range = Le pas doit être compris entre 1 et { $max } secondes.
+error-unknown-frequencies = Je ne connais pas les fréquences de ce satellite. Essayez `/transmitters` pour les autres.
+error-no-transmitters = SatNOGS DB ne répertorie aucun émetteur actif sur ce satellite.
+error-no-observations = SatNOGS Network n'a aucune bonne observation récente de ce satellite.
+error-no-launches = Aucun lancement à venir de ce type n'est répertorié.
+error-not-subscribed-to-launches = Ce salon n'est pas abonné aux alertes de lancement.
+error-no-starlink-trains = Aucun lot de Starlink n'a été lancé assez récemment pour encore former un train.
+error-unknown-pass = Ce passage ne peut plus être suivi.
+error-pass-over = Ce passage est déjà terminé.
+error-pass-too-early = Le suivi en direct peut commencer jusqu'à { $minutes } minutes avant le passage.
+error-already-tracking = Ce passage est déjà suivi dans ce salon.
+error-tracking-unavailable = Ce passage ne peut pas être suivi pour le moment, veuillez réessayer plus tard.
+error-invalid-satellite = `{ $satellite }` n'est ni un identifiant NORAD ni un groupe. Groupes : { $groups }.
+error-no-satellites = Indiquez au moins un identifiant NORAD ou un nom de groupe.
+error-too-many-satellites = Indiquez au plus { $max } satellites à la fois.
+error-no-favorites = Vous n'avez aucun satellite favori. Ajoutez-en avec `/favorite-satellite add`.
+error-too-many-favorites = Vous pouvez avoir au plus { $max } satellites favoris.
+error-invalid-satellite-id = `{ $satellite }` n'est ni un identifiant NORAD ni un alias de ce serveur.
+error-alias-is-group = `{ $alias }` désigne plusieurs satellites. Donnez un seul identifiant NORAD.
+error-invalid-alias = Les alias doivent faire de 1 à { $max } caractères, ne peuvent pas être un nombre et ne peuvent pas contenir de virgules.
+error-reserved-alias = `{ $alias }` est déjà le nom d'un groupe.
+error-not-geostationary = { $satellite } n'est pas géostationnaire et se déplace dans le ciel. Essayez plutôt `/get-upcoming-passes`.
+error-unknown-satellite = Ni N2YO ni CelesTrak ne connaissent de satellite avec cet identifiant NORAD.
+error-satcat-not-loaded = Le SATCAT est encore en cours de téléchargement. Réessayez dans une minute.
+error-no-satcat-results = Rien dans le SATCAT ne correspond à cette recherche.
+error-too-many-aliases = Un serveur peut avoir au plus { $max } alias.
+error-no-such-alias = Il n'existe aucun alias de ce nom.
+error-no-aliases = Ce serveur n'a aucun alias. Ajoutez-en avec `/satellite-alias set`.
+error-already-favorite = Ce satellite fait déjà partie de vos favoris.
+error-not-favorite = Ce satellite ne fait pas partie de vos favoris.
+error-empty-session = Aucun de ces satellites ne passe assez haut pendant la session.
+error-session-too-late = La session doit se terminer dans les 10 jours.
+error-notes-too-long = Les notes peuvent contenir au plus { $max } caractères.
+error-not-an-image = La pièce jointe doit être une image.
+error-unknown-pass-time = Ce passage n'a pas été notifié dans ce salon. Choisissez-en un dans la liste, ou laissez vide.
+error-unknown-pass-id = Aucun passage avec cet identifiant n'a été notifié ici. Les identifiants figurent au pied des notifications et sont conservés { $days } jours après le passage.
+error-template-too-long = Les modèles peuvent faire au plus { $max } caractères.
+error-template-braces = Ce modèle contient une accolade non fermée. Écrivez les variables comme { $placeholders } entre accolades simples, et les accolades elles-mêmes comme {"{{"} et {"}}"}.
+error-unknown-placeholder = La variable {"{"}{ $placeholder }{"}"} n'existe pas. Les modèles peuvent utiliser { $placeholders }.
+error-empty-gallery = Aucune observation avec image n'a encore été enregistrée ici. Utilisez `/log-observation` pour en ajouter une.
+error-no-location-given = aucun lieu indiqué et aucun lieu par défaut défini, voir `/settings default-location`
+error-location-exists = ce lieu existe déjà
+error-location-not-creator = le lieu ne peut être supprimé que par son créateur
+error-already-watched = ce satellite est déjà suivi dans ce salon avec ces paramètres
+error-no-such-watch = suivi inconnu
+error-no-such-watch-of-yours = vous n'avez aucun suivi correspondant
+error-invalid-tag = Les étiquettes ne peuvent pas être vides ni dépasser { $max } caractères.
+error-too-many-tags = Un suivi peut avoir au plus { $max } étiquettes.
+error-no-watches-tagged = Aucun suivi n'a l'étiquette { $tag }.
+error-no-watches-of-yours-tagged = Aucun de vos suivis n'a l'étiquette { $tag }.
+error-same-channel = Les suivis ne peuvent pas être déplacés vers le salon où ils sont déjà.
+error-no-watches-to-move = Ce salon n'a aucun suivi que vous pouvez déplacer.
+error-too-many-watches = Vous avez déjà { $max } suivis sur ce serveur, le maximum autorisé. Supprimez-en un avec `/unwatch-satellite` d'abord.
+error-max-watches-range = La limite de suivis doit être comprise entre 1 et { $max }.
+error-cooldown = Veuillez patienter { $seconds ->
+    [one] une seconde
+   *[other] { $seconds } secondes
+} avant de réessayer.
+error-cooldown-range = Les temps de recharge doivent être compris entre 0 et { $max } secondes.
+error-not-admin = Seuls les membres qui peuvent gérer le serveur, ou qui ont l'un de ses rôles d'administration, peuvent utiliser cette commande.
+error-manage-guild-only = Seuls les membres qui peuvent gérer le serveur peuvent modifier les rôles d'administration.
+error-invalid-duration = Ce n'est pas une durée. Indiquez-en une comme 90m, 12h, 3d ou 1w.
+error-mute-too-long = Les salons peuvent être mis en sourdine { $days } jours au plus.
+error-not-muted = Ce salon n'est pas en sourdine.
+error-invalid-vacation-end = Indiquez la date de reprise au format AAAA-MM-JJ, ou une durée comme 2w.
+error-vacation-range = Les vacances doivent se terminer dans le futur, et dans les { $days } jours.
+error-not-on-vacation = Vous n'êtes pas en vacances.
+error-not-watcher = le suivi ne peut être supprimé que par son créateur
+error-ambiguous-watch = plusieurs suivis correspondent, précisez le salon
+error-unknown-timezone = fuseau horaire inconnu { $timezone }, utilisez un nom comme Europe/Paris
+error-guild-only = cette commande ne peut être utilisée que sur un serveur
+
+## Locations
+
+location-added = Lieu ajouté
+location-added-description = { $name } ({ $author })
+locations-title = Lieux
+location-removed = Lieu supprimé
+location-removed-description = { $name }, créé par { $author }
+
+## Passes
+
+no-passes = Aucun passage trouvé
+note-days-clamped = Affichage { $days ->
+    [one] d'{ $days } jour
+   *[other] de { $days } jours
+}, la valeur prise en charge la plus proche.
+passes-title = Passages de { $satellite } { $days ->
+    [one] dans la journée
+   *[other] dans les { $days } prochains jours
+}
+passes-downlink = Écoutez { $frequency } pour { $description }, en { $mode }.
+pass-details = Élévation maximale : { $elevation }° · Qualité : { $quality }/100
+pass-path = AOS { $aos } → max { $elevation }° → LOS { $los }
+pass-table-time = Heure
+pass-table-azimuth = Azimut
+pass-table-elevation = Élévation
+pass-table-duration = Durée : { $duration }
+pass-visible = 👁 Visible à l'œil nu
+pass-radio-only = 📡 Radio uniquement
+pass-near-moon = 🌙 Passe à { $separation }° de la Lune (éclairée à { $illumination } %)
+pass-cloud-cover = ☁️ Couverture nuageuse : { $cover } %
+pass-magnitude = Magnitude { $magnitude }
+export-pass = Passage de { $satellite } à { $location }, { $time }
+data-source = Source des données : { $source }
+raw-unavailable = Il n'y a pas de réponse brute pour { $satellites }, car N2YO était indisponible et les passages ont été calculés localement.
+compare-title = Passages de { $satellite } à { $first } et { $second }
+compare-better = { $location } a les meilleurs passages en moyenne.
+compare-even = Aucun des lieux n'a les meilleurs passages en moyenne.
+compare-pass = { $time } · { $elevation }° · { $quality }/100
+compare-average = **Moyenne** : { $elevation }° · { $quality }/100
+best-pass-title = Meilleur passage de { $satellite } dans les { $days } prochains jours
+best-pass-rise = Lever
+best-pass-highest = Point culminant
+best-pass-set = Coucher
+best-pass-calendar = Ajouter au calendrier
+calendar-event-title = Passage de { $satellite }
+calendar-event-details = Passage au-dessus de { $location }, jusqu'à { $elevation }° d'élévation.
+next-pass = Le prochain passage de **{ $satellite }** commence { $countdown }, à { $time }, et atteint { $elevation }° de { $from } à { $to }.
+next-pass-none = **{ $satellite }** n'a aucun passage assez haut dans les { $days } prochains jours.
+pass-info-title = Passage { $id } de { $satellite }
+pass-info-attendees = Participants
+pass-info-nobody = Personne n'a annoncé sa participation.
+pass-info-observations = Observations
+pass-info-image = image
+session-title = Session d'observation à { $location }
+session-description = { $count } passages de { $start } à { $end }, avec le temps de tourner l'antenne entre chacun.
+session-pass = { $time } · { $elevation }° · { $quality }/100
+session-conflicts = { $count } passages qui se chevauchaient ont été écartés au profit de meilleurs.
+logbook-logged = Observation de { $satellite } enregistrée
+logbook-notified-pass = Passage de { $time } à { $location }
+logbook-time = Observé { $time }
+logbook-notes = Notes
+gallery-observer = Observé par { $observer } { $time }
+gallery-location = Depuis { $location }
+page-number = Page { $page } sur { $pages }
+tracking-title = { $satellite } en direct au-dessus de { $location }
+tracking-rises = Se lève { $time }
+tracking-sets = Se couche { $time }
+tracking-over = Le passage est terminé, le satellite s'est couché à { $time }.
+tracking-azimuth = Azimut
+tracking-elevation = Élévation
+tracking-range = Distance
+
+## Watches
+
+watch-added = Satellite suivi
+watch-added-description = { $satellite } avec une élévation minimale de { $elevation }° à { $location }, { $days ->
+    [one] 1 jour
+   *[other] { $days } jours
+} à l'avance, par { $author }
+watches-title = Satellites suivis
+watch-details =
+    Salon : { $channel }
+    Lieu : { $location }
+    Élévation minimale : { $elevation }°
+    Anticipation : { $days ->
+        [one] 1 jour
+       *[other] { $days } jours
+    }
+watch-notify-within = Prévenir dans les : { $hours } h
+watch-timezone = Fuseau horaire : { $timezone }
+watch-min-quality = Qualité minimale : { $quality }/100
+watch-visual = Passages visibles
+watch-brighter-than = Passages visibles plus brillants que la magnitude { $magnitude }
+watch-threads = Crée un fil pour chaque passage
+watch-templated = Modèle de notification personnalisé
+watch-tags = Étiquettes : { $tags }
+watch-paused = **En pause**
+watch-tag-choice = { $tag } ({ $count ->
+    [one] 1 suivi
+   *[other] { $count } suivis
+})
+watches-paused = { $count ->
+    [one] 1 suivi mis
+   *[other] { $count } suivis mis
+} en pause avec l'étiquette { $tag }.
+watches-resumed = { $count ->
+    [one] 1 suivi repris
+   *[other] { $count } suivis repris
+} avec l'étiquette { $tag }.
+mute-started = Les notifications de ce salon sont en sourdine jusqu'à { $time }. Les passages trouvés entre-temps seront listés à la fin de la sourdine.
+mute-ended = Les notifications de ce salon ne sont plus en sourdine.
+mute-lifted-title = Fin de la sourdine
+mute-lifted-pass = **{ $satellite }** depuis { $location } : { $time }, { $elevation }° (`{ $id }`)
+mute-lifted-more = …et { $count } de plus.
+mute-lifted-none = Aucun passage à venir n'a été trouvé pendant la sourdine.
+vacation-started = Vos suivis sont en pause jusqu'à { $time }. Vous recevrez un message privé à leur reprise.
+vacation-ended = Vos vacances sont terminées, et vos suivis reprendront à la prochaine vérification des notifications.
+vacation-over = Bon retour ! Vos suivis ont repris.
+vacation-over-passes = Les meilleurs passages à venir, d'une qualité d'au moins { $quality } :
+vacation-over-pass = **{ $satellite }** : { $time }, { $elevation }°, qualité { $quality }
+watch-disabled = **Désactivé** : { $reason }
+watch-removed = Suivi supprimé
+watch-updated = Suivi mis à jour
+watch-cloned = Suivi copié
+watches-moved = { $count ->
+    [one] 1 suivi déplacé
+   *[other] { $count } suivis déplacés
+} de { $from } vers { $to }.
+watch-description = { $satellite } ({ $author })
+watches-updated = Satellites suivis mis à jour
+
+## Settings
+
+settings-title = Paramètres
+settings-default-location = Lieu par défaut
+settings-units = Unités
+settings-time-format = Format de l'heure
+settings-ephemeral = Réponses privées
+settings-direct-messages = Messages privés
+settings-on = Activé
+settings-off = Désactivé
+settings-none = Aucun
+server-settings-title = Paramètres du serveur
+server-settings-default-elevation = Élévation minimale par défaut
+server-settings-max-watches = Suivis par membre
+server-settings-cooldowns = Temps de recharge des demandes de passages
+server-settings-cooldowns-value = { $user } s par membre, { $channel } s par salon
+server-settings-admin-roles = Rôles d'administration
+server-settings-no-admin-roles = Aucun, seuls les membres qui peuvent gérer le serveur
+favorites-title = Satellites favoris
+favorites-footer = Donnez `favorites` à /plan-session ou /get-upcoming-passes-multi pour tous les consulter.
+favorites-added = { $satellite } a été ajouté à vos favoris.
+favorites-removed = { $satellite } a été retiré de vos favoris.
+aliases-title = Alias de satellites
+alias-set = `{ $alias }` désigne désormais { $satellites }.
+alias-removed = L'alias `{ $alias }` a été supprimé.
+
+## Notifications
+
+notification-title = Prochain passage de { $satellite } à { $location }
+notification-frequency = Liaison descendante
+notification-sstv = SSTV
+notification-sstv-active = 📡 SSTV actif sur { $frequency } ({ $mode })
+notification-track-live = Suivre en direct
+notification-track-pass = Suivre le passage { $number }
+notification-pass-id = Passage { $id }
+notification-rsvp = J'en suis
+notification-rsvp-pass = J'en suis : passage { $number }
+rsvp-going = ✋ { $count } participants : { $names }
+rsvp-more = { " " }et { $count } autres
+rsvp-reminder = ⏰ Le passage de { $satellite } auquel vous participez commence { $time }, voir <#{ $channel }>.
+notification-watch-disabled = Votre suivi de { $satellite } à { $location } a été désactivé car je ne peux plus publier dans <#{ $channel }>. Utilisez `/edit-watch` avec un nouveau salon pour le réactiver.
+decay-reentry-title = { $satellite } devrait bientôt rentrer dans l'atmosphère
+decay-reentry = L'orbite de { $satellite } est descendue à { $perigee } × { $apogee } km, et il pourrait rentrer d'ici { $days } jours. Les passages s'arrêteront alors.
+decay-stale-title = Les éléments orbitaux de { $satellite } sont périmés
+decay-stale = Le TLE le plus récent de { $satellite } date de { $days } jours, ses passages peuvent donc être mal prédits. Il est peut-être rentré ou n'est plus suivi.
+decay-new-tle-title = { $satellite } a de nouveaux éléments orbitaux
+decay-new-tle = { $satellite } a un nouveau TLE après { $days } jours sans, et son orbite est maintenant de { $perigee } × { $apogee } km. Les passages notifiés jusqu'ici peuvent être faux.
+decay-manoeuvre = { $satellite } semble avoir manœuvré, son orbite passant de { $previous_perigee } × { $previous_apogee } km à { $perigee } × { $apogee } km. Les passages notifiés jusqu'ici peuvent être faux.
+
+## Space weather
+
+space-weather-title = Météo spatiale
+space-weather-current = Indice Kp actuel
+space-weather-predicted = Indice Kp prévu (prochaines 24 heures)
+space-weather-no-prediction = Aucune prévision disponible
+space-weather-kp = Kp { $kp }
+space-weather-kp-storm = Kp { $kp } (tempête G{ $level })
+space-weather-aurora = Aurore à { $location }
+space-weather-aurora-chance = { $chance } % de probabilité au zénith
+space-weather-source = Données de NOAA SWPC
+solar-title = Indices solaires
+solar-flux = Indice de flux solaire (SFI)
+solar-sunspot-number = Nombre de taches solaires
+solar-a-index = Indice A
+solar-k-index = Indice K
+solar-unavailable = Indisponible
+
+## Moon
+
+moon-title = La Lune depuis { $location }
+moon-phase = Phase
+moon-illumination = Éclairée
+moon-position = Position
+moon-position-up = { $azimuth }° d'azimut, { $elevation }° d'élévation
+moon-position-down = Sous l'horizon
+moon-rise = Prochain lever de Lune
+moon-set = Prochain coucher de Lune
+moon-not-soon = Pas dans les 2 prochains jours
+moon-phase-new = Nouvelle Lune
+moon-phase-waxing-crescent = Premier croissant
+moon-phase-first-quarter = Premier quartier
+moon-phase-waxing-gibbous = Gibbeuse croissante
+moon-phase-full = Pleine Lune
+moon-phase-waning-gibbous = Gibbeuse décroissante
+moon-phase-last-quarter = Dernier quartier
+moon-phase-waning-crescent = Dernier croissant
+geo-title = Pointage vers { $satellite } depuis { $location }
+geo-above-horizon = Il reste au même endroit dans le ciel, l'antenne n'a donc besoin d'être pointée qu'une fois.
+geo-below-horizon = Il est sous l'horizon ici et ne peut pas être atteint depuis cet endroit.
+geo-azimuth = Azimut
+geo-elevation = Élévation
+geo-range = Distance
+geo-longitude = Au-dessus de
+geo-east = { $longitude }° E
+geo-west = { $longitude }° O
+geo-tle-epoch = D'après le TLE du { $time }
+satellite-info-id = Identifiant NORAD
+satellite-info-designator = Désignation internationale
+satellite-info-launched = Lancé
+satellite-info-type = Type
+satellite-info-period = Période
+satellite-info-minutes = { $minutes } minutes
+satellite-info-inclination = Inclinaison
+satellite-info-orbit = Périgée × apogée
+satellite-info-tle-age = Âge du TLE
+satellite-info-days = { $days ->
+    [one] { $days } jour
+   *[other] { $days } jours
+}
+satellite-info-no-tle = Aucun TLE
+satellite-info-unknown = Inconnu
+satellite-info-source = D'après le dernier TLE et le SATCAT de CelesTrak
+satcat-search-title = Résultats du SATCAT pour « { $query } »
+satcat-search-truncated = Seuls les { $max } premiers résultats sont affichés. Ajoutez des filtres pour affiner la recherche.
+satcat-search-result = { $designator } · { $kind } · { $owner } · lancé le { $launched }
+satcat-search-decayed = {" "}· rentré le { $date }
+satcat-search-watch = Suivre
+satcat-search-watch-hint = Lancez `/watch-satellite satellite_id:{ $id }` dans le salon pour être averti de ses passages.
+
+## Frequencies
+
+frequencies-title = Fréquences de { $satellite }
+frequencies-downlink = Liaison descendante : { $frequency }
+frequencies-uplink = Liaison montante : { $frequency }
+frequencies-mode = Mode : { $mode }
+frequencies-tone = Tonalité : { $tone } Hz
+transmitters-title = Émetteurs actifs de { $satellite_id }
+transmitters-baud = Bauds : { $baud }
+transmitters-more = Et { $count } de plus sur SatNOGS DB
+observation-title = Observation { $id } depuis { $station }
+
+## Launches
+
+launches-title = Lancements à venir
+launches-source = Données de Launch Library 2 par The Space Devs
+launch-net-unknown = Date à confirmer
+launch-alert-title = Nouvelles d'un lancement
+launch-alert-footer = Suivez les nouveaux satellites dès que leurs TLE apparaissent
+launch-scheduled = { $launch } a été programmé
+launch-slipped = { $launch } a été repoussé depuis { $previous }
+launch-alerts-subscribed = Ce salon sera informé des lancements de type { $categories }.
+launch-alerts-unsubscribed = Ce salon ne sera plus informé des lancements.
+
+## Starlink
+
+starlink-title = Trains Starlink depuis { $location }
+starlink-description = Les heures sont celles du premier satellite de chaque train, les autres suivant dans les minutes qui suivent.
+starlink-batch = Lancement { $launch } · { $count } satellites à environ { $altitude } km
+starlink-pass = { $time }, jusqu'à { $elevation }°, de { $from } vers { $to }
+starlink-no-passes = Aucun passage visible
+starlink-source = Orbites de CelesTrak, prédites localement
+
+## Statistics
+
+stats-title = Statistiques
+stats-started = Démarré
+stats-locations = Lieux
+stats-watches = Satellites suivis
+stats-notifications-sent = Notifications envoyées (depuis le démarrage)
+stats-last-notify-run = Dernière vérification
+stats-notify-restarts = Redémarrages des notifications
+stats-never = Jamais
+stats-notifications-this-week = Notifications cette semaine
+stats-commands-this-week = Commandes cette semaine
+stats-top-satellites = Satellites les plus notifiés
+stats-top-commands = Commandes les plus utilisées
+stats-n2yo-hourly = Transactions N2YO (dernière heure)
+stats-n2yo-today = Transactions N2YO (aujourd'hui, UTC)
+stats-n2yo-total = Transactions N2YO (depuis le démarrage)
+stats-n2yo-keys = Clés N2YO
+commands-synced = { $count } commandes enregistrées auprès de Discord. Elles peuvent mettre une minute à apparaître.
+
+## Help
+
+help-title = Commandes
+help-description = Choisissez une commande ci-dessous pour voir son utilisation et des exemples.
+help-placeholder = Choisissez une commande
+help-placeholder-more = Plus de commandes
+help-overview = Vue d'ensemble
+help-overview-description = Toutes les commandes par catégorie
+help-usage = Utilisation
+help-parameters = Paramètres
+help-details = Détails
+help-optional = (facultatif)
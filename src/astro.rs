@@ -0,0 +1,384 @@
+use poise::ChoiceParameter;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    database::Location,
+    n2yo::SatellitePass,
+    propagation::{self, LookAngles, Orbit, EARTH_RADIUS},
+};
+
+/// The mean distance from the Earth to the Sun, in kilometres.
+const ASTRONOMICAL_UNIT: f64 = 149_597_870.7;
+/// How far below the horizon the Sun must be for it to be dark enough to observe, in degrees.
+/// This is the end of civil twilight.
+const DARKNESS_ELEVATION: f64 = -6.0;
+/// The time step used when checking whether a satellite can be seen during a pass, in seconds.
+const VISIBILITY_STEP: usize = 10;
+/// The obliquity of the ecliptic, in degrees. It changes too slowly to matter here.
+const OBLIQUITY: f64 = 23.439;
+/// The elevation of the Moon's centre when its upper limb is on the horizon, allowing for
+/// refraction, in degrees.
+const MOON_HORIZON: f64 = -0.833;
+/// The time step used when searching for moonrise and moonset, in seconds.
+const RISE_SET_STEP: i64 = 5 * 60;
+
+/// The position of the Sun in the equatorial (TEME) frame at a unix timestamp, in kilometres,
+/// using the low precision formulae from the Astronomical Almanac. This is accurate to around
+/// a hundredth of a degree, far better than needed for deciding whether it is dark.
+pub fn sun_position(time: i64) -> [f64; 3] {
+    let days = time as f64 / 86400.0 + 2440587.5 - 2451545.0;
+    let mean_longitude = 280.460 + 0.9856474 * days;
+    let mean_anomaly = (357.528 + 0.9856003 * days).to_radians();
+    let ecliptic_longitude =
+        (mean_longitude + 1.915 * mean_anomaly.sin() + 0.020 * (2.0 * mean_anomaly).sin())
+            .to_radians();
+    let obliquity = (23.439 - 0.0000004 * days).to_radians();
+    let distance = (1.00014 - 0.01671 * mean_anomaly.cos() - 0.00014 * (2.0 * mean_anomaly).cos())
+        * ASTRONOMICAL_UNIT;
+
+    [
+        distance * ecliptic_longitude.cos(),
+        distance * obliquity.cos() * ecliptic_longitude.sin(),
+        distance * obliquity.sin() * ecliptic_longitude.sin(),
+    ]
+}
+
+/// Where the Sun is in the sky at a location and unix timestamp.
+pub fn sun_look_angles(location: &Location, time: i64) -> LookAngles {
+    propagation::topocentric(
+        location,
+        propagation::teme_to_ecef(sun_position(time), time),
+    )
+}
+
+/// Whether a position in the equatorial (TEME) frame is lit by the Sun, treating the Earth's
+/// shadow as a cylinder. Penumbra is ignored, as it lasts only seconds for low orbits.
+pub fn is_sunlit(position: [f64; 3], time: i64) -> bool {
+    let sun = sun_position(time);
+    let sun_distance = dot(sun, sun).sqrt();
+    let towards_sun = dot(position, sun) / sun_distance;
+
+    towards_sun > 0.0 || dot(position, position) - towards_sun.powi(2) > EARTH_RADIUS.powi(2)
+}
+
+/// Whether it is dark enough at a location to see satellites.
+pub fn is_dark(location: &Location, time: i64) -> bool {
+    sun_look_angles(location, time).elevation < DARKNESS_ELEVATION
+}
+
+/// The fraction of a pass for which the satellite can be seen with the naked eye, which needs it
+/// to be above the horizon and lit by the Sun while the observer is in darkness.
+pub fn visible_fraction(
+    orbit: &Orbit,
+    location: &Location,
+    pass: &SatellitePass,
+) -> anyhow::Result<f64> {
+    let times = (pass.start_utc as i64..=pass.end_utc as i64)
+        .step_by(VISIBILITY_STEP)
+        .collect::<Vec<_>>();
+    let mut visible = 0;
+
+    for &time in times.iter() {
+        if !is_dark(location, time) {
+            continue;
+        }
+
+        let position = orbit.position(time)?;
+
+        if is_sunlit(position, time)
+            && propagation::topocentric(location, propagation::teme_to_ecef(position, time))
+                .elevation
+                > 0.0
+        {
+            visible += 1;
+        }
+    }
+
+    Ok(visible as f64 / times.len().max(1) as f64)
+}
+
+/// The position of the Moon in the equatorial (TEME) frame at a unix timestamp, in kilometres,
+/// using the low precision formulae from the Astronomical Almanac. This is accurate to around
+/// three tenths of a degree, plenty for rise and set times and whether it is near a satellite.
+pub fn moon_position(time: i64) -> [f64; 3] {
+    let centuries = (time as f64 / 86400.0 + 2440587.5 - 2451545.0) / 36525.0;
+    let term = |a: f64, b: f64| (a + b * centuries).to_radians();
+
+    let longitude = (218.32 + 481267.881 * centuries + 6.29 * term(134.9, 477198.85).sin()
+        - 1.27 * term(259.2, -413335.38).sin()
+        + 0.66 * term(235.7, 890534.23).sin()
+        + 0.21 * term(269.9, 954397.70).sin()
+        - 0.19 * term(357.5, 35999.05).sin()
+        - 0.11 * term(186.6, 966404.05).sin())
+    .to_radians();
+    let latitude = (5.13 * term(93.3, 483202.03).sin() + 0.28 * term(228.2, 960400.87).sin()
+        - 0.28 * term(318.3, 6003.18).sin()
+        - 0.17 * term(217.6, -407332.20).sin())
+    .to_radians();
+    let parallax = (0.9508
+        + 0.0518 * term(134.9, 477198.85).cos()
+        + 0.0095 * term(259.2, -413335.38).cos()
+        + 0.0078 * term(235.7, 890534.23).cos()
+        + 0.0028 * term(269.9, 954397.70).cos())
+    .to_radians();
+
+    let distance = EARTH_RADIUS / parallax.sin();
+    let obliquity = OBLIQUITY.to_radians();
+
+    [
+        distance * latitude.cos() * longitude.cos(),
+        distance
+            * (obliquity.cos() * latitude.cos() * longitude.sin()
+                - obliquity.sin() * latitude.sin()),
+        distance
+            * (obliquity.sin() * latitude.cos() * longitude.sin()
+                + obliquity.cos() * latitude.sin()),
+    ]
+}
+
+/// Where the Moon is in the sky at a location and unix timestamp.
+pub fn moon_look_angles(location: &Location, time: i64) -> LookAngles {
+    propagation::topocentric(
+        location,
+        propagation::teme_to_ecef(moon_position(time), time),
+    )
+}
+
+/// How far the Moon is around its orbit from new, in degrees, measured eastwards from the Sun
+/// along the ecliptic. It is full at 180°.
+pub fn moon_age(time: i64) -> f64 {
+    (ecliptic_longitude(moon_position(time)) - ecliptic_longitude(sun_position(time)))
+        .rem_euclid(360.0)
+}
+
+/// The fraction of the Moon's disc that is lit, from 0 at new Moon to 1 at full Moon.
+pub fn moon_illumination(time: i64) -> f64 {
+    let sun = sun_position(time);
+    let moon = moon_position(time);
+    let elongation = dot(sun, moon) / (dot(sun, sun).sqrt() * dot(moon, moon).sqrt());
+
+    (1.0 - elongation.clamp(-1.0, 1.0)) / 2.0
+}
+
+/// The ecliptic longitude of a position in the equatorial (TEME) frame, in degrees.
+fn ecliptic_longitude(position: [f64; 3]) -> f64 {
+    let obliquity = OBLIQUITY.to_radians();
+
+    (position[1] * obliquity.cos() + position[2] * obliquity.sin())
+        .atan2(position[0])
+        .to_degrees()
+}
+
+/// The phases of the Moon.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoonPhase {
+    New,
+    WaxingCrescent,
+    FirstQuarter,
+    WaxingGibbous,
+    Full,
+    WaningGibbous,
+    LastQuarter,
+    WaningCrescent,
+}
+
+impl MoonPhase {
+    /// The phase of the Moon at a unix timestamp.
+    pub fn at(time: i64) -> Self {
+        const PHASES: [MoonPhase; 8] = [
+            MoonPhase::New,
+            MoonPhase::WaxingCrescent,
+            MoonPhase::FirstQuarter,
+            MoonPhase::WaxingGibbous,
+            MoonPhase::Full,
+            MoonPhase::WaningGibbous,
+            MoonPhase::LastQuarter,
+            MoonPhase::WaningCrescent,
+        ];
+
+        PHASES[((moon_age(time) + 22.5) / 45.0) as usize % 8]
+    }
+
+    /// The translation key naming this phase.
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::New => "moon-phase-new",
+            Self::WaxingCrescent => "moon-phase-waxing-crescent",
+            Self::FirstQuarter => "moon-phase-first-quarter",
+            Self::WaxingGibbous => "moon-phase-waxing-gibbous",
+            Self::Full => "moon-phase-full",
+            Self::WaningGibbous => "moon-phase-waning-gibbous",
+            Self::LastQuarter => "moon-phase-last-quarter",
+            Self::WaningCrescent => "moon-phase-waning-crescent",
+        }
+    }
+
+    pub fn emoji(self) -> &'static str {
+        match self {
+            Self::New => "🌑",
+            Self::WaxingCrescent => "🌒",
+            Self::FirstQuarter => "🌓",
+            Self::WaxingGibbous => "🌔",
+            Self::Full => "🌕",
+            Self::WaningGibbous => "🌖",
+            Self::LastQuarter => "🌗",
+            Self::WaningCrescent => "🌘",
+        }
+    }
+}
+
+/// The next times the Moon rises and sets at a location within the given number of seconds,
+/// accurate to a few minutes.
+pub fn moon_rise_set(location: &Location, start: i64, within: i64) -> (Option<i64>, Option<i64>) {
+    let altitude = |time| moon_look_angles(location, time).elevation - MOON_HORIZON;
+    let mut rise = None;
+    let mut set = None;
+    let mut time = start;
+    let mut previous = altitude(time);
+
+    while time < start + within && (rise.is_none() || set.is_none()) {
+        let next = altitude(time + RISE_SET_STEP);
+
+        if previous < 0.0 && next >= 0.0 && rise.is_none() {
+            rise = Some(time + (RISE_SET_STEP as f64 * previous / (previous - next)) as i64);
+        } else if previous >= 0.0 && next < 0.0 && set.is_none() {
+            set = Some(time + (RISE_SET_STEP as f64 * previous / (previous - next)) as i64);
+        }
+
+        time += RISE_SET_STEP;
+        previous = next;
+    }
+
+    (rise, set)
+}
+
+/// The angle between two directions in the sky, in degrees.
+pub fn angular_separation(a: &LookAngles, b: &LookAngles) -> f64 {
+    let (a_elevation, b_elevation) = (a.elevation.to_radians(), b.elevation.to_radians());
+
+    (a_elevation.sin() * b_elevation.sin()
+        + a_elevation.cos() * b_elevation.cos() * (a.azimuth - b.azimuth).to_radians().cos())
+    .clamp(-1.0, 1.0)
+    .acos()
+    .to_degrees()
+}
+
+/// How close a satellite comes to the Moon during a pass, in degrees, if the Moon is up at all
+/// while it is.
+pub fn moon_separation(
+    orbit: &Orbit,
+    location: &Location,
+    pass: &SatellitePass,
+) -> anyhow::Result<Option<f64>> {
+    let mut closest: Option<f64> = None;
+
+    for time in (pass.start_utc as i64..=pass.end_utc as i64).step_by(VISIBILITY_STEP) {
+        let moon = moon_look_angles(location, time);
+
+        if moon.elevation <= 0.0 {
+            continue;
+        }
+
+        let satellite = propagation::topocentric(
+            location,
+            propagation::teme_to_ecef(orbit.position(time)?, time),
+        );
+
+        if satellite.elevation > 0.0 {
+            let separation = angular_separation(&satellite, &moon);
+            closest = Some(closest.map_or(separation, |closest| closest.min(separation)));
+        }
+    }
+
+    Ok(closest)
+}
+
+fn dot(a: [f64; 3], b: [f64; 3]) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+/// Which passes to include, by whether it is light at the location.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ChoiceParameter)]
+pub enum Lighting {
+    #[default]
+    #[name = "any"]
+    Any,
+    /// The Sun is up, which some APT decoders prefer.
+    #[name = "daytime"]
+    Day,
+    /// The Sun is far enough below the horizon for visual observing.
+    #[name = "nighttime"]
+    Night,
+}
+
+impl Lighting {
+    /// Whether a pass at a location happens in this lighting, judged at its highest point.
+    pub fn includes(self, location: &Location, pass: &SatellitePass) -> bool {
+        match self {
+            Self::Any => true,
+            Self::Day => sun_look_angles(location, pass.max_utc as i64).elevation > 0.0,
+            Self::Night => is_dark(location, pass.max_utc as i64),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        database::{LocationName, Snowflake},
+        testing::pass,
+    };
+
+    /// Noon UTC on the June solstice of 2024.
+    const SOLSTICE_NOON: i64 = 1_718_971_200;
+
+    fn london() -> Location {
+        Location {
+            name: LocationName("london".to_string()),
+            creator: Snowflake(1),
+            latitude: 51.5,
+            longitude: -0.1,
+            altitude: 0.0,
+            guild: None,
+        }
+    }
+
+    /// A pass peaking at `max_utc`.
+    fn peaking_at(max_utc: i64) -> SatellitePass {
+        pass(max_utc - 300, 45.0)
+    }
+
+    #[test]
+    fn finds_the_sun() {
+        let london = london();
+
+        // At noon the Sun is 90° - 51.5° + 23.44° up, and due south
+        let noon = sun_look_angles(&london, SOLSTICE_NOON);
+        assert!((noon.elevation - 61.9).abs() < 0.5);
+        assert!((noon.azimuth - 180.0).abs() < 2.0);
+
+        // At midnight it is as far below the northern horizon
+        let midnight = sun_look_angles(&london, SOLSTICE_NOON - 12 * 60 * 60);
+        assert!((midnight.elevation + 15.0).abs() < 0.5);
+        assert!(midnight.azimuth < 2.0 || midnight.azimuth > 358.0);
+    }
+
+    #[test]
+    fn tells_day_from_night() {
+        let london = london();
+        let twilight = SOLSTICE_NOON + (8 * 60 + 45) * 60;
+
+        assert!(Lighting::Day.includes(&london, &peaking_at(SOLSTICE_NOON)));
+        assert!(!Lighting::Night.includes(&london, &peaking_at(SOLSTICE_NOON)));
+
+        assert!(!Lighting::Day.includes(&london, &peaking_at(SOLSTICE_NOON - 12 * 60 * 60)));
+        assert!(Lighting::Night.includes(&london, &peaking_at(SOLSTICE_NOON - 12 * 60 * 60)));
+
+        // After sunset, but too light to see satellites until civil twilight ends
+        assert!(sun_look_angles(&london, twilight).elevation < 0.0);
+        assert!(!Lighting::Day.includes(&london, &peaking_at(twilight)));
+        assert!(!Lighting::Night.includes(&london, &peaking_at(twilight)));
+        assert!(Lighting::Any.includes(&london, &peaking_at(twilight)));
+    }
+}
@@ -0,0 +1,144 @@
+use std::{sync::Arc, time::Duration};
+
+use serde::{Deserialize, Serialize};
+use tokio::{sync::RwLock, task::JoinHandle, time::interval};
+use tracing::{error, info, warn};
+
+use crate::{
+    celestrak::CelesTrakAPI,
+    config,
+    database::{Database, SatelliteId},
+    n2yo::N2YOAPI,
+    propagation::CachedTle,
+    util,
+};
+
+/// Cached information about a satellite.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SatelliteMetadata {
+    pub name: String,
+    pub international_designator: Option<String>,
+    pub category: Option<String>,
+    pub updated_at: i64,
+}
+
+/// Gets the metadata of a satellite, fetching it and caching it in the database if it is not
+/// already known. The database is not locked while fetching, so callers must not hold it.
+pub async fn lookup(
+    database: &RwLock<Database>,
+    n2yo_api: &N2YOAPI,
+    celestrak_api: &CelesTrakAPI,
+    satellite_id: usize,
+) -> anyhow::Result<SatelliteMetadata> {
+    let cached = database
+        .read()
+        .await
+        .contents
+        .satellites
+        .get(&SatelliteId(satellite_id))
+        .cloned();
+
+    if let Some(metadata) = cached {
+        return Ok(metadata);
+    }
+
+    let (metadata, tle) = fetch(n2yo_api, celestrak_api, satellite_id).await?;
+    store(
+        &mut *database.write().await,
+        satellite_id,
+        metadata.clone(),
+        tle,
+    );
+    Ok(metadata)
+}
+
+/// Fetches the metadata and current TLE of a satellite. The name is taken from N2YO, so that it
+/// matches pass embeds, while SATCAT details are added where CelesTrak has them.
+pub async fn fetch(
+    n2yo_api: &N2YOAPI,
+    celestrak_api: &CelesTrakAPI,
+    satellite_id: usize,
+) -> anyhow::Result<(SatelliteMetadata, Option<CachedTle>)> {
+    let tle = n2yo_api.get_tle(satellite_id).await?;
+    let record = match celestrak_api.get_satcat_record(satellite_id).await {
+        Ok(record) => record,
+        Err(e) => {
+            warn!("Failed to get SATCAT record for {}: {}", satellite_id, e);
+            None
+        }
+    };
+
+    let metadata = SatelliteMetadata {
+        name: tle.info.name,
+        international_designator: record
+            .as_ref()
+            .map(|record| record.international_designator.clone()),
+        category: record.as_ref().map(|record| record.category()),
+        updated_at: util::current_utc(),
+    };
+
+    Ok((
+        metadata,
+        CachedTle::parse(&tle.tle, util::current_utc()).ok(),
+    ))
+}
+
+fn store(
+    database: &mut Database,
+    satellite_id: usize,
+    metadata: SatelliteMetadata,
+    tle: Option<CachedTle>,
+) {
+    database
+        .contents
+        .satellites
+        .insert(SatelliteId(satellite_id), metadata);
+
+    if let Some(tle) = tle {
+        database.contents.store_tle(SatelliteId(satellite_id), tle);
+    }
+
+    database.mark_dirty();
+}
+
+/// Spawns the task that periodically refreshes stale entries in the satellite cache.
+pub fn spawn_refresh_task(
+    database: Arc<RwLock<Database>>,
+    n2yo_api: Arc<N2YOAPI>,
+    celestrak_api: Arc<CelesTrakAPI>,
+) -> anyhow::Result<JoinHandle<()>> {
+    let max_age = config::get().catalog.max_age_hours * 60 * 60;
+
+    Ok(tokio::spawn(async move {
+        let mut interval = interval(Duration::from_secs(60 * 60));
+
+        loop {
+            interval.tick().await;
+
+            let stale = database
+                .read()
+                .await
+                .contents
+                .satellites
+                .iter()
+                .filter(|(_, metadata)| util::current_utc() - metadata.updated_at > max_age)
+                .map(|(id, _)| id.0)
+                .collect::<Vec<_>>();
+
+            if stale.is_empty() {
+                continue;
+            }
+
+            info!("Refreshing {} satellite cache entries", stale.len());
+
+            for satellite_id in stale {
+                match fetch(&n2yo_api, &celestrak_api, satellite_id).await {
+                    Ok((metadata, tle)) => {
+                        store(&mut *database.write().await, satellite_id, metadata, tle)
+                    }
+                    Err(e) => error!("Failed to refresh satellite {}: {}", satellite_id, e),
+                }
+            }
+        }
+    }))
+}
@@ -0,0 +1,195 @@
+use std::{collections::HashMap, sync::Mutex};
+
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use crate::{mock, util};
+
+/// How long the TLEs of a group are reused for, in seconds. CelesTrak only updates them every
+/// couple of hours, and blocks clients that ask more often.
+const GROUP_TTL: i64 = 2 * 60 * 60;
+
+pub struct CelesTrakAPI {
+    client: reqwest::Client,
+    /// The TLEs of each group, with when they were fetched.
+    group_cache: Mutex<HashMap<String, (i64, String)>>,
+    /// Whether canned responses are returned instead of calling CelesTrak.
+    mock: bool,
+}
+
+impl CelesTrakAPI {
+    pub fn new() -> anyhow::Result<Self> {
+        info!("Creating CelesTrak API client");
+        Ok(Self {
+            client: reqwest::ClientBuilder::new()
+                .user_agent(util::USER_AGENT)
+                .build()?,
+            group_cache: Mutex::new(HashMap::new()),
+            mock: mock::is_enabled(),
+        })
+    }
+
+    /// A client that returns canned responses, whatever the configuration.
+    #[cfg(test)]
+    pub fn mocked() -> Self {
+        Self {
+            mock: true,
+            ..Self::new().unwrap()
+        }
+    }
+
+    /// Gets the SATCAT record of a satellite, if CelesTrak knows of it.
+    pub async fn get_satcat_record(
+        &self,
+        satellite_id: usize,
+    ) -> anyhow::Result<Option<SatcatRecord>> {
+        if self.mock {
+            return Ok(None);
+        }
+
+        let url = format!(
+            "https://celestrak.org/satcat/records.php?CATNR={}&FORMAT=JSON",
+            satellite_id
+        );
+
+        info!("Sending request to {}", url);
+
+        let response = self.client.get(&url).send().await?.error_for_status()?;
+        let text = response.text().await?;
+
+        // CelesTrak responds with plain text rather than an empty array for unknown satellites
+        if !text.trim_start().starts_with('[') {
+            return Ok(None);
+        }
+
+        Ok(serde_json::from_str::<Vec<SatcatRecord>>(&text)?
+            .into_iter()
+            .next())
+    }
+
+    /// Gets CelesTrak's whole SATCAT as CSV, with a header row. It is several megabytes, so should
+    /// be fetched at most daily.
+    pub async fn get_satcat_csv(&self) -> anyhow::Result<String> {
+        if self.mock {
+            return Ok(mock::satcat_csv());
+        }
+
+        let url = "https://celestrak.org/pub/satcat.csv";
+
+        info!("Sending request to {}", url);
+
+        Ok(self
+            .client
+            .get(url)
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?)
+    }
+
+    /// Gets the current TLE of a satellite, including its title line.
+    pub async fn get_tle(&self, satellite_id: usize) -> anyhow::Result<String> {
+        if self.mock {
+            return Ok(mock::tle_text(satellite_id));
+        }
+
+        let url = format!(
+            "https://celestrak.org/NORAD/elements/gp.php?CATNR={}&FORMAT=TLE",
+            satellite_id
+        );
+
+        info!("Sending request to {}", url);
+
+        Ok(self
+            .client
+            .get(&url)
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?)
+    }
+
+    /// Gets the TLEs of every object in a CelesTrak group, such as `last-30-days`, each with its
+    /// title line.
+    pub async fn get_group_tles(&self, group: &str) -> anyhow::Result<String> {
+        if self.mock {
+            return Ok(mock::group_tles(group));
+        }
+
+        if let Some((fetched_at, text)) = self.group_cache.lock().unwrap().get(group) {
+            if util::current_utc() - fetched_at < GROUP_TTL {
+                return Ok(text.clone());
+            }
+        }
+
+        let url = format!(
+            "https://celestrak.org/NORAD/elements/gp.php?GROUP={}&FORMAT=TLE",
+            group
+        );
+
+        info!("Sending request to {}", url);
+
+        let text = self
+            .client
+            .get(&url)
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+
+        self.group_cache
+            .lock()
+            .unwrap()
+            .insert(group.to_string(), (util::current_utc(), text.clone()));
+        Ok(text)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SatcatRecord {
+    #[serde(rename = "OBJECT_NAME")]
+    pub name: String,
+    #[serde(rename = "OBJECT_ID")]
+    pub international_designator: String,
+    #[serde(rename = "NORAD_CAT_ID")]
+    pub id: usize,
+    #[serde(rename = "OBJECT_TYPE")]
+    pub object_type: String,
+    /// The country or organisation that owns the object, as a SATCAT code such as `US` or `PRC`.
+    #[serde(rename = "OWNER", default)]
+    pub owner: Option<String>,
+    /// The launch date, as `YYYY-MM-DD`.
+    #[serde(rename = "LAUNCH_DATE", default)]
+    pub launch_date: Option<String>,
+    /// The date the object reentered, as `YYYY-MM-DD`, if it has.
+    #[serde(rename = "DECAY_DATE", default)]
+    pub decay_date: Option<String>,
+    /// The orbital period, in minutes.
+    #[serde(rename = "PERIOD", default)]
+    pub period: Option<f64>,
+    /// The inclination, in degrees.
+    #[serde(rename = "INCLINATION", default)]
+    pub inclination: Option<f64>,
+    /// The highest altitude of the orbit, in kilometres.
+    #[serde(rename = "APOGEE", default)]
+    pub apogee: Option<f64>,
+    /// The lowest altitude of the orbit, in kilometres.
+    #[serde(rename = "PERIGEE", default)]
+    pub perigee: Option<f64>,
+}
+
+impl SatcatRecord {
+    /// A human readable description of the object type.
+    pub fn category(&self) -> String {
+        match self.object_type.as_str() {
+            "PAY" => "Payload",
+            "R/B" => "Rocket body",
+            "DEB" => "Debris",
+            _ => "Unknown",
+        }
+        .to_string()
+    }
+}
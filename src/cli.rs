@@ -0,0 +1,130 @@
+use std::sync::Arc;
+
+use clap::{Parser, Subcommand};
+use serenity::http::Http;
+use tokio::sync::RwLock;
+use tracing::info;
+
+use crate::{
+    celestrak::CelesTrakAPI,
+    config,
+    database::{self, Database},
+    metrics::Metrics,
+    n2yo::N2YOAPI,
+    notify, preflight,
+    provider::PassProvider,
+    registration::{self, RegistrationMode},
+    satnogs::SatnogsAPI,
+    weather::WeatherAPI,
+};
+
+/// A Discord bot that notifies you of satellite passes.
+#[derive(Debug, Parser)]
+#[command(version)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<CliCommand>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum CliCommand {
+    /// Runs the bot (the default).
+    Run,
+    /// Checks the configuration and exits.
+    ValidateConfig,
+    /// Checks that the database can be written and that N2YO and Discord accept their credentials,
+    /// as is done before the bot starts.
+    Preflight,
+    /// Registers slash commands according to COMMAND_REGISTRATION, without starting the bot.
+    RegisterCommands,
+    /// Rewrites the database in the current format, keeping a backup of the original.
+    MigrateDb,
+    /// Runs a single notification check.
+    Notify {
+        /// Only log what would be sent, without sending or recording anything.
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+/// The configuration is checked as it is loaded, so getting this far means it is valid.
+pub fn validate_config() -> anyhow::Result<()> {
+    println!("Configuration is valid");
+    Ok(())
+}
+
+pub async fn preflight() -> anyhow::Result<()> {
+    preflight::check(&N2YOAPI::new()?).await?;
+    println!("Preflight checks passed");
+    Ok(())
+}
+
+pub async fn register_commands() -> anyhow::Result<()> {
+    let http = http().await?;
+    let guilds = match config::get().discord.command_registration {
+        RegistrationMode::AllGuilds => registration::fetch_guilds(&http).await?,
+        _ => Vec::new(),
+    };
+
+    registration::register(&http, &crate::commands(), guilds.into_iter()).await?;
+    println!("Registered commands");
+    Ok(())
+}
+
+pub fn migrate_db() -> anyhow::Result<()> {
+    let path = &config::get().database.path;
+    let backup = format!("{}.bak", path.display());
+
+    if path.exists() {
+        std::fs::copy(path, &backup)?;
+        info!("Backed up database to {}", backup);
+    }
+
+    // Loading fills in any fields missing from older databases, and saving writes them back
+    let (database, _) = Database::open()?;
+    database.save()?;
+    println!(
+        "Migrated database with {} location(s) and {} watch(es)",
+        database.contents.locations.len(),
+        database.contents.watched_satellites.len()
+    );
+    Ok(())
+}
+
+pub async fn notify(dry_run: bool) -> anyhow::Result<()> {
+    let http = Arc::new(http().await?);
+    let (database, persistence_receiver) = Database::open()?;
+    let database = Arc::new(RwLock::new(database));
+    let persistence_worker =
+        database::spawn_persistence_worker(database.clone(), persistence_receiver);
+    let pass_provider = PassProvider::new(
+        Arc::new(N2YOAPI::new()?),
+        Arc::new(CelesTrakAPI::new()?),
+        Arc::new(WeatherAPI::new()?),
+        Arc::new(SatnogsAPI::new()?),
+        database.clone(),
+    )?;
+    let metrics = Metrics::new();
+
+    let result =
+        notify::notify_of_new_passes(&*http, &database, &pass_provider, &metrics, dry_run).await;
+
+    database::flush(&database).await;
+    persistence_worker.abort();
+    result?;
+
+    if dry_run {
+        println!("Dry run complete, nothing was sent");
+    } else {
+        println!("Sent {} notification(s)", metrics.notifications_sent());
+    }
+    Ok(())
+}
+
+/// A Discord HTTP client for use without a gateway connection.
+async fn http() -> anyhow::Result<Http> {
+    let http = Http::new(&config::get().discord.token);
+    let application = http.get_current_application_info().await?;
+    http.set_application_id(application.id.0);
+    Ok(http)
+}
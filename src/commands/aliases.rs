@@ -0,0 +1,152 @@
+use poise::command;
+
+use crate::{
+    commands::{admin_only, autocomplete, resolve_satellites},
+    database::{SatelliteId, Snowflake},
+    error::BotError,
+    groups,
+    i18n::{self, tr},
+    preferences::GuildSettings,
+    Context,
+};
+
+/// The most aliases a server can have, as Discord lists at most 25 autocomplete choices.
+const MAX_ALIASES: usize = 25;
+
+/// Names satellites, or groups of them, for the whole server.
+///
+/// Aliases can be given anywhere a NORAD ID is, and those for several satellites anywhere a list
+/// of them is.
+#[command(
+    slash_command,
+    rename = "satellite-alias",
+    category = "Satellites",
+    guild_only,
+    default_member_permissions = "MANAGE_GUILD",
+    check = "admin_only",
+    subcommands("set", "remove", "list")
+)]
+pub async fn satellite_alias(_ctx: Context<'_>) -> anyhow::Result<()> {
+    Ok(())
+}
+
+/// Names a satellite, or a group of them, replacing any alias with the same name.
+///
+/// Example: `/satellite-alias set name:noaa19 satellites:33591`
+/// Example: `/satellite-alias set name:weather birds satellites:33591, 40069, 57166`
+#[command(slash_command, rename = "set")]
+async fn set(
+    ctx: Context<'_>,
+    #[description = "the alias, which cannot be a number or contain commas"] name: String,
+    #[description = "NORAD IDs separated by commas, or a group name such as noaa"]
+    satellites: String,
+) -> anyhow::Result<()> {
+    ctx.defer_ephemeral().await?;
+    let locale = i18n::locale(&ctx);
+    let name = groups::check_alias_name(&*ctx.data().database.read().await, locale, &name)?;
+    let satellite_ids = resolve_satellites(ctx, &satellites)
+        .await?
+        .into_iter()
+        .map(SatelliteId)
+        .collect::<Vec<_>>();
+
+    update(ctx, |settings| {
+        if !settings.aliases.contains_key(&name) && settings.aliases.len() >= MAX_ALIASES {
+            return Err(
+                BotError::user(tr!(locale, "error-too-many-aliases", max = MAX_ALIASES)).into(),
+            );
+        }
+
+        settings.aliases.insert(name.clone(), satellite_ids.clone());
+        Ok(())
+    })
+    .await?;
+
+    ctx.say(tr!(
+        locale,
+        "alias-set",
+        alias = name.as_str(),
+        satellites = groups::list(&satellite_ids)
+    ))
+    .await?;
+    Ok(())
+}
+
+/// Removes an alias.
+#[command(slash_command, rename = "remove")]
+async fn remove(
+    ctx: Context<'_>,
+    #[description = "the alias to remove"]
+    #[autocomplete = "autocomplete::alias"]
+    name: String,
+) -> anyhow::Result<()> {
+    ctx.defer_ephemeral().await?;
+    let locale = i18n::locale(&ctx);
+    let name = name.trim().to_lowercase();
+
+    update(ctx, |settings| {
+        settings
+            .aliases
+            .remove(&name)
+            .map(|_| ())
+            .ok_or_else(|| BotError::user(tr!(locale, "error-no-such-alias")).into())
+    })
+    .await?;
+
+    ctx.say(tr!(locale, "alias-removed", alias = name.as_str()))
+        .await?;
+    Ok(())
+}
+
+/// Lists the server's aliases.
+#[command(slash_command, rename = "list")]
+async fn list(ctx: Context<'_>) -> anyhow::Result<()> {
+    ctx.defer_ephemeral().await?;
+    let locale = i18n::locale(&ctx);
+    let guild = ctx.guild_id().map(|guild| Snowflake(guild.0));
+
+    let aliases = GuildSettings::for_guild(&*ctx.data().database.read().await, guild.as_ref())
+        .aliases
+        .iter()
+        .map(|(alias, satellite_ids)| format!("`{}` → {}", alias, groups::list(satellite_ids)))
+        .collect::<Vec<_>>();
+
+    if aliases.is_empty() {
+        return Err(BotError::user(tr!(locale, "error-no-aliases")).into());
+    }
+
+    ctx.send(|b| {
+        b.embed(|e| {
+            e.title(tr!(locale, "aliases-title"));
+            e.description(aliases.join("\n"))
+        })
+        .ephemeral(true)
+    })
+    .await?;
+
+    Ok(())
+}
+
+/// Applies a change to the guild's aliases, saving it only if it succeeds.
+async fn update(
+    ctx: Context<'_>,
+    change: impl FnOnce(&mut GuildSettings) -> anyhow::Result<()>,
+) -> anyhow::Result<()> {
+    let guild = ctx
+        .guild_id()
+        .map(|guild| Snowflake(guild.0))
+        .ok_or_else(|| BotError::user(tr!(i18n::locale(&ctx), "error-guild-only")))?;
+
+    let mut database = ctx.data().database.write().await;
+    let mut settings = GuildSettings::for_guild(&database, Some(&guild));
+    change(&mut settings)?;
+
+    if settings == GuildSettings::default() {
+        database.contents.guild_settings.remove(&guild);
+    } else {
+        database.contents.guild_settings.insert(guild, settings);
+    }
+
+    database.mark_dirty();
+    Ok(())
+}
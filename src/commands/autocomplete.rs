@@ -1,11 +1,133 @@
-use serenity::futures::{self, Stream, StreamExt};
+use serenity::futures::{self, Stream};
 
-use crate::Context;
+use std::{cmp::Reverse, collections::BTreeMap};
+
+use poise::{serenity_prelude::ChannelId, AutocompleteChoice};
+
+use crate::{
+    database::{Database, Snowflake, WatchedSatellite},
+    groups,
+    i18n::{self, tr},
+    preferences::{GuildSettings, UserPreferences},
+    util::{self, TimeStyle},
+    Context,
+};
+
+/// The most choices Discord accepts in an autocomplete response.
+const MAX_CHOICES: usize = 25;
+/// The most values remembered of each kind of option, per user.
+const MAX_RECENT: usize = 5;
+
+/// The kind of value an autocompleted option holds, so that what a user picks for one option is
+/// suggested for others like it, such as `first_location` and `location`.
+fn kind(option: &str) -> Option<&'static str> {
+    match option {
+        "satellite_id" => Some("satellite"),
+        "timezone" => Some("timezone"),
+        option if option.ends_with("location") => Some("location"),
+        _ => None,
+    }
+}
+
+/// Remembers the values a user ran a command with, so that autocomplete suggests them first next
+/// time. Options that are not autocompleted are ignored.
+pub fn remember_choices<'a>(
+    database: &mut Database,
+    user: &Snowflake,
+    options: impl IntoIterator<Item = (&'a str, String)>,
+) {
+    for (option, value) in options {
+        let Some(kind) = kind(option) else {
+            continue;
+        };
+        let recent = database
+            .contents
+            .recent_choices
+            .entry(user.clone())
+            .or_default()
+            .entry(kind.to_string())
+            .or_default();
+
+        recent.retain(|other| other != &value);
+        recent.insert(0, value);
+        recent.truncate(MAX_RECENT);
+        database.mark_dirty();
+    }
+}
+
+/// The values the caller most recently ran commands with for a kind of option, newest first.
+async fn recent(ctx: Context<'_>, kind: &str) -> Vec<String> {
+    ctx.data()
+        .database
+        .read()
+        .await
+        .contents
+        .recent_choices
+        .get(&Snowflake(ctx.author().id.0))
+        .and_then(|recent| recent.get(kind))
+        .cloned()
+        .unwrap_or_default()
+}
+
+/// The caller's favorite satellites, in the order they were added.
+async fn favorites(ctx: Context<'_>) -> Vec<String> {
+    UserPreferences::for_user(
+        &*ctx.data().database.read().await,
+        &Snowflake(ctx.author().id.0),
+    )
+    .favorites
+    .iter()
+    .map(|satellite_id| satellite_id.0.to_string())
+    .collect()
+}
+
+/// Picks the choices matching what the user has typed, case-insensitively, and orders them: the
+/// preferred ones, such as those used recently, first, then ones that start with it, then ones with
+/// a word that starts with it, then the rest, each in their original order. At most 25 are kept.
+fn rank<T: ToString>(
+    partial: &str,
+    preferred: &[String],
+    choices: Vec<AutocompleteChoice<T>>,
+) -> Vec<AutocompleteChoice<T>> {
+    let partial = partial.trim().to_lowercase();
+    let mut ranked = choices
+        .into_iter()
+        .filter_map(|choice| {
+            let name = choice.name.to_lowercase();
+
+            if !name.contains(&partial) {
+                return None;
+            }
+
+            let value = choice.value.to_string();
+            let rank = match preferred.iter().position(|preferred| preferred == &value) {
+                Some(position) => position,
+                None if name.starts_with(&partial) => preferred.len(),
+                None if name
+                    .split(|c: char| !c.is_alphanumeric())
+                    .any(|word| word.starts_with(&partial)) =>
+                {
+                    preferred.len() + 1
+                }
+                None => preferred.len() + 2,
+            };
+
+            Some((rank, choice))
+        })
+        .collect::<Vec<_>>();
+
+    ranked.sort_by_key(|(rank, _)| *rank);
+    ranked
+        .into_iter()
+        .take(MAX_CHOICES)
+        .map(|(_, choice)| choice)
+        .collect()
+}
 
 pub async fn location<'ctx, 'a>(
     ctx: Context<'ctx>,
     partial: &'a str,
-) -> impl Stream<Item = String> + 'a
+) -> impl Stream<Item = AutocompleteChoice<String>> + 'a
 where
     'ctx: 'a,
 {
@@ -16,32 +138,331 @@ where
         .await
         .contents
         .locations
-        .clone()
-        .into_iter();
-    futures::stream::iter(locations)
-        .map(|location| location.name.0.clone())
-        .filter(move |name| futures::future::ready(name.starts_with(partial)))
-        .map(|name| name.to_string())
+        .iter()
+        .map(|location| AutocompleteChoice {
+            name: location.name.0.clone(),
+            value: location.name.0.clone(),
+        })
+        .collect();
+
+    futures::stream::iter(rank(partial, &recent(ctx, "location").await, locations))
+}
+
+/// Satellites in the catalog cache whose NORAD ID or name matches, as "25338 – NOAA 15", with
+/// the ID as the value, after the server's aliases for single satellites, as "noaa19 → 33591".
+/// The caller's favorites come first, then those they used recently.
+pub async fn satellite<'ctx, 'a>(
+    ctx: Context<'ctx>,
+    partial: &'a str,
+) -> impl Stream<Item = AutocompleteChoice<String>> + 'a
+where
+    'ctx: 'a,
+{
+    let guild = ctx.guild_id().map(|guild| Snowflake(guild.0));
+    let database = ctx.data().database.read().await;
+
+    let mut satellites = database.contents.satellites.iter().collect::<Vec<_>>();
+    satellites.sort_by_key(|(satellite_id, _)| satellite_id.0);
+
+    let choices = GuildSettings::for_guild(&database, guild.as_ref())
+        .aliases
+        .into_iter()
+        .filter_map(|(alias, satellite_ids)| match &satellite_ids[..] {
+            [satellite_id] => Some(AutocompleteChoice {
+                name: format!("{} → {}", alias, satellite_id.0),
+                value: alias,
+            }),
+            _ => None,
+        })
+        .chain(
+            satellites
+                .into_iter()
+                .map(|(satellite_id, metadata)| AutocompleteChoice {
+                    name: format!("{} – {}", satellite_id.0, metadata.name),
+                    value: satellite_id.0.to_string(),
+                }),
+        )
+        .collect();
+    drop(database);
+
+    let mut preferred = favorites(ctx).await;
+    preferred.extend(recent(ctx, "satellite").await);
+
+    futures::stream::iter(rank(partial, &preferred, choices))
+}
+
+/// The server's aliases, as "weather birds → 33591, 40069", with the alias as the value.
+pub async fn alias<'ctx, 'a>(
+    ctx: Context<'ctx>,
+    partial: &'a str,
+) -> impl Stream<Item = AutocompleteChoice<String>> + 'a
+where
+    'ctx: 'a,
+{
+    let guild = ctx.guild_id().map(|guild| Snowflake(guild.0));
+    let aliases = GuildSettings::for_guild(&*ctx.data().database.read().await, guild.as_ref())
+        .aliases
+        .into_iter()
+        .map(|(alias, satellite_ids)| AutocompleteChoice {
+            name: format!("{} → {}", alias, groups::list(&satellite_ids)),
+            value: alias,
+        })
+        .collect();
+
+    futures::stream::iter(rank(partial, &[], aliases))
 }
 
+/// The caller's favorite satellites, as "25338 – NOAA 15", with the ID as the value.
+pub async fn favorite<'ctx, 'a>(
+    ctx: Context<'ctx>,
+    partial: &'a str,
+) -> impl Stream<Item = AutocompleteChoice<String>> + 'a
+where
+    'ctx: 'a,
+{
+    let database = ctx.data().database.read().await;
+    let favorites = UserPreferences::for_user(&database, &Snowflake(ctx.author().id.0))
+        .favorites
+        .iter()
+        .map(|satellite_id| AutocompleteChoice {
+            name: match database.contents.satellites.get(satellite_id) {
+                Some(metadata) => format!("{} – {}", satellite_id.0, metadata.name),
+                None => satellite_id.0.to_string(),
+            },
+            value: satellite_id.0.to_string(),
+        })
+        .collect();
+    drop(database);
+
+    futures::stream::iter(rank(partial, &[], favorites))
+}
+
+/// The caller's watches, as "33591 – NOAA 19 · #passes · home", with the NORAD ID as the value.
 pub async fn watched_satellite<'ctx, 'a>(
     ctx: Context<'ctx>,
     partial: &'a str,
-) -> impl Stream<Item = String> + 'a
+) -> impl Stream<Item = AutocompleteChoice<String>> + 'a
 where
     'ctx: 'a,
 {
-    let watched_satellites = ctx
+    let author = Snowflake(ctx.author().id.0);
+    let watches = ctx
         .data()
         .database
         .read()
         .await
         .contents
         .watched_satellites
-        .clone()
-        .into_iter();
-    futures::stream::iter(watched_satellites)
-        .map(|watched_satellite| watched_satellite.name.clone())
-        .filter(move |name| futures::future::ready(name.starts_with(partial)))
-        .map(|name| name.to_string())
+        .iter()
+        .filter(|watched_satellite| watched_satellite.watcher == author)
+        .map(|watched_satellite| {
+            (
+                watched_satellite.satellite_id.0,
+                watched_satellite.name.clone(),
+                watched_satellite.channel.0,
+                watched_satellite.location.0.clone(),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let mut choices = Vec::new();
+
+    for (satellite_id, name, channel, location) in watches {
+        // Channels the bot cannot see are given by ID
+        let channel = match ChannelId(channel).name(ctx).await {
+            Some(name) => format!("#{}", name),
+            None => channel.to_string(),
+        };
+
+        choices.push(AutocompleteChoice {
+            name: format!("{} – {} · {} · {}", satellite_id, name, channel, location),
+            value: satellite_id.to_string(),
+        });
+    }
+
+    futures::stream::iter(rank(partial, &recent(ctx, "satellite").await, choices))
+}
+
+/// The tags given to watches in this server, as "weather (3 watches)", with the tag as the value.
+pub async fn watch_tag<'ctx, 'a>(
+    ctx: Context<'ctx>,
+    partial: &'a str,
+) -> impl Stream<Item = AutocompleteChoice<String>> + 'a
+where
+    'ctx: 'a,
+{
+    let guild = ctx.guild_id().map(|guild| Snowflake(guild.0));
+    let choices = tag_choices(
+        &*ctx.data().database.read().await,
+        i18n::locale(&ctx),
+        |watched_satellite| watched_satellite.guild == guild,
+    );
+
+    futures::stream::iter(rank(partial, &[], choices))
+}
+
+/// The tags given to the caller's watches, as "weather (3 watches)", with the tag as the value.
+pub async fn own_watch_tag<'ctx, 'a>(
+    ctx: Context<'ctx>,
+    partial: &'a str,
+) -> impl Stream<Item = AutocompleteChoice<String>> + 'a
+where
+    'ctx: 'a,
+{
+    let author = Snowflake(ctx.author().id.0);
+    let choices = tag_choices(
+        &*ctx.data().database.read().await,
+        i18n::locale(&ctx),
+        |watched_satellite| watched_satellite.watcher == author,
+    );
+
+    futures::stream::iter(rank(partial, &[], choices))
+}
+
+/// The tags given to the watches that match `filter`, with how many watches have each.
+fn tag_choices(
+    database: &Database,
+    locale: &str,
+    filter: impl Fn(&WatchedSatellite) -> bool,
+) -> Vec<AutocompleteChoice<String>> {
+    let mut counts = BTreeMap::<String, usize>::new();
+
+    for watched_satellite in database
+        .contents
+        .watched_satellites
+        .iter()
+        .filter(|watched_satellite| filter(watched_satellite))
+    {
+        for tag in &watched_satellite.tags {
+            *counts.entry(tag.clone()).or_default() += 1;
+        }
+    }
+
+    counts
+        .into_iter()
+        .map(|(tag, count)| AutocompleteChoice {
+            name: tr!(
+                locale,
+                "watch-tag-choice",
+                tag = tag.as_str(),
+                count = count
+            ),
+            value: tag,
+        })
+        .collect()
+}
+
+/// The passes notified of in this channel that have started, newest first, by their start time.
+pub async fn notified_pass<'ctx, 'a>(
+    ctx: Context<'ctx>,
+    partial: &'a str,
+) -> impl Stream<Item = AutocompleteChoice<String>> + 'a
+where
+    'ctx: 'a,
+{
+    let channel = Snowflake(ctx.channel_id().0);
+    let now = util::current_utc();
+    let database = ctx.data().database.read().await;
+    let time_format = UserPreferences::for_user(&database, &Snowflake(ctx.author().id.0))
+        .time_format(i18n::locale(&ctx));
+
+    let mut passes = database
+        .contents
+        .watched_satellites
+        .iter()
+        .filter(|watched_satellite| watched_satellite.channel == channel)
+        .flat_map(|watched_satellite| {
+            watched_satellite
+                .previous_notifications
+                .iter()
+                .filter(|(start, _)| (*start as i64) <= now)
+                .map(|(start, _)| (*start, watched_satellite.name.clone()))
+        })
+        .collect::<Vec<_>>();
+    drop(database);
+    passes.sort_by_key(|(start, _)| Reverse(*start));
+
+    let choices = passes
+        .into_iter()
+        .map(|(start, name)| AutocompleteChoice {
+            name: format!(
+                "{} · {}",
+                name,
+                util::format_time(start as i64, TimeStyle::Plain(&time_format))
+            ),
+            value: start.to_string(),
+        })
+        .collect();
+
+    futures::stream::iter(rank(partial, &[], choices))
+}
+
+pub async fn timezone<'ctx, 'a>(
+    ctx: Context<'ctx>,
+    partial: &'a str,
+) -> impl Stream<Item = AutocompleteChoice<String>> + 'a
+where
+    'ctx: 'a,
+{
+    let timezones = chrono_tz::TZ_VARIANTS
+        .iter()
+        .map(|timezone| AutocompleteChoice {
+            name: timezone.name().to_string(),
+            value: timezone.name().to_string(),
+        })
+        .collect();
+
+    futures::stream::iter(rank(partial, &recent(ctx, "timezone").await, timezones))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn choices(names: &[&str]) -> Vec<AutocompleteChoice<String>> {
+        names
+            .iter()
+            .map(|name| AutocompleteChoice {
+                name: name.to_string(),
+                value: name.to_string(),
+            })
+            .collect()
+    }
+
+    fn names(choices: Vec<AutocompleteChoice<String>>) -> Vec<String> {
+        choices.into_iter().map(|choice| choice.name).collect()
+    }
+
+    #[test]
+    fn ranks_recent_then_prefix_then_word_matches() {
+        let ranked = rank(
+            "no",
+            &["Meteor-M2 NOAA relay".to_string()],
+            choices(&[
+                "ISS",
+                "Snow station",
+                "Meteor-M2 NOAA relay",
+                "33591 – NOAA 19",
+                "NOAA 15",
+            ]),
+        );
+
+        assert_eq!(
+            names(ranked),
+            vec![
+                "Meteor-M2 NOAA relay",
+                "NOAA 15",
+                "33591 – NOAA 19",
+                "Snow station"
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_at_most_25_choices() {
+        let many = (0..100).map(|i| format!("home {}", i)).collect::<Vec<_>>();
+        let many = many.iter().map(String::as_str).collect::<Vec<_>>();
+
+        assert_eq!(rank("", &[], choices(&many)).len(), 25);
+    }
 }
@@ -0,0 +1,288 @@
+use std::borrow::Cow;
+
+use poise::{command, serenity_prelude::AttachmentType, ChoiceParameter};
+
+use crate::{
+    commands::{
+        autocomplete, defer_with_preferences, find_location, resolve_min_max_elevation,
+        resolve_satellite, MAX_DAYS,
+    },
+    database::Location,
+    error::BotError,
+    i18n::{self, tr},
+    n2yo::SatellitePass,
+    propagation::{self, CachedTle, Orbit},
+    util::{self, TimeStyle},
+    Context,
+};
+
+/// The longest step allowed between rows of a rotator schedule, in seconds.
+const MAX_STEP: i64 = 60;
+/// The step between rows of a Gpredict pass table, in seconds, matching Gpredict's own default.
+const PASS_TABLE_STEP: i64 = 30;
+
+/// Exports a pass's azimuth and elevation as a CSV file, for antenna rotator controllers.
+///
+/// Passes are predicted locally from the latest TLE, so may differ slightly from other listings.
+/// Example: `/export-rotator-schedule satellite_id:25544 location:home pass:2 step:5`
+#[command(slash_command, rename = "export-rotator-schedule", category = "Passes")]
+pub async fn export_rotator_schedule(
+    ctx: Context<'_>,
+    #[description = "the NORAD ID of the satellite, or an alias for it"] satellite_id: String,
+    #[description = "the location to track from, if not your default location"]
+    #[autocomplete = "autocomplete::location"]
+    location: Option<String>,
+    #[description = "which upcoming pass to export, 1 for the next (default 1)"] pass: Option<
+        usize,
+    >,
+    #[description = "the seconds between each row, from 1 to 60 (default 1)"] step: Option<i64>,
+    #[description = "the minimum elevation of the passes to count (default 20° unless configured)"]
+    min_max_elevation: Option<f64>,
+) -> anyhow::Result<()> {
+    let satellite_id = resolve_satellite(ctx, &satellite_id).await?;
+    let step = step.unwrap_or(1);
+
+    if !(1..=MAX_STEP).contains(&step) {
+        return Err(
+            BotError::user(tr!(i18n::locale(&ctx), "error-step-range", max = MAX_STEP)).into(),
+        );
+    }
+
+    let exported = find_pass(ctx, satellite_id, location, pass, min_max_elevation).await?;
+    let mut csv = String::from("time_utc,azimuth,elevation\n");
+
+    for (time, azimuth, elevation) in exported.track(step)? {
+        csv.push_str(&format!(
+            "{},{:.1},{:.1}\n",
+            util::iso_time(time)?,
+            azimuth,
+            elevation
+        ));
+    }
+
+    exported
+        .send(
+            ctx,
+            vec![(
+                format!("{}-{}.csv", satellite_id, exported.pass.start_utc),
+                csv,
+            )],
+        )
+        .await
+}
+
+/// The station software a pass can be exported for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ChoiceParameter)]
+pub enum ExportFormat {
+    /// The TLE to import into Gpredict, with a table of the pass to check it against.
+    #[name = "Gpredict (TLE and pass table)"]
+    Gpredict,
+    /// A shell script pointing a rotator through the pass with Hamlib's `rotctl`.
+    #[name = "rotctld script"]
+    Rotctld,
+}
+
+/// Exports a pass for station software such as Gpredict or Hamlib's rotctld.
+///
+/// Gpredict exports include the TLE used, to import with "Update TLE from local files". Run
+/// rotctld scripts shortly before the pass with `sh script.sh [host] [port]`.
+/// Example: `/export-pass satellite_id:25544 format:rotctld script location:home`
+#[command(slash_command, rename = "export-pass", category = "Passes")]
+pub async fn export_pass(
+    ctx: Context<'_>,
+    #[description = "the NORAD ID of the satellite, or an alias for it"] satellite_id: String,
+    #[description = "the software to export for"] format: ExportFormat,
+    #[description = "the location to track from, if not your default location"]
+    #[autocomplete = "autocomplete::location"]
+    location: Option<String>,
+    #[description = "which upcoming pass to export, 1 for the next (default 1)"] pass: Option<
+        usize,
+    >,
+    #[description = "the minimum elevation of the passes to count (default 20° unless configured)"]
+    min_max_elevation: Option<f64>,
+) -> anyhow::Result<()> {
+    let satellite_id = resolve_satellite(ctx, &satellite_id).await?;
+    let exported = find_pass(ctx, satellite_id, location, pass, min_max_elevation).await?;
+    let file_stem = format!("{}-{}", satellite_id, exported.pass.start_utc);
+
+    let files = match format {
+        ExportFormat::Gpredict => vec![
+            (
+                format!("{}.tle", satellite_id),
+                format!(
+                    "{}\n{}\n{}\n",
+                    exported.name, exported.tle.line1, exported.tle.line2
+                ),
+            ),
+            (format!("{}.txt", file_stem), exported.pass_table()?),
+        ],
+        ExportFormat::Rotctld => vec![(format!("{}.sh", file_stem), exported.rotctld_script()?)],
+    };
+
+    exported.send(ctx, files).await
+}
+
+/// A pass chosen for export, with what is needed to track it.
+struct ExportedPass {
+    name: String,
+    tle: CachedTle,
+    orbit: Orbit,
+    location: Location,
+    pass: SatellitePass,
+}
+
+/// Finds the nth upcoming pass of a satellite, predicting it locally so that it can be tracked
+/// precisely.
+async fn find_pass(
+    ctx: Context<'_>,
+    satellite_id: usize,
+    location: Option<String>,
+    pass_number: Option<usize>,
+    min_max_elevation: Option<f64>,
+) -> anyhow::Result<ExportedPass> {
+    let preferences = defer_with_preferences(ctx).await?;
+    let locale = i18n::locale(&ctx);
+    let pass_number = pass_number.unwrap_or(1);
+
+    if pass_number == 0 {
+        return Err(BotError::user(tr!(locale, "error-no-such-pass")).into());
+    }
+
+    let (location, min_max_elevation) = {
+        let database = ctx.data().database.read().await;
+        (
+            find_location(&database, locale, location.as_deref(), &preferences)?,
+            resolve_min_max_elevation(ctx, &database, locale, min_max_elevation)?,
+        )
+    };
+
+    let (tle, name) = ctx.data().pass_provider.tle(satellite_id).await?;
+    let orbit = Orbit::from_tle(&tle)?;
+    let start = util::current_utc();
+    let pass = propagation::predict_passes(
+        &orbit,
+        &location,
+        start,
+        start + MAX_DAYS as i64 * 24 * 60 * 60,
+        min_max_elevation,
+    )?
+    .into_iter()
+    .nth(pass_number - 1)
+    .ok_or_else(|| BotError::user(tr!(locale, "error-no-such-pass")))?;
+
+    Ok(ExportedPass {
+        name,
+        tle,
+        orbit,
+        location,
+        pass,
+    })
+}
+
+impl ExportedPass {
+    /// The time, azimuth, and elevation of the satellite through the pass. Elevations are clamped
+    /// to the horizon, as the ends of a pass can be fractionally below it.
+    fn track(&self, step: i64) -> anyhow::Result<Vec<(i64, f64, f64)>> {
+        Ok(propagation::track(
+            &self.orbit,
+            &self.location,
+            self.pass.start_utc as i64,
+            self.pass.end_utc as i64,
+            step,
+        )?
+        .into_iter()
+        .map(|(time, look_angles)| (time, look_angles.azimuth, look_angles.elevation.max(0.0)))
+        .collect())
+    }
+
+    /// A plain text table of the pass, laid out like Gpredict's pass details.
+    fn pass_table(&self) -> anyhow::Result<String> {
+        let mut table = format!(
+            "{} pass from {} ({:.4}, {:.4})\n\n{:<20}  {:>7}  {:>7}  {:>9}\n",
+            self.name,
+            self.location.name.0,
+            self.location.latitude,
+            self.location.longitude,
+            "Time (UTC)",
+            "Az",
+            "El",
+            "Range"
+        );
+
+        for (time, look_angles) in propagation::track(
+            &self.orbit,
+            &self.location,
+            self.pass.start_utc as i64,
+            self.pass.end_utc as i64,
+            PASS_TABLE_STEP,
+        )? {
+            table.push_str(&format!(
+                "{:<20}  {:>7.2}  {:>7.2}  {:>6.0} km\n",
+                util::iso_time(time)?,
+                look_angles.azimuth,
+                look_angles.elevation.max(0.0),
+                look_angles.range
+            ));
+        }
+
+        Ok(table)
+    }
+
+    /// A shell script that waits for each second of the pass and points the rotator with `rotctl`.
+    fn rotctld_script(&self) -> anyhow::Result<String> {
+        let mut script = format!(
+            "#!/bin/sh\n\
+             # {} pass from {}, {} to {}\n\
+             # Usage: sh <this file> [host] [port]\n\
+             HOST=${{1:-localhost}}\n\
+             PORT=${{2:-4533}}\n\n\
+             point_at() {{\n    \
+                 while [ \"$(date -u +%s)\" -lt \"$1\" ]; do sleep 0.2; done\n    \
+                 rotctl -m 2 -r \"$HOST:$PORT\" P \"$2\" \"$3\"\n\
+             }}\n\n",
+            self.name,
+            self.location.name.0,
+            util::iso_time(self.pass.start_utc as i64)?,
+            util::iso_time(self.pass.end_utc as i64)?,
+        );
+
+        for (time, azimuth, elevation) in self.track(1)? {
+            script.push_str(&format!(
+                "point_at {} {:.1} {:.1}\n",
+                time, azimuth, elevation
+            ));
+        }
+
+        Ok(script)
+    }
+
+    async fn send(&self, ctx: Context<'_>, files: Vec<(String, String)>) -> anyhow::Result<()> {
+        let locale = i18n::locale(&ctx);
+
+        ctx.send(|b| {
+            b.content(tr!(
+                locale,
+                "export-pass",
+                satellite = self.name.as_str(),
+                location = self.location.name.0.as_str(),
+                time = util::format_pass_time(
+                    self.pass.start_utc as i64,
+                    self.pass.end_utc as i64,
+                    TimeStyle::Discord
+                )
+            ));
+
+            for (filename, contents) in files {
+                b.attachment(AttachmentType::Bytes {
+                    data: Cow::Owned(contents.into_bytes()),
+                    filename,
+                });
+            }
+
+            b
+        })
+        .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,154 @@
+use poise::command;
+
+use crate::{
+    catalog,
+    commands::{autocomplete, resolve_satellite},
+    database::{SatelliteId, Snowflake},
+    error::BotError,
+    groups,
+    i18n::{self, tr},
+    preferences::UserPreferences,
+    Context,
+};
+
+/// Keeps a shortlist of your favorite satellites.
+///
+/// Favorites are suggested first when giving a NORAD ID, and can all be looked up at once by giving
+/// `favorites` to `/plan-session` or `/get-upcoming-passes-multi`.
+#[command(
+    slash_command,
+    rename = "favorite-satellite",
+    category = "Satellites",
+    subcommands("add", "remove", "list")
+)]
+pub async fn favorite_satellite(_ctx: Context<'_>) -> anyhow::Result<()> {
+    Ok(())
+}
+
+/// Adds a satellite to your favorites.
+/// Example: `/favorite-satellite add satellite_id:33591`
+#[command(slash_command, rename = "add")]
+async fn add(
+    ctx: Context<'_>,
+    #[description = "the NORAD ID of the satellite, or an alias for it"]
+    #[autocomplete = "autocomplete::satellite"]
+    satellite_id: String,
+) -> anyhow::Result<()> {
+    let satellite_id = resolve_satellite(ctx, &satellite_id).await?;
+    ctx.defer_ephemeral().await?;
+    let locale = i18n::locale(&ctx);
+    let user = Snowflake(ctx.author().id.0);
+
+    // Checks the satellite exists, and caches its name for autocomplete. This is done before
+    // locking the database, as it may have to ask N2YO and CelesTrak.
+    let name = catalog::lookup(
+        &ctx.data().database,
+        &ctx.data().n2yo_api,
+        &ctx.data().celestrak_api,
+        satellite_id,
+    )
+    .await?
+    .name;
+
+    {
+        let mut database = ctx.data().database.write().await;
+        let mut preferences = UserPreferences::for_user(&database, &user);
+
+        if preferences.favorites.contains(&SatelliteId(satellite_id)) {
+            return Err(BotError::user(tr!(locale, "error-already-favorite")).into());
+        }
+
+        if preferences.favorites.len() >= groups::MAX_SATELLITES {
+            return Err(BotError::user(tr!(
+                locale,
+                "error-too-many-favorites",
+                max = groups::MAX_SATELLITES
+            ))
+            .into());
+        }
+
+        preferences.favorites.push(SatelliteId(satellite_id));
+        database.contents.preferences.insert(user, preferences);
+        database.mark_dirty();
+    }
+
+    ctx.say(tr!(locale, "favorites-added", satellite = name.as_str()))
+        .await?;
+    Ok(())
+}
+
+/// Removes a satellite from your favorites.
+#[command(slash_command, rename = "remove")]
+async fn remove(
+    ctx: Context<'_>,
+    #[description = "the NORAD ID of the satellite, or an alias for it"]
+    #[autocomplete = "autocomplete::favorite"]
+    satellite_id: String,
+) -> anyhow::Result<()> {
+    let satellite_id = resolve_satellite(ctx, &satellite_id).await?;
+    ctx.defer_ephemeral().await?;
+    let locale = i18n::locale(&ctx);
+    let user = Snowflake(ctx.author().id.0);
+
+    {
+        let mut database = ctx.data().database.write().await;
+        let mut preferences = UserPreferences::for_user(&database, &user);
+        let before = preferences.favorites.len();
+        preferences
+            .favorites
+            .retain(|favorite| favorite.0 != satellite_id);
+
+        if preferences.favorites.len() == before {
+            return Err(BotError::user(tr!(locale, "error-not-favorite")).into());
+        }
+
+        if preferences == UserPreferences::default() {
+            database.contents.preferences.remove(&user);
+        } else {
+            database.contents.preferences.insert(user, preferences);
+        }
+
+        database.mark_dirty();
+    }
+
+    ctx.say(tr!(locale, "favorites-removed", satellite = satellite_id))
+        .await?;
+    Ok(())
+}
+
+/// Lists your favorite satellites.
+#[command(slash_command, rename = "list")]
+async fn list(ctx: Context<'_>) -> anyhow::Result<()> {
+    ctx.defer_ephemeral().await?;
+    let locale = i18n::locale(&ctx);
+
+    let favorites = {
+        let database = ctx.data().database.read().await;
+        UserPreferences::for_user(&database, &Snowflake(ctx.author().id.0))
+            .favorites
+            .iter()
+            .map(
+                |satellite_id| match database.contents.satellites.get(satellite_id) {
+                    Some(metadata) => format!("{} – {}", satellite_id.0, metadata.name),
+                    None => satellite_id.0.to_string(),
+                },
+            )
+            .collect::<Vec<_>>()
+    };
+
+    if favorites.is_empty() {
+        return Err(BotError::user(tr!(locale, "error-no-favorites")).into());
+    }
+
+    ctx.send(|b| {
+        b.embed(|e| {
+            e.title(tr!(locale, "favorites-title"));
+            e.description(favorites.join("\n"));
+            e.footer(|f| f.text(tr!(locale, "favorites-footer")))
+        })
+        .ephemeral(true)
+    })
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,142 @@
+use poise::command;
+
+use crate::{
+    commands::{defer_with_preferences, resolve_satellite},
+    error::BotError,
+    frequencies::{self, format_frequency},
+    i18n::{self, tr},
+    Context,
+};
+
+/// The most fields Discord allows in an embed.
+const MAX_FIELDS: usize = 25;
+
+/// Lists the radio frequencies of a commonly listened to satellite.
+///
+/// Covers the NOAA and Meteor weather satellites, the ISS, and the popular FM amateur satellites.
+/// Example: `/frequencies satellite_id:25544`
+#[command(slash_command, rename = "frequencies", category = "Satellites")]
+pub async fn frequencies(
+    ctx: Context<'_>,
+    #[description = "the NORAD ID of the satellite, or an alias for it"] satellite_id: String,
+) -> anyhow::Result<()> {
+    let satellite_id = resolve_satellite(ctx, &satellite_id).await?;
+    defer_with_preferences(ctx).await?;
+    let locale = i18n::locale(&ctx);
+
+    let satellite = frequencies::lookup(satellite_id)
+        .ok_or_else(|| BotError::user(tr!(locale, "error-unknown-frequencies")))?;
+
+    ctx.send(|b| {
+        b.embed(|e| {
+            e.title(tr!(locale, "frequencies-title", satellite = satellite.name));
+
+            for transmitter in satellite.transmitters {
+                let mut lines = vec![tr!(
+                    locale,
+                    "frequencies-downlink",
+                    frequency = format_frequency(transmitter.downlink)
+                )];
+
+                if let Some(uplink) = transmitter.uplink {
+                    lines.push(tr!(
+                        locale,
+                        "frequencies-uplink",
+                        frequency = format_frequency(uplink)
+                    ));
+                }
+
+                lines.push(tr!(locale, "frequencies-mode", mode = transmitter.mode));
+
+                if let Some(tone) = transmitter.tone {
+                    lines.push(tr!(locale, "frequencies-tone", tone = tone));
+                }
+
+                e.field(transmitter.description, lines.join("\n"), true);
+            }
+
+            e
+        })
+    })
+    .await?;
+
+    Ok(())
+}
+
+/// Lists the active transmitters of any satellite, from SatNOGS DB.
+///
+/// Example: `/transmitters satellite_id:43017`
+#[command(slash_command, rename = "transmitters", category = "Satellites")]
+pub async fn transmitters(
+    ctx: Context<'_>,
+    #[description = "the NORAD ID of the satellite, or an alias for it"] satellite_id: String,
+) -> anyhow::Result<()> {
+    let satellite_id = resolve_satellite(ctx, &satellite_id).await?;
+    defer_with_preferences(ctx).await?;
+    let locale = i18n::locale(&ctx);
+
+    let transmitters = ctx
+        .data()
+        .satnogs_api
+        .get_active_transmitters(satellite_id)
+        .await?;
+
+    if transmitters.is_empty() {
+        return Err(BotError::user(tr!(locale, "error-no-transmitters")).into());
+    }
+
+    ctx.send(|b| {
+        b.embed(|e| {
+            e.title(tr!(
+                locale,
+                "transmitters-title",
+                satellite_id = satellite_id
+            ));
+
+            for transmitter in transmitters.iter().take(MAX_FIELDS) {
+                let mut lines = Vec::new();
+
+                if let Some(downlink) = transmitter.downlink_low {
+                    lines.push(tr!(
+                        locale,
+                        "frequencies-downlink",
+                        frequency = format_frequency(downlink as f64 / 1_000_000.0)
+                    ));
+                }
+
+                if let Some(uplink) = transmitter.uplink_low {
+                    lines.push(tr!(
+                        locale,
+                        "frequencies-uplink",
+                        frequency = format_frequency(uplink as f64 / 1_000_000.0)
+                    ));
+                }
+
+                if let Some(mode) = &transmitter.mode {
+                    lines.push(tr!(locale, "frequencies-mode", mode = mode.as_str()));
+                }
+
+                if let Some(baud) = transmitter.baud {
+                    lines.push(tr!(locale, "transmitters-baud", baud = baud));
+                }
+
+                e.field(&transmitter.description, lines.join("\n"), true);
+            }
+
+            if transmitters.len() > MAX_FIELDS {
+                e.footer(|f| {
+                    f.text(tr!(
+                        locale,
+                        "transmitters-more",
+                        count = transmitters.len() - MAX_FIELDS
+                    ))
+                });
+            }
+
+            e
+        })
+    })
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,115 @@
+use poise::command;
+
+use crate::{
+    commands::{autocomplete, defer_with_preferences, find_location, resolve_satellite},
+    error::BotError,
+    i18n::{self, tr},
+    propagation::{self, Orbit},
+    util::{self, TimeStyle},
+    Context,
+};
+
+/// Shows where to point an antenna at a geostationary satellite, such as QO-100 or GOES.
+///
+/// Geostationary satellites stay in the same place in the sky, so they have no passes, only a
+/// fixed azimuth and elevation. The angles are worked out from the latest TLE.
+/// Example: `/geo-pointing satellite_id:43700 location:home`
+#[command(slash_command, rename = "geo-pointing", category = "Satellites")]
+pub async fn geo_pointing(
+    ctx: Context<'_>,
+    #[description = "the NORAD ID of the satellite, or an alias for it"]
+    #[autocomplete = "autocomplete::satellite"]
+    satellite_id: String,
+    #[description = "the location to point from, if not your default location"]
+    #[autocomplete = "autocomplete::location"]
+    location: Option<String>,
+) -> anyhow::Result<()> {
+    let satellite_id = resolve_satellite(ctx, &satellite_id).await?;
+    let preferences = defer_with_preferences(ctx).await?;
+    let locale = i18n::locale(&ctx);
+
+    let location = {
+        let database = ctx.data().database.read().await;
+        find_location(&database, locale, location.as_deref(), &preferences)?
+    };
+
+    let (tle, name) = ctx.data().pass_provider.tle(satellite_id).await?;
+
+    if !tle.is_geostationary() {
+        return Err(BotError::user(tr!(
+            locale,
+            "error-not-geostationary",
+            satellite = name.as_str()
+        ))
+        .into());
+    }
+
+    let now = util::current_utc();
+    let position = propagation::teme_to_ecef(Orbit::from_tle(&tle)?.position(now)?, now);
+    let look_angles = propagation::topocentric(&location, position);
+    let longitude = propagation::longitude(position);
+
+    ctx.send(|b| {
+        b.embed(|e| {
+            e.title(tr!(
+                locale,
+                "geo-title",
+                satellite = name.as_str(),
+                location = location.name.0.as_str()
+            ));
+            e.description(if look_angles.elevation > 0.0 {
+                tr!(locale, "geo-above-horizon")
+            } else {
+                tr!(locale, "geo-below-horizon")
+            });
+            e.field(
+                tr!(locale, "geo-azimuth"),
+                format!(
+                    "{:.1}° ({})",
+                    look_angles.azimuth,
+                    propagation::compass(look_angles.azimuth)
+                ),
+                true,
+            );
+            e.field(
+                tr!(locale, "geo-elevation"),
+                format!("{:.1}°", look_angles.elevation),
+                true,
+            );
+            e.field(
+                tr!(locale, "geo-range"),
+                format!("{:.0} km", look_angles.range),
+                true,
+            );
+            e.field(
+                tr!(locale, "geo-longitude"),
+                if longitude >= 0.0 {
+                    tr!(locale, "geo-east", longitude = format!("{:.1}", longitude))
+                } else {
+                    tr!(locale, "geo-west", longitude = format!("{:.1}", -longitude))
+                },
+                true,
+            );
+
+            // Discord timestamps are not rendered in footers
+            if let Some(epoch) = tle.epoch() {
+                e.footer(|f| {
+                    f.text(tr!(
+                        locale,
+                        "geo-tle-epoch",
+                        time = util::format_time(
+                            epoch,
+                            TimeStyle::Plain(&preferences.time_format(locale))
+                        )
+                    ))
+                });
+            }
+
+            e
+        })
+        .ephemeral(preferences.ephemeral)
+    })
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,204 @@
+use std::{collections::BTreeMap, time::Duration};
+
+use poise::{
+    command,
+    serenity_prelude::{CreateEmbed, InteractionResponseType},
+    Command,
+};
+
+use crate::{
+    commands::command_preferences,
+    i18n::{self, tr},
+    util, ApplicationContext, Context,
+};
+
+const OVERVIEW: &str = "overview";
+/// The most options Discord allows in a select menu.
+const MENU_OPTIONS: usize = 25;
+/// The most select menus Discord allows in a message, one per action row.
+const MAX_MENUS: usize = 5;
+
+/// Lists all commands, with details on how to use each one.
+#[command(slash_command, rename = "help", category = "Other")]
+pub async fn help(ctx: Context<'_>) -> anyhow::Result<()> {
+    let commands = &ctx.framework().options().commands;
+    let locale = i18n::locale(&ctx);
+    let custom_id = format!("help-{}-", ctx.id());
+    let preferences = command_preferences(ctx).await;
+
+    let reply = ctx
+        .send(|b| {
+            b.embed(|e| {
+                embed_overview(e, locale, commands);
+                e
+            })
+            .components(|c| {
+                let options = std::iter::once((
+                    tr!(locale, "help-overview"),
+                    OVERVIEW.to_string(),
+                    tr!(locale, "help-overview-description"),
+                ))
+                .chain(commands.iter().filter(|c| !c.hide_in_help).map(|command| {
+                    (
+                        format!("/{}", command.name),
+                        command.name.clone(),
+                        util::truncate(description(command, locale), 100),
+                    )
+                }))
+                .collect::<Vec<_>>();
+
+                // Each menu takes at most 25 options, so long command lists are split across
+                // several
+                for (page, options) in options.chunks(MENU_OPTIONS).take(MAX_MENUS).enumerate() {
+                    c.create_action_row(|r| {
+                        r.create_select_menu(|m| {
+                            m.custom_id(format!("{}{}", custom_id, page))
+                                .placeholder(if page == 0 {
+                                    tr!(locale, "help-placeholder")
+                                } else {
+                                    tr!(locale, "help-placeholder-more")
+                                })
+                                .options(|o| {
+                                    for (label, value, description) in options {
+                                        o.create_option(|opt| {
+                                            opt.label(label).value(value).description(description)
+                                        });
+                                    }
+
+                                    o
+                                })
+                        })
+                    });
+                }
+
+                c
+            })
+            .ephemeral(preferences.ephemeral)
+        })
+        .await?;
+
+    let message = reply.message().await?;
+
+    while let Some(interaction) = message
+        .await_component_interaction(ctx.serenity_context())
+        .author_id(ctx.author().id)
+        .filter({
+            let custom_id = custom_id.clone();
+            move |interaction| interaction.data.custom_id.starts_with(&custom_id)
+        })
+        .timeout(Duration::from_secs(5 * 60))
+        .await
+    {
+        let selected = interaction.data.values.first().cloned().unwrap_or_default();
+        let mut embed = CreateEmbed::default();
+
+        match commands.iter().find(|command| command.name == selected) {
+            Some(command) => embed_command(&mut embed, locale, command),
+            None => embed_overview(&mut embed, locale, commands),
+        }
+
+        interaction
+            .create_interaction_response(ctx.serenity_context(), |r| {
+                r.kind(InteractionResponseType::UpdateMessage)
+                    .interaction_response_data(|d| d.set_embed(embed))
+            })
+            .await?;
+    }
+
+    Ok(())
+}
+
+fn embed_overview(
+    e: &mut CreateEmbed,
+    locale: &str,
+    commands: &[Command<ApplicationContext, anyhow::Error>],
+) {
+    let mut categories = BTreeMap::<&str, Vec<String>>::new();
+
+    for command in commands.iter().filter(|command| !command.hide_in_help) {
+        categories
+            .entry(command.category.unwrap_or("Other"))
+            .or_default()
+            .push(format!(
+                "`/{}` - {}",
+                command.name,
+                description(command, locale)
+            ));
+    }
+
+    e.title(tr!(locale, "help-title"));
+    e.description(tr!(locale, "help-description"));
+    e.fields(
+        categories
+            .into_iter()
+            .map(|(category, lines)| (category, lines.join("\n"), false)),
+    );
+}
+
+/// A command's description in the given Discord locale.
+fn description<'a>(
+    command: &'a Command<ApplicationContext, anyhow::Error>,
+    locale: &str,
+) -> &'a str {
+    i18n::localized(
+        &command.description_localizations,
+        locale,
+        command.description.as_deref().unwrap_or(""),
+    )
+}
+
+fn embed_command(
+    e: &mut CreateEmbed,
+    locale: &str,
+    command: &Command<ApplicationContext, anyhow::Error>,
+) {
+    e.title(format!("/{}", command.name));
+    e.description(description(command, locale));
+
+    let usage = command
+        .parameters
+        .iter()
+        .map(|parameter| {
+            if parameter.required {
+                format!("{}:<...>", parameter.name)
+            } else {
+                format!("[{}:<...>]", parameter.name)
+            }
+        })
+        .fold(format!("/{}", command.name), |usage, parameter| {
+            format!("{} {}", usage, parameter)
+        });
+    e.field(tr!(locale, "help-usage"), format!("`{}`", usage), false);
+
+    if !command.parameters.is_empty() {
+        e.field(
+            tr!(locale, "help-parameters"),
+            command
+                .parameters
+                .iter()
+                .map(|parameter| {
+                    format!(
+                        "`{}`{} - {}",
+                        parameter.name,
+                        if parameter.required {
+                            String::new()
+                        } else {
+                            format!(" {}", tr!(locale, "help-optional"))
+                        },
+                        i18n::localized(
+                            &parameter.description_localizations,
+                            locale,
+                            parameter.description.as_deref().unwrap_or("")
+                        )
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+            false,
+        );
+    }
+
+    if let Some(help_text) = command.help_text {
+        e.field(tr!(locale, "help-details"), help_text(), false);
+    }
+}
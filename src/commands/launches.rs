@@ -0,0 +1,154 @@
+use poise::command;
+
+use crate::{
+    commands::defer_with_preferences,
+    database::Snowflake,
+    error::BotError,
+    i18n::{self, tr},
+    launches::{LaunchCategory, LaunchSubscription},
+    Context,
+};
+
+/// The most launches listed at once.
+const MAX_LAUNCHES: usize = 10;
+
+/// Lists the next launches, from Launch Library 2.
+///
+/// Useful for knowing when a new weather or amateur satellite will go up, so that it can be
+/// watched as soon as its TLEs appear.
+/// Example: `/upcoming-launches category:weather`
+#[command(slash_command, rename = "upcoming-launches", category = "Satellites")]
+pub async fn upcoming_launches(
+    ctx: Context<'_>,
+    #[description = "the kind of launch to list (default all)"] category: Option<LaunchCategory>,
+) -> anyhow::Result<()> {
+    defer_with_preferences(ctx).await?;
+    let locale = i18n::locale(&ctx);
+    let category = category.unwrap_or(LaunchCategory::All);
+
+    let launches = ctx
+        .data()
+        .launch_api
+        .get_upcoming_launches()
+        .await?
+        .into_iter()
+        .filter(|launch| category.matches(launch))
+        .take(MAX_LAUNCHES)
+        .collect::<Vec<_>>();
+
+    if launches.is_empty() {
+        return Err(BotError::user(tr!(locale, "error-no-launches")).into());
+    }
+
+    ctx.send(|b| {
+        b.embed(|e| {
+            e.title(tr!(locale, "launches-title"));
+
+            for launch in &launches {
+                e.field(&launch.name, launch.summary(locale), false);
+            }
+
+            e.footer(|f| f.text(tr!(locale, "launches-source")))
+        })
+    })
+    .await?;
+
+    Ok(())
+}
+
+/// Posts in this channel when launches are scheduled or slip.
+#[command(
+    slash_command,
+    rename = "launch-alerts",
+    category = "Satellites",
+    guild_only,
+    default_member_permissions = "MANAGE_CHANNELS",
+    required_permissions = "MANAGE_CHANNELS",
+    subcommands("subscribe", "unsubscribe")
+)]
+pub async fn launch_alerts(_ctx: Context<'_>) -> anyhow::Result<()> {
+    Ok(())
+}
+
+/// Posts in this channel when launches of a kind are scheduled or slip.
+///
+/// Subscribing again with another kind posts about both.
+#[command(slash_command, rename = "subscribe")]
+async fn subscribe(
+    ctx: Context<'_>,
+    #[description = "the kind of launch to post about"] category: LaunchCategory,
+) -> anyhow::Result<()> {
+    ctx.defer_ephemeral().await?;
+    let locale = i18n::locale(&ctx);
+    let channel = Snowflake(ctx.channel_id().0);
+
+    let categories = {
+        let mut database = ctx.data().database.write().await;
+        let subscriptions = &mut database.contents.launch_subscriptions;
+
+        let subscription = match subscriptions
+            .iter()
+            .position(|subscription| subscription.channel == channel)
+        {
+            Some(index) => &mut subscriptions[index],
+            None => {
+                subscriptions.push(LaunchSubscription {
+                    channel,
+                    guild: ctx.guild_id().map(|guild| Snowflake(guild.0)),
+                    subscriber: Snowflake(ctx.author().id.0),
+                    locale: locale.to_string(),
+                    categories: Vec::new(),
+                });
+                subscriptions.last_mut().unwrap()
+            }
+        };
+
+        if !subscription.categories.contains(&category) {
+            subscription.categories.push(category);
+        }
+
+        let categories = describe_categories(&subscription.categories);
+        database.mark_dirty();
+        categories
+    };
+
+    ctx.say(tr!(
+        locale,
+        "launch-alerts-subscribed",
+        categories = categories
+    ))
+    .await?;
+    Ok(())
+}
+
+/// Stops posting about launches in this channel.
+#[command(slash_command, rename = "unsubscribe")]
+async fn unsubscribe(ctx: Context<'_>) -> anyhow::Result<()> {
+    ctx.defer_ephemeral().await?;
+    let locale = i18n::locale(&ctx);
+    let channel = Snowflake(ctx.channel_id().0);
+
+    {
+        let mut database = ctx.data().database.write().await;
+        let subscriptions = &mut database.contents.launch_subscriptions;
+        let before = subscriptions.len();
+        subscriptions.retain(|subscription| subscription.channel != channel);
+
+        if subscriptions.len() == before {
+            return Err(BotError::user(tr!(locale, "error-not-subscribed-to-launches")).into());
+        }
+
+        database.mark_dirty();
+    }
+
+    ctx.say(tr!(locale, "launch-alerts-unsubscribed")).await?;
+    Ok(())
+}
+
+fn describe_categories(categories: &[LaunchCategory]) -> String {
+    categories
+        .iter()
+        .map(|category| format!("`{}`", category.name()))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -1,21 +1,26 @@
 use poise::command;
 
 use crate::{
-    commands::autocomplete,
+    commands::{autocomplete, defer_with_preferences},
     database::{Location, LocationName, Snowflake},
+    error::BotError,
+    i18n::{self, tr},
     Context,
 };
 
 /// Adds an observation location.
-#[command(slash_command, rename = "add-location")]
+///
+/// Example: `/add-location name:home latitude:51.5 longitude:-0.12 altitude:20`
+#[command(slash_command, rename = "add-location", category = "Locations")]
 pub async fn add_location(
     ctx: Context<'_>,
     #[description = "name"] name: String,
     #[description = "latitude"] latitude: f64,
     #[description = "longitude"] longitude: f64,
-    #[description = "altitude"] altitude: f64,
+    #[description = "altitude in metres"] altitude: f64,
 ) -> anyhow::Result<()> {
     ctx.defer().await?;
+    let locale = i18n::locale(&ctx);
     let mut database = ctx.data().database.write().await;
 
     if database
@@ -24,7 +29,7 @@ pub async fn add_location(
         .iter()
         .any(|location| location.name.0 == name)
     {
-        return Err(anyhow::anyhow!("location already exists"));
+        return Err(BotError::user(tr!(locale, "error-location-exists")).into());
     }
 
     let location = Location {
@@ -33,15 +38,21 @@ pub async fn add_location(
         latitude,
         longitude,
         altitude,
+        guild: ctx.guild_id().map(|guild| Snowflake(guild.0)),
     };
 
     database.contents.locations.push(location);
-    database.save()?;
+    database.mark_dirty();
 
     ctx.send(|b| {
         b.embed(|e| {
-            e.title("Location added");
-            e.description(format!("{} ({})", name, ctx.author().name));
+            e.title(tr!(locale, "location-added"));
+            e.description(tr!(
+                locale,
+                "location-added-description",
+                name = name.as_str(),
+                author = ctx.author().name.as_str()
+            ));
             e
         })
         .ephemeral(false)
@@ -52,27 +63,31 @@ pub async fn add_location(
 }
 
 /// Lists all observation locations.
-#[command(slash_command, rename = "list-locations")]
+#[command(slash_command, rename = "list-locations", category = "Locations")]
 pub async fn list_locations(ctx: Context<'_>) -> anyhow::Result<()> {
-    ctx.defer().await?;
+    let preferences = defer_with_preferences(ctx).await?;
+    let locale = i18n::locale(&ctx);
+    let units = preferences.units(locale);
     let database = ctx.data().database.read().await;
 
     ctx.send(|b| {
         b.embed(|e| {
-            e.title("Locations");
+            e.title(tr!(locale, "locations-title"));
             e.fields(database.contents.locations.iter().map(|location| {
                 (
                     location.name.0.clone(),
                     format!(
-                        "{}°N {}°E @ {}m",
-                        location.latitude, location.longitude, location.altitude
+                        "{}°N {}°E @ {}",
+                        location.latitude,
+                        location.longitude,
+                        units.length(location.altitude)
                     ),
                     false,
                 )
             }));
             e
         })
-        .ephemeral(false)
+        .ephemeral(preferences.ephemeral)
     })
     .await?;
 
@@ -80,7 +95,9 @@ pub async fn list_locations(ctx: Context<'_>) -> anyhow::Result<()> {
 }
 
 /// Removes an observation location.
-#[command(slash_command, rename = "remove-location")]
+///
+/// Example: `/remove-location name:home`
+#[command(slash_command, rename = "remove-location", category = "Locations")]
 pub async fn remove_location(
     ctx: Context<'_>,
     #[description = "name"]
@@ -88,25 +105,31 @@ pub async fn remove_location(
     name: String,
 ) -> anyhow::Result<()> {
     ctx.defer().await?;
+    let locale = i18n::locale(&ctx);
     let mut database = ctx.data().database.write().await;
     let index = database
         .contents
         .locations
         .iter()
         .position(|location| location.name.0 == name)
-        .ok_or_else(|| anyhow::anyhow!("no such location"))?;
+        .ok_or_else(|| BotError::user(tr!(locale, "error-no-such-location")))?;
 
     if ctx.author().id.0 != database.contents.locations[index].creator.0 {
-        return Err(anyhow::anyhow!("location must be removed by its creator"));
+        return Err(BotError::user(tr!(locale, "error-location-not-creator")).into());
     }
 
     database.contents.locations.remove(index);
-    database.save()?;
+    database.mark_dirty();
 
     ctx.send(|b| {
         b.embed(|e| {
-            e.title("Location removed");
-            e.description(format!("{}, created by {}", name, ctx.author().name));
+            e.title(tr!(locale, "location-removed"));
+            e.description(tr!(
+                locale,
+                "location-removed-description",
+                name = name.as_str(),
+                author = ctx.author().name.as_str()
+            ));
             e
         })
         .ephemeral(false)
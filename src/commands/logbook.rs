@@ -0,0 +1,334 @@
+use std::{borrow::Cow, cmp::Reverse};
+
+use poise::{
+    command,
+    serenity_prelude::{Attachment, AttachmentType, ChannelId, Http},
+};
+use serenity::futures::future::join_all;
+
+use crate::{
+    catalog,
+    commands::{autocomplete, defer_with_preferences, paginator, resolve_satellite},
+    database::{LoggedObservation, ObservationImage, SatelliteId, Snowflake},
+    error::BotError,
+    i18n::{self, tr},
+    util::{self, TimeStyle},
+    Context,
+};
+
+/// The longest notes kept with an observation, as Discord allows 1024 characters in a field.
+const MAX_NOTES_LENGTH: usize = 1000;
+/// The most images `/gallery` pages through, newest first, as each needs a fresh link fetching.
+const MAX_GALLERY_IMAGES: usize = 25;
+
+/// Logs that you received or saw a pass, optionally with notes and an image.
+///
+/// Passes the bot notified this channel of can be picked from the list, which links the log to
+/// them. Otherwise the most recent notified pass of the satellite is used, or the current time if
+/// there is none. Images, such as decoded APT pictures, are shown in `/gallery`, and so are always
+/// replied to publicly.
+/// Example: `/log-observation satellite_id:33591 notes:Clear APT, some noise near the end image:noaa19.png`
+#[command(slash_command, rename = "log-observation", category = "Observations")]
+pub async fn log_observation(
+    ctx: Context<'_>,
+    #[description = "the NORAD ID of the satellite, or an alias for it"] satellite_id: String,
+    #[description = "the pass observed, if not the most recent one notified of here"]
+    #[autocomplete = "autocomplete::notified_pass"]
+    pass: Option<String>,
+    #[description = "notes on the observation, such as signal quality or equipment"] notes: Option<
+        String,
+    >,
+    #[description = "an image of the observation, such as a decoded picture"] image: Option<
+        Attachment,
+    >,
+) -> anyhow::Result<()> {
+    let satellite_id = resolve_satellite(ctx, &satellite_id).await?;
+    let preferences = defer_with_preferences(ctx).await?;
+    let locale = i18n::locale(&ctx);
+    let channel = Snowflake(ctx.channel_id().0);
+
+    if notes
+        .as_ref()
+        .is_some_and(|notes| notes.chars().count() > MAX_NOTES_LENGTH)
+    {
+        return Err(
+            BotError::user(tr!(locale, "error-notes-too-long", max = MAX_NOTES_LENGTH)).into(),
+        );
+    }
+
+    if image.as_ref().is_some_and(|image| {
+        !image
+            .content_type
+            .as_deref()
+            .is_some_and(|kind| kind.starts_with("image/"))
+    }) {
+        return Err(BotError::user(tr!(locale, "error-not-an-image")).into());
+    }
+
+    let pass_start = match pass {
+        Some(pass) => Some(
+            pass.parse::<i64>()
+                .map_err(|_| BotError::user(tr!(locale, "error-unknown-pass-time")))?,
+        ),
+        None => None,
+    };
+
+    let now = util::current_utc();
+
+    // The notified pass being logged, and the watch that notified of it
+    let notified = ctx
+        .data()
+        .database
+        .read()
+        .await
+        .contents
+        .watched_satellites
+        .iter()
+        .filter(|watched_satellite| {
+            watched_satellite.channel == channel && watched_satellite.satellite_id.0 == satellite_id
+        })
+        .flat_map(|watched_satellite| {
+            watched_satellite
+                .previous_notifications
+                .iter()
+                .map(move |(start, end)| (*start as i64, *end as i64, watched_satellite))
+        })
+        .filter(|(start, _, _)| match pass_start {
+            Some(pass_start) => *start == pass_start,
+            None => *start <= now,
+        })
+        .max_by_key(|(start, _, _)| *start)
+        .map(|(start, end, watched_satellite)| {
+            (
+                start,
+                end,
+                watched_satellite.location.clone(),
+                watched_satellite.name.clone(),
+            )
+        });
+
+    if pass_start.is_some() && notified.is_none() {
+        return Err(BotError::user(tr!(locale, "error-unknown-pass-time")).into());
+    }
+
+    let satellite = match &notified {
+        Some((_, _, _, name)) => name.clone(),
+        None => {
+            catalog::lookup(
+                &ctx.data().database,
+                &ctx.data().n2yo_api,
+                &ctx.data().celestrak_api,
+                satellite_id,
+            )
+            .await?
+            .name
+        }
+    };
+
+    let mut observation = LoggedObservation {
+        satellite_id: SatelliteId(satellite_id),
+        satellite,
+        observer: Snowflake(ctx.author().id.0),
+        guild: ctx.guild_id().map(|guild| Snowflake(guild.0)),
+        channel,
+        pass_start: notified.as_ref().map_or(now, |(start, ..)| *start),
+        pass_end: notified.as_ref().map(|(_, end, ..)| *end),
+        location: notified.map(|(_, _, location, _)| location),
+        logged_at: now,
+        notes,
+        image: None,
+    };
+
+    // The image is attached to the reply, so that it can be shown again once the link to the
+    // uploaded attachment has expired
+    let image = match image {
+        Some(image) => Some((image.download().await?, image.filename)),
+        None => None,
+    };
+
+    let reply = ctx
+        .send(|b| {
+            b.embed(|e| {
+                e.title(tr!(
+                    locale,
+                    "logbook-logged",
+                    satellite = observation.satellite.as_str()
+                ));
+                e.description(match (observation.pass_end, &observation.location) {
+                    (Some(end), Some(location)) => tr!(
+                        locale,
+                        "logbook-notified-pass",
+                        time =
+                            util::format_pass_time(observation.pass_start, end, TimeStyle::Discord),
+                        location = location.0.as_str()
+                    ),
+                    _ => tr!(
+                        locale,
+                        "logbook-time",
+                        time = util::format_time(observation.pass_start, TimeStyle::Discord)
+                    ),
+                });
+
+                if let Some(notes) = &observation.notes {
+                    e.field(tr!(locale, "logbook-notes"), notes, false);
+                }
+
+                if let Some((_, filename)) = &image {
+                    e.image(format!("attachment://{}", filename));
+                }
+
+                e
+            });
+
+            if let Some((data, filename)) = &image {
+                b.attachment(AttachmentType::Bytes {
+                    data: Cow::Borrowed(data),
+                    filename: filename.clone(),
+                });
+            }
+
+            b.ephemeral(preferences.ephemeral && image.is_none())
+        })
+        .await?;
+
+    if image.is_some() {
+        let message = reply.message().await?;
+        observation.image = message
+            .attachments
+            .first()
+            .map(|attachment| ObservationImage {
+                channel: Snowflake(message.channel_id.0),
+                message: Snowflake(message.id.0),
+                attachment: Snowflake(attachment.id.0),
+            });
+    }
+
+    let mut database = ctx.data().database.write().await;
+    database.contents.logged_observations.push(observation);
+    database.mark_dirty();
+
+    Ok(())
+}
+
+/// A fresh link to an image logged with an observation, or `None` if the message it is attached to
+/// has been deleted.
+pub async fn image_url(http: &Http, image: &ObservationImage) -> Option<String> {
+    ChannelId(image.channel.0)
+        .message(http, image.message.0)
+        .await
+        .ok()?
+        .attachments
+        .into_iter()
+        .find(|attachment| attachment.id.0 == image.attachment.0)
+        .map(|attachment| attachment.url)
+}
+
+/// A link to the message an image logged with an observation is attached to, which unlike a link
+/// to the image itself does not expire.
+pub fn image_message_link(guild: Option<&Snowflake>, image: &ObservationImage) -> String {
+    format!(
+        "https://discord.com/channels/{}/{}/{}",
+        guild.map_or("@me".to_string(), |guild| guild.0.to_string()),
+        image.channel.0,
+        image.message.0
+    )
+}
+
+/// Pages through the images logged with observations in this server, newest first.
+///
+/// Give a satellite, a location, or both to only show their observations. Only the newest 25 are
+/// shown.
+/// Example: `/gallery satellite_id:33591`
+/// Example: `/gallery location:home`
+#[command(slash_command, rename = "gallery", category = "Observations")]
+pub async fn gallery(
+    ctx: Context<'_>,
+    #[description = "the NORAD ID of the satellite to show observations of, or an alias for it"]
+    satellite_id: Option<String>,
+    #[description = "the location to show observations from"]
+    #[autocomplete = "autocomplete::location"]
+    location: Option<String>,
+) -> anyhow::Result<()> {
+    let satellite_id = match satellite_id {
+        Some(satellite_id) => Some(resolve_satellite(ctx, &satellite_id).await?),
+        None => None,
+    };
+    let preferences = defer_with_preferences(ctx).await?;
+    let locale = i18n::locale(&ctx);
+    let guild = ctx.guild_id().map(|guild| Snowflake(guild.0));
+    let channel = Snowflake(ctx.channel_id().0);
+
+    let mut observations = ctx
+        .data()
+        .database
+        .read()
+        .await
+        .contents
+        .logged_observations
+        .iter()
+        .filter(|observation| {
+            observation.image.is_some()
+                // Outside of servers, only the observations logged in the same DM are shown
+                && match &guild {
+                    Some(_) => observation.guild == guild,
+                    None => observation.channel == channel,
+                }
+                && satellite_id.is_none_or(|satellite_id| observation.satellite_id.0 == satellite_id)
+                && location.as_ref().is_none_or(|location| {
+                    observation
+                        .location
+                        .as_ref()
+                        .is_some_and(|observed| &observed.0 == location)
+                })
+        })
+        .cloned()
+        .collect::<Vec<_>>();
+
+    if observations.is_empty() {
+        return Err(BotError::user(tr!(locale, "error-empty-gallery")).into());
+    }
+
+    observations.sort_by_key(|observation| Reverse(observation.pass_start));
+    observations.truncate(MAX_GALLERY_IMAGES);
+
+    let http = &ctx.serenity_context().http;
+    let image_urls = join_all(
+        observations
+            .iter()
+            .map(|observation| async move { image_url(http, observation.image.as_ref()?).await }),
+    )
+    .await;
+
+    paginator::paginate(ctx, observations.len(), preferences.ephemeral, |page, e| {
+        let observation = &observations[page];
+        e.title(&observation.satellite);
+
+        let mut description = tr!(
+            locale,
+            "gallery-observer",
+            observer = format!("<@{}>", observation.observer.0),
+            time = util::format_time(observation.pass_start, TimeStyle::Discord)
+        );
+
+        if let Some(location) = &observation.location {
+            description.push('\n');
+            description.push_str(&tr!(
+                locale,
+                "gallery-location",
+                location = location.0.as_str()
+            ));
+        }
+
+        if let Some(notes) = &observation.notes {
+            description.push_str("\n\n");
+            description.push_str(notes);
+        }
+
+        e.description(description);
+
+        if let Some(image) = &image_urls[page] {
+            e.image(image);
+        }
+    })
+    .await
+}
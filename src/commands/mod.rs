@@ -1,27 +1,376 @@
+mod aliases;
 mod autocomplete;
+mod export;
+mod favorites;
+mod frequencies;
+mod geo;
+mod help;
+mod launches;
 mod location;
+mod logbook;
+mod moon;
+mod mute;
+mod observations;
+mod paginator;
+mod satellite;
+mod server_settings;
+mod session;
+mod settings;
+mod space_weather;
+mod starlink;
+mod stats;
+mod sync;
 mod upcoming;
+mod vacation;
 mod watch;
 
+pub use aliases::*;
+pub use autocomplete::remember_choices;
+pub use export::*;
+pub use favorites::*;
+pub use frequencies::*;
+pub use geo::*;
+pub use help::*;
+pub use launches::*;
 pub use location::*;
+pub use logbook::*;
+pub use moon::*;
+pub use mute::*;
+pub use observations::*;
+pub use satellite::*;
+use std::{borrow::Cow, cmp::Reverse, time::Duration};
+
+use poise::{serenity_prelude::AttachmentType, ChoiceParameter, CooldownConfig};
 use serenity::builder::CreateEmbed;
+pub use server_settings::*;
+pub use session::*;
+pub use settings::*;
+pub use space_weather::*;
+pub use starlink::*;
+pub use stats::*;
+pub use sync::*;
 pub use upcoming::*;
+pub use vacation::*;
 pub use watch::*;
 
-use crate::{n2yo::SatellitePasses, util, Context};
+use crate::{
+    config,
+    database::{Database, Location, Snowflake},
+    error::BotError,
+    groups,
+    i18n::{self, tr},
+    n2yo::{SatellitePass, SatellitePasses},
+    passes,
+    preferences::{GuildSettings, TimeFormat, UserPreferences},
+    util::{self, TimeStyle},
+    Context,
+};
+
+/// The user's preferences, with `ephemeral` being whether they prefer private replies to the
+/// command being run.
+pub async fn command_preferences(ctx: Context<'_>) -> UserPreferences {
+    let mut preferences = {
+        let database = ctx.data().database.read().await;
+        UserPreferences::for_user(&database, &Snowflake(ctx.author().id.0))
+    };
+    preferences.ephemeral = preferences.ephemeral_in(ctx.command().category);
+    preferences
+}
+
+/// Defers the response, privately if the user prefers ephemeral replies to the command, and
+/// returns their preferences as [`command_preferences`] does.
+pub async fn defer_with_preferences(ctx: Context<'_>) -> anyhow::Result<UserPreferences> {
+    let preferences = command_preferences(ctx).await;
+
+    if preferences.ephemeral {
+        ctx.defer_ephemeral().await?;
+    } else {
+        ctx.defer().await?;
+    }
+
+    Ok(preferences)
+}
+
+/// Reads a satellite option, given as a NORAD ID or one of the server's aliases.
+pub async fn resolve_satellite(ctx: Context<'_>, text: &str) -> anyhow::Result<usize> {
+    groups::resolve_one(
+        &*ctx.data().database.read().await,
+        ctx.guild_id().map(|guild| Snowflake(guild.0)).as_ref(),
+        i18n::locale(&ctx),
+        text,
+    )
+}
+
+/// Reads a satellites option, given as a group, `favorites`, or a list of NORAD IDs and the
+/// server's aliases.
+pub async fn resolve_satellites(ctx: Context<'_>, text: &str) -> anyhow::Result<Vec<usize>> {
+    groups::resolve(
+        &*ctx.data().database.read().await,
+        ctx.guild_id().map(|guild| Snowflake(guild.0)).as_ref(),
+        &Snowflake(ctx.author().id.0),
+        i18n::locale(&ctx),
+        text,
+    )
+}
+
+/// Whether the author can manage the server.
+pub async fn can_manage_guild(ctx: Context<'_>) -> bool {
+    ctx.author_member()
+        .await
+        .and_then(|member| member.permissions)
+        .is_some_and(|permissions| permissions.manage_guild())
+}
+
+/// Whether the author can use the admin commands, by being able to manage the server or having
+/// one of its admin roles. Nobody can outside of servers.
+pub async fn is_admin(ctx: Context<'_>) -> bool {
+    let (Some(guild), Some(member)) = (ctx.guild_id(), ctx.author_member().await) else {
+        return false;
+    };
+
+    if member
+        .permissions
+        .is_some_and(|permissions| permissions.manage_guild())
+    {
+        return true;
+    }
+
+    let settings = GuildSettings::for_guild(
+        &*ctx.data().database.read().await,
+        Some(&Snowflake(guild.0)),
+    );
+    member
+        .roles
+        .iter()
+        .any(|role| settings.admin_roles.contains(&Snowflake(role.0)))
+}
+
+/// A check for the admin commands, which change things for the whole server.
+pub async fn admin_only(ctx: Context<'_>) -> anyhow::Result<bool> {
+    if !is_admin(ctx).await {
+        return Err(BotError::user(tr!(i18n::locale(&ctx), "error-not-admin")).into());
+    }
+
+    Ok(true)
+}
+
+/// A check for the commands that request passes from N2YO, refusing them until the member's and
+/// channel's cooldowns, set by each server, have passed since they last ran it.
+pub async fn pass_request_cooldown(ctx: Context<'_>) -> anyhow::Result<bool> {
+    let settings = GuildSettings::for_guild(
+        &*ctx.data().database.read().await,
+        ctx.guild_id().map(|guild| Snowflake(guild.0)).as_ref(),
+    );
+    let cooldown = |seconds: u64| Some(Duration::from_secs(seconds)).filter(|d| !d.is_zero());
+    let config = CooldownConfig {
+        user: cooldown(settings.user_cooldown_seconds()),
+        channel: cooldown(settings.channel_cooldown_seconds()),
+        ..Default::default()
+    };
+    let remaining = ctx
+        .command()
+        .cooldowns
+        .lock()
+        .unwrap()
+        .remaining_cooldown_2(ctx, &config);
+
+    match remaining {
+        Some(remaining) => Err(BotError::user(tr!(
+            i18n::locale(&ctx),
+            "error-cooldown",
+            seconds = remaining.as_secs_f64().ceil() as u64
+        ))
+        .into()),
+        None => Ok(true),
+    }
+}
+
+/// Finds a location by name, or the user's default location if no name is given.
+pub fn find_location(
+    database: &Database,
+    locale: &str,
+    name: Option<&str>,
+    preferences: &UserPreferences,
+) -> anyhow::Result<Location> {
+    let name = name
+        .or(preferences
+            .default_location
+            .as_ref()
+            .map(|location| location.0.as_str()))
+        .ok_or_else(|| BotError::user(tr!(locale, "error-no-location-given")))?;
+
+    database
+        .contents
+        .locations
+        .iter()
+        .find(|location| location.name.0 == name)
+        .cloned()
+        .ok_or_else(|| BotError::user(tr!(locale, "error-no-such-location")).into())
+}
+
+/// The most fields Discord allows in an embed, and so the most passes listed at once.
+const MAX_LISTED_PASSES: usize = 25;
+/// The most passes listed at once as tables, which are long enough that more would not fit in
+/// the 6000 characters Discord allows across a message's embeds.
+const MAX_DETAILED_PASSES: usize = 10;
+
+/// The order passes are listed in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ChoiceParameter)]
+pub enum PassOrder {
+    #[name = "time"]
+    Time,
+    #[name = "elevation"]
+    Elevation,
+    #[name = "duration"]
+    Duration,
+    #[name = "quality"]
+    Quality,
+}
+
+/// The most passes to list in each of a number of embeds, fewer when they are given as tables.
+pub fn pass_limit(limit: Option<usize>, detailed: bool, embeds: usize) -> Option<usize> {
+    if detailed {
+        let most = (MAX_DETAILED_PASSES / embeds.max(1)).max(1);
+        Some(limit.unwrap_or(most).min(most))
+    } else {
+        limit
+    }
+}
+
+/// The responses N2YO gave for passes, as they were received, to attach to a reply. Passes
+/// predicted locally have none, which the returned note points out.
+pub fn raw_pass_attachments<'a>(
+    locale: &str,
+    passes: impl IntoIterator<Item = &'a SatellitePasses>,
+) -> (Vec<AttachmentType<'static>>, Option<String>) {
+    let mut attachments = Vec::new();
+    let mut missing = Vec::new();
+
+    for passes in passes {
+        match &passes.raw {
+            Some(raw) => attachments.push(AttachmentType::Bytes {
+                data: Cow::Owned(raw.clone().into_bytes()),
+                filename: format!("passes-{}.json", passes.info.id),
+            }),
+            None => missing.push(passes.info.name.as_str()),
+        }
+    }
+
+    let note = (!missing.is_empty())
+        .then(|| tr!(locale, "raw-unavailable", satellites = missing.join(", ")));
+    (attachments, note)
+}
+
+/// Orders passes, best first for anything but time, and keeps only the first few.
+pub fn sort_and_limit(passes: &mut Vec<SatellitePass>, order: PassOrder, limit: Option<usize>) {
+    match order {
+        PassOrder::Time => passes.sort_by_key(|pass| pass.start_utc),
+        PassOrder::Elevation => passes.sort_by(|a, b| b.max_elevation.total_cmp(&a.max_elevation)),
+        PassOrder::Duration => {
+            passes.sort_by_key(|pass| Reverse(pass.end_utc.saturating_sub(pass.start_utc)))
+        }
+        PassOrder::Quality => passes.sort_by_key(|pass| Reverse(passes::score(pass))),
+    }
+
+    passes.truncate(limit.unwrap_or(MAX_LISTED_PASSES).min(MAX_LISTED_PASSES));
+}
 
-pub fn embed_passes(e: &mut CreateEmbed, passes: SatellitePasses, days: usize) {
-    e.title(format!(
-        "Upcoming passes for {} in the next {} days",
-        passes.info.name, days
+/// The most days ahead N2YO gives passes for.
+const MAX_DAYS: usize = 10;
+
+/// Picks the configured number of days to look ahead if none is given, and clamps it to what
+/// N2YO supports. Returns a note for the reply if the given value was changed.
+pub fn resolve_days(locale: &str, given: Option<usize>) -> (usize, Option<String>) {
+    let days = match given {
+        Some(days) => days,
+        None => config::get().defaults.days,
+    };
+    let clamped = days.clamp(1, MAX_DAYS);
+
+    (
+        clamped,
+        (given.is_some() && clamped != days)
+            .then(|| tr!(locale, "note-days-clamped", days = clamped)),
+    )
+}
+
+/// Checks that a minimum elevation is between 0° and 90°.
+pub fn check_min_max_elevation(locale: &str, elevation: f64) -> anyhow::Result<()> {
+    if !(0.0..=90.0).contains(&elevation) {
+        return Err(BotError::user(tr!(locale, "error-elevation-range")).into());
+    }
+
+    Ok(())
+}
+
+/// Checks the given minimum elevation, or picks the guild's default if none is given.
+pub fn resolve_min_max_elevation(
+    ctx: Context<'_>,
+    database: &Database,
+    locale: &str,
+    given: Option<f64>,
+) -> anyhow::Result<f64> {
+    let elevation = match given {
+        Some(elevation) => elevation,
+        None => GuildSettings::for_guild(
+            database,
+            ctx.guild_id().map(|guild| Snowflake(guild.0)).as_ref(),
+        )
+        .default_min_max_elevation(),
+    };
+
+    check_min_max_elevation(locale, elevation)?;
+    Ok(elevation)
+}
+
+pub fn embed_passes(
+    e: &mut CreateEmbed,
+    locale: &str,
+    time_format: &TimeFormat,
+    passes: SatellitePasses,
+    days: usize,
+    detailed: bool,
+) {
+    e.title(tr!(
+        locale,
+        "passes-title",
+        satellite = passes.info.name.as_str(),
+        days = days
     ));
+    e.footer(|f| {
+        f.text(tr!(
+            locale,
+            "data-source",
+            source = passes.source.to_string()
+        ))
+    });
+    // Listings are coloured by their best pass
+    if let Some(max_elevation) = passes
+        .passes
+        .iter()
+        .map(|pass| pass.max_elevation)
+        .reduce(f64::max)
+    {
+        e.colour(passes::colour(max_elevation));
+    }
+
     e.fields(passes.passes.iter().map(|pass| {
         (
+            // Discord timestamps are not rendered in field names
             util::format_pass_time(
                 pass.start_utc as i64,
                 pass.end_utc as i64,
+                TimeStyle::Plain(time_format),
             ),
-            format!("Max Elevation: {}°", pass.max_elevation),
+            if detailed {
+                passes::table(locale, time_format, pass)
+            } else {
+                format!(
+                    "{}\n{}",
+                    passes::describe_path(locale, pass),
+                    passes::describe(locale, pass)
+                )
+            },
             false,
         )
     }));
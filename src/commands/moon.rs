@@ -0,0 +1,84 @@
+use poise::command;
+
+use crate::{
+    astro::{self, MoonPhase},
+    commands::{autocomplete, defer_with_preferences, find_location},
+    i18n::{self, tr},
+    util::{self, TimeStyle},
+    Context,
+};
+
+/// How far ahead moonrise and moonset are searched for, in seconds. The Moon rises around 50
+/// minutes later each day, so some days have no rise or set at all.
+const RISE_SET_WINDOW: i64 = 48 * 60 * 60;
+
+/// Shows the phase of the Moon, where it is in the sky, and when it next rises and sets.
+///
+/// A bright Moon near a satellite's track makes it harder to see. Visual passes that come close
+/// to the Moon are pointed out in pass listings.
+/// Example: `/moon location:home`
+#[command(slash_command, rename = "moon", category = "Conditions")]
+pub async fn moon(
+    ctx: Context<'_>,
+    #[description = "the location to show the Moon from, if not your default location"]
+    #[autocomplete = "autocomplete::location"]
+    location: Option<String>,
+) -> anyhow::Result<()> {
+    let preferences = defer_with_preferences(ctx).await?;
+    let locale = i18n::locale(&ctx);
+
+    let location = {
+        let database = ctx.data().database.read().await;
+        find_location(&database, locale, location.as_deref(), &preferences)?
+    };
+
+    let now = util::current_utc();
+    let phase = MoonPhase::at(now);
+    let illumination = (astro::moon_illumination(now) * 100.0).round();
+    let look_angles = astro::moon_look_angles(&location, now);
+    let (rise, set) = astro::moon_rise_set(&location, now, RISE_SET_WINDOW);
+
+    let describe_time = |time: Option<i64>| {
+        time.map(|time| util::format_time(time, TimeStyle::Discord))
+            .unwrap_or_else(|| tr!(locale, "moon-not-soon"))
+    };
+
+    ctx.send(|b| {
+        b.embed(|e| {
+            e.title(tr!(
+                locale,
+                "moon-title",
+                location = location.name.0.clone()
+            ));
+            e.field(
+                tr!(locale, "moon-phase"),
+                format!("{} {}", phase.emoji(), tr!(locale, phase.key())),
+                true,
+            );
+            e.field(
+                tr!(locale, "moon-illumination"),
+                format!("{}%", illumination),
+                true,
+            );
+            e.field(
+                tr!(locale, "moon-position"),
+                if look_angles.elevation > 0.0 {
+                    tr!(
+                        locale,
+                        "moon-position-up",
+                        azimuth = look_angles.azimuth.round(),
+                        elevation = look_angles.elevation.round()
+                    )
+                } else {
+                    tr!(locale, "moon-position-down")
+                },
+                true,
+            );
+            e.field(tr!(locale, "moon-rise"), describe_time(rise), true);
+            e.field(tr!(locale, "moon-set"), describe_time(set), true)
+        })
+    })
+    .await?;
+
+    Ok(())
+}
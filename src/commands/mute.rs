@@ -0,0 +1,88 @@
+use poise::command;
+
+use crate::{
+    database::{ChannelMute, Snowflake},
+    error::BotError,
+    i18n::{self, tr},
+    notify, util, Context,
+};
+
+/// The longest a channel can be muted for, in seconds.
+const MAX_MUTE: i64 = 30 * 24 * 60 * 60;
+
+/// Holds back every watch notification in this channel for a while.
+///
+/// The passes found while muted are listed in one message once the mute lifts. Muting again
+/// changes when it lifts, and `off` lifts it now.
+/// Example: `/mute-notifications duration:2d`
+#[command(
+    slash_command,
+    rename = "mute-notifications",
+    category = "Watches",
+    default_member_permissions = "MANAGE_CHANNELS"
+)]
+pub async fn mute_notifications(
+    ctx: Context<'_>,
+    #[description = "how long to mute for, such as 90m, 12h, or 3d, or off to unmute"]
+    duration: String,
+) -> anyhow::Result<()> {
+    ctx.defer().await?;
+    let locale = i18n::locale(&ctx);
+    let channel = Snowflake(ctx.channel_id().0);
+    let now = util::current_utc();
+
+    if duration.trim().eq_ignore_ascii_case("off") {
+        {
+            let mut database = ctx.data().database.write().await;
+            let mute = database
+                .contents
+                .channel_mutes
+                .get_mut(&channel)
+                .ok_or_else(|| BotError::user(tr!(locale, "error-not-muted")))?;
+            mute.until = now;
+            database.mark_dirty();
+        }
+
+        ctx.say(tr!(locale, "mute-ended")).await?;
+        notify::lift_expired_mutes(&*ctx.serenity_context().http, &ctx.data().database).await;
+        return Ok(());
+    }
+
+    let duration = util::parse_duration(&duration)
+        .ok_or_else(|| BotError::user(tr!(locale, "error-invalid-duration")))?;
+
+    if duration > MAX_MUTE {
+        return Err(BotError::user(tr!(
+            locale,
+            "error-mute-too-long",
+            days = MAX_MUTE / (24 * 60 * 60)
+        ))
+        .into());
+    }
+
+    let until = now + duration;
+
+    {
+        let mut database = ctx.data().database.write().await;
+        database
+            .contents
+            .channel_mutes
+            .entry(channel)
+            .and_modify(|mute| mute.until = until)
+            .or_insert_with(|| ChannelMute {
+                until,
+                locale: locale.to_string(),
+                held_passes: Vec::new(),
+            });
+        database.mark_dirty();
+    }
+
+    ctx.say(tr!(
+        locale,
+        "mute-started",
+        time = util::format_time(until, util::TimeStyle::Discord)
+    ))
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,75 @@
+use poise::command;
+
+use crate::{
+    commands::{defer_with_preferences, resolve_satellite},
+    error::BotError,
+    frequencies::format_frequency,
+    i18n::{self, tr},
+    util::{self, TimeStyle},
+    Context,
+};
+
+/// Lists the latest good SatNOGS Network observations of a satellite.
+///
+/// Useful for checking that a satellite is actually transmitting before staying up for a pass.
+/// Example: `/recent-observations satellite_id:43017`
+#[command(slash_command, rename = "recent-observations", category = "Satellites")]
+pub async fn recent_observations(
+    ctx: Context<'_>,
+    #[description = "the NORAD ID of the satellite, or an alias for it"] satellite_id: String,
+) -> anyhow::Result<()> {
+    let satellite_id = resolve_satellite(ctx, &satellite_id).await?;
+    defer_with_preferences(ctx).await?;
+    let locale = i18n::locale(&ctx);
+
+    let observations = ctx
+        .data()
+        .satnogs_api
+        .get_recent_observations(satellite_id)
+        .await?;
+
+    if observations.is_empty() {
+        return Err(BotError::user(tr!(locale, "error-no-observations")).into());
+    }
+
+    ctx.send(|b| {
+        for observation in observations.iter() {
+            b.embed(|e| {
+                e.title(tr!(
+                    locale,
+                    "observation-title",
+                    id = observation.id,
+                    station = observation.station_name.as_str()
+                ));
+                e.url(observation.url());
+
+                let mut lines = Vec::new();
+
+                if let Some(start) = observation.start_utc() {
+                    lines.push(util::format_time(start, TimeStyle::Discord));
+                }
+
+                if let Some(frequency) = observation.observation_frequency {
+                    lines.push(format!(
+                        "{} {}",
+                        format_frequency(frequency as f64 / 1_000_000.0),
+                        observation.transmitter_mode.as_deref().unwrap_or_default()
+                    ));
+                }
+
+                e.description(lines.join("\n"));
+
+                if let Some(waterfall) = &observation.waterfall {
+                    e.thumbnail(waterfall);
+                }
+
+                e
+            });
+        }
+
+        b
+    })
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,116 @@
+use std::time::Duration;
+
+use poise::serenity_prelude::{
+    ButtonStyle, CreateComponents, CreateEmbed, InteractionResponseType,
+};
+
+use crate::{
+    commands::Context,
+    i18n::{self, tr},
+};
+
+/// How long the buttons keep working after the last press.
+const TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
+/// Replies with the first of a number of pages, with buttons for the invoker to move between them.
+/// Each page's embed is drawn when it is shown, with its number in the footer.
+pub async fn paginate(
+    ctx: Context<'_>,
+    pages: usize,
+    ephemeral: bool,
+    draw: impl Fn(usize, &mut CreateEmbed),
+) -> anyhow::Result<()> {
+    paginate_with_components(ctx, pages, ephemeral, draw, |_, _| {}).await
+}
+
+/// Like [`paginate`], with rows of components of each page's own above the buttons. At most four
+/// rows can be added, and their presses are left to the event handler.
+pub async fn paginate_with_components(
+    ctx: Context<'_>,
+    pages: usize,
+    ephemeral: bool,
+    draw: impl Fn(usize, &mut CreateEmbed),
+    add_components: impl Fn(usize, &mut CreateComponents),
+) -> anyhow::Result<()> {
+    let locale = i18n::locale(&ctx);
+    let custom_id = format!("page-{}-", ctx.id());
+    let mut page = 0;
+
+    let embed = |page: usize| {
+        let mut embed = CreateEmbed::default();
+        draw(page, &mut embed);
+        embed.footer(|f| f.text(tr!(locale, "page-number", page = page + 1, pages = pages)));
+        embed
+    };
+    let buttons = |page: usize| {
+        let mut components = CreateComponents::default();
+        add_components(page, &mut components);
+
+        if pages > 1 {
+            components.create_action_row(|r| {
+                r.create_button(|b| {
+                    b.custom_id(format!("{}previous", custom_id))
+                        .label("◀")
+                        .style(ButtonStyle::Secondary)
+                        .disabled(page == 0)
+                })
+                .create_button(|b| {
+                    b.custom_id(format!("{}next", custom_id))
+                        .label("▶")
+                        .style(ButtonStyle::Secondary)
+                        .disabled(page + 1 == pages)
+                })
+            });
+        }
+
+        components
+    };
+
+    let reply = ctx
+        .send(|b| {
+            b.embed(|e| {
+                *e = embed(page);
+                e
+            })
+            .components(|c| {
+                *c = buttons(page);
+                c
+            })
+            .ephemeral(ephemeral)
+        })
+        .await?;
+
+    if pages <= 1 {
+        return Ok(());
+    }
+
+    let message = reply.message().await?;
+
+    while let Some(interaction) = message
+        .await_component_interaction(ctx.serenity_context())
+        .author_id(ctx.author().id)
+        .filter({
+            let custom_id = custom_id.clone();
+            move |interaction| interaction.data.custom_id.starts_with(&custom_id)
+        })
+        .timeout(TIMEOUT)
+        .await
+    {
+        if interaction.data.custom_id.ends_with("next") {
+            page = (page + 1).min(pages - 1);
+        } else {
+            page = page.saturating_sub(1);
+        }
+
+        interaction
+            .create_interaction_response(ctx.serenity_context(), |r| {
+                r.kind(InteractionResponseType::UpdateMessage)
+                    .interaction_response_data(|d| {
+                        d.set_embed(embed(page)).set_components(buttons(page))
+                    })
+            })
+            .await?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,310 @@
+use poise::{
+    command,
+    serenity_prelude::{CreateEmbed, Http},
+    ChoiceParameter,
+};
+use tracing::warn;
+
+use crate::{
+    celestrak::SatcatRecord,
+    commands::{autocomplete, defer_with_preferences, logbook, paginator, resolve_satellite},
+    database::Snowflake,
+    decay::OrbitSummary,
+    error::BotError,
+    i18n::{self, tr},
+    satcat::{self, SatcatFilter},
+    ApplicationContext, Context,
+};
+
+/// The most results a SATCAT search shows.
+const MAX_RESULTS: usize = 40;
+/// The results on each page of a SATCAT search, so that each has a row of buttons and there is
+/// still room for the page buttons.
+const RESULTS_PER_PAGE: usize = 4;
+
+/// Shows what is known about a satellite: when it launched, its orbit, and how old its TLE is.
+///
+/// The orbit is worked out from the latest TLE where there is one, and otherwise taken from
+/// CelesTrak's SATCAT. The latest image logged of the satellite in this server is shown with it.
+/// Example: `/satellite-info satellite_id:25544`
+#[command(slash_command, rename = "satellite-info", category = "Satellites")]
+pub async fn satellite_info(
+    ctx: Context<'_>,
+    #[description = "the NORAD ID of the satellite, or an alias for it"]
+    #[autocomplete = "autocomplete::satellite"]
+    satellite_id: String,
+) -> anyhow::Result<()> {
+    let satellite_id = resolve_satellite(ctx, &satellite_id).await?;
+    let preferences = defer_with_preferences(ctx).await?;
+    let embed = satellite_info_embed(
+        &ctx.serenity_context().http,
+        ctx.data(),
+        i18n::locale(&ctx),
+        ctx.guild_id().map(|guild| Snowflake(guild.0)),
+        Snowflake(ctx.channel_id().0),
+        satellite_id,
+    )
+    .await?;
+
+    ctx.send(|b| {
+        b.embed(|e| {
+            *e = embed;
+            e
+        })
+        .ephemeral(preferences.ephemeral)
+    })
+    .await?;
+
+    Ok(())
+}
+
+/// Builds the `/satellite-info` embed of a satellite, for a command or a button in the given
+/// channel.
+pub async fn satellite_info_embed(
+    http: &Http,
+    data: &ApplicationContext,
+    locale: &str,
+    guild: Option<Snowflake>,
+    channel: Snowflake,
+    satellite_id: usize,
+) -> anyhow::Result<CreateEmbed> {
+    let tle = match data.pass_provider.tle(satellite_id).await {
+        Ok(tle) => Some(tle),
+        Err(e) => {
+            warn!("Failed to get TLE of {}: {}", satellite_id, e);
+            None
+        }
+    };
+    let record = match data.celestrak_api.get_satcat_record(satellite_id).await {
+        Ok(record) => record,
+        Err(e) => {
+            warn!("Failed to get SATCAT record for {}: {}", satellite_id, e);
+            None
+        }
+    };
+
+    let name = match (&tle, &record) {
+        (Some((_, name)), _) => name.clone(),
+        (None, Some(record)) => record.name.clone(),
+        (None, None) => return Err(BotError::user(tr!(locale, "error-unknown-satellite")).into()),
+    };
+    let tle = tle.map(|(tle, _)| tle);
+    let summary = tle.as_ref().and_then(OrbitSummary::from_tle);
+
+    let designator = record
+        .as_ref()
+        .map(|record| record.international_designator.clone())
+        .or_else(|| tle.as_ref().and_then(|tle| tle.launch()));
+    let launch_date = record
+        .as_ref()
+        .and_then(|record| record.launch_date.clone());
+    let period = tle
+        .as_ref()
+        .and_then(|tle| tle.period())
+        .or_else(|| record.as_ref().and_then(|record| record.period));
+    let inclination = tle
+        .as_ref()
+        .and_then(|tle| tle.inclination())
+        .or_else(|| record.as_ref().and_then(|record| record.inclination));
+    let apsides = summary
+        .map(|summary| (summary.perigee, summary.apogee))
+        .or_else(|| {
+            record
+                .as_ref()
+                .and_then(|record| Some((record.perigee?, record.apogee?)))
+        });
+
+    let image = data
+        .database
+        .read()
+        .await
+        .contents
+        .logged_observations
+        .iter()
+        .filter(|observation| {
+            observation.satellite_id.0 == satellite_id
+                    // Outside of servers, only the images logged in the same DM are shown
+                    && match &guild {
+                        Some(_) => observation.guild == guild,
+                        None => observation.channel == channel,
+                    }
+        })
+        .filter_map(|observation| Some((observation.pass_start, observation.image.clone()?)))
+        .max_by_key(|(start, _)| *start)
+        .map(|(_, image)| image);
+    let image = match image {
+        Some(image) => logbook::image_url(http, &image).await,
+        None => None,
+    };
+
+    let unknown = || tr!(locale, "satellite-info-unknown");
+
+    let mut e = CreateEmbed::default();
+    e.title(&name);
+    e.field(tr!(locale, "satellite-info-id"), satellite_id, true);
+    e.field(
+        tr!(locale, "satellite-info-designator"),
+        designator.unwrap_or_else(unknown),
+        true,
+    );
+    e.field(
+        tr!(locale, "satellite-info-launched"),
+        launch_date.unwrap_or_else(unknown),
+        true,
+    );
+
+    if let Some(record) = &record {
+        e.field(tr!(locale, "satellite-info-type"), record.category(), true);
+    }
+
+    e.field(
+        tr!(locale, "satellite-info-period"),
+        period
+            .map(|period| {
+                tr!(
+                    locale,
+                    "satellite-info-minutes",
+                    minutes = format!("{:.1}", period)
+                )
+            })
+            .unwrap_or_else(unknown),
+        true,
+    );
+    e.field(
+        tr!(locale, "satellite-info-inclination"),
+        inclination
+            .map(|inclination| format!("{:.2}°", inclination))
+            .unwrap_or_else(unknown),
+        true,
+    );
+    e.field(
+        tr!(locale, "satellite-info-orbit"),
+        apsides
+            .map(|(perigee, apogee)| format!("{:.0} × {:.0} km", perigee, apogee))
+            .unwrap_or_else(unknown),
+        true,
+    );
+    e.field(
+        tr!(locale, "satellite-info-tle-age"),
+        summary
+            .map(|summary| tr!(locale, "satellite-info-days", days = summary.age_days()))
+            .unwrap_or_else(|| tr!(locale, "satellite-info-no-tle")),
+        true,
+    );
+
+    if let Some(image) = &image {
+        e.thumbnail(image);
+    }
+
+    e.footer(|f| f.text(tr!(locale, "satellite-info-source")));
+    Ok(e)
+}
+
+/// The kinds of object in the SATCAT.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ChoiceParameter)]
+pub enum ObjectType {
+    #[name = "Payload"]
+    Payload,
+    #[name = "Rocket body"]
+    RocketBody,
+    #[name = "Debris"]
+    Debris,
+    #[name = "Unknown"]
+    Unknown,
+}
+
+impl ObjectType {
+    /// The code the SATCAT uses for the type.
+    fn code(self) -> &'static str {
+        match self {
+            ObjectType::Payload => "PAY",
+            ObjectType::RocketBody => "R/B",
+            ObjectType::Debris => "DEB",
+            ObjectType::Unknown => "UNK",
+        }
+    }
+}
+
+/// Searches CelesTrak's catalog of every tracked object by name, NORAD ID, or designator.
+///
+/// The catalog is downloaded daily. Each result has buttons to show its `/satellite-info`, or to
+/// find out how to watch it.
+/// Example: `/satcat-search query:noaa owner:US launch_year:2009`
+#[command(slash_command, rename = "satcat-search", category = "Satellites")]
+pub async fn satcat_search(
+    ctx: Context<'_>,
+    #[description = "part of a name, a NORAD ID, or the start of an international designator"]
+    query: String,
+    #[description = "only objects launched in this year"] launch_year: Option<i32>,
+    #[description = "only objects owned by this country or organisation, such as US, PRC, or CIS"]
+    owner: Option<String>,
+    #[description = "only objects of this type"] object_type: Option<ObjectType>,
+) -> anyhow::Result<()> {
+    let preferences = defer_with_preferences(ctx).await?;
+    let locale = i18n::locale(&ctx);
+    let satcat = &ctx.data().satcat;
+
+    if !satcat.is_loaded().await {
+        return Err(BotError::user(tr!(locale, "error-satcat-not-loaded")).into());
+    }
+
+    let filter = SatcatFilter {
+        text: query.clone(),
+        launch_year,
+        owner: owner.map(|owner| owner.trim().to_string()),
+        object_type: object_type.map(ObjectType::code),
+    };
+    let results = satcat.search(&filter, MAX_RESULTS).await;
+
+    if results.is_empty() {
+        return Err(BotError::user(tr!(locale, "error-no-satcat-results")).into());
+    }
+
+    let pages = results.chunks(RESULTS_PER_PAGE).collect::<Vec<_>>();
+
+    paginator::paginate_with_components(
+        ctx,
+        pages.len(),
+        preferences.ephemeral,
+        |page, e| {
+            e.title(tr!(locale, "satcat-search-title", query = query.as_str()));
+
+            if results.len() == MAX_RESULTS {
+                e.description(tr!(locale, "satcat-search-truncated", max = MAX_RESULTS));
+            }
+
+            for record in pages[page] {
+                e.field(
+                    format!("{} – {}", record.id, record.name),
+                    describe(locale, record),
+                    false,
+                );
+            }
+        },
+        |page, c| satcat::create_buttons(c, locale, pages[page]),
+    )
+    .await
+}
+
+/// A line describing a SATCAT record, for search results.
+fn describe(locale: &str, record: &SatcatRecord) -> String {
+    let unknown = || tr!(locale, "satellite-info-unknown");
+    let mut description = tr!(
+        locale,
+        "satcat-search-result",
+        designator = record.international_designator.as_str(),
+        kind = record.category(),
+        owner = record.owner.clone().unwrap_or_else(unknown),
+        launched = record.launch_date.clone().unwrap_or_else(unknown)
+    );
+
+    if let Some(decay_date) = &record.decay_date {
+        description.push_str(&tr!(
+            locale,
+            "satcat-search-decayed",
+            date = decay_date.as_str()
+        ));
+    }
+
+    description
+}
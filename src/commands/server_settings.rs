@@ -0,0 +1,213 @@
+use poise::{command, serenity_prelude::Role};
+
+use crate::{
+    commands::{admin_only, can_manage_guild, check_min_max_elevation},
+    database::Snowflake,
+    error::BotError,
+    i18n::{self, tr},
+    preferences::GuildSettings,
+    Context,
+};
+
+/// The highest per-member watch limit a server can set.
+const MAX_WATCHES_LIMIT: usize = 1000;
+/// The longest cooldown a server can set, in seconds.
+const MAX_COOLDOWN_SECONDS: u64 = 60 * 60;
+
+/// Shows and changes settings for the whole server.
+#[command(
+    slash_command,
+    rename = "server-settings",
+    category = "Settings",
+    guild_only,
+    default_member_permissions = "MANAGE_GUILD",
+    check = "admin_only",
+    subcommands("show", "default_elevation", "max_watches", "cooldowns", "admin_role")
+)]
+pub async fn server_settings(_ctx: Context<'_>) -> anyhow::Result<()> {
+    Ok(())
+}
+
+/// Shows the server's current settings.
+#[command(slash_command, rename = "show")]
+async fn show(ctx: Context<'_>) -> anyhow::Result<()> {
+    update(ctx, |_| {}).await
+}
+
+/// Sets the minimum elevation used when a command is not given one.
+///
+/// Leave out the elevation to use the bot's default.
+#[command(slash_command, rename = "default-elevation")]
+async fn default_elevation(
+    ctx: Context<'_>,
+    #[description = "the minimum elevation, between 0 and 90"] elevation: Option<f64>,
+) -> anyhow::Result<()> {
+    if let Some(elevation) = elevation {
+        check_min_max_elevation(i18n::locale(&ctx), elevation)?;
+    }
+
+    update(ctx, |settings| {
+        settings.default_min_max_elevation = elevation
+    })
+    .await
+}
+
+/// Sets the most watches each member can have in the server.
+///
+/// This stops one member from using up the bot's N2YO quota. Leave out the limit to use the
+/// bot's default.
+#[command(slash_command, rename = "max-watches")]
+async fn max_watches(
+    ctx: Context<'_>,
+    #[description = "the most watches per member, between 1 and 1000"] limit: Option<usize>,
+) -> anyhow::Result<()> {
+    if limit.is_some_and(|limit| !(1..=MAX_WATCHES_LIMIT).contains(&limit)) {
+        return Err(BotError::user(tr!(
+            i18n::locale(&ctx),
+            "error-max-watches-range",
+            max = MAX_WATCHES_LIMIT
+        ))
+        .into());
+    }
+
+    update(ctx, |settings| settings.max_watches_per_user = limit).await
+}
+
+/// Sets how long members and channels wait between commands that request passes from N2YO.
+///
+/// This covers `/get-upcoming-passes` and `/update-watched-satellites`. A cooldown of 0 turns it
+/// off, and leaving one out uses the bot's default.
+#[command(slash_command, rename = "cooldowns")]
+async fn cooldowns(
+    ctx: Context<'_>,
+    #[description = "the seconds each member waits between commands, up to 3600"]
+    user_seconds: Option<u64>,
+    #[description = "the seconds each channel waits between commands, up to 3600"]
+    channel_seconds: Option<u64>,
+) -> anyhow::Result<()> {
+    if [user_seconds, channel_seconds]
+        .into_iter()
+        .flatten()
+        .any(|seconds| seconds > MAX_COOLDOWN_SECONDS)
+    {
+        return Err(BotError::user(tr!(
+            i18n::locale(&ctx),
+            "error-cooldown-range",
+            max = MAX_COOLDOWN_SECONDS
+        ))
+        .into());
+    }
+
+    update(ctx, |settings| {
+        settings.user_cooldown_seconds = user_seconds;
+        settings.channel_cooldown_seconds = channel_seconds;
+    })
+    .await
+}
+
+/// Lets members with a role use the admin commands, such as this one, or stops them.
+///
+/// Only members who can manage the server can change the admin roles.
+/// Example: `/server-settings admin-role role:@Club Committee allowed:true`
+#[command(slash_command, rename = "admin-role")]
+async fn admin_role(
+    ctx: Context<'_>,
+    #[description = "the role to allow or disallow"] role: Role,
+    #[description = "whether members with the role can use the admin commands"] allowed: bool,
+) -> anyhow::Result<()> {
+    if !can_manage_guild(ctx).await {
+        return Err(BotError::user(tr!(i18n::locale(&ctx), "error-manage-guild-only")).into());
+    }
+
+    let role = Snowflake(role.id.0);
+
+    update(ctx, |settings| {
+        settings.admin_roles.retain(|other| *other != role);
+
+        if allowed {
+            settings.admin_roles.push(role);
+        }
+    })
+    .await
+}
+
+/// Applies a change to the guild's settings, then shows them.
+async fn update(ctx: Context<'_>, change: impl FnOnce(&mut GuildSettings)) -> anyhow::Result<()> {
+    ctx.defer_ephemeral().await?;
+    let locale = i18n::locale(&ctx);
+    let guild = ctx
+        .guild_id()
+        .map(|guild| Snowflake(guild.0))
+        .ok_or_else(|| BotError::user(tr!(locale, "error-guild-only")))?;
+
+    let settings = {
+        let mut database = ctx.data().database.write().await;
+        let mut settings = GuildSettings::for_guild(&database, Some(&guild));
+        let previous = settings.clone();
+        change(&mut settings);
+
+        if settings != previous {
+            if settings == GuildSettings::default() {
+                database.contents.guild_settings.remove(&guild);
+            } else {
+                database
+                    .contents
+                    .guild_settings
+                    .insert(guild, settings.clone());
+            }
+
+            database.mark_dirty();
+        }
+
+        settings
+    };
+    let default_min_max_elevation = settings.default_min_max_elevation();
+    let max_watches_per_user = settings.max_watches_per_user();
+    let user_cooldown_seconds = settings.user_cooldown_seconds();
+    let channel_cooldown_seconds = settings.channel_cooldown_seconds();
+
+    ctx.send(|b| {
+        b.embed(|e| {
+            e.title(tr!(locale, "server-settings-title"));
+            e.field(
+                tr!(locale, "server-settings-default-elevation"),
+                format!("{}°", default_min_max_elevation),
+                true,
+            );
+            e.field(
+                tr!(locale, "server-settings-max-watches"),
+                max_watches_per_user,
+                true,
+            );
+            e.field(
+                tr!(locale, "server-settings-cooldowns"),
+                tr!(
+                    locale,
+                    "server-settings-cooldowns-value",
+                    user = user_cooldown_seconds,
+                    channel = channel_cooldown_seconds
+                ),
+                true,
+            );
+            e.field(
+                tr!(locale, "server-settings-admin-roles"),
+                if settings.admin_roles.is_empty() {
+                    tr!(locale, "server-settings-no-admin-roles")
+                } else {
+                    settings
+                        .admin_roles
+                        .iter()
+                        .map(|role| format!("<@&{}>", role.0))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                },
+                false,
+            );
+            e
+        })
+        .ephemeral(true)
+    })
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,185 @@
+use std::borrow::Cow;
+
+use poise::{command, serenity_prelude::AttachmentType, ChoiceParameter};
+
+use crate::{
+    commands::{
+        autocomplete, defer_with_preferences, find_location, resolve_min_max_elevation,
+        resolve_satellites, Context, MAX_DAYS, MAX_LISTED_PASSES,
+    },
+    error::BotError,
+    i18n::{self, tr},
+    n2yo::{CacheMode, PassKind},
+    planning::{self, PlannedPass},
+    provider::PassRequest,
+    util::{self, TimeStyle},
+};
+
+/// The longest session that can be planned, in hours.
+const MAX_SESSION_HOURS: usize = 72;
+
+/// The files a session plan can be attached as.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ChoiceParameter)]
+pub enum SessionFile {
+    #[name = "CSV"]
+    Csv,
+    #[name = "iCalendar"]
+    Ics,
+}
+
+/// Plans an observing session, picking passes that do not overlap, best first.
+///
+/// Satellites are given as NORAD IDs separated by commas, or as a group such as `noaa`.
+/// Your favorite satellites can be given as `favorites`. Where two passes overlap, or leave less
+/// than a minute to turn the antenna, only the better one is kept.
+/// Example: `/plan-session satellites:noaa location:home hours:12`
+/// Example: `/plan-session satellites:25544,43017,27607 starts_in:2 hours:6 file:iCalendar`
+#[command(slash_command, rename = "plan-session", category = "Passes")]
+#[allow(clippy::too_many_arguments)]
+pub async fn plan_session(
+    ctx: Context<'_>,
+    #[description = "NORAD IDs separated by commas, or a group name such as noaa or favorites"]
+    satellites: String,
+    #[description = "the location to plan the session for, if not your default location"]
+    #[autocomplete = "autocomplete::location"]
+    location: Option<String>,
+    #[description = "how many hours from now the session starts (default 0)"] starts_in: Option<
+        usize,
+    >,
+    #[description = "how long the session lasts, in hours (max 72, default 12)"] hours: Option<
+        usize,
+    >,
+    #[description = "the minimum elevation of the passes to plan (default 20° unless configured)"]
+    min_max_elevation: Option<f64>,
+    #[description = "whether to plan radio or visual passes (default radio)"] kind: Option<
+        PassKind,
+    >,
+    #[description = "a file of the plan to attach"] file: Option<SessionFile>,
+) -> anyhow::Result<()> {
+    let preferences = defer_with_preferences(ctx).await?;
+    let locale = i18n::locale(&ctx);
+    let starts_in = starts_in.unwrap_or(0);
+    let hours = hours.unwrap_or(12);
+
+    if hours == 0 || hours > MAX_SESSION_HOURS {
+        return Err(BotError::user(tr!(
+            locale,
+            "error-count-range",
+            parameter = "hours",
+            max = MAX_SESSION_HOURS
+        ))
+        .into());
+    }
+
+    let days = (starts_in + hours).div_ceil(24);
+
+    if days > MAX_DAYS {
+        return Err(BotError::user(tr!(locale, "error-session-too-late")).into());
+    }
+
+    let satellite_ids = resolve_satellites(ctx, &satellites).await?;
+    let (location, min_max_elevation) = {
+        let database = ctx.data().database.read().await;
+        (
+            find_location(&database, locale, location.as_deref(), &preferences)?,
+            resolve_min_max_elevation(ctx, &database, locale, min_max_elevation)?,
+        )
+    };
+
+    let requests = satellite_ids
+        .iter()
+        .map(|satellite_id| PassRequest {
+            satellite_id: *satellite_id,
+            location: location.clone(),
+            days,
+            min_max_elevation,
+            kind: kind.unwrap_or_default(),
+        })
+        .collect();
+    let candidates = ctx
+        .data()
+        .pass_provider
+        .get_many_satellite_passes(requests, CacheMode::Use)
+        .await
+        .into_iter()
+        .zip(&satellite_ids)
+        .map(|(passes, satellite_id)| {
+            let passes = passes?;
+            Ok(passes
+                .passes
+                .into_iter()
+                .map(|pass| PlannedPass::new(*satellite_id, passes.info.name.clone(), pass))
+                .collect::<Vec<_>>())
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?
+        .into_iter()
+        .flatten()
+        .collect();
+
+    let start = util::current_utc() + starts_in as i64 * 60 * 60;
+    let end = start + hours as i64 * 60 * 60;
+    let plan = planning::plan(candidates, start, end);
+
+    if plan.passes.is_empty() {
+        return Err(BotError::user(tr!(locale, "error-empty-session")).into());
+    }
+
+    let attachment = match file {
+        Some(SessionFile::Csv) => Some(("session.csv", plan.to_csv()?)),
+        Some(SessionFile::Ics) => Some(("session.ics", plan.to_ics(&location.name.0)?)),
+        None => None,
+    };
+
+    ctx.send(|b| {
+        b.embed(|e| {
+            e.title(tr!(
+                locale,
+                "session-title",
+                location = location.name.0.as_str()
+            ));
+            e.description(tr!(
+                locale,
+                "session-description",
+                start = util::format_time(start, TimeStyle::Discord),
+                end = util::format_time(end, TimeStyle::Discord),
+                count = plan.passes.len()
+            ));
+
+            for planned in plan.passes.iter().take(MAX_LISTED_PASSES) {
+                e.field(
+                    &planned.satellite,
+                    tr!(
+                        locale,
+                        "session-pass",
+                        time = util::format_pass_time(
+                            planned.pass.start_utc as i64,
+                            planned.pass.end_utc as i64,
+                            TimeStyle::Discord
+                        ),
+                        elevation = planned.pass.max_elevation,
+                        quality = planned.quality
+                    ),
+                    false,
+                );
+            }
+
+            if plan.conflicts > 0 {
+                e.footer(|f| f.text(tr!(locale, "session-conflicts", count = plan.conflicts)));
+            }
+
+            e
+        });
+
+        if let Some((filename, contents)) = attachment {
+            b.attachment(AttachmentType::Bytes {
+                data: Cow::Owned(contents.into_bytes()),
+                filename: filename.to_string(),
+            });
+        }
+
+        b.ephemeral(preferences.ephemeral)
+    })
+    .await?;
+
+    Ok(())
+}
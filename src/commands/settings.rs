@@ -0,0 +1,230 @@
+use poise::{command, serenity_prelude::CreateEmbed};
+
+use crate::{
+    commands::autocomplete,
+    database::{LocationName, Snowflake},
+    error::BotError,
+    i18n::{self, tr},
+    preferences::{ClockFormat, CommandCategory, DateFormat, Units, UserPreferences},
+    util, Context,
+};
+
+/// Shows and changes your personal settings.
+#[command(
+    slash_command,
+    rename = "settings",
+    category = "Settings",
+    subcommands(
+        "show",
+        "default_location",
+        "units",
+        "time_format",
+        "ephemeral",
+        "direct_messages",
+        "reset"
+    )
+)]
+pub async fn settings(_ctx: Context<'_>) -> anyhow::Result<()> {
+    Ok(())
+}
+
+/// Shows your current settings.
+#[command(slash_command, rename = "show")]
+async fn show(ctx: Context<'_>) -> anyhow::Result<()> {
+    update(ctx, |_| {}).await
+}
+
+/// Sets the location used when a command is not given one.
+///
+/// Leave out the location to clear it.
+#[command(slash_command, rename = "default-location")]
+async fn default_location(
+    ctx: Context<'_>,
+    #[description = "the location to use by default"]
+    #[autocomplete = "autocomplete::location"]
+    location: Option<String>,
+) -> anyhow::Result<()> {
+    if let Some(location) = &location {
+        let database = ctx.data().database.read().await;
+
+        if !database
+            .contents
+            .locations
+            .iter()
+            .any(|other_location| &other_location.name.0 == location)
+        {
+            return Err(BotError::user(tr!(i18n::locale(&ctx), "error-no-such-location")).into());
+        }
+    }
+
+    update(ctx, |preferences| {
+        preferences.default_location = location.map(LocationName)
+    })
+    .await
+}
+
+/// Sets the units lengths such as altitudes are written in.
+#[command(slash_command, rename = "units")]
+async fn units(
+    ctx: Context<'_>,
+    #[description = "the units to use, or none to follow your Discord language"] units: Option<
+        Units,
+    >,
+) -> anyhow::Result<()> {
+    update(ctx, |preferences| preferences.units = units).await
+}
+
+/// Sets how times and dates are written where Discord can't show them in your own format.
+///
+/// Unset options follow your Discord language.
+/// Example: `/settings time-format clock:24-hour date:ISO 8601`
+#[command(slash_command, rename = "time-format")]
+async fn time_format(
+    ctx: Context<'_>,
+    #[description = "whether to use a 12 or 24-hour clock"] clock: Option<ClockFormat>,
+    #[description = "the order to write dates in"] date: Option<DateFormat>,
+) -> anyhow::Result<()> {
+    update(ctx, |preferences| {
+        preferences.clock = clock;
+        preferences.date = date;
+    })
+    .await
+}
+
+/// Sets whether lookups such as upcoming passes are only shown to you.
+///
+/// Give a category to set it for only those commands, such as to keep pass lookups quiet in busy
+/// channels. Leaving out the category sets it for every command. Notifications are always public.
+/// Example: `/settings ephemeral enabled:true category:Passes`
+#[command(slash_command, rename = "ephemeral")]
+async fn ephemeral(
+    ctx: Context<'_>,
+    #[description = "whether only you see the replies"] enabled: bool,
+    #[description = "the category of commands to set it for, if not every command"]
+    category: Option<CommandCategory>,
+) -> anyhow::Result<()> {
+    update(ctx, |preferences| match category {
+        Some(category) => {
+            preferences
+                .ephemeral_categories
+                .insert(category.name().to_string(), enabled);
+        }
+        None => {
+            preferences.ephemeral = enabled;
+            preferences.ephemeral_categories.clear();
+        }
+    })
+    .await
+}
+
+/// Sets whether the bot may message you directly, such as when one of your watches is disabled.
+#[command(slash_command, rename = "direct-messages")]
+async fn direct_messages(
+    ctx: Context<'_>,
+    #[description = "whether the bot may message you"] enabled: bool,
+) -> anyhow::Result<()> {
+    update(ctx, |preferences| preferences.direct_messages = enabled).await
+}
+
+/// Resets all of your settings to their defaults, keeping your favorite satellites.
+#[command(slash_command, rename = "reset")]
+async fn reset(ctx: Context<'_>) -> anyhow::Result<()> {
+    update(ctx, |preferences| {
+        *preferences = UserPreferences {
+            favorites: std::mem::take(&mut preferences.favorites),
+            ..Default::default()
+        }
+    })
+    .await
+}
+
+/// Applies a change to the user's preferences, then shows them.
+async fn update(ctx: Context<'_>, change: impl FnOnce(&mut UserPreferences)) -> anyhow::Result<()> {
+    ctx.defer_ephemeral().await?;
+    let locale = i18n::locale(&ctx);
+    let user = Snowflake(ctx.author().id.0);
+
+    let preferences = {
+        let mut database = ctx.data().database.write().await;
+        let mut preferences = UserPreferences::for_user(&database, &user);
+        let previous = preferences.clone();
+        change(&mut preferences);
+
+        if preferences != previous {
+            if preferences == UserPreferences::default() {
+                database.contents.preferences.remove(&user);
+            } else {
+                database
+                    .contents
+                    .preferences
+                    .insert(user, preferences.clone());
+            }
+
+            database.mark_dirty();
+        }
+
+        preferences
+    };
+
+    ctx.send(|b| {
+        b.embed(|e| {
+            embed_settings(e, locale, &preferences);
+            e
+        })
+        .ephemeral(true)
+    })
+    .await?;
+
+    Ok(())
+}
+
+fn embed_settings(e: &mut CreateEmbed, locale: &str, preferences: &UserPreferences) {
+    let on_off = |enabled: bool| {
+        if enabled {
+            tr!(locale, "settings-on")
+        } else {
+            tr!(locale, "settings-off")
+        }
+    };
+
+    e.title(tr!(locale, "settings-title"));
+    e.field(
+        tr!(locale, "settings-default-location"),
+        preferences
+            .default_location
+            .as_ref()
+            .map(|location| location.0.clone())
+            .unwrap_or_else(|| tr!(locale, "settings-none")),
+        true,
+    );
+    e.field(
+        tr!(locale, "settings-units"),
+        preferences.units(locale).name(),
+        true,
+    );
+    e.field(
+        tr!(locale, "settings-time-format"),
+        preferences
+            .time_format(locale)
+            .date_time(util::current_utc()),
+        true,
+    );
+    e.field(
+        tr!(locale, "settings-ephemeral"),
+        std::iter::once(on_off(preferences.ephemeral))
+            .chain(
+                preferences
+                    .ephemeral_categories
+                    .iter()
+                    .map(|(category, enabled)| format!("{}: {}", category, on_off(*enabled))),
+            )
+            .collect::<Vec<_>>()
+            .join("\n"),
+        true,
+    );
+    e.field(
+        tr!(locale, "settings-direct-messages"),
+        on_off(preferences.direct_messages),
+        true,
+    );
+}
@@ -0,0 +1,138 @@
+use poise::command;
+
+use crate::{
+    commands::{autocomplete, defer_with_preferences, find_location},
+    i18n::{self, tr},
+    swpc,
+    util::{self, TimeStyle},
+    Context,
+};
+
+/// Shows the geomagnetic conditions and the chance of seeing an aurora.
+///
+/// The Kp index measures how disturbed the Earth's magnetic field is, from 0 to 9. Storms from Kp
+/// 5 upwards disrupt HF and VHF propagation, and push aurorae towards the equator.
+/// Example: `/space-weather location:home`
+#[command(slash_command, rename = "space-weather", category = "Conditions")]
+pub async fn space_weather(
+    ctx: Context<'_>,
+    #[description = "the location to get the aurora forecast for, if not your default location"]
+    #[autocomplete = "autocomplete::location"]
+    location: Option<String>,
+) -> anyhow::Result<()> {
+    let preferences = defer_with_preferences(ctx).await?;
+    let locale = i18n::locale(&ctx);
+
+    let location = {
+        let database = ctx.data().database.read().await;
+        find_location(&database, locale, location.as_deref(), &preferences)?
+    };
+    let report = ctx.data().swpc_api.get_space_weather().await?;
+
+    let describe_kp = |kp: f64| match swpc::storm_level(kp) {
+        Some(level) => tr!(locale, "space-weather-kp-storm", kp = kp, level = level),
+        None => tr!(locale, "space-weather-kp", kp = kp),
+    };
+
+    let (measured_at, current_kp) = report.current;
+    let predicted = if report.predicted.is_empty() {
+        tr!(locale, "space-weather-no-prediction")
+    } else {
+        report
+            .predicted
+            .iter()
+            .map(|(time, kp)| {
+                format!(
+                    "{} — {}",
+                    util::format_time(*time, TimeStyle::Discord),
+                    describe_kp(*kp)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    ctx.send(|b| {
+        b.embed(|e| {
+            e.title(tr!(locale, "space-weather-title"));
+            e.field(
+                tr!(locale, "space-weather-current"),
+                format!(
+                    "{}\n{}",
+                    describe_kp(current_kp),
+                    util::format_time(measured_at, TimeStyle::Discord)
+                ),
+                true,
+            );
+            e.field(
+                tr!(
+                    locale,
+                    "space-weather-aurora",
+                    location = location.name.0.clone()
+                ),
+                tr!(
+                    locale,
+                    "space-weather-aurora-chance",
+                    chance = report.aurora.probability_at(&location)
+                ),
+                true,
+            );
+            e.field(tr!(locale, "space-weather-predicted"), predicted, false);
+            e.footer(|f| f.text(tr!(locale, "space-weather-source")))
+        })
+    })
+    .await?;
+
+    Ok(())
+}
+
+/// Shows the solar indices used to judge radio propagation.
+///
+/// A higher solar flux index (SFI) and sunspot number mean better conditions on the higher HF
+/// bands, while higher A and K indices mean a disturbed, noisier ionosphere.
+/// Example: `/solar`
+#[command(slash_command, rename = "solar", category = "Conditions")]
+pub async fn solar(ctx: Context<'_>) -> anyhow::Result<()> {
+    defer_with_preferences(ctx).await?;
+    let locale = i18n::locale(&ctx);
+    let indices = ctx.data().swpc_api.get_solar_indices().await?;
+
+    let describe = |index: Option<(i64, f64)>| match index {
+        Some((measured_at, value)) => format!(
+            "**{:.0}**\n{}",
+            value,
+            util::format_time(measured_at, TimeStyle::Discord)
+        ),
+        None => tr!(locale, "solar-unavailable"),
+    };
+
+    ctx.send(|b| {
+        b.embed(|e| {
+            e.title(tr!(locale, "solar-title"));
+            e.field(
+                tr!(locale, "solar-flux"),
+                describe(indices.solar_flux),
+                true,
+            );
+            e.field(
+                tr!(locale, "solar-sunspot-number"),
+                describe(indices.sunspot_number),
+                true,
+            );
+            e.field(
+                tr!(locale, "solar-a-index"),
+                describe(indices.a_index),
+                true,
+            );
+            e.field(
+                tr!(locale, "solar-k-index"),
+                describe(indices.k_index),
+                true,
+            );
+            e.footer(|f| f.text(tr!(locale, "space-weather-source")))
+        })
+    })
+    .await?;
+
+    Ok(())
+}
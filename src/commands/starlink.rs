@@ -0,0 +1,115 @@
+use poise::command;
+
+use crate::{
+    commands::{autocomplete, defer_with_preferences, find_location},
+    error::BotError,
+    i18n::{self, tr},
+    starlink,
+    util::{self, TimeStyle},
+    Context,
+};
+
+/// The most recent batches listed.
+const MAX_BATCHES: usize = 3;
+/// The most passes listed for each batch.
+const MAX_PASSES: usize = 5;
+/// The most evenings ahead that can be searched.
+const MAX_DAYS: usize = 7;
+
+/// Predicts when recently launched Starlink trains can be seen from a location.
+///
+/// New batches of Starlink satellites fly in a line, seen as a "train" of bright dots crossing the
+/// sky shortly after dusk or before dawn, until they spread out as they climb to their final
+/// orbits over the following weeks.
+/// Example: `/starlink-train location:home days:3`
+#[command(slash_command, rename = "starlink-train", category = "Passes")]
+pub async fn starlink_train(
+    ctx: Context<'_>,
+    #[description = "the location to predict the trains for, if not your default location"]
+    #[autocomplete = "autocomplete::location"]
+    location: Option<String>,
+    #[description = "how many days ahead to look (max 7, default 3)"] days: Option<usize>,
+) -> anyhow::Result<()> {
+    let preferences = defer_with_preferences(ctx).await?;
+    let locale = i18n::locale(&ctx);
+    let days = days.unwrap_or(3);
+
+    if days == 0 || days > MAX_DAYS {
+        return Err(BotError::user(tr!(locale, "error-days-range", parameter = "days")).into());
+    }
+
+    let location = {
+        let database = ctx.data().database.read().await;
+        find_location(&database, locale, location.as_deref(), &preferences)?
+    };
+
+    let text = ctx
+        .data()
+        .celestrak_api
+        .get_group_tles(starlink::RECENT_LAUNCHES)
+        .await?;
+    let batches = starlink::find_batches(&text);
+
+    if batches.is_empty() {
+        return Err(BotError::user(tr!(locale, "error-no-starlink-trains")).into());
+    }
+
+    let start = util::current_utc();
+    let end = start + days as i64 * 24 * 60 * 60;
+    let mut fields = Vec::new();
+
+    for batch in batches.iter().take(MAX_BATCHES) {
+        let passes = batch.visible_passes(&location, start, end)?;
+        let value = if passes.is_empty() {
+            tr!(locale, "starlink-no-passes")
+        } else {
+            passes
+                .iter()
+                .take(MAX_PASSES)
+                .map(|pass| {
+                    tr!(
+                        locale,
+                        "starlink-pass",
+                        time = util::format_pass_time(
+                            pass.start_utc as i64,
+                            pass.end_utc as i64,
+                            TimeStyle::Discord
+                        ),
+                        elevation = pass.max_elevation,
+                        from = pass.start_azimuth_compass.as_str(),
+                        to = pass.end_azimuth_compass.as_str()
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+
+        fields.push((
+            tr!(
+                locale,
+                "starlink-batch",
+                launch = batch.launch.as_str(),
+                count = batch.satellites.len(),
+                altitude = batch.altitude.round()
+            ),
+            value,
+            false,
+        ));
+    }
+
+    ctx.send(|b| {
+        b.embed(|e| {
+            e.title(tr!(
+                locale,
+                "starlink-title",
+                location = location.name.0.clone()
+            ));
+            e.description(tr!(locale, "starlink-description"));
+            e.fields(fields);
+            e.footer(|f| f.text(tr!(locale, "starlink-source")))
+        })
+    })
+    .await?;
+
+    Ok(())
+}
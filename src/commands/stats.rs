@@ -0,0 +1,139 @@
+use poise::command;
+
+use crate::{
+    commands::defer_with_preferences,
+    i18n::{self, tr},
+    usage,
+    util::{self, TimeStyle},
+    Context,
+};
+
+/// Shows usage statistics for the bot.
+#[command(slash_command, rename = "stats", category = "Other")]
+pub async fn stats(ctx: Context<'_>) -> anyhow::Result<()> {
+    let preferences = defer_with_preferences(ctx).await?;
+    let locale = i18n::locale(&ctx);
+    let n2yo_api = &ctx.data().n2yo_api;
+    let metrics = &ctx.data().metrics;
+    let (locations, watches, usage, top_satellites) = {
+        let database = ctx.data().database.read().await;
+        (
+            database.contents.locations.len(),
+            database.contents.watched_satellites.len(),
+            database.contents.usage.clone(),
+            usage::top_satellites(&database, 5),
+        )
+    };
+    let this_week = usage.this_week();
+    let last_week = usage.last_week();
+    let top_commands = usage::top_commands(&usage, 5);
+
+    ctx.send(|b| {
+        b.embed(|e| {
+            e.title(tr!(locale, "stats-title"));
+            e.field(
+                tr!(locale, "stats-started"),
+                util::format_time(metrics.started_at(), TimeStyle::Discord),
+                true,
+            );
+            e.field(tr!(locale, "stats-locations"), locations, true);
+            e.field(tr!(locale, "stats-watches"), watches, true);
+            e.field(
+                tr!(locale, "stats-notifications-sent"),
+                metrics.notifications_sent(),
+                true,
+            );
+            e.field(
+                tr!(locale, "stats-last-notify-run"),
+                metrics
+                    .last_notify_run()
+                    .map(|time| util::format_time(time, TimeStyle::Discord))
+                    .unwrap_or_else(|| tr!(locale, "stats-never")),
+                true,
+            );
+            e.field(
+                tr!(locale, "stats-notify-restarts"),
+                metrics.notify_restarts(),
+                true,
+            );
+            e.field(
+                tr!(locale, "stats-notifications-this-week"),
+                usage::trend(this_week.notifications, last_week.notifications),
+                true,
+            );
+            e.field(
+                tr!(locale, "stats-commands-this-week"),
+                usage::trend(this_week.command_invocations, last_week.command_invocations),
+                true,
+            );
+
+            if !top_satellites.is_empty() {
+                e.field(
+                    tr!(locale, "stats-top-satellites"),
+                    top_satellites.join("\n"),
+                    false,
+                );
+            }
+
+            if !top_commands.is_empty() {
+                e.field(
+                    tr!(locale, "stats-top-commands"),
+                    top_commands.join("\n"),
+                    false,
+                );
+            }
+
+            e.field(
+                tr!(locale, "stats-n2yo-hourly"),
+                format!(
+                    "{}/{}",
+                    n2yo_api.hourly_transactions(),
+                    n2yo_api.total_hourly_limit()
+                ),
+                false,
+            );
+            e.field(
+                tr!(locale, "stats-n2yo-today"),
+                n2yo_api.transactions_today(),
+                false,
+            );
+            e.field(
+                tr!(locale, "stats-n2yo-total"),
+                n2yo_api.total_transactions(),
+                false,
+            );
+
+            if n2yo_api.keys().len() > 1 {
+                e.field(
+                    tr!(locale, "stats-n2yo-keys"),
+                    n2yo_api
+                        .keys()
+                        .iter()
+                        .map(|key| {
+                            format!(
+                                "`{}`: {}/{} last hour, {} since start{}",
+                                key.masked(),
+                                key.hourly_transactions(),
+                                n2yo_api.hourly_limit(),
+                                key.total_transactions(),
+                                if key.is_rate_limited() {
+                                    " (rate limited)"
+                                } else {
+                                    ""
+                                }
+                            )
+                        })
+                        .collect::<Vec<_>>()
+                        .join("\n"),
+                    false,
+                );
+            }
+
+            e
+        })
+        .ephemeral(preferences.ephemeral)
+    })
+    .await?;
+
+    Ok(())
+}
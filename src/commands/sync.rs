@@ -0,0 +1,43 @@
+use poise::command;
+
+use crate::{
+    commands::admin_only,
+    error::BotError,
+    i18n::{self, tr},
+    registration, Context,
+};
+
+/// Registers the bot's commands with Discord again, such as when some are missing.
+///
+/// Commands are registered wherever the bot is configured to register them, or only in this
+/// server when they are registered in each server separately.
+#[command(
+    slash_command,
+    rename = "sync-commands",
+    category = "Other",
+    guild_only,
+    default_member_permissions = "MANAGE_GUILD",
+    check = "admin_only",
+    guild_cooldown = 60
+)]
+pub async fn sync_commands(ctx: Context<'_>) -> anyhow::Result<()> {
+    ctx.defer_ephemeral().await?;
+    let locale = i18n::locale(&ctx);
+    let guild = ctx
+        .guild_id()
+        .ok_or_else(|| BotError::user(tr!(locale, "error-guild-only")))?;
+    let commands = &ctx.framework().options().commands;
+
+    registration::sync(
+        &ctx.serenity_context().http,
+        &ctx.data().database,
+        commands,
+        std::iter::once(guild),
+    )
+    .await?;
+
+    ctx.say(tr!(locale, "commands-synced", count = commands.len()))
+        .await?;
+
+    Ok(())
+}
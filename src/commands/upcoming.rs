@@ -1,63 +1,157 @@
-use poise::command;
+use std::collections::BTreeSet;
 
-use crate::commands::{autocomplete, embed_passes, Context};
+use poise::{command, serenity_prelude::ButtonStyle, ChoiceParameter};
+
+use crate::{
+    astro::Lighting,
+    commands::{
+        autocomplete, defer_with_preferences, embed_passes, find_location, logbook, pass_limit,
+        pass_request_cooldown, raw_pass_attachments, resolve_days, resolve_min_max_elevation,
+        resolve_satellite, resolve_satellites, sort_and_limit, Context, PassOrder,
+    },
+    database::Snowflake,
+    error::BotError,
+    frequencies, groups,
+    i18n::{self, tr},
+    n2yo::{CacheMode, PassKind, SatellitePass},
+    notify::PASS_HISTORY_DAYS,
+    passes,
+    provider::PassRequest,
+    util::{self, TimeStyle},
+};
+
+/// The most passes compared at each location.
+const MAX_COMPARED_PASSES: usize = 10;
+/// The most locations `/pass-info` gives the path of a pass for.
+const MAX_PASS_INFO_PATHS: usize = 10;
+/// The most observations `/pass-info` lists, so that they fit in a field.
+const MAX_PASS_INFO_OBSERVATIONS: usize = 5;
+/// The most characters of each observation's notes `/pass-info` shows.
+const MAX_PASS_INFO_NOTES_LENGTH: usize = 100;
 
 /// Gets all the upcoming passes for a satellite.
-#[command(slash_command, rename = "get-upcoming-passes")]
+///
+/// Visual passes are those where the satellite can be seen with the naked eye, and give its
+/// magnitude, where lower numbers are brighter. Giving `brighter_than` lists only visual passes.
+/// Detailed listings give a table of each pass's rise, peak, and set, for aiming antennas by hand.
+/// Example: `/get-upcoming-passes satellite_id:25544 min_max_elevation:20 location:home days:7 sort_by:elevation limit:5`
+/// Example: `/get-upcoming-passes satellite_id:25544 kind:visual brighter_than:2`
+#[command(
+    slash_command,
+    rename = "get-upcoming-passes",
+    category = "Passes",
+    check = "pass_request_cooldown"
+)]
+#[allow(clippy::too_many_arguments)]
 pub async fn get_upcoming_passes(
     ctx: Context<'_>,
-    #[description = "the NORAD ID of the satellite"] satellite_id: usize,
-    #[description = "the location to get passes for"]
+    #[description = "the NORAD ID of the satellite, or an alias for it"]
+    #[autocomplete = "autocomplete::satellite"]
+    satellite_id: String,
+    #[description = "the minimum elevation of the passes to get (default 20° unless configured)"]
+    min_max_elevation: Option<f64>,
+    #[description = "the location to get passes for, if not your default location"]
     #[autocomplete = "autocomplete::location"]
-    location: String,
-    #[description = "the number of days in the future to get passes for (max 10)"] days: usize,
-    #[description = "the minimum elevation of the passes to get"] min_max_elevation: f64,
+    location: Option<String>,
+    #[description = "the number of days in the future to get passes for (max 10, default 3)"]
+    days: Option<usize>,
+    #[description = "what to list passes by, best first (default time)"] sort_by: Option<PassOrder>,
+    #[description = "the most passes to list (max 25)"]
+    #[min = 1]
+    #[max = 25]
+    limit: Option<usize>,
+    #[description = "whether to only list daytime or nighttime passes"] lighting: Option<Lighting>,
+    #[description = "whether to list radio or visual passes (default radio)"] kind: Option<
+        PassKind,
+    >,
+    #[description = "only list visual passes brighter than this magnitude, such as 2"]
+    brighter_than: Option<f64>,
+    #[description = "whether to list each pass as a table of its rise, peak and set (default false)"]
+    detailed: Option<bool>,
+    #[description = "whether to also attach the response from N2YO as JSON, for your own scripts"]
+    raw: Option<bool>,
 ) -> anyhow::Result<()> {
-    ctx.defer().await?;
-
-    if days > 10 || days == 0 {
-        return Err(anyhow::anyhow!("days must be between 1 and 10"));
-    }
+    let satellite_id = resolve_satellite(ctx, &satellite_id).await?;
+    let preferences = defer_with_preferences(ctx).await?;
+    let locale = i18n::locale(&ctx);
 
-    if min_max_elevation > 90.0 || min_max_elevation == 0.0 {
-        return Err(anyhow::anyhow!(
-            "min_max_elevation must be between 1 and 90"
-        ));
-    }
+    let (days, days_note) = resolve_days(locale, days);
+    let kind = match (kind, brighter_than) {
+        (Some(kind), _) => kind,
+        (None, Some(_)) => PassKind::Visual,
+        (None, None) => PassKind::Radio,
+    };
 
-    let location = {
+    let (location, min_max_elevation) = {
         let database = ctx.data().database.read().await;
-        let location = database
-            .contents
-            .locations
-            .iter()
-            .find(|other_location| other_location.name.0 == location)
-            .ok_or_else(|| anyhow::anyhow!("no such location"))?;
-        location.clone()
+        (
+            find_location(&database, locale, location.as_deref(), &preferences)?,
+            resolve_min_max_elevation(ctx, &database, locale, min_max_elevation)?,
+        )
     };
+    let time_format = preferences.time_format(locale);
 
-    let passes = ctx
+    let mut passes = ctx
         .data()
-        .n2yo_api
-        .get_satellite_passes(satellite_id, &location, days, min_max_elevation)
+        .pass_provider
+        .get_satellite_passes(
+            satellite_id,
+            &location,
+            days,
+            min_max_elevation,
+            kind,
+            CacheMode::Use,
+        )
         .await?;
+    let (attachments, raw_note) = if raw.unwrap_or(false) {
+        raw_pass_attachments(locale, [&passes])
+    } else {
+        (Vec::new(), None)
+    };
+    let note = days_note.into_iter().chain(raw_note).collect::<Vec<_>>();
+    passes.passes.retain(|pass| {
+        lighting.unwrap_or_default().includes(&location, pass)
+            && passes::is_brighter_than(pass, brighter_than)
+    });
+    let detailed = detailed.unwrap_or(false);
+    sort_and_limit(
+        &mut passes.passes,
+        sort_by.unwrap_or(PassOrder::Time),
+        pass_limit(limit, detailed, 1),
+    );
 
     if !passes.passes.is_empty() {
         ctx.send(|b| {
+            if !note.is_empty() {
+                b.content(note.join("\n"));
+            }
+
+            for attachment in attachments {
+                b.attachment(attachment);
+            }
+
             b.embed(|e| {
-                embed_passes(e, passes, days);
+                embed_passes(e, locale, &time_format, passes, days, detailed);
                 e
             })
-            .ephemeral(false)
+            .ephemeral(preferences.ephemeral)
         })
         .await?;
     } else {
         ctx.send(|m| {
+            if !note.is_empty() {
+                m.content(note.join("\n"));
+            }
+
+            for attachment in attachments {
+                m.attachment(attachment);
+            }
+
             m.embed(|e| {
-                e.title("No passes found");
+                e.title(tr!(locale, "no-passes"));
                 e
             })
-            .ephemeral(false)
+            .ephemeral(preferences.ephemeral)
         })
         .await?;
     }
@@ -65,98 +159,919 @@ pub async fn get_upcoming_passes(
     Ok(())
 }
 
-/// Gets all the upcoming passes for NOAA 15, 18, and 19.
-#[command(slash_command, rename = "get-upcoming-noaa-passes")]
+/// Gets all the upcoming passes for the weather satellites, NOAA 15, 18, and 19 and Meteor-M.
+///
+/// The satellites are those in the `weather` group, which the bot's operator can change. Sorting
+/// and limits apply to each satellite's passes. Detailed listings show fewer passes of each, so
+/// that they fit in one message.
+///
+/// Example: `/get-upcoming-noaa-passes min_max_elevation:30 location:home days:2`
+#[command(
+    slash_command,
+    rename = "get-upcoming-noaa-passes",
+    category = "Passes"
+)]
+#[allow(clippy::too_many_arguments)]
 pub async fn get_upcoming_noaa_passes(
     ctx: Context<'_>,
-    #[description = "the location to get passes for"]
+    #[description = "the minimum elevation of the passes to get (default 20° unless configured)"]
+    min_max_elevation: Option<f64>,
+    #[description = "the location to get passes for, if not your default location"]
+    #[autocomplete = "autocomplete::location"]
+    location: Option<String>,
+    #[description = "the number of days in the future to get passes for (max 10, default 3)"]
+    days: Option<usize>,
+    #[description = "what to list passes by, best first (default time)"] sort_by: Option<PassOrder>,
+    #[description = "the most passes to list (max 25)"]
+    #[min = 1]
+    #[max = 25]
+    limit: Option<usize>,
+    #[description = "whether to only list daytime or nighttime passes"] lighting: Option<Lighting>,
+    #[description = "whether to list each pass as a table of its rise, peak and set (default false)"]
+    detailed: Option<bool>,
+    #[description = "whether to also attach the response from N2YO as JSON, for your own scripts"]
+    raw: Option<bool>,
+) -> anyhow::Result<()> {
+    let satellite_ids = preset(ctx, groups::WEATHER).await;
+
+    send_many_passes(
+        ctx,
+        &satellite_ids,
+        min_max_elevation,
+        location,
+        days,
+        sort_by,
+        limit,
+        lighting,
+        detailed.unwrap_or(false),
+        raw.unwrap_or(false),
+        false,
+    )
+    .await
+}
+
+/// Gets all the upcoming passes for the Meteor-M satellites, with their LRPT image frequencies.
+///
+/// The satellites are those in the `meteor` group, which the bot's operator can change. Sorting
+/// and limits apply to each satellite's passes.
+///
+/// Example: `/get-upcoming-meteor-passes min_max_elevation:30 location:home days:2`
+#[command(
+    slash_command,
+    rename = "get-upcoming-meteor-passes",
+    category = "Passes"
+)]
+#[allow(clippy::too_many_arguments)]
+pub async fn get_upcoming_meteor_passes(
+    ctx: Context<'_>,
+    #[description = "the minimum elevation of the passes to get (default 20° unless configured)"]
+    min_max_elevation: Option<f64>,
+    #[description = "the location to get passes for, if not your default location"]
     #[autocomplete = "autocomplete::location"]
-    location: String,
-    #[description = "the number of days in the future to get passes for (max 10)"] days: usize,
-    #[description = "the minimum elevation of the passes to get"] min_max_elevation: f64,
+    location: Option<String>,
+    #[description = "the number of days in the future to get passes for (max 10, default 3)"]
+    days: Option<usize>,
+    #[description = "what to list passes by, best first (default time)"] sort_by: Option<PassOrder>,
+    #[description = "the most passes to list (max 25)"]
+    #[min = 1]
+    #[max = 25]
+    limit: Option<usize>,
+    #[description = "whether to only list daytime or nighttime passes"] lighting: Option<Lighting>,
+    #[description = "whether to list each pass as a table of its rise, peak and set (default false)"]
+    detailed: Option<bool>,
+    #[description = "whether to also attach the response from N2YO as JSON, for your own scripts"]
+    raw: Option<bool>,
 ) -> anyhow::Result<()> {
-    ctx.defer().await?;
+    let satellite_ids = preset(ctx, groups::METEOR).await;
+
+    send_many_passes(
+        ctx,
+        &satellite_ids,
+        min_max_elevation,
+        location,
+        days,
+        sort_by,
+        limit,
+        lighting,
+        detailed.unwrap_or(false),
+        raw.unwrap_or(false),
+        true,
+    )
+    .await
+}
+
+/// The satellites in a group listed by a command of its own, up to as many as fit in a message.
+async fn preset(ctx: Context<'_>, group: &str) -> Vec<usize> {
+    let mut satellite_ids = ctx
+        .data()
+        .database
+        .read()
+        .await
+        .contents
+        .groups
+        .get(group)
+        .unwrap_or_default();
+    satellite_ids.truncate(groups::MAX_SATELLITES);
+    satellite_ids
+}
+
+/// Gets all the upcoming passes for several satellites, one embed each.
+///
+/// Satellites are given as NORAD IDs separated by commas, up to 10, or as the name of a group such
+/// as `noaa`, or `fm` for the FM repeaters easiest to start with. Your favorite satellites can be
+/// given as `favorites`. Sorting and limits apply to each satellite's passes.
+///
+/// Example: `/get-upcoming-passes-multi satellites:25544,43017,27607 location:home days:2`
+/// Example: `/get-upcoming-passes-multi satellites:noaa min_max_elevation:30`
+/// Example: `/get-upcoming-passes-multi satellites:fm`
+#[command(
+    slash_command,
+    rename = "get-upcoming-passes-multi",
+    category = "Passes"
+)]
+#[allow(clippy::too_many_arguments)]
+pub async fn get_upcoming_passes_multi(
+    ctx: Context<'_>,
+    #[description = "NORAD IDs separated by commas, or a group name such as noaa or favorites"]
+    satellites: String,
+    #[description = "the minimum elevation of the passes to get (default 20° unless configured)"]
+    min_max_elevation: Option<f64>,
+    #[description = "the location to get passes for, if not your default location"]
+    #[autocomplete = "autocomplete::location"]
+    location: Option<String>,
+    #[description = "the number of days in the future to get passes for (max 10, default 3)"]
+    days: Option<usize>,
+    #[description = "what to list passes by, best first (default time)"] sort_by: Option<PassOrder>,
+    #[description = "the most passes to list (max 25)"]
+    #[min = 1]
+    #[max = 25]
+    limit: Option<usize>,
+    #[description = "whether to only list daytime or nighttime passes"] lighting: Option<Lighting>,
+    #[description = "whether to list each pass as a table of its rise, peak and set (default false)"]
+    detailed: Option<bool>,
+    #[description = "whether to also attach the response from N2YO as JSON, for your own scripts"]
+    raw: Option<bool>,
+) -> anyhow::Result<()> {
+    let satellite_ids = resolve_satellites(ctx, &satellites).await?;
+
+    send_many_passes(
+        ctx,
+        &satellite_ids,
+        min_max_elevation,
+        location,
+        days,
+        sort_by,
+        limit,
+        lighting,
+        detailed.unwrap_or(false),
+        raw.unwrap_or(false),
+        false,
+    )
+    .await
+}
+
+/// Fetches the passes of several satellites at once and replies with an embed for each that has
+/// any.
+#[allow(clippy::too_many_arguments)]
+async fn send_many_passes(
+    ctx: Context<'_>,
+    satellite_ids: &[usize],
+    min_max_elevation: Option<f64>,
+    location: Option<String>,
+    days: Option<usize>,
+    sort_by: Option<PassOrder>,
+    limit: Option<usize>,
+    lighting: Option<Lighting>,
+    detailed: bool,
+    raw: bool,
+    downlinks: bool,
+) -> anyhow::Result<()> {
+    let preferences = defer_with_preferences(ctx).await?;
+    let locale = i18n::locale(&ctx);
+
+    let (days, days_note) = resolve_days(locale, days);
+
+    let (location, min_max_elevation) = {
+        let database = ctx.data().database.read().await;
+        (
+            find_location(&database, locale, location.as_deref(), &preferences)?,
+            resolve_min_max_elevation(ctx, &database, locale, min_max_elevation)?,
+        )
+    };
+    let time_format = preferences.time_format(locale);
+
+    let requests = satellite_ids
+        .iter()
+        .map(|satellite_id| PassRequest {
+            satellite_id: *satellite_id,
+            location: location.clone(),
+            days,
+            min_max_elevation,
+            kind: PassKind::Radio,
+        })
+        .collect();
+    let passes = ctx
+        .data()
+        .pass_provider
+        .get_many_satellite_passes(requests, CacheMode::Use)
+        .await
+        .into_iter()
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let (attachments, raw_note) = if raw {
+        raw_pass_attachments(locale, &passes)
+    } else {
+        (Vec::new(), None)
+    };
+    let note = days_note.into_iter().chain(raw_note).collect::<Vec<_>>();
+    let passes = passes
+        .into_iter()
+        .map(|mut passes| {
+            passes
+                .passes
+                .retain(|pass| lighting.unwrap_or_default().includes(&location, pass));
+            passes
+        })
+        .filter(|passes| !passes.passes.is_empty())
+        .map(|mut passes| {
+            sort_and_limit(
+                &mut passes.passes,
+                sort_by.unwrap_or(PassOrder::Time),
+                pass_limit(limit, detailed, satellite_ids.len()),
+            );
+            passes
+        })
+        .collect::<Vec<_>>();
 
-    if days > 10 || days == 0 {
-        return Err(anyhow::anyhow!("days must be between 1 and 10"));
+    if passes.is_empty() {
+        ctx.send(|m| {
+            if !note.is_empty() {
+                m.content(note.join("\n"));
+            }
+
+            for attachment in attachments {
+                m.attachment(attachment);
+            }
+
+            m.embed(|e| {
+                e.title(tr!(locale, "no-passes"));
+                e
+            })
+            .ephemeral(preferences.ephemeral)
+        })
+        .await?;
+        return Ok(());
     }
 
-    if min_max_elevation > 90.0 || min_max_elevation == 0.0 {
-        return Err(anyhow::anyhow!(
-            "min_max_elevation must be between 1 and 90"
-        ));
+    ctx.send(|b| {
+        if !note.is_empty() {
+            b.content(note.join("\n"));
+        }
+
+        for attachment in attachments {
+            b.attachment(attachment);
+        }
+
+        for passes in passes {
+            b.embed(|e| {
+                // Tells those new to the satellites what to tune to
+                if let Some(satellite) = downlinks
+                    .then(|| frequencies::lookup(passes.info.id))
+                    .flatten()
+                {
+                    let transmitter = satellite.primary();
+                    e.description(tr!(
+                        locale,
+                        "passes-downlink",
+                        description = transmitter.description,
+                        frequency = frequencies::format_frequency(transmitter.downlink),
+                        mode = transmitter.mode
+                    ));
+                }
+
+                embed_passes(e, locale, &time_format, passes, days, detailed);
+                e
+            });
+        }
+
+        b.ephemeral(preferences.ephemeral)
+    })
+    .await?;
+
+    Ok(())
+}
+
+/// Compares the next passes of a satellite at two locations, side by side.
+///
+/// Useful for choosing between observing sites, as each pass is listed with its highest elevation
+/// and quality, along with the average of each.
+/// Example: `/compare-passes satellite_id:25544 first_location:home second_location:hilltop count:5`
+#[command(slash_command, rename = "compare-passes", category = "Passes")]
+pub async fn compare_passes(
+    ctx: Context<'_>,
+    #[description = "the NORAD ID of the satellite, or an alias for it"] satellite_id: String,
+    #[description = "the first location to compare"]
+    #[autocomplete = "autocomplete::location"]
+    first_location: String,
+    #[description = "the second location to compare"]
+    #[autocomplete = "autocomplete::location"]
+    second_location: String,
+    #[description = "how many passes to compare at each location (max 10, default 5)"]
+    count: Option<usize>,
+    #[description = "the minimum elevation of the passes to compare (default 20° unless configured)"]
+    min_max_elevation: Option<f64>,
+    #[description = "the number of days in the future to get passes for (max 10, default 3)"]
+    days: Option<usize>,
+) -> anyhow::Result<()> {
+    let satellite_id = resolve_satellite(ctx, &satellite_id).await?;
+    let preferences = defer_with_preferences(ctx).await?;
+    let locale = i18n::locale(&ctx);
+    let count = count.unwrap_or(5);
+
+    if count == 0 || count > MAX_COMPARED_PASSES {
+        return Err(BotError::user(tr!(
+            locale,
+            "error-count-range",
+            parameter = "count",
+            max = MAX_COMPARED_PASSES
+        ))
+        .into());
     }
 
-    let location = {
+    let (days, days_note) = resolve_days(locale, days);
+
+    let (locations, min_max_elevation) = {
         let database = ctx.data().database.read().await;
-        let location = database
-            .contents
-            .locations
+        (
+            [
+                find_location(&database, locale, Some(&first_location), &preferences)?,
+                find_location(&database, locale, Some(&second_location), &preferences)?,
+            ],
+            resolve_min_max_elevation(ctx, &database, locale, min_max_elevation)?,
+        )
+    };
+
+    let requests = locations
+        .iter()
+        .map(|location| PassRequest {
+            satellite_id,
+            location: location.clone(),
+            days,
+            min_max_elevation,
+            kind: PassKind::Radio,
+        })
+        .collect();
+    let all_passes = ctx
+        .data()
+        .pass_provider
+        .get_many_satellite_passes(requests, CacheMode::Use)
+        .await
+        .into_iter()
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    let satellite = all_passes
+        .first()
+        .map(|passes| passes.info.name.clone())
+        .unwrap_or_else(|| satellite_id.to_string());
+    let compared = locations
+        .iter()
+        .zip(all_passes)
+        .map(|(location, mut passes)| {
+            passes.passes.truncate(count);
+            (location, passes.passes)
+        })
+        .collect::<Vec<_>>();
+    let averages = compared
+        .iter()
+        .map(|(_, passes)| average_quality(passes))
+        .collect::<Vec<_>>();
+
+    let verdict = match (averages[0], averages[1]) {
+        (Some(first), Some(second)) if first != second => tr!(
+            locale,
+            "compare-better",
+            location = if first > second {
+                compared[0].0.name.0.as_str()
+            } else {
+                compared[1].0.name.0.as_str()
+            }
+        ),
+        (Some(_), None) => tr!(
+            locale,
+            "compare-better",
+            location = compared[0].0.name.0.as_str()
+        ),
+        (None, Some(_)) => tr!(
+            locale,
+            "compare-better",
+            location = compared[1].0.name.0.as_str()
+        ),
+        _ => tr!(locale, "compare-even"),
+    };
+
+    ctx.send(|b| {
+        if let Some(note) = &days_note {
+            b.content(note);
+        }
+
+        b.embed(|e| {
+            e.title(tr!(
+                locale,
+                "compare-title",
+                satellite = satellite.as_str(),
+                first = compared[0].0.name.0.as_str(),
+                second = compared[1].0.name.0.as_str()
+            ));
+            e.description(verdict);
+
+            for (location, passes) in &compared {
+                e.field(&location.name.0, describe_compared(locale, passes), true);
+            }
+
+            e
+        })
+        .ephemeral(preferences.ephemeral)
+    })
+    .await?;
+
+    Ok(())
+}
+
+/// The average quality of some passes, if there are any.
+fn average_quality(passes: &[SatellitePass]) -> Option<f64> {
+    (!passes.is_empty()).then(|| {
+        passes
             .iter()
-            .find(|other_location| other_location.name.0 == location)
-            .ok_or_else(|| anyhow::anyhow!("no such location"))?;
-        location.clone()
+            .map(|pass| passes::score(pass) as f64)
+            .sum::<f64>()
+            / passes.len() as f64
+    })
+}
+
+/// Lists passes one per line for a column of a comparison, followed by their averages.
+fn describe_compared(locale: &str, passes: &[SatellitePass]) -> String {
+    if passes.is_empty() {
+        return tr!(locale, "no-passes");
+    }
+
+    let mut lines = passes
+        .iter()
+        .map(|pass| {
+            tr!(
+                locale,
+                "compare-pass",
+                time = util::format_time(pass.start_utc as i64, TimeStyle::Discord),
+                elevation = pass.max_elevation,
+                quality = passes::score(pass)
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let average_elevation =
+        passes.iter().map(|pass| pass.max_elevation).sum::<f64>() / passes.len() as f64;
+    lines.push(tr!(
+        locale,
+        "compare-average",
+        elevation = average_elevation.round(),
+        quality = average_quality(passes).unwrap_or_default().round()
+    ));
+
+    lines.join("\n")
+}
+
+/// The days ahead searched for the next pass, the most N2YO gives.
+const NEXT_PASS_DAYS: usize = 10;
+
+/// Says when the next pass of a satellite starts, counting down to it.
+///
+/// Example: `/next-pass satellite_id:33591 location:home`
+#[command(slash_command, rename = "next-pass", category = "Passes")]
+pub async fn next_pass(
+    ctx: Context<'_>,
+    #[description = "the NORAD ID of the satellite, or an alias for it"] satellite_id: String,
+    #[description = "the location to get the pass for, if not your default location"]
+    #[autocomplete = "autocomplete::location"]
+    location: Option<String>,
+    #[description = "the minimum elevation of the pass to get (default 20° unless configured)"]
+    min_max_elevation: Option<f64>,
+) -> anyhow::Result<()> {
+    let satellite_id = resolve_satellite(ctx, &satellite_id).await?;
+    let preferences = defer_with_preferences(ctx).await?;
+    let locale = i18n::locale(&ctx);
+
+    let (location, min_max_elevation) = {
+        let database = ctx.data().database.read().await;
+        (
+            find_location(&database, locale, location.as_deref(), &preferences)?,
+            resolve_min_max_elevation(ctx, &database, locale, min_max_elevation)?,
+        )
     };
 
-    let noaa_15_passes = ctx
+    let mut passes = ctx
         .data()
-        .n2yo_api
-        .get_satellite_passes(25338, &location, days, min_max_elevation)
+        .pass_provider
+        .get_satellite_passes(
+            satellite_id,
+            &location,
+            NEXT_PASS_DAYS,
+            min_max_elevation,
+            PassKind::Radio,
+            CacheMode::Use,
+        )
         .await?;
-    let noaa_18_passes = ctx
-        .data()
-        .n2yo_api
-        .get_satellite_passes(28654, &location, days, min_max_elevation)
+    sort_and_limit(&mut passes.passes, PassOrder::Time, Some(1));
+
+    let reply = match passes.passes.first() {
+        Some(pass) => tr!(
+            locale,
+            "next-pass",
+            satellite = passes.info.name.as_str(),
+            countdown = util::format_time(pass.start_utc as i64, TimeStyle::Discord),
+            time = format!("<t:{}:t>", pass.start_utc),
+            elevation = pass.max_elevation,
+            from = pass.start_azimuth_compass.as_str(),
+            to = pass.end_azimuth_compass.as_str()
+        ),
+        None => tr!(
+            locale,
+            "next-pass-none",
+            satellite = passes.info.name.as_str(),
+            days = NEXT_PASS_DAYS
+        ),
+    };
+
+    ctx.send(|b| b.content(reply).ephemeral(preferences.ephemeral))
         .await?;
-    let noaa_19_passes = ctx
+
+    Ok(())
+}
+
+/// The days ahead searched for the best pass.
+const BEST_PASS_DAYS: usize = 7;
+
+/// What makes one pass better than another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ChoiceParameter)]
+pub enum BestBy {
+    #[name = "elevation"]
+    Elevation,
+    #[name = "quality"]
+    Quality,
+}
+
+/// Finds the single best pass of a satellite in the next week, with a button to add it to a
+/// calendar.
+///
+/// Passes are ranked by their highest elevation unless `by:quality` is given, which also weighs
+/// their length and, for visual passes, how much of them is lit.
+/// Example: `/best-pass satellite_id:25544 location:home by:quality`
+#[command(slash_command, rename = "best-pass", category = "Passes")]
+pub async fn best_pass(
+    ctx: Context<'_>,
+    #[description = "the NORAD ID of the satellite, or an alias for it"] satellite_id: String,
+    #[description = "the location to find the pass for, if not your default location"]
+    #[autocomplete = "autocomplete::location"]
+    location: Option<String>,
+    #[description = "what makes a pass best (default elevation)"] by: Option<BestBy>,
+    #[description = "whether to look for radio or visual passes (default radio)"] kind: Option<
+        PassKind,
+    >,
+    #[description = "the minimum elevation of the passes to get (default 20° unless configured)"]
+    min_max_elevation: Option<f64>,
+) -> anyhow::Result<()> {
+    let satellite_id = resolve_satellite(ctx, &satellite_id).await?;
+    let preferences = defer_with_preferences(ctx).await?;
+    let locale = i18n::locale(&ctx);
+
+    let (location, min_max_elevation) = {
+        let database = ctx.data().database.read().await;
+        (
+            find_location(&database, locale, location.as_deref(), &preferences)?,
+            resolve_min_max_elevation(ctx, &database, locale, min_max_elevation)?,
+        )
+    };
+
+    let mut passes = ctx
         .data()
-        .n2yo_api
-        .get_satellite_passes(33591, &location, days, min_max_elevation)
+        .pass_provider
+        .get_satellite_passes(
+            satellite_id,
+            &location,
+            BEST_PASS_DAYS,
+            min_max_elevation,
+            kind.unwrap_or_default(),
+            CacheMode::Use,
+        )
         .await?;
+    let order = match by.unwrap_or(BestBy::Elevation) {
+        BestBy::Elevation => PassOrder::Elevation,
+        BestBy::Quality => PassOrder::Quality,
+    };
+    sort_and_limit(&mut passes.passes, order, Some(1));
 
-    if noaa_15_passes.passes.is_empty()
-        && noaa_18_passes.passes.is_empty()
-        && noaa_19_passes.passes.is_empty()
-    {
+    let Some(pass) = passes.passes.first() else {
         ctx.send(|m| {
             m.embed(|e| {
-                e.title("No passes found");
+                e.title(tr!(locale, "no-passes"));
                 e
             })
-            .ephemeral(false)
+            .ephemeral(preferences.ephemeral)
         })
         .await?;
         return Ok(());
-    }
+    };
+
+    let satellite = passes.info.name.as_str();
+    let calendar = passes::calendar_link(
+        &tr!(locale, "calendar-event-title", satellite = satellite),
+        &tr!(
+            locale,
+            "calendar-event-details",
+            location = location.name.0.as_str(),
+            elevation = pass.max_elevation
+        ),
+        pass,
+    )?;
 
     ctx.send(|b| {
-        if !noaa_15_passes.passes.is_empty() {
-            b.embed(|e| {
-                embed_passes(e, noaa_15_passes, days);
-                e
+        b.embed(|e| {
+            e.colour(passes::colour(pass.max_elevation));
+            e.title(tr!(
+                locale,
+                "best-pass-title",
+                satellite = satellite,
+                days = BEST_PASS_DAYS
+            ));
+            e.description(format!(
+                "{}\n{}",
+                util::format_pass_time(
+                    pass.start_utc as i64,
+                    pass.end_utc as i64,
+                    TimeStyle::Discord
+                ),
+                passes::describe(locale, pass)
+            ));
+            e.field(
+                tr!(locale, "best-pass-rise"),
+                describe_point(
+                    pass.start_azimuth,
+                    &pass.start_azimuth_compass,
+                    pass.start_utc,
+                ),
+                true,
+            );
+            e.field(
+                tr!(locale, "best-pass-highest"),
+                describe_point(pass.max_azimuth, &pass.max_azimuth_compass, pass.max_utc),
+                true,
+            );
+            e.field(
+                tr!(locale, "best-pass-set"),
+                describe_point(pass.end_azimuth, &pass.end_azimuth_compass, pass.end_utc),
+                true,
+            );
+            e.footer(|f| {
+                f.text(tr!(
+                    locale,
+                    "data-source",
+                    source = passes.source.to_string()
+                ))
             })
-            .ephemeral(false);
-        }
+        })
+        .components(|c| {
+            c.create_action_row(|r| {
+                r.create_button(|button| {
+                    button
+                        .style(ButtonStyle::Link)
+                        .label(tr!(locale, "best-pass-calendar"))
+                        .url(calendar)
+                })
+            })
+        })
+        .ephemeral(preferences.ephemeral)
+    })
+    .await?;
 
-        if !noaa_18_passes.passes.is_empty() {
-            b.embed(|e| {
-                embed_passes(e, noaa_18_passes, days);
-                e
+    Ok(())
+}
+
+/// Recalls a pass notified of here by its ID, with who went and the observations logged of it.
+///
+/// Pass IDs are in the footer of notifications, and passes can be looked up for 30 days after
+/// they end. Where a pass was notified of for several locations, its path across the sky is given
+/// for each.
+/// Example: `/pass-info id:33591-guyo8`
+#[command(slash_command, rename = "pass-info", category = "Passes")]
+pub async fn pass_info(
+    ctx: Context<'_>,
+    #[description = "the ID of the pass, from the footer of its notification"] id: String,
+) -> anyhow::Result<()> {
+    let preferences = defer_with_preferences(ctx).await?;
+    let locale = i18n::locale(&ctx);
+    let id = id.trim().to_lowercase();
+    let guild = ctx.guild_id().map(|guild| Snowflake(guild.0));
+    let channel = Snowflake(ctx.channel_id().0);
+
+    let (satellite_id, satellite, pass, paths, attendees, observations) = {
+        let database = ctx.data().database.read().await;
+        // Outside of servers, only passes notified of in the same DM are shown
+        let visible = database
+            .contents
+            .watched_satellites
+            .iter()
+            .filter(|watched_satellite| match &guild {
+                Some(_) => watched_satellite.guild == guild,
+                None => watched_satellite.channel == channel,
             })
-            .ephemeral(false);
+            .collect::<Vec<_>>();
+        let found = visible.iter().find_map(|watched_satellite| {
+            watched_satellite
+                .announced_passes
+                .iter()
+                .find(|announced| announced.id == id)
+                .map(|announced| {
+                    (
+                        watched_satellite.satellite_id.0,
+                        watched_satellite.name.clone(),
+                        announced.pass.clone(),
+                    )
+                })
+        });
+
+        let Some((satellite_id, satellite, pass)) = found else {
+            return Err(BotError::user(tr!(
+                locale,
+                "error-unknown-pass-id",
+                days = PASS_HISTORY_DAYS
+            ))
+            .into());
+        };
+
+        // Other locations may have given the pass a different ID, so it is matched by its peak
+        let mut channels = Vec::new();
+        let mut paths = Vec::<(String, SatellitePass)>::new();
+
+        for watched_satellite in visible
+            .iter()
+            .filter(|watched_satellite| watched_satellite.satellite_id.0 == satellite_id)
+        {
+            let Some(announced) = watched_satellite
+                .announced_passes
+                .iter()
+                .find(|announced| passes::is_same_pass(&announced.pass, &pass))
+            else {
+                continue;
+            };
+
+            channels.push((watched_satellite.channel.clone(), announced.pass.clone()));
+
+            if !paths
+                .iter()
+                .any(|(location, _)| location == &watched_satellite.location.0)
+            {
+                paths.push((watched_satellite.location.0.clone(), announced.pass.clone()));
+            }
         }
 
-        if !noaa_19_passes.passes.is_empty() {
-            b.embed(|e| {
-                embed_passes(e, noaa_19_passes, days);
-                e
+        let attendees = database
+            .contents
+            .pass_rsvps
+            .iter()
+            .filter(|rsvp| {
+                rsvp.satellite == satellite
+                    && channels.iter().any(|(channel, pass)| {
+                        *channel == rsvp.channel
+                            && util::are_within_10_seconds(rsvp.start, pass.start_utc as i64)
+                    })
             })
-            .ephemeral(false);
-        }
+            .flat_map(|rsvp| rsvp.attendees.iter().map(|attendee| attendee.user.0))
+            .collect::<BTreeSet<_>>();
+        let observations = database
+            .contents
+            .logged_observations
+            .iter()
+            .filter(|observation| {
+                observation.satellite_id.0 == satellite_id
+                    && channels.iter().any(|(channel, pass)| {
+                        *channel == observation.channel
+                            && observation.pass_start >= pass.start_utc as i64 - 10
+                            && observation.pass_start <= pass.end_utc as i64
+                    })
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+
+        (
+            satellite_id,
+            satellite,
+            pass,
+            paths,
+            attendees,
+            observations,
+        )
+    };
+
+    ctx.send(|b| {
+        b.embed(|e| {
+            e.colour(passes::colour(pass.max_elevation));
+            e.title(tr!(
+                locale,
+                "pass-info-title",
+                satellite = satellite.as_str(),
+                id = id.as_str()
+            ));
+            e.description(util::format_pass_time(
+                pass.start_utc as i64,
+                pass.end_utc as i64,
+                TimeStyle::Discord,
+            ));
 
-        b
+            for (location, pass) in paths.iter().take(MAX_PASS_INFO_PATHS) {
+                e.field(
+                    location,
+                    format!(
+                        "{}\n{}: {}\n{}: {}\n{}: {}",
+                        passes::describe(locale, pass),
+                        tr!(locale, "best-pass-rise"),
+                        describe_path_point(
+                            pass.start_azimuth,
+                            &pass.start_azimuth_compass,
+                            pass.start_utc
+                        ),
+                        tr!(locale, "best-pass-highest"),
+                        describe_path_point(
+                            pass.max_azimuth,
+                            &pass.max_azimuth_compass,
+                            pass.max_utc
+                        ),
+                        tr!(locale, "best-pass-set"),
+                        describe_path_point(
+                            pass.end_azimuth,
+                            &pass.end_azimuth_compass,
+                            pass.end_utc
+                        ),
+                    ),
+                    false,
+                );
+            }
+
+            e.field(
+                tr!(locale, "pass-info-attendees"),
+                if attendees.is_empty() {
+                    tr!(locale, "pass-info-nobody")
+                } else {
+                    attendees
+                        .iter()
+                        .map(|user| format!("<@{}>", user))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                },
+                false,
+            );
+
+            if !observations.is_empty() {
+                e.field(
+                    tr!(locale, "pass-info-observations"),
+                    observations
+                        .iter()
+                        .take(MAX_PASS_INFO_OBSERVATIONS)
+                        .map(|observation| {
+                            let mut line = format!("<@{}>", observation.observer.0);
+
+                            if let Some(notes) = &observation.notes {
+                                line.push_str(": ");
+                                line.push_str(&util::truncate(notes, MAX_PASS_INFO_NOTES_LENGTH));
+                            }
+
+                            if let Some(image) = &observation.image {
+                                line.push_str(&format!(
+                                    " ([{}]({}))",
+                                    tr!(locale, "pass-info-image"),
+                                    logbook::image_message_link(observation.guild.as_ref(), image)
+                                ));
+                            }
+
+                            line
+                        })
+                        .collect::<Vec<_>>()
+                        .join("\n"),
+                    false,
+                );
+            }
+
+            e.footer(|f| f.text(format!("NORAD {}", satellite_id)))
+        })
+        .ephemeral(preferences.ephemeral)
     })
     .await?;
 
     Ok(())
 }
+
+/// Describes where in the sky a satellite is at a point in a pass, and when, on one line.
+fn describe_path_point(azimuth: f64, compass: &str, time: usize) -> String {
+    format!("{} ({:.0}°), <t:{}:t>", compass, azimuth, time)
+}
+
+/// Describes where in the sky a satellite is at a point in a pass, and when.
+fn describe_point(azimuth: f64, compass: &str, time: usize) -> String {
+    format!("{} ({:.0}°)\n<t:{}:t>", compass, azimuth, time)
+}
@@ -0,0 +1,102 @@
+use chrono::NaiveDate;
+use poise::command;
+
+use crate::{
+    database::{Snowflake, Vacation},
+    error::BotError,
+    i18n::{self, tr},
+    util, Context,
+};
+
+/// The longest a vacation can be, in seconds.
+const MAX_VACATION: i64 = 365 * 24 * 60 * 60;
+
+/// Pauses all of your watches while you are away, resuming them automatically.
+///
+/// When they resume, you are sent a direct message with the best passes coming up. Going on
+/// vacation again changes when it ends, and `off` ends it now.
+/// Example: `/vacation until:2026-08-31`
+#[command(slash_command, rename = "vacation", category = "Watches")]
+pub async fn vacation(
+    ctx: Context<'_>,
+    #[description = "the date to resume on, as YYYY-MM-DD in UTC, a duration such as 2w, or off"]
+    until: String,
+) -> anyhow::Result<()> {
+    ctx.defer_ephemeral().await?;
+    let locale = i18n::locale(&ctx);
+    let user = Snowflake(ctx.author().id.0);
+    let now = util::current_utc();
+
+    if until.trim().eq_ignore_ascii_case("off") {
+        {
+            let mut database = ctx.data().database.write().await;
+            let vacation = database
+                .contents
+                .vacations
+                .get_mut(&user)
+                .ok_or_else(|| BotError::user(tr!(locale, "error-not-on-vacation")))?;
+            // Ended by the next notification run, which also sends the reminder
+            vacation.until = now;
+            database.mark_dirty();
+        }
+
+        ctx.say(tr!(locale, "vacation-ended")).await?;
+        return Ok(());
+    }
+
+    let until = parse_until(&until, now)
+        .ok_or_else(|| BotError::user(tr!(locale, "error-invalid-vacation-end")))?;
+
+    if until <= now || until - now > MAX_VACATION {
+        return Err(BotError::user(tr!(
+            locale,
+            "error-vacation-range",
+            days = MAX_VACATION / (24 * 60 * 60)
+        ))
+        .into());
+    }
+
+    {
+        let mut database = ctx.data().database.write().await;
+        database.contents.vacations.insert(
+            user,
+            Vacation {
+                until,
+                locale: locale.to_string(),
+            },
+        );
+        database.mark_dirty();
+    }
+
+    ctx.say(tr!(
+        locale,
+        "vacation-started",
+        time = util::format_time(until, util::TimeStyle::Discord)
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Reads when a vacation ends, as a date or a duration from now.
+fn parse_until(text: &str, now: i64) -> Option<i64> {
+    match NaiveDate::parse_from_str(text.trim(), "%Y-%m-%d") {
+        Ok(date) => Some(date.and_hms_opt(0, 0, 0)?.and_utc().timestamp()),
+        Err(_) => now.checked_add(util::parse_duration(text)?),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_vacation_ends() {
+        let now = 1_718_928_000;
+
+        assert_eq!(parse_until("2024-06-21", 0), Some(now));
+        assert_eq!(parse_until("2d", now), Some(now + 2 * 24 * 60 * 60));
+        assert_eq!(parse_until("106751991167300d", now), None);
+        assert_eq!(parse_until("soon", now), None);
+    }
+}
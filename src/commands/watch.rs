@@ -1,38 +1,127 @@
-use std::sync::Arc;
-
 use poise::command;
 use poise::serenity_prelude::Channel;
-use serenity::builder::CreateMessage;
-use serenity::http::Http;
-use serenity::json::Value;
-use tokio::sync::RwLock;
 
 use crate::{
-    commands::autocomplete,
+    astro::Lighting,
+    catalog,
+    commands::{
+        admin_only, autocomplete, check_min_max_elevation, defer_with_preferences, find_location,
+        is_admin, pass_request_cooldown, resolve_min_max_elevation, resolve_satellite,
+    },
+    config,
     database::{Database, LocationName, SatelliteId, Snowflake, WatchedSatellite},
-    n2yo::N2YOAPI,
-    util, Context,
+    error::BotError,
+    i18n::{self, tr},
+    n2yo::PassKind,
+    notify,
+    preferences::{self, GuildSettings, UserPreferences},
+    templates, Context,
 };
 
+/// The longest title template, as Discord allows 256 characters in embed titles.
+const MAX_TITLE_TEMPLATE_LENGTH: usize = 200;
+/// The longest description template, leaving room for what the placeholders are replaced with.
+const MAX_DESCRIPTION_TEMPLATE_LENGTH: usize = 2000;
+/// The most tags a watch can have.
+const MAX_TAGS: usize = 5;
+/// The longest tag, so that they fit in autocomplete choices alongside their watch count.
+const MAX_TAG_LENGTH: usize = 32;
+
 /// Watch a satellite, sending updates when a suitable pass is identified.
-#[command(slash_command, rename = "watch-satellite")]
+///
+/// Notifications can be restyled with templates, where `{name}`, `{location}`, `{start}`, `{end}`,
+/// `{duration}`, `{max_el}`, `{quality}`, `{path}` and `{id}` are replaced with details of the
+/// pass, and `{{` and `}}` give braces.
+/// Example: `/watch-satellite satellite_id:33591 min_max_elevation:30 location:home days_ahead:2`
+#[command(slash_command, rename = "watch-satellite", category = "Watches")]
+#[allow(clippy::too_many_arguments)]
 pub async fn watch_satellite(
     ctx: Context<'_>,
-    #[description = "the NORAD ID of the satellite"] satellite_id: usize,
-    #[description = "the location to notify of passes for"]
+    #[description = "the NORAD ID of the satellite, or an alias for it"]
+    #[autocomplete = "autocomplete::satellite"]
+    satellite_id: String,
+    #[description = "the minimum elevation of the passes to notify (default 20° unless configured)"]
+    min_max_elevation: Option<f64>,
+    #[description = "the location to notify of passes for, if not your default location"]
     #[autocomplete = "autocomplete::location"]
-    location: String,
-    #[description = "the minimum elevation of the passes to notify"] min_max_elevation: f64,
+    location: Option<String>,
+    #[description = "how many days in advance to notify of passes (max 10, default 1 unless configured)"]
+    days_ahead: Option<usize>,
+    #[description = "only notify of passes starting within this many hours"]
+    notify_within_hours: Option<usize>,
+    #[description = "the observer's timezone, such as Europe/London, to also give local times in"]
+    #[autocomplete = "autocomplete::timezone"]
+    timezone: Option<String>,
+    #[description = "whether to only notify of daytime or nighttime passes"] lighting: Option<
+        Lighting,
+    >,
+    #[description = "the lowest quality score of passes to notify, from 0 to 100, instead of an elevation"]
+    min_quality: Option<u8>,
+    #[description = "whether to include the satellite's main transmitter from SatNOGS DB"]
+    show_transmitter: Option<bool>,
+    #[description = "whether to notify of radio or visual passes (default radio)"] kind: Option<
+        PassKind,
+    >,
+    #[description = "only notify of visual passes brighter than this magnitude, such as 2"]
+    brighter_than: Option<f64>,
+    #[description = "whether to start a thread on each notification for reports of the pass"]
+    thread_per_pass: Option<bool>,
+    #[description = "a template for the title of notifications, such as {name} over {location}"]
+    title_template: Option<String>,
+    #[description = "a template for the description of notifications, such as {start}: {max_el}°"]
+    description_template: Option<String>,
+    #[description = "tags separated by commas, such as weather, to list or pause watches by"]
+    tags: Option<String>,
 ) -> anyhow::Result<()> {
+    let satellite_id = resolve_satellite(ctx, &satellite_id).await?;
     ctx.defer().await?;
+    let locale = i18n::locale(&ctx);
+    check_min_quality(locale, min_quality)?;
+    let tags = tags
+        .map(|tags| parse_tags(locale, &tags))
+        .transpose()?
+        .unwrap_or_default();
+    check_templates(
+        locale,
+        title_template.as_deref(),
+        description_template.as_deref(),
+    )?;
+    let timezone = timezone
+        .map(|timezone| parse_timezone(locale, &timezone))
+        .transpose()?;
+    let kind = match (kind, brighter_than) {
+        (Some(kind), _) => kind,
+        (None, Some(_)) => PassKind::Visual,
+        (None, None) => PassKind::Radio,
+    };
+    let days_ahead = match days_ahead {
+        Some(days_ahead) => days_ahead,
+        None => config::get().defaults.days_ahead,
+    };
 
-    if min_max_elevation > 90.0 || min_max_elevation == 0.0 {
-        return Err(anyhow::anyhow!(
-            "min_max_elevation must be between 1 and 90"
-        ));
+    if days_ahead > 10 || days_ahead == 0 {
+        return Err(
+            BotError::user(tr!(locale, "error-days-range", parameter = "days_ahead")).into(),
+        );
     }
 
+    // Looked up before locking the database, as it may have to ask N2YO and CelesTrak
+    let name = catalog::lookup(
+        &ctx.data().database,
+        &ctx.data().n2yo_api,
+        &ctx.data().celestrak_api,
+        satellite_id,
+    )
+    .await?
+    .name;
     let mut database = ctx.data().database.write().await;
+    // Passes are filtered by quality instead, so fetch all of them unless asked otherwise
+    let min_max_elevation = match (min_max_elevation, min_quality) {
+        (None, Some(_)) => 0.0,
+        _ => resolve_min_max_elevation(ctx, &database, locale, min_max_elevation)?,
+    };
+    let preferences = UserPreferences::for_user(&database, &Snowflake(ctx.author().id.0));
+    let location = find_location(&database, locale, location.as_deref(), &preferences)?;
 
     if database
         .contents
@@ -40,53 +129,67 @@ pub async fn watch_satellite(
         .iter()
         .any(|watched_satellite| {
             watched_satellite.satellite_id.0 == satellite_id
-                && watched_satellite.location.0 == location
+                && watched_satellite.location == location.name
                 && watched_satellite.min_max_elevation == min_max_elevation
+                && watched_satellite.kind == kind
                 && watched_satellite.channel.0 == ctx.channel_id().0
         })
     {
-        return Err(anyhow::anyhow!(
-            "satellite already being watched in this channel with these parameters"
-        ));
+        return Err(BotError::user(tr!(locale, "error-already-watched")).into());
     }
 
-    let location = {
-        let location = database
-            .contents
-            .locations
-            .iter()
-            .find(|other_location| other_location.name.0 == location)
-            .ok_or_else(|| anyhow::anyhow!("no such location"))?;
-        location.clone()
-    };
+    check_watch_quota(ctx, &database)?;
 
-    let name = ctx
-        .data()
-        .n2yo_api
-        .get_name_from_norad_id(satellite_id)
-        .await?;
+    // Only element changes from now on are news to the watcher
+    let tle_epoch = database
+        .contents
+        .tles
+        .get(&SatelliteId(satellite_id))
+        .and_then(|tle| tle.epoch());
 
     database.contents.watched_satellites.push(WatchedSatellite {
         satellite_id: SatelliteId(satellite_id),
         channel: Snowflake(ctx.channel_id().0),
         watcher: Snowflake(ctx.author().id.0),
-        __legacy_locale: ctx.locale().unwrap_or("en-GB").to_string(),
+        locale: locale.to_string(),
         location: LocationName(location.name.0.clone()),
         name: name.clone(),
         min_max_elevation,
         previous_notifications: Vec::new(),
+        days_ahead,
+        notify_within_hours,
+        pending_passes: Vec::new(),
+        broken: None,
+        guild: ctx.guild_id().map(|guild| Snowflake(guild.0)),
+        timezone,
+        lighting: lighting.unwrap_or_default(),
+        min_quality,
+        show_transmitter: show_transmitter.unwrap_or(false),
+        reentry_alerted: false,
+        stale_tle_alerted: None,
+        new_tle_alerted: tle_epoch,
+        kind,
+        max_magnitude: brighter_than.filter(|_| kind == PassKind::Visual),
+        threads: thread_per_pass.unwrap_or(false),
+        announced_passes: Vec::new(),
+        title_template,
+        description_template,
+        tags,
+        paused: false,
     });
-    database.save()?;
+    database.mark_dirty();
 
     ctx.send(|b| {
         b.embed(|e| {
-            e.title("Satellite watched");
-            e.description(format!(
-                "{} with a minimum elevation of {}° at {} by {}",
-                name,
-                min_max_elevation,
-                location.name.0,
-                ctx.author().name,
+            e.title(tr!(locale, "watch-added"));
+            e.description(tr!(
+                locale,
+                "watch-added-description",
+                satellite = name.as_str(),
+                elevation = min_max_elevation,
+                location = location.name.0.as_str(),
+                days = days_ahead,
+                author = ctx.author().name.as_str(),
             ));
             e
         })
@@ -97,36 +200,130 @@ pub async fn watch_satellite(
     Ok(())
 }
 
-/// Lists all watched satellites.
-#[command(slash_command, rename = "list-watched-satellites")]
-pub async fn list_watched_satellites(ctx: Context<'_>) -> anyhow::Result<()> {
-    ctx.defer().await?;
+/// Lists all watched satellites, or only those with a tag.
+#[command(
+    slash_command,
+    rename = "list-watched-satellites",
+    category = "Watches"
+)]
+pub async fn list_watched_satellites(
+    ctx: Context<'_>,
+    #[description = "only list watches with this tag"]
+    #[autocomplete = "autocomplete::watch_tag"]
+    tag: Option<String>,
+) -> anyhow::Result<()> {
+    let preferences = defer_with_preferences(ctx).await?;
+    let locale = i18n::locale(&ctx);
+    let tag = tag.map(|tag| tag.trim().to_lowercase());
 
     let database = ctx.data().database.read().await;
+    let watched_satellites = database
+        .contents
+        .watched_satellites
+        .iter()
+        .filter(|watched_satellite| {
+            tag.as_ref()
+                .is_none_or(|tag| watched_satellite.tags.contains(tag))
+        })
+        .collect::<Vec<_>>();
+
+    if let (Some(tag), true) = (&tag, watched_satellites.is_empty()) {
+        return Err(
+            BotError::user(tr!(locale, "error-no-watches-tagged", tag = tag.as_str())).into(),
+        );
+    }
+
     ctx.send(|b| {
         b.embed(|e| {
-            e.title("Watched satellites");
-            e.fields(
-                database
-                    .contents
-                    .watched_satellites
-                    .iter()
-                    .map(|watched_satellite| {
-                        (
-                            watched_satellite.name.clone(),
-                            format!(
-                                "Channel: {}\nLocation: {}\nMinimum Elevation: {}°",
-                                watched_satellite.channel.0,
-                                watched_satellite.location.0,
-                                watched_satellite.min_max_elevation
+            e.title(tr!(locale, "watches-title"));
+            e.fields(watched_satellites.iter().map(|watched_satellite| {
+                (
+                    watched_satellite.name.clone(),
+                    format!(
+                        "{}{}{}{}{}{}{}{}{}{}",
+                        tr!(
+                            locale,
+                            "watch-details",
+                            channel = watched_satellite.channel.0.to_string(),
+                            location = watched_satellite.location.0.as_str(),
+                            elevation = watched_satellite.min_max_elevation,
+                            days = watched_satellite.days_ahead,
+                        ),
+                        watched_satellite
+                            .notify_within_hours
+                            .map(|hours| format!(
+                                "\n{}",
+                                tr!(locale, "watch-notify-within", hours = hours)
+                            ))
+                            .unwrap_or_default(),
+                        watched_satellite
+                            .timezone
+                            .as_ref()
+                            .map(|timezone| format!(
+                                "\n{}",
+                                tr!(locale, "watch-timezone", timezone = timezone.as_str())
+                            ))
+                            .unwrap_or_default(),
+                        watched_satellite
+                            .min_quality
+                            .map(|min_quality| format!(
+                                "\n{}",
+                                tr!(locale, "watch-min-quality", quality = min_quality)
+                            ))
+                            .unwrap_or_default(),
+                        match (watched_satellite.kind, watched_satellite.max_magnitude) {
+                            (PassKind::Radio, _) => String::new(),
+                            (PassKind::Visual, None) =>
+                                format!("\n{}", tr!(locale, "watch-visual")),
+                            (PassKind::Visual, Some(magnitude)) => format!(
+                                "\n{}",
+                                tr!(locale, "watch-brighter-than", magnitude = magnitude)
                             ),
-                            false,
-                        )
-                    }),
-            );
+                        },
+                        if watched_satellite.threads {
+                            format!("\n{}", tr!(locale, "watch-threads"))
+                        } else {
+                            String::new()
+                        },
+                        if watched_satellite.title_template.is_some()
+                            || watched_satellite.description_template.is_some()
+                        {
+                            format!("\n{}", tr!(locale, "watch-templated"))
+                        } else {
+                            String::new()
+                        },
+                        if watched_satellite.tags.is_empty() {
+                            String::new()
+                        } else {
+                            format!(
+                                "\n{}",
+                                tr!(
+                                    locale,
+                                    "watch-tags",
+                                    tags = watched_satellite.tags.join(", ")
+                                )
+                            )
+                        },
+                        if watched_satellite.paused {
+                            format!("\n{}", tr!(locale, "watch-paused"))
+                        } else {
+                            String::new()
+                        },
+                        watched_satellite
+                            .broken
+                            .as_ref()
+                            .map(|reason| format!(
+                                "\n{}",
+                                tr!(locale, "watch-disabled", reason = reason.as_str())
+                            ))
+                            .unwrap_or_default()
+                    ),
+                    false,
+                )
+            }));
             e
         })
-        .ephemeral(false)
+        .ephemeral(preferences.ephemeral)
     })
     .await?;
 
@@ -134,18 +331,22 @@ pub async fn list_watched_satellites(ctx: Context<'_>) -> anyhow::Result<()> {
 }
 
 /// Removes a watched satellite.
-#[command(slash_command, rename = "unwatch-satellite")]
+///
+/// Example: `/unwatch-satellite satellite_id:33591 channel:#passes location:home`
+#[command(slash_command, rename = "unwatch-satellite", category = "Watches")]
 pub async fn unwatch_satellite(
     ctx: Context<'_>,
-    #[description = "the NORAD ID of the satellite"]
+    #[description = "the NORAD ID of the satellite, or an alias for it"]
     #[autocomplete = "autocomplete::watched_satellite"]
-    satellite_id: usize,
+    satellite_id: String,
     #[description = "the channel the satellite is being watched in"] channel: Channel,
     #[description = "the location the satellite is being watched from"]
     #[autocomplete = "autocomplete::location"]
     location: String,
 ) -> anyhow::Result<()> {
+    let satellite_id = resolve_satellite(ctx, &satellite_id).await?;
     ctx.defer().await?;
+    let locale = i18n::locale(&ctx);
     let mut database = ctx.data().database.write().await;
     let index = database
         .contents
@@ -156,24 +357,23 @@ pub async fn unwatch_satellite(
                 && watched_satellite.channel.0 == channel.id().0
                 && watched_satellite.location.0 == location
         })
-        .ok_or_else(|| anyhow::anyhow!("no such watched satellite"))?;
+        .ok_or_else(|| BotError::user(tr!(locale, "error-no-such-watch")))?;
 
     if ctx.author().id.0 != database.contents.watched_satellites[index].watcher.0 {
-        return Err(anyhow::anyhow!(
-            "watched satellite must be removed by its watcher"
-        ));
+        return Err(BotError::user(tr!(locale, "error-not-watcher")).into());
     }
 
-    database.contents.watched_satellites.remove(index);
-    database.save()?;
+    let removed = database.contents.watched_satellites.remove(index);
+    database.mark_dirty();
 
     ctx.send(|b| {
         b.embed(|e| {
-            e.title("Watched satellite removed");
-            e.description(format!(
-                "{} ({})",
-                database.contents.watched_satellites[index].name,
-                ctx.author().name
+            e.title(tr!(locale, "watch-removed"));
+            e.description(tr!(
+                locale,
+                "watch-description",
+                satellite = removed.name.as_str(),
+                author = ctx.author().name.as_str()
             ));
             e
         })
@@ -184,125 +384,547 @@ pub async fn unwatch_satellite(
     Ok(())
 }
 
-/// Update watched satellites.
-#[command(slash_command, rename = "update-watched-satellites")]
-pub async fn update_watched_satellites(ctx: Context<'_>) -> anyhow::Result<()> {
+/// Edits a watched satellite, re-enabling it if it was disabled.
+///
+/// Example: `/edit-watch satellite_id:33591 location:home new_channel:#weather-sats`
+#[command(slash_command, rename = "edit-watch", category = "Watches")]
+#[allow(clippy::too_many_arguments)]
+pub async fn edit_watch(
+    ctx: Context<'_>,
+    #[description = "the NORAD ID of the satellite, or an alias for it"]
+    #[autocomplete = "autocomplete::watched_satellite"]
+    satellite_id: String,
+    #[description = "the location the satellite is being watched from"]
+    #[autocomplete = "autocomplete::location"]
+    location: String,
+    #[description = "the channel the satellite is being watched in, if you have several watches"]
+    channel: Option<Channel>,
+    #[description = "the channel to send notifications to instead"] new_channel: Option<Channel>,
+    #[description = "the minimum elevation of the passes to notify"] min_max_elevation: Option<f64>,
+    #[description = "how many days in advance to notify of passes (max 10)"] days_ahead: Option<
+        usize,
+    >,
+    #[description = "only notify of passes starting within this many hours (0 to disable)"]
+    notify_within_hours: Option<usize>,
+    #[description = "the observer's timezone, such as Europe/London (none to remove)"]
+    #[autocomplete = "autocomplete::timezone"]
+    timezone: Option<String>,
+    #[description = "whether to only notify of daytime or nighttime passes"] lighting: Option<
+        Lighting,
+    >,
+    #[description = "the lowest quality score of passes to notify, from 0 to 100 (0 to disable)"]
+    min_quality: Option<u8>,
+    #[description = "whether to include the satellite's main transmitter from SatNOGS DB"]
+    show_transmitter: Option<bool>,
+    #[description = "whether to notify of radio or visual passes"] kind: Option<PassKind>,
+    #[description = "only notify of visual passes brighter than this magnitude"]
+    brighter_than: Option<f64>,
+    #[description = "whether to start a thread on each notification for reports of the pass"]
+    thread_per_pass: Option<bool>,
+    #[description = "a template for the title of notifications (none to remove)"]
+    title_template: Option<String>,
+    #[description = "a template for the description of notifications (none to remove)"]
+    description_template: Option<String>,
+    #[description = "tags separated by commas, replacing the current ones (none to remove)"]
+    tags: Option<String>,
+) -> anyhow::Result<()> {
+    let satellite_id = resolve_satellite(ctx, &satellite_id).await?;
     ctx.defer().await?;
-    notify_of_new_passes(
-        &ctx.serenity_context().http,
-        &ctx.data().database,
-        &ctx.data().n2yo_api,
-    )
+    let locale = i18n::locale(&ctx);
+    check_min_quality(locale, min_quality)?;
+    let tags = match tags.as_deref() {
+        Some("none") => Some(Vec::new()),
+        Some(tags) => Some(parse_tags(locale, tags)?),
+        None => None,
+    };
+    let title_template = title_template.map(|template| (template != "none").then_some(template));
+    let description_template =
+        description_template.map(|template| (template != "none").then_some(template));
+    check_templates(
+        locale,
+        title_template.clone().flatten().as_deref(),
+        description_template.clone().flatten().as_deref(),
+    )?;
+    let timezone = match timezone.as_deref() {
+        Some("none") => Some(None),
+        Some(timezone) => Some(Some(parse_timezone(locale, timezone)?)),
+        None => None,
+    };
+
+    if let Some(min_max_elevation) = min_max_elevation {
+        check_min_max_elevation(locale, min_max_elevation)?;
+    }
+
+    if let Some(days_ahead) = days_ahead {
+        if days_ahead > 10 || days_ahead == 0 {
+            return Err(
+                BotError::user(tr!(locale, "error-days-range", parameter = "days_ahead")).into(),
+            );
+        }
+    }
+
+    let mut database = ctx.data().database.write().await;
+    let index = find_own_watch(ctx, &database, satellite_id, &location, channel.as_ref())?;
+    let watched_satellite = &mut database.contents.watched_satellites[index];
+
+    if let Some(new_channel) = new_channel {
+        watched_satellite.channel = Snowflake(new_channel.id().0);
+    }
+
+    if let Some(min_max_elevation) = min_max_elevation {
+        watched_satellite.min_max_elevation = min_max_elevation;
+    }
+
+    if let Some(days_ahead) = days_ahead {
+        watched_satellite.days_ahead = days_ahead;
+    }
+
+    if let Some(notify_within_hours) = notify_within_hours {
+        watched_satellite.notify_within_hours =
+            (notify_within_hours > 0).then_some(notify_within_hours);
+    }
+
+    if let Some(timezone) = timezone {
+        watched_satellite.timezone = timezone;
+    }
+
+    if let Some(lighting) = lighting {
+        watched_satellite.lighting = lighting;
+    }
+
+    if let Some(min_quality) = min_quality {
+        watched_satellite.min_quality = (min_quality > 0).then_some(min_quality);
+    }
+
+    if let Some(show_transmitter) = show_transmitter {
+        watched_satellite.show_transmitter = show_transmitter;
+    }
+
+    if let Some(thread_per_pass) = thread_per_pass {
+        watched_satellite.threads = thread_per_pass;
+    }
+
+    if let Some(title_template) = title_template {
+        watched_satellite.title_template = title_template;
+    }
+
+    if let Some(description_template) = description_template {
+        watched_satellite.description_template = description_template;
+    }
+
+    if let Some(tags) = tags {
+        watched_satellite.tags = tags;
+    }
+
+    // A magnitude only makes sense for visual passes, so giving one switches to them
+    match (kind, brighter_than) {
+        (Some(PassKind::Radio), _) => {
+            watched_satellite.kind = PassKind::Radio;
+            watched_satellite.max_magnitude = None;
+        }
+        (_, Some(brighter_than)) => {
+            watched_satellite.kind = PassKind::Visual;
+            watched_satellite.max_magnitude = Some(brighter_than);
+        }
+        (Some(PassKind::Visual), None) => watched_satellite.kind = PassKind::Visual,
+        (None, None) => {}
+    }
+
+    watched_satellite.broken = None;
+    let name = watched_satellite.name.clone();
+    database.mark_dirty();
+
+    ctx.send(|b| {
+        b.embed(|e| {
+            e.title(tr!(locale, "watch-updated"));
+            e.description(tr!(
+                locale,
+                "watch-description",
+                satellite = name.as_str(),
+                author = ctx.author().name.as_str()
+            ));
+            e
+        })
+        .ephemeral(false)
+    })
     .await?;
 
-    ctx.say("Updated watched satellites").await?;
+    Ok(())
+}
+
+/// Copies one of your watches, with all of its settings, to another channel or location.
+///
+/// Example: `/clone-watch satellite_id:33591 location:home new_channel:#club-night`
+#[command(slash_command, rename = "clone-watch", category = "Watches")]
+pub async fn clone_watch(
+    ctx: Context<'_>,
+    #[description = "the NORAD ID of the satellite, or an alias for it"]
+    #[autocomplete = "autocomplete::watched_satellite"]
+    satellite_id: String,
+    #[description = "the location the satellite is being watched from"]
+    #[autocomplete = "autocomplete::location"]
+    location: String,
+    #[description = "the channel the satellite is being watched in, if you have several watches"]
+    channel: Option<Channel>,
+    #[description = "the channel to send the copy's notifications to, if not this one"]
+    new_channel: Option<Channel>,
+    #[description = "the location the copy notifies of passes for, if not the same one"]
+    #[autocomplete = "autocomplete::location"]
+    new_location: Option<String>,
+) -> anyhow::Result<()> {
+    let satellite_id = resolve_satellite(ctx, &satellite_id).await?;
+    ctx.defer().await?;
+    let locale = i18n::locale(&ctx);
+
+    let mut database = ctx.data().database.write().await;
+    let index = find_own_watch(ctx, &database, satellite_id, &location, channel.as_ref())?;
+    let location = match new_location {
+        Some(new_location) => {
+            let preferences = UserPreferences::for_user(&database, &Snowflake(ctx.author().id.0));
+            find_location(&database, locale, Some(&new_location), &preferences)?.name
+        }
+        None => LocationName(location),
+    };
+    let tle_epoch = database
+        .contents
+        .tles
+        .get(&SatelliteId(satellite_id))
+        .and_then(|tle| tle.epoch());
+
+    // Only the settings are copied, so the copy notifies of passes the original already has
+    let copy = WatchedSatellite {
+        location,
+        channel: Snowflake(new_channel.map_or(ctx.channel_id().0, |channel| channel.id().0)),
+        locale: locale.to_string(),
+        previous_notifications: Vec::new(),
+        pending_passes: Vec::new(),
+        broken: None,
+        guild: ctx.guild_id().map(|guild| Snowflake(guild.0)),
+        reentry_alerted: false,
+        stale_tle_alerted: None,
+        new_tle_alerted: tle_epoch,
+        announced_passes: Vec::new(),
+        paused: false,
+        ..database.contents.watched_satellites[index].clone()
+    };
+
+    if database
+        .contents
+        .watched_satellites
+        .iter()
+        .any(|watched_satellite| watched_satellite.is_same_watch(&copy))
+    {
+        return Err(BotError::user(tr!(locale, "error-already-watched")).into());
+    }
+
+    check_watch_quota(ctx, &database)?;
+    database.contents.watched_satellites.push(copy.clone());
+    database.mark_dirty();
+    drop(database);
+
+    ctx.send(|b| {
+        b.embed(|e| {
+            e.title(tr!(locale, "watch-cloned"));
+            e.description(tr!(
+                locale,
+                "watch-added-description",
+                satellite = copy.name.as_str(),
+                elevation = copy.min_max_elevation,
+                location = copy.location.0.as_str(),
+                days = copy.days_ahead,
+                author = ctx.author().name.as_str(),
+            ));
+            e
+        })
+        .ephemeral(false)
+    })
+    .await?;
 
     Ok(())
 }
 
-pub async fn notify_of_new_passes(
-    http: &Arc<Http>,
-    database: &Arc<RwLock<Database>>,
-    n2yo_api: &Arc<N2YOAPI>,
+/// Moves the watches in one channel to another, such as when a channel is archived.
+///
+/// Only your own watches are moved, unless you are one of the server's admins. Watches the other
+/// channel already has are removed instead.
+///
+/// Example: `/move-watch from:#passes to:#weather-sats`
+#[command(slash_command, rename = "move-watch", category = "Watches", guild_only)]
+pub async fn move_watch(
+    ctx: Context<'_>,
+    #[description = "the channel to move watches out of"] from: Channel,
+    #[description = "the channel to move them to"] to: Channel,
 ) -> anyhow::Result<()> {
-    let mut successful_notifications = Vec::new();
-    let mut database = database.write().await;
-
-    for watched_satellite in database.contents.watched_satellites.iter() {
-        let passes = n2yo_api
-            .get_satellite_passes(
-                watched_satellite.satellite_id.0,
-                database
-                    .contents
-                    .locations
-                    .iter()
-                    .find(|location| location.name.0 == watched_satellite.location.0)
-                    .unwrap(),
-                1,
-                watched_satellite.min_max_elevation,
-            )
-            .await?;
-
-        if passes.passes.is_empty() {
-            continue;
+    ctx.defer().await?;
+    let locale = i18n::locale(&ctx);
+    let (from, to) = (Snowflake(from.id().0), Snowflake(to.id().0));
+
+    if from == to {
+        return Err(BotError::user(tr!(locale, "error-same-channel")).into());
+    }
+
+    let author = Snowflake(ctx.author().id.0);
+    let is_admin = is_admin(ctx).await;
+
+    let moved = {
+        let mut database = ctx.data().database.write().await;
+        let watched_satellites = &mut database.contents.watched_satellites;
+        let movable = |watched_satellite: &WatchedSatellite| {
+            watched_satellite.channel == from && (is_admin || watched_satellite.watcher == author)
+        };
+        let moved = watched_satellites
+            .iter()
+            .filter(|watched_satellite| movable(watched_satellite))
+            .count();
+
+        if moved == 0 {
+            return Err(BotError::user(tr!(locale, "error-no-watches-to-move")).into());
         }
 
-        let mut b = CreateMessage::default();
-
-        for pass in passes.passes.iter() {
-            if pass.max_elevation >= watched_satellite.min_max_elevation {
-                if watched_satellite
-                    .previous_notifications
-                    .iter()
-                    .any(|(start, end)| {
-                        util::are_within_10_seconds(*start as i64, pass.start_utc as i64)
-                            && util::are_within_10_seconds(*end as i64, pass.end_utc as i64)
-                    })
-                {
-                    continue;
-                } else {
-                    successful_notifications.push((
-                        watched_satellite.satellite_id.0,
-                        pass.start_utc,
-                        pass.end_utc,
-                    ));
-                }
-
-                b.add_embed(|e| {
-                    e.title(format!(
-                        "Upcoming pass for {} at {}",
-                        passes.info.name, watched_satellite.location.0
-                    ));
-
-                    e.description(format!(
-                        "{}\nMax Elevation: {}°",
-                        util::format_pass_time(
-                            pass.start_utc as i64,
-                            pass.end_utc as i64
-                        ),
-                        pass.max_elevation
-                    ));
-                    e
-                });
+        let already_there = watched_satellites
+            .iter()
+            .filter(|watched_satellite| watched_satellite.channel == to)
+            .cloned()
+            .collect::<Vec<_>>();
+
+        watched_satellites.retain_mut(|watched_satellite| {
+            if !movable(watched_satellite) {
+                return true;
             }
+
+            watched_satellite.channel = to.clone();
+            // The old channel may be why the watch was disabled
+            watched_satellite.broken = None;
+            !already_there
+                .iter()
+                .any(|other| other.is_same_watch(watched_satellite))
+        });
+
+        database.mark_dirty();
+        moved
+    };
+
+    ctx.say(tr!(
+        locale,
+        "watches-moved",
+        count = moved,
+        from = format!("<#{}>", from.0),
+        to = format!("<#{}>", to.0)
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Pauses the notifications of all of your watches with a tag, until they are resumed.
+///
+/// Example: `/pause-watch tag:club-night`
+#[command(slash_command, rename = "pause-watch", category = "Watches")]
+pub async fn pause_watch(
+    ctx: Context<'_>,
+    #[description = "the tag of the watches to pause"]
+    #[autocomplete = "autocomplete::own_watch_tag"]
+    tag: String,
+) -> anyhow::Result<()> {
+    set_paused(ctx, &tag, true).await
+}
+
+/// Resumes the notifications of all of your watches with a tag.
+///
+/// Example: `/resume-watch tag:club-night`
+#[command(slash_command, rename = "resume-watch", category = "Watches")]
+pub async fn resume_watch(
+    ctx: Context<'_>,
+    #[description = "the tag of the watches to resume"]
+    #[autocomplete = "autocomplete::own_watch_tag"]
+    tag: String,
+) -> anyhow::Result<()> {
+    set_paused(ctx, &tag, false).await
+}
+
+/// Pauses or resumes the caller's watches with a tag.
+async fn set_paused(ctx: Context<'_>, tag: &str, paused: bool) -> anyhow::Result<()> {
+    ctx.defer().await?;
+    let locale = i18n::locale(&ctx);
+    let tag = tag.trim().to_lowercase();
+    let author = Snowflake(ctx.author().id.0);
+
+    let count = {
+        let mut database = ctx.data().database.write().await;
+        let mut count = 0;
+
+        for watched_satellite in
+            database
+                .contents
+                .watched_satellites
+                .iter_mut()
+                .filter(|watched_satellite| {
+                    watched_satellite.watcher == author && watched_satellite.tags.contains(&tag)
+                })
+        {
+            watched_satellite.paused = paused;
+            count += 1;
         }
 
-        let mut map = serde_json::Map::new();
-        for (key, value) in b.0 {
-            map.insert(key.to_string(), value);
+        if count == 0 {
+            return Err(BotError::user(tr!(
+                locale,
+                "error-no-watches-of-yours-tagged",
+                tag = tag.as_str()
+            ))
+            .into());
         }
 
-        http.send_message(watched_satellite.channel.0, &Value::Object(map))
-            .await?;
+        database.mark_dirty();
+        count
+    };
+
+    ctx.say(tr!(
+        locale,
+        if paused {
+            "watches-paused"
+        } else {
+            "watches-resumed"
+        },
+        count = count,
+        tag = tag.as_str()
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Update watched satellites.
+#[command(
+    slash_command,
+    rename = "update-watched-satellites",
+    category = "Watches",
+    guild_only,
+    default_member_permissions = "MANAGE_GUILD",
+    check = "admin_only",
+    check = "pass_request_cooldown"
+)]
+pub async fn update_watched_satellites(ctx: Context<'_>) -> anyhow::Result<()> {
+    ctx.defer().await?;
+    notify::notify_of_new_passes(
+        &*ctx.serenity_context().http,
+        &ctx.data().database,
+        &*ctx.data().pass_provider,
+        &ctx.data().metrics,
+        false,
+    )
+    .await?;
+
+    ctx.say(tr!(i18n::locale(&ctx), "watches-updated")).await?;
+
+    Ok(())
+}
+
+fn check_min_quality(locale: &str, min_quality: Option<u8>) -> anyhow::Result<()> {
+    if min_quality.is_some_and(|min_quality| min_quality > 100) {
+        return Err(BotError::user(tr!(locale, "error-quality-range")).into());
+    }
+
+    Ok(())
+}
+
+fn check_templates(
+    locale: &str,
+    title_template: Option<&str>,
+    description_template: Option<&str>,
+) -> anyhow::Result<()> {
+    if let Some(template) = title_template {
+        templates::check(locale, template, MAX_TITLE_TEMPLATE_LENGTH)?;
     }
 
-    for successful in successful_notifications.iter() {
-        database
-            .contents
-            .watched_satellites
-            .iter_mut()
-            .find(|watched_satellite| watched_satellite.satellite_id.0 == successful.0)
-            .unwrap()
-            .previous_notifications
-            .push((successful.1, successful.2));
+    if let Some(template) = description_template {
+        templates::check(locale, template, MAX_DESCRIPTION_TEMPLATE_LENGTH)?;
     }
 
-    database
+    Ok(())
+}
+
+/// Checks that the caller can have another watch in this server.
+fn check_watch_quota(ctx: Context<'_>, database: &Database) -> anyhow::Result<()> {
+    let guild = ctx.guild_id().map(|guild| Snowflake(guild.0));
+    let max = GuildSettings::for_guild(database, guild.as_ref()).max_watches_per_user();
+    let count = database
         .contents
         .watched_satellites
-        .iter_mut()
-        .for_each(|ws| {
-            ws.previous_notifications.retain(|(start, end)| {
-                !has_more_than_one_day_passed(*start as i64)
-                    && !has_more_than_one_day_passed(*end as i64)
-            });
-        });
+        .iter()
+        .filter(|watched_satellite| {
+            watched_satellite.watcher.0 == ctx.author().id.0 && watched_satellite.guild == guild
+        })
+        .count();
 
-    database.save()?;
+    if count >= max {
+        return Err(
+            BotError::user(tr!(i18n::locale(&ctx), "error-too-many-watches", max = max)).into(),
+        );
+    }
 
     Ok(())
 }
 
-fn has_more_than_one_day_passed(since: i64) -> bool {
-    util::current_utc() - since > 24 * 60 * 60
+/// Finds the caller's watch of a satellite from a location, which must also be in the given
+/// channel if they have several.
+fn find_own_watch(
+    ctx: Context<'_>,
+    database: &Database,
+    satellite_id: usize,
+    location: &str,
+    channel: Option<&Channel>,
+) -> anyhow::Result<usize> {
+    let locale = i18n::locale(&ctx);
+    let matching = database
+        .contents
+        .watched_satellites
+        .iter()
+        .enumerate()
+        .filter(|(_, watched_satellite)| {
+            watched_satellite.satellite_id.0 == satellite_id
+                && watched_satellite.location.0 == location
+                && watched_satellite.watcher.0 == ctx.author().id.0
+                && channel.is_none_or(|channel| watched_satellite.channel.0 == channel.id().0)
+        })
+        .map(|(index, _)| index)
+        .collect::<Vec<_>>();
+
+    match matching.as_slice() {
+        [index] => Ok(*index),
+        [] => Err(BotError::user(tr!(locale, "error-no-such-watch-of-yours")).into()),
+        _ => Err(BotError::user(tr!(locale, "error-ambiguous-watch")).into()),
+    }
+}
+
+/// Reads a list of tags separated by commas, lowercasing them and dropping duplicates.
+fn parse_tags(locale: &str, tags: &str) -> anyhow::Result<Vec<String>> {
+    let mut parsed = Vec::new();
+
+    for tag in tags.split(',').map(|tag| tag.trim().to_lowercase()) {
+        if tag.is_empty() || tag.chars().count() > MAX_TAG_LENGTH {
+            return Err(
+                BotError::user(tr!(locale, "error-invalid-tag", max = MAX_TAG_LENGTH)).into(),
+            );
+        }
+
+        if !parsed.contains(&tag) {
+            parsed.push(tag);
+        }
+    }
+
+    if parsed.len() > MAX_TAGS {
+        return Err(BotError::user(tr!(locale, "error-too-many-tags", max = MAX_TAGS)).into());
+    }
+
+    Ok(parsed)
+}
+
+/// Checks that a timezone is known, returning its canonical name.
+fn parse_timezone(locale: &str, timezone: &str) -> anyhow::Result<String> {
+    preferences::parse_timezone(timezone)
+        .map(|timezone| timezone.name().to_string())
+        .ok_or_else(|| {
+            BotError::user(tr!(locale, "error-unknown-timezone", timezone = timezone)).into()
+        })
 }
@@ -0,0 +1,886 @@
+use std::{fmt::Display, path::PathBuf, str::FromStr, sync::OnceLock};
+
+use serde::{de, Deserialize, Deserializer};
+use tracing::info;
+
+use crate::{n2yo, registration::RegistrationMode, schedule::CronExpression, sharding::Sharding};
+
+/// The config file read when `CONFIG_PATH` is not set.
+const DEFAULT_CONFIG_PATH: &str = "config.toml";
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+/// The kind of value a setting holds, used to read it from its environment variable.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Kind {
+    Text,
+    Integer,
+    Float,
+    Bool,
+    /// A comma separated list in the environment, or an array in the config file.
+    List,
+}
+
+/// A setting that can be given in the config file or overridden by an environment variable.
+struct Setting {
+    /// The dotted path of the setting in the config file.
+    key: &'static str,
+    /// The environment variable that overrides the setting.
+    env: &'static str,
+    kind: Kind,
+    required: Required,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Required {
+    No,
+    Yes,
+    /// Required unless running in mock mode, where the setting's service is faked.
+    UnlessMocked,
+}
+
+const fn setting(key: &'static str, env: &'static str, kind: Kind) -> Setting {
+    Setting {
+        key,
+        env,
+        kind,
+        required: Required::No,
+    }
+}
+
+const fn required(key: &'static str, env: &'static str, kind: Kind) -> Setting {
+    Setting {
+        key,
+        env,
+        kind,
+        required: Required::Yes,
+    }
+}
+
+const fn required_unless_mocked(key: &'static str, env: &'static str, kind: Kind) -> Setting {
+    Setting {
+        key,
+        env,
+        kind,
+        required: Required::UnlessMocked,
+    }
+}
+
+const SETTINGS: &[Setting] = &[
+    required("discord.token", "DISCORD_TOKEN", Kind::Text),
+    setting("discord.guild_id", "GUILD_ID", Kind::Integer),
+    setting(
+        "discord.command_registration",
+        "COMMAND_REGISTRATION",
+        Kind::Text,
+    ),
+    setting("discord.sharding", "SHARDING", Kind::Text),
+    setting("discord.ops_channel_id", "OPS_CHANNEL_ID", Kind::Integer),
+    setting("sentry.dsn", "SENTRY_DSN", Kind::Text),
+    setting("sentry.environment", "SENTRY_ENVIRONMENT", Kind::Text),
+    required("database.path", "DATABASE_PATH", Kind::Text),
+    setting(
+        "database.reload_seconds",
+        "DATABASE_RELOAD_SECONDS",
+        Kind::Integer,
+    ),
+    required_unless_mocked("n2yo.keys", "N2YO_KEY", Kind::List),
+    setting("n2yo.hourly_limit", "N2YO_HOURLY_LIMIT", Kind::Integer),
+    setting(
+        "n2yo.requests_per_hour",
+        "N2YO_REQUESTS_PER_HOUR",
+        Kind::Integer,
+    ),
+    setting("n2yo.retry_attempts", "N2YO_RETRY_ATTEMPTS", Kind::Integer),
+    setting(
+        "n2yo.retry_base_delay_ms",
+        "N2YO_RETRY_BASE_DELAY_MS",
+        Kind::Integer,
+    ),
+    setting("n2yo.base_url", "N2YO_BASE_URL", Kind::Text),
+    setting("n2yo.timeout_secs", "N2YO_TIMEOUT_SECS", Kind::Integer),
+    setting("n2yo.proxy", "N2YO_PROXY", Kind::Text),
+    setting("n2yo.cache_ttl_secs", "N2YO_CACHE_TTL_SECS", Kind::Integer),
+    setting("n2yo.concurrency", "N2YO_CONCURRENCY", Kind::Integer),
+    setting(
+        "notifications.interval_minutes",
+        "NOTIFY_INTERVAL_MINUTES",
+        Kind::Integer,
+    ),
+    setting("notifications.cron", "NOTIFY_CRON", Kind::Text),
+    setting(
+        "notifications.within_hours",
+        "NOTIFY_WITHIN_HOURS",
+        Kind::Integer,
+    ),
+    setting(
+        "notifications.request_delay_ms",
+        "NOTIFY_REQUEST_DELAY_MS",
+        Kind::Integer,
+    ),
+    setting(
+        "notifications.request_jitter_ms",
+        "NOTIFY_REQUEST_JITTER_MS",
+        Kind::Integer,
+    ),
+    setting(
+        "notifications.delivery_max_attempts",
+        "DELIVERY_MAX_ATTEMPTS",
+        Kind::Integer,
+    ),
+    setting(
+        "notifications.rsvp_reminder_minutes",
+        "RSVP_REMINDER_MINUTES",
+        Kind::Integer,
+    ),
+    setting(
+        "notifications.thread_archive_hours",
+        "THREAD_ARCHIVE_HOURS",
+        Kind::Integer,
+    ),
+    setting(
+        "catalog.max_age_hours",
+        "CATALOG_MAX_AGE_HOURS",
+        Kind::Integer,
+    ),
+    setting(
+        "satcat.refresh_hours",
+        "SATCAT_REFRESH_HOURS",
+        Kind::Integer,
+    ),
+    setting("decay.alert_days", "DECAY_ALERT_DAYS", Kind::Float),
+    setting("decay.tle_stale_days", "TLE_STALE_DAYS", Kind::Integer),
+    setting("decay.tle_gap_days", "TLE_GAP_DAYS", Kind::Float),
+    setting("sstv.events_url", "SSTV_EVENTS_URL", Kind::Text),
+    setting(
+        "sstv.check_interval_minutes",
+        "SSTV_CHECK_INTERVAL_MINUTES",
+        Kind::Integer,
+    ),
+    setting(
+        "sstv.min_max_elevation",
+        "SSTV_MIN_MAX_ELEVATION",
+        Kind::Float,
+    ),
+    setting(
+        "launches.check_interval_minutes",
+        "LAUNCH_CHECK_INTERVAL_MINUTES",
+        Kind::Integer,
+    ),
+    setting("defaults.days", "DEFAULT_DAYS", Kind::Integer),
+    setting("defaults.days_ahead", "DEFAULT_DAYS_AHEAD", Kind::Integer),
+    setting(
+        "defaults.min_max_elevation",
+        "DEFAULT_MIN_MAX_ELEVATION",
+        Kind::Float,
+    ),
+    setting(
+        "defaults.max_watches_per_user",
+        "MAX_WATCHES_PER_USER",
+        Kind::Integer,
+    ),
+    setting(
+        "defaults.user_cooldown_seconds",
+        "USER_COOLDOWN_SECONDS",
+        Kind::Integer,
+    ),
+    setting(
+        "defaults.channel_cooldown_seconds",
+        "CHANNEL_COOLDOWN_SECONDS",
+        Kind::Integer,
+    ),
+    setting("logging.format", "LOG_FORMAT", Kind::Text),
+    setting("logging.directory", "LOG_DIRECTORY", Kind::Text),
+    setting("logging.max_files", "LOG_MAX_FILES", Kind::Integer),
+    setting("features.member_cleanup", "MEMBER_CLEANUP", Kind::Bool),
+    setting("features.mock", "SATBOT_MOCK", Kind::Bool),
+];
+
+/// The bot's settings, from the config file and environment variables.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub discord: DiscordConfig,
+    pub sentry: SentryConfig,
+    pub database: DatabaseConfig,
+    pub n2yo: N2YOConfig,
+    pub notifications: NotificationConfig,
+    pub catalog: CatalogConfig,
+    pub satcat: SatcatConfig,
+    pub decay: DecayConfig,
+    pub sstv: SstvConfig,
+    pub launches: LaunchConfig,
+    pub defaults: DefaultsConfig,
+    pub logging: LoggingConfig,
+    pub features: FeatureConfig,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DiscordConfig {
+    pub token: String,
+    pub guild_id: Option<u64>,
+    #[serde(deserialize_with = "parsed")]
+    pub command_registration: RegistrationMode,
+    #[serde(deserialize_with = "parsed")]
+    pub sharding: Sharding,
+    pub ops_channel_id: Option<u64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SentryConfig {
+    pub dsn: Option<String>,
+    pub environment: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DatabaseConfig {
+    pub path: PathBuf,
+    /// Zero turns reloading off.
+    pub reload_seconds: u64,
+}
+
+impl Default for DatabaseConfig {
+    fn default() -> Self {
+        Self {
+            path: PathBuf::new(),
+            reload_seconds: 5,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct N2YOConfig {
+    pub keys: Vec<String>,
+    /// Per key.
+    pub hourly_limit: usize,
+    /// Defaults to the combined hourly limit of all keys.
+    pub requests_per_hour: Option<usize>,
+    pub retry_attempts: usize,
+    pub retry_base_delay_ms: u64,
+    pub base_url: String,
+    pub timeout_secs: u64,
+    pub proxy: Option<String>,
+    /// Zero disables the cache.
+    pub cache_ttl_secs: u64,
+    pub concurrency: usize,
+}
+
+impl Default for N2YOConfig {
+    fn default() -> Self {
+        Self {
+            keys: Vec::new(),
+            hourly_limit: 100,
+            requests_per_hour: None,
+            retry_attempts: 3,
+            retry_base_delay_ms: 500,
+            base_url: n2yo::DEFAULT_BASE_URL.to_string(),
+            timeout_secs: 30,
+            proxy: None,
+            cache_ttl_secs: 600,
+            concurrency: 4,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NotificationConfig {
+    pub interval_minutes: u64,
+    /// Replaces the interval when given.
+    #[serde(deserialize_with = "parsed_option")]
+    pub cron: Option<CronExpression>,
+    /// Zero announces passes immediately.
+    pub within_hours: usize,
+    pub request_delay_ms: u64,
+    pub request_jitter_ms: u64,
+    pub delivery_max_attempts: usize,
+    pub rsvp_reminder_minutes: i64,
+    pub thread_archive_hours: i64,
+}
+
+impl Default for NotificationConfig {
+    fn default() -> Self {
+        Self {
+            interval_minutes: 30,
+            cron: None,
+            within_hours: 0,
+            request_delay_ms: 250,
+            request_jitter_ms: 250,
+            delivery_max_attempts: 5,
+            rsvp_reminder_minutes: 15,
+            thread_archive_hours: 24,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CatalogConfig {
+    pub max_age_hours: i64,
+}
+
+impl Default for CatalogConfig {
+    fn default() -> Self {
+        Self {
+            max_age_hours: 24 * 7,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SatcatConfig {
+    pub refresh_hours: i64,
+}
+
+impl Default for SatcatConfig {
+    fn default() -> Self {
+        Self { refresh_hours: 24 }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DecayConfig {
+    pub alert_days: f64,
+    pub tle_stale_days: i64,
+    pub tle_gap_days: f64,
+}
+
+impl Default for DecayConfig {
+    fn default() -> Self {
+        Self {
+            alert_days: 30.0,
+            tle_stale_days: 14,
+            tle_gap_days: 3.0,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SstvConfig {
+    pub events_url: Option<String>,
+    pub check_interval_minutes: u64,
+    pub min_max_elevation: f64,
+}
+
+impl Default for SstvConfig {
+    fn default() -> Self {
+        Self {
+            events_url: None,
+            check_interval_minutes: 60,
+            min_max_elevation: 10.0,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LaunchConfig {
+    pub check_interval_minutes: u64,
+}
+
+impl Default for LaunchConfig {
+    fn default() -> Self {
+        Self {
+            check_interval_minutes: 60,
+        }
+    }
+}
+
+/// Defaults for commands, which servers can override with `/server-settings`.
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DefaultsConfig {
+    pub days: usize,
+    pub days_ahead: usize,
+    pub min_max_elevation: f64,
+    pub max_watches_per_user: usize,
+    /// Zero turns the cooldown off.
+    pub user_cooldown_seconds: u64,
+    /// Zero turns the cooldown off.
+    pub channel_cooldown_seconds: u64,
+}
+
+impl Default for DefaultsConfig {
+    fn default() -> Self {
+        Self {
+            days: 3,
+            days_ahead: 1,
+            min_max_elevation: 20.0,
+            max_watches_per_user: 20,
+            user_cooldown_seconds: 30,
+            channel_cooldown_seconds: 10,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LoggingConfig {
+    pub format: LogFormat,
+    /// Logs go to stdout unless this is given.
+    pub directory: Option<PathBuf>,
+    pub max_files: usize,
+}
+
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        Self {
+            format: LogFormat::Text,
+            directory: None,
+            max_files: 7,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    #[default]
+    Text,
+    /// A line of JSON per event.
+    Json,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FeatureConfig {
+    pub member_cleanup: bool,
+    pub mock: bool,
+}
+
+/// The loaded settings, or the defaults if [`load`] has not been called, as in tests.
+pub fn get() -> &'static Config {
+    CONFIG.get_or_init(Config::default)
+}
+
+/// Loads the config file, if there is one, applies the environment variables over it, and checks
+/// the result, reporting every problem found at once.
+pub fn load() -> anyhow::Result<()> {
+    let (path, explicit) = match std::env::var("CONFIG_PATH") {
+        Ok(path) => (PathBuf::from(path), true),
+        Err(_) => (PathBuf::from(DEFAULT_CONFIG_PATH), false),
+    };
+    let mut table = toml::Table::new();
+
+    if path.exists() {
+        info!("Loading config from {}", path.display());
+        let contents = std::fs::read_to_string(&path)?;
+        table = contents
+            .parse::<toml::Table>()
+            .map_err(|e| anyhow::anyhow!("failed to parse {}: {}", path.display(), e))?;
+        check_keys(&table, "")
+            .map_err(|e| anyhow::anyhow!("invalid config file {}: {}", path.display(), e))?;
+    } else if explicit {
+        return Err(anyhow::anyhow!(
+            "config file {} does not exist",
+            path.display()
+        ));
+    }
+
+    let config = resolve(table, |env| std::env::var(env).ok())?;
+    CONFIG
+        .set(config)
+        .map_err(|_| anyhow::anyhow!("the configuration was already loaded"))
+}
+
+/// Applies the environment over a config table and reads the settings from it.
+fn resolve(mut table: toml::Table, env: impl Fn(&str) -> Option<String>) -> anyhow::Result<Config> {
+    let mut problems = Vec::new();
+
+    for setting in SETTINGS {
+        let Some(value) = env(setting.env) else {
+            continue;
+        };
+
+        match from_env(setting.kind, &value) {
+            Ok(value) => {
+                if lookup(&table, setting.key).is_some() {
+                    info!("{} is overridden by {}", setting.key, setting.env);
+                }
+
+                insert(&mut table, setting.key, value);
+            }
+            Err(e) => problems.push(format!(
+                "{} ({}) is invalid: {}",
+                setting.key, setting.env, e
+            )),
+        }
+    }
+
+    let mock = matches!(
+        lookup(&table, "features.mock"),
+        Some(toml::Value::Boolean(true))
+    );
+
+    for setting in SETTINGS {
+        let needed = match setting.required {
+            Required::No => false,
+            Required::Yes => true,
+            Required::UnlessMocked => !mock,
+        };
+
+        if needed && lookup(&table, setting.key).is_none() {
+            problems.push(format!(
+                "{} is missing; set it in the config file or the {} environment variable",
+                setting.key, setting.env
+            ));
+        }
+    }
+
+    if !problems.is_empty() {
+        return Err(invalid(problems));
+    }
+
+    let config = toml::Value::Table(table)
+        .try_into::<Config>()
+        .map_err(|e| invalid(vec![e.to_string().trim().replace('\n', " ")]))?;
+    let problems = config.problems();
+
+    if problems.is_empty() {
+        Ok(config)
+    } else {
+        Err(invalid(problems))
+    }
+}
+
+impl Config {
+    /// Describes every setting that is out of its allowed range.
+    fn problems(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        let notifications = &self.notifications;
+        let n2yo = &self.n2yo;
+
+        between(
+            &mut problems,
+            "notifications.interval_minutes",
+            notifications.interval_minutes as f64,
+            5.0,
+            24.0 * 60.0,
+        );
+        at_least(
+            &mut problems,
+            "notifications.delivery_max_attempts",
+            notifications.delivery_max_attempts as u64,
+            1,
+        );
+        at_least(
+            &mut problems,
+            "n2yo.hourly_limit",
+            n2yo.hourly_limit as u64,
+            1,
+        );
+        if let Some(requests_per_hour) = n2yo.requests_per_hour {
+            at_least(
+                &mut problems,
+                "n2yo.requests_per_hour",
+                requests_per_hour as u64,
+                1,
+            );
+        }
+        at_least(
+            &mut problems,
+            "n2yo.retry_attempts",
+            n2yo.retry_attempts as u64,
+            1,
+        );
+        at_least(&mut problems, "n2yo.timeout_secs", n2yo.timeout_secs, 1);
+        at_least(
+            &mut problems,
+            "n2yo.concurrency",
+            n2yo.concurrency as u64,
+            1,
+        );
+        between(
+            &mut problems,
+            "defaults.days",
+            self.defaults.days as f64,
+            1.0,
+            10.0,
+        );
+        between(
+            &mut problems,
+            "defaults.days_ahead",
+            self.defaults.days_ahead as f64,
+            1.0,
+            10.0,
+        );
+        between(
+            &mut problems,
+            "defaults.min_max_elevation",
+            self.defaults.min_max_elevation,
+            0.0,
+            90.0,
+        );
+        at_least(
+            &mut problems,
+            "sstv.check_interval_minutes",
+            self.sstv.check_interval_minutes,
+            1,
+        );
+        at_least(
+            &mut problems,
+            "launches.check_interval_minutes",
+            self.launches.check_interval_minutes,
+            1,
+        );
+        between(
+            &mut problems,
+            "sstv.min_max_elevation",
+            self.sstv.min_max_elevation,
+            0.0,
+            90.0,
+        );
+        at_least(
+            &mut problems,
+            "logging.max_files",
+            self.logging.max_files as u64,
+            1,
+        );
+
+        if n2yo.keys.iter().all(|key| key.trim().is_empty()) && !self.features.mock {
+            problems.push("n2yo.keys must contain at least one key".to_string());
+        }
+
+        problems
+    }
+}
+
+fn at_least(problems: &mut Vec<String>, key: &str, value: u64, min: u64) {
+    if value < min {
+        problems.push(format!("{} must be at least {}, got {}", key, min, value));
+    }
+}
+
+fn between(problems: &mut Vec<String>, key: &str, value: f64, min: f64, max: f64) {
+    if !(min..=max).contains(&value) {
+        problems.push(format!(
+            "{} must be between {} and {}, got {}",
+            key, min, max, value
+        ));
+    }
+}
+
+fn invalid(problems: Vec<String>) -> anyhow::Error {
+    anyhow::anyhow!("invalid configuration:\n  {}", problems.join("\n  "))
+}
+
+/// Checks that every key in a config table is a known setting.
+fn check_keys(table: &toml::Table, prefix: &str) -> anyhow::Result<()> {
+    for (name, value) in table {
+        let key = format!("{}{}", prefix, name);
+
+        match value {
+            toml::Value::Table(table) => check_keys(table, &format!("{}.", key))?,
+            _ if SETTINGS.iter().any(|setting| setting.key == key) => {}
+            _ => return Err(anyhow::anyhow!("unknown setting {}", key)),
+        }
+    }
+
+    Ok(())
+}
+
+fn lookup<'a>(table: &'a toml::Table, key: &str) -> Option<&'a toml::Value> {
+    let (section, name) = key.split_once('.')?;
+    table.get(section)?.as_table()?.get(name)
+}
+
+fn insert(table: &mut toml::Table, key: &str, value: toml::Value) {
+    let (section, name) = key.split_once('.').unwrap_or(("", key));
+
+    if let toml::Value::Table(section) = table
+        .entry(section)
+        .or_insert_with(|| toml::Value::Table(toml::Table::new()))
+    {
+        section.insert(name.to_string(), value);
+    }
+}
+
+/// Reads the value of an environment variable as the kind of value its setting holds.
+fn from_env(kind: Kind, value: &str) -> Result<toml::Value, String> {
+    match kind {
+        Kind::Text => Ok(toml::Value::String(value.to_string())),
+        Kind::Integer => value
+            .parse()
+            .map(toml::Value::Integer)
+            .map_err(|_| format!("expected a whole number, got '{}'", value)),
+        Kind::Float => value
+            .parse()
+            .map(toml::Value::Float)
+            .map_err(|_| format!("expected a number, got '{}'", value)),
+        Kind::Bool => match value {
+            "true" | "1" => Ok(toml::Value::Boolean(true)),
+            "false" | "0" => Ok(toml::Value::Boolean(false)),
+            _ => Err(format!("expected true or false, got '{}'", value)),
+        },
+        Kind::List => {
+            let items = value
+                .split(',')
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .map(|item| toml::Value::String(item.to_string()))
+                .collect::<Vec<_>>();
+
+            if items.is_empty() {
+                Err("expected at least one value".to_string())
+            } else {
+                Ok(toml::Value::Array(items))
+            }
+        }
+    }
+}
+
+/// Reads a setting given as text with its [`FromStr`] implementation.
+fn parsed<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: Display,
+{
+    String::deserialize(deserializer)?
+        .parse()
+        .map_err(de::Error::custom)
+}
+
+fn parsed_option<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: Display,
+{
+    parsed(deserializer).map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn resolve_with(file: &str, env: &[(&str, &str)]) -> anyhow::Result<Config> {
+        let env = env
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect::<HashMap<_, _>>();
+
+        resolve(file.parse().unwrap(), |name| env.get(name).cloned())
+    }
+
+    const REQUIRED: &str = r#"
+        [discord]
+        token = "token"
+        [database]
+        path = "sat_bot.json"
+        [n2yo]
+        keys = ["key"]
+    "#;
+
+    #[test]
+    fn reads_the_file_with_the_environment_taking_precedence() {
+        let config = resolve_with(
+            REQUIRED,
+            &[
+                ("N2YO_KEY", "a, b"),
+                ("NOTIFY_INTERVAL_MINUTES", "60"),
+                ("DEFAULT_MIN_MAX_ELEVATION", "12.5"),
+                ("NOTIFY_CRON", "0 6,18 * * *"),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(config.discord.token, "token");
+        assert_eq!(config.n2yo.keys, vec!["a", "b"]);
+        assert_eq!(config.notifications.interval_minutes, 60);
+        assert_eq!(config.defaults.min_max_elevation, 12.5);
+        assert!(config.notifications.cron.is_some());
+        assert_eq!(config.n2yo.timeout_secs, 30);
+    }
+
+    #[test]
+    fn reports_every_problem_at_once() {
+        let problems = resolve_with("[n2yo]\nkeys = [\"key\"]", &[("N2YO_TIMEOUT_SECS", "soon")])
+            .unwrap_err()
+            .to_string();
+
+        assert!(
+            problems.contains("discord.token is missing"),
+            "{}",
+            problems
+        );
+        assert!(
+            problems.contains("database.path is missing"),
+            "{}",
+            problems
+        );
+        assert!(problems.contains("N2YO_TIMEOUT_SECS"), "{}", problems);
+    }
+
+    #[test]
+    fn rejects_values_out_of_range() {
+        let problems = resolve_with(
+            REQUIRED,
+            &[
+                ("NOTIFY_INTERVAL_MINUTES", "1"),
+                ("N2YO_RETRY_ATTEMPTS", "0"),
+                ("DEFAULT_DAYS", "11"),
+                ("SSTV_CHECK_INTERVAL_MINUTES", "0"),
+                ("LAUNCH_CHECK_INTERVAL_MINUTES", "0"),
+            ],
+        )
+        .unwrap_err()
+        .to_string();
+
+        assert!(
+            problems.contains("notifications.interval_minutes must be between 5 and 1440, got 1"),
+            "{}",
+            problems
+        );
+        assert!(
+            problems.contains("n2yo.retry_attempts must be at least 1, got 0"),
+            "{}",
+            problems
+        );
+        assert!(problems.contains("defaults.days"), "{}", problems);
+        assert!(
+            problems.contains("sstv.check_interval_minutes must be at least 1, got 0"),
+            "{}",
+            problems
+        );
+        assert!(
+            problems.contains("launches.check_interval_minutes must be at least 1, got 0"),
+            "{}",
+            problems
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_settings() {
+        let problems = resolve_with(REQUIRED, &[("COMMAND_REGISTRATION", "everywhere")])
+            .unwrap_err()
+            .to_string();
+
+        assert!(
+            problems.contains("guild, global, or all-guilds"),
+            "{}",
+            problems
+        );
+        assert!(resolve_with(REQUIRED, &[("NOTIFY_CRON", "61 * * * *")]).is_err());
+    }
+
+    #[test]
+    fn does_not_require_n2yo_keys_in_mock_mode() {
+        let config = resolve_with(
+            "[discord]\ntoken = \"token\"\n[database]\npath = \"sat_bot.json\"",
+            &[("SATBOT_MOCK", "1")],
+        )
+        .unwrap();
+
+        assert!(config.features.mock);
+    }
+}
@@ -1,69 +1,508 @@
-use std::path::PathBuf;
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap, HashSet},
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime},
+};
 
+use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
-use tracing::info;
+use tokio::{
+    sync::{
+        mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender},
+        oneshot, RwLock,
+    },
+    task::JoinHandle,
+    time::{interval, sleep},
+};
+use tracing::{error, info, warn};
 
-use crate::util;
+use crate::{
+    astro::Lighting,
+    catalog::SatelliteMetadata,
+    config,
+    error::BotError,
+    groups::SatelliteGroups,
+    launches::LaunchSubscription,
+    n2yo::{PassKind, SatellitePass},
+    preferences::{self, GuildSettings, UserPreferences},
+    propagation::CachedTle,
+    rsvp::PassRsvp,
+    sstv::SstvEvent,
+    usage::UsageStats,
+};
+
+/// How long the persistence worker waits for further changes before writing.
+const WRITE_BEHIND_DELAY: Duration = Duration::from_millis(500);
 
 /// A JSON based database.
 pub struct Database {
     pub contents: DatabaseContents,
-    path: PathBuf,
+    /// Where the database is stored, or `None` if it is only kept in memory.
+    path: Option<PathBuf>,
+    /// The file as it was when last read or written, to tell when something else changes it.
+    synced: Arc<Mutex<Option<Synced>>>,
+    persistence: UnboundedSender<PersistenceMessage>,
+}
+
+/// The state of the database file when the bot last read or wrote it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Synced {
+    modified: Option<SystemTime>,
+    hash: u64,
+}
+
+/// A message sent to the persistence worker.
+pub enum PersistenceMessage {
+    /// The contents have changed and should be written eventually.
+    Dirty,
+    /// The contents should be written immediately, acknowledging once done.
+    Flush(oneshot::Sender<()>),
 }
 
 impl Database {
-    pub fn open() -> anyhow::Result<Self> {
+    pub fn open() -> anyhow::Result<(Self, UnboundedReceiver<PersistenceMessage>)> {
         info!("Opening database");
+        let (sender, receiver) = unbounded_channel();
+        let path = config::get().database.path.clone();
         let mut database = Self {
-            path: PathBuf::from(util::env("DATABASE_PATH")?),
-            contents: DatabaseContents {
-                locations: vec![],
-                watched_satellites: vec![],
-            },
+            path: Some(path.clone()),
+            contents: DatabaseContents::default(),
+            synced: Default::default(),
+            persistence: sender,
         };
 
-        if !database.path.exists() {
+        if !path.exists() {
             info!("Creating blank database");
             database.save()?;
         } else {
             database.load()?;
         }
 
-        Ok(database)
+        Ok((database, receiver))
+    }
+
+    /// A database that is never written to disk, for tests.
+    #[cfg(test)]
+    pub fn in_memory(contents: DatabaseContents) -> Self {
+        let (sender, _) = unbounded_channel();
+        Self {
+            contents,
+            path: None,
+            synced: Default::default(),
+            persistence: sender,
+        }
     }
 
     pub fn load(&mut self) -> anyhow::Result<()> {
-        let contents = std::fs::read_to_string(&self.path)?;
-        self.contents = serde_json::from_str(&contents)?;
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let contents = std::fs::read_to_string(path).map_err(|e| BotError::Database(e.into()))?;
+        self.contents =
+            serde_json::from_str(&contents).map_err(|e| BotError::Database(e.into()))?;
+        *self.synced.lock().unwrap() = Some(Synced::of(path, &contents));
         info!("Loading database from existing file");
         Ok(())
     }
 
+    /// Writes the contents to disk, unless the file has been changed by something else since it
+    /// was last read or written.
     pub fn save(&self) -> anyhow::Result<()> {
-        let contents = serde_json::to_string(&self.contents)?;
-        std::fs::write(&self.path, contents)?;
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let contents =
+            serde_json::to_string(&self.contents).map_err(|e| BotError::Database(e.into()))?;
+        write_file(path, &contents, &mut self.synced.lock().unwrap())?;
         info!("Saving database to file");
         Ok(())
     }
+
+    /// Marks the contents as changed, so that the persistence worker writes them to disk.
+    pub fn mark_dirty(&self) {
+        if self.path.is_none() {
+            return;
+        }
+
+        if self.persistence.send(PersistenceMessage::Dirty).is_err() {
+            error!("Persistence worker is not running, saving synchronously");
+            let _ = self.save();
+        }
+    }
 }
 
 impl Drop for Database {
     fn drop(&mut self) {
         info!("Dropping database");
-        self.save().unwrap();
+
+        if let Err(e) = self.save() {
+            error!("Failed to save database: {}", e);
+        }
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Spawns the task that writes the database to disk whenever it is marked dirty. Changes
+/// arriving in quick succession are batched into a single write.
+pub fn spawn_persistence_worker(
+    database: Arc<RwLock<Database>>,
+    mut receiver: UnboundedReceiver<PersistenceMessage>,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        while let Some(message) = receiver.recv().await {
+            let mut acknowledgements = Vec::new();
+
+            if let PersistenceMessage::Flush(ack) = message {
+                acknowledgements.push(ack);
+            } else {
+                sleep(WRITE_BEHIND_DELAY).await;
+            }
+
+            while let Ok(message) = receiver.try_recv() {
+                if let PersistenceMessage::Flush(ack) = message {
+                    acknowledgements.push(ack);
+                }
+            }
+
+            if let Err(e) = write(&database).await {
+                error!("Failed to save database: {}", e);
+            }
+
+            for ack in acknowledgements {
+                let _ = ack.send(());
+            }
+        }
+
+        info!("Persistence worker stopped");
+    })
+}
+
+/// Writes any pending changes to disk, waiting until the write has completed.
+pub async fn flush(database: &RwLock<Database>) {
+    info!("Flushing database");
+    let (sender, receiver) = oneshot::channel();
+    let sent = database
+        .read()
+        .await
+        .persistence
+        .send(PersistenceMessage::Flush(sender));
+
+    if sent.is_err() || receiver.await.is_err() {
+        if let Err(e) = write(database).await {
+            error!("Failed to flush database: {}", e);
+        }
+    }
+}
+
+async fn write(database: &RwLock<Database>) -> anyhow::Result<()> {
+    let (path, contents, synced, expected) = {
+        let database = database.read().await;
+        let Some(path) = database.path.clone() else {
+            return Ok(());
+        };
+        let expected = *database.synced.lock().unwrap();
+        (
+            path,
+            serde_json::to_string(&database.contents)?,
+            database.synced.clone(),
+            expected,
+        )
+    };
+
+    tokio::task::spawn_blocking(move || {
+        let mut synced = synced.lock().unwrap();
+
+        // Reloaded since the contents were copied, so they are out of date
+        if *synced != expected {
+            return Ok(());
+        }
+
+        write_file(&path, &contents, &mut synced)
+    })
+    .await??;
+    info!("Saving database to file");
+    Ok(())
+}
+
+impl Synced {
+    fn of(path: &Path, contents: &str) -> Self {
+        Self {
+            modified: modified(path),
+            hash: hash(contents),
+        }
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).ok()?.modified().ok()
+}
+
+fn hash(contents: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    contents.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// The contents of the database file if something other than the bot has changed it since the
+/// bot last read or wrote it.
+fn changed_on_disk(path: &Path, synced: &mut Option<Synced>) -> anyhow::Result<Option<String>> {
+    let Some(last) = synced.as_mut() else {
+        return Ok(None);
+    };
+
+    if !path.exists() || modified(path) == last.modified {
+        return Ok(None);
+    }
+
+    let contents = std::fs::read_to_string(path)?;
+
+    if hash(&contents) == last.hash {
+        // Touched, but not changed
+        last.modified = modified(path);
+        return Ok(None);
+    }
+
+    Ok(Some(contents))
+}
+
+/// Writes the database file, refusing to if something else has changed it so that hand edits are
+/// not overwritten before they are reloaded.
+fn write_file(path: &Path, contents: &str, synced: &mut Option<Synced>) -> anyhow::Result<()> {
+    if changed_on_disk(path, synced)
+        .map_err(BotError::Database)?
+        .is_some()
+    {
+        return Err(BotError::Database(anyhow::anyhow!(
+            "{} was changed by something else, so it is not being overwritten until it has been \
+             reloaded",
+            path.display()
+        ))
+        .into());
+    }
+
+    std::fs::write(path, contents).map_err(|e| BotError::Database(e.into()))?;
+    *synced = Some(Synced::of(path, contents));
+    Ok(())
+}
+
+/// Spawns the task that reloads the database whenever its file is changed by something else, such
+/// as an operator editing it by hand, checking every `DATABASE_RELOAD_SECONDS`. Changes the bot
+/// made that had not yet been written are lost. A file that cannot be read is left alone, and is
+/// not overwritten until it has been fixed.
+pub fn spawn_reload_task(
+    database: Arc<RwLock<Database>>,
+) -> anyhow::Result<Option<JoinHandle<()>>> {
+    let seconds = config::get().database.reload_seconds;
+
+    if seconds == 0 {
+        return Ok(None);
+    }
+
+    Ok(Some(tokio::spawn(async move {
+        let mut interval = interval(Duration::from_secs(seconds));
+        let mut unreadable = None;
+
+        loop {
+            interval.tick().await;
+
+            let (path, synced) = {
+                let database = database.read().await;
+                let Some(path) = database.path.clone() else {
+                    return;
+                };
+                (path, database.synced.clone())
+            };
+
+            let changed = changed_on_disk(&path, &mut synced.lock().unwrap());
+            let contents = match changed {
+                Ok(Some(contents)) => contents,
+                Ok(None) => continue,
+                Err(e) => {
+                    error!("Failed to check the database for changes: {}", e);
+                    continue;
+                }
+            };
+
+            let contents_hash = hash(&contents);
+
+            // Until the new state is recorded, writes are refused, so the file is left as it is
+            match serde_json::from_str::<DatabaseContents>(&contents) {
+                Ok(contents) => {
+                    warn!("{} was changed on disk, reloading it", path.display());
+                    database.write().await.contents = contents;
+                    *synced.lock().unwrap() = Some(Synced {
+                        modified: modified(&path),
+                        hash: contents_hash,
+                    });
+                    unreadable = None;
+                }
+                Err(e) if unreadable != Some(contents_hash) => {
+                    error!(
+                        "{} was changed on disk but cannot be read, so it is not being reloaded \
+                         or overwritten until it is fixed: {}",
+                        path.display(),
+                        e
+                    );
+                    unreadable = Some(contents_hash);
+                }
+                Err(_) => {}
+            }
+        }
+    })))
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct DatabaseContents {
     pub locations: Vec<Location>,
     pub watched_satellites: Vec<WatchedSatellite>,
+    #[serde(default)]
+    pub satellites: HashMap<SatelliteId, SatelliteMetadata>,
+    #[serde(default)]
+    pub tles: HashMap<SatelliteId, CachedTle>,
+    /// The TLE each cached TLE replaced, kept so that large changes between them can be spotted.
+    #[serde(default)]
+    pub previous_tles: HashMap<SatelliteId, CachedTle>,
+    #[serde(default)]
+    pub failed_deliveries: Vec<PendingDelivery>,
+    #[serde(default)]
+    pub usage: UsageStats,
+    #[serde(default)]
+    pub preferences: HashMap<Snowflake, UserPreferences>,
+    #[serde(default)]
+    pub guild_settings: HashMap<Snowflake, GuildSettings>,
+    /// Announced ISS SSTV events that have not yet ended.
+    #[serde(default)]
+    pub sstv_events: Vec<SstvEvent>,
+    #[serde(default)]
+    pub launch_subscriptions: Vec<LaunchSubscription>,
+    /// The NET of each upcoming launch when it was last checked, by Launch Library 2 ID.
+    #[serde(default)]
+    pub announced_launches: HashMap<String, i64>,
+    #[serde(default)]
+    pub logged_observations: Vec<LoggedObservation>,
+    #[serde(default)]
+    pub pass_rsvps: Vec<PassRsvp>,
+    #[serde(default)]
+    pub pass_threads: Vec<PassThread>,
+    /// The values each user last ran commands with, by kind of option, newest first, which
+    /// autocomplete suggests first.
+    #[serde(default)]
+    pub recent_choices: HashMap<Snowflake, HashMap<String, Vec<String>>>,
+    #[serde(default)]
+    pub groups: SatelliteGroups,
+    /// Channels whose notifications are muted, by channel.
+    #[serde(default)]
+    pub channel_mutes: HashMap<Snowflake, ChannelMute>,
+    /// Users whose watches are paused while they are away, by user.
+    #[serde(default)]
+    pub vacations: HashMap<Snowflake, Vacation>,
+    /// A hash of the commands last registered with Discord, and where they were registered.
+    #[serde(default)]
+    pub registered_commands: Option<u64>,
+    /// The guilds those commands are registered in, in `all-guilds` mode.
+    #[serde(default)]
+    pub registered_guilds: HashSet<Snowflake>,
 }
 
+impl DatabaseContents {
+    /// Caches a satellite's TLE, keeping the one it replaces if it has a different epoch.
+    pub fn store_tle(&mut self, satellite_id: SatelliteId, tle: CachedTle) {
+        if let Some(replaced) = self.tles.insert(satellite_id.clone(), tle.clone()) {
+            if replaced.epoch() != tle.epoch() {
+                self.previous_tles.insert(satellite_id, replaced);
+            }
+        }
+    }
+}
+
+/// A pass a user says they received or saw, logged with `/log-observation`.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-pub struct Snowflake(pub u64);
+pub struct LoggedObservation {
+    pub satellite_id: SatelliteId,
+    pub satellite: String,
+    pub observer: Snowflake,
+    pub guild: Option<Snowflake>,
+    pub channel: Snowflake,
+    /// The start and end of the pass, as unix timestamps. The end is only known when the pass was
+    /// one the bot notified of.
+    pub pass_start: i64,
+    pub pass_end: Option<i64>,
+    /// The location of the watch that notified of the pass, if it was one.
+    pub location: Option<LocationName>,
+    pub logged_at: i64,
+    pub notes: Option<String>,
+    /// An image of the observation, such as a decoded APT picture. Earlier versions kept the
+    /// image's URL under `image`, which Discord expires, so those are dropped.
+    #[serde(default, rename = "image_message")]
+    pub image: Option<ObservationImage>,
+}
+
+/// Where an image logged with an observation is kept: attached to the bot's reply to
+/// `/log-observation`. Discord's links to attachments expire, so a fresh one is fetched from the
+/// message whenever the image is shown.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ObservationImage {
+    pub channel: Snowflake,
+    pub message: Snowflake,
+    pub attachment: Snowflake,
+}
+
+/// A notification that could not be delivered, kept so that it can be retried.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PendingDelivery {
+    pub channel: Snowflake,
+    pub message: serde_json::Value,
+    pub attempts: usize,
+    /// When the last pass in the notification ends, as a unix timestamp, after which it is no
+    /// longer worth sending.
+    #[serde(default)]
+    pub end: Option<i64>,
+}
+
+/// A thread started on a notification, which is archived some time after its passes end.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PassThread {
+    pub thread: Snowflake,
+    /// When the last pass in the notification ends, as a unix timestamp.
+    pub end: i64,
+}
+
+/// A channel's notifications being held back, to be summarised in one message when the mute lifts.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChannelMute {
+    /// When the mute lifts, as a unix timestamp.
+    pub until: i64,
+    /// The Discord locale of whoever muted the channel, which the summary is written in.
+    pub locale: String,
+    /// The passes that would have been notified of while muted.
+    #[serde(default)]
+    pub held_passes: Vec<HeldPass>,
+}
+
+/// A user being away, during which none of their watches notify.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Vacation {
+    /// When the user's watches resume, as a unix timestamp.
+    pub until: i64,
+    /// The Discord locale of the user, which the reminder on their return is written in.
+    pub locale: String,
+}
 
+/// A pass a muted channel was not notified of.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HeldPass {
+    pub satellite: String,
+    pub location: LocationName,
+    /// The ID the pass can be looked up by, from [`crate::passes::pass_id`].
+    pub id: String,
+    pub pass: SatellitePass,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Snowflake(pub u64);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct SatelliteId(pub usize);
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -73,10 +512,104 @@ pub struct WatchedSatellite {
     pub location: LocationName,
     pub channel: Snowflake,
     pub watcher: Snowflake,
-    #[serde(rename = "locale")]
-    pub __legacy_locale: String,
+    /// The Discord locale of the watcher, which notifications are written in.
+    pub locale: String,
     pub min_max_elevation: f64,
     pub previous_notifications: Vec<(usize, usize)>,
+    #[serde(default = "default_days_ahead")]
+    pub days_ahead: usize,
+    /// Passes are held back until they start within this many hours, if set.
+    #[serde(default)]
+    pub notify_within_hours: Option<usize>,
+    /// Passes that have been found but not yet announced.
+    #[serde(default)]
+    pub pending_passes: Vec<SatellitePass>,
+    /// Why the watch has been disabled, if it has been.
+    #[serde(default)]
+    pub broken: Option<String>,
+    /// The guild the watch was created in, if known.
+    #[serde(default)]
+    pub guild: Option<Snowflake>,
+    /// The IANA timezone of the observer, which notifications also give local times in.
+    #[serde(default)]
+    pub timezone: Option<String>,
+    /// Whether only daytime or nighttime passes are notified.
+    #[serde(default)]
+    pub lighting: Lighting,
+    /// The lowest quality score, from 0 to 100, of passes that are notified.
+    #[serde(default)]
+    pub min_quality: Option<u8>,
+    /// Whether notifications include the satellite's primary transmitter from SatNOGS DB, for
+    /// satellites without bundled frequencies.
+    #[serde(default)]
+    pub show_transmitter: bool,
+    /// Whether the watcher has been warned that the satellite is about to reenter.
+    #[serde(default)]
+    pub reentry_alerted: bool,
+    /// The epoch of the last TLE the watcher was warned had gone stale.
+    #[serde(default)]
+    pub stale_tle_alerted: Option<i64>,
+    /// The epoch of the last TLE the watcher was told replaced one after a long gap or a
+    /// manoeuvre, or of the TLE cached when the watch was made.
+    #[serde(default)]
+    pub new_tle_alerted: Option<i64>,
+    /// Whether radio or visual passes are notified.
+    #[serde(default)]
+    pub kind: PassKind,
+    /// Only visual passes brighter than this magnitude are notified, if set.
+    #[serde(default)]
+    pub max_magnitude: Option<f64>,
+    /// Whether a thread is started on each notification, for reports of the passes in it.
+    #[serde(default)]
+    pub threads: bool,
+    /// The passes notified of in the last month, which can be looked up by ID with `/pass-info`.
+    #[serde(default)]
+    pub announced_passes: Vec<AnnouncedPass>,
+    /// Replaces the title of notifications, with placeholders from [`crate::templates`].
+    #[serde(default)]
+    pub title_template: Option<String>,
+    /// Replaces the description of notifications, with placeholders from [`crate::templates`].
+    #[serde(default)]
+    pub description_template: Option<String>,
+    /// Labels such as `weather` or `club-night`, so that related watches can be listed or paused
+    /// together.
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Whether notifications are held off until the watch is resumed.
+    #[serde(default)]
+    pub paused: bool,
+}
+
+/// A pass a watch notified of.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnnouncedPass {
+    /// The ID shown in the notification, from [`crate::passes::pass_id`].
+    pub id: String,
+    pub pass: SatellitePass,
+    pub announced_at: i64,
+}
+
+impl WatchedSatellite {
+    pub fn timezone(&self) -> Option<Tz> {
+        self.timezone
+            .as_deref()
+            .and_then(preferences::parse_timezone)
+    }
+
+    /// Whether this and another entry describe the same watch: the same kind of pass of the same
+    /// satellite from the same location, with the same minimum elevation, notified in the same
+    /// channel. Other settings and notification state are ignored.
+    pub fn is_same_watch(&self, other: &WatchedSatellite) -> bool {
+        self.satellite_id == other.satellite_id
+            && self.channel == other.channel
+            && self.location == other.location
+            && self.min_max_elevation == other.min_max_elevation
+            && self.kind == other.kind
+    }
+}
+
+fn default_days_ahead() -> usize {
+    1
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -89,4 +622,51 @@ pub struct Location {
     pub latitude: f64,
     pub longitude: f64,
     pub altitude: f64,
+    /// The guild the location was created in, if known.
+    #[serde(default)]
+    pub guild: Option<Snowflake>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::watch;
+
+    #[test]
+    fn refuses_to_overwrite_external_changes() {
+        let path = std::env::temp_dir().join(format!("sat-bot-{}.json", std::process::id()));
+        let mut synced = None;
+
+        write_file(&path, "{}", &mut synced).unwrap();
+        assert_eq!(changed_on_disk(&path, &mut synced).unwrap(), None);
+
+        std::fs::write(&path, r#"{"locations": []}"#).unwrap();
+        assert_eq!(
+            changed_on_disk(&path, &mut synced).unwrap().as_deref(),
+            Some(r#"{"locations": []}"#)
+        );
+        assert!(write_file(&path, "{}", &mut synced).is_err());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn tells_radio_and_visual_watches_apart() {
+        let mut visual = watch(1, 10);
+        visual.kind = PassKind::Visual;
+
+        assert!(!watch(1, 10).is_same_watch(&visual));
+    }
+
+    #[test]
+    fn tells_watches_apart_by_their_minimum_elevation() {
+        let mut other = watch(1, 10);
+        other.previous_notifications.push((1000, 1600));
+        other.tags.push("weather".to_string());
+        assert!(watch(1, 10).is_same_watch(&other));
+
+        other.min_max_elevation = 45.0;
+        assert!(!watch(1, 10).is_same_watch(&other));
+        assert!(!watch(1, 10).is_same_watch(&watch(1, 20)));
+    }
 }
@@ -0,0 +1,474 @@
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use serde_json::json;
+use serenity::http::Http;
+use tokio::{sync::RwLock, task::JoinHandle, time::interval};
+use tracing::{info, warn};
+
+use crate::{
+    celestrak::CelesTrakAPI,
+    config,
+    database::{Database, SatelliteId},
+    i18n::tr,
+    notify::Notifier,
+    propagation::{CachedTle, EARTH_RADIUS},
+    util,
+};
+
+/// The Earth's gravitational parameter, in cubic kilometres per second squared.
+const EARTH_MU: f64 = 398_600.441_8;
+/// The altitude below which a satellite is taken to have reentered, in kilometres.
+const REENTRY_ALTITUDE: f64 = 120.0;
+/// The perigee below which reentry is imminent however slowly the orbit is decaying, in
+/// kilometres.
+const IMMINENT_PERIGEE: f64 = 150.0;
+/// How often watched satellites are checked, in seconds.
+const CHECK_INTERVAL: u64 = 12 * 60 * 60;
+/// How far the mean motion of a new TLE can differ from what drag alone would give before the
+/// satellite is taken to have manoeuvred, in revolutions per day. This is around half a kilometre
+/// of altitude in low Earth orbit, well above the noise between successive TLEs.
+const MANOEUVRE_MEAN_MOTION: f64 = 0.002;
+
+/// The shape of an orbit and how quickly it is decaying, from a TLE.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OrbitSummary {
+    /// The lowest altitude of the orbit, in kilometres.
+    pub perigee: f64,
+    /// The highest altitude of the orbit, in kilometres.
+    pub apogee: f64,
+    /// A rough estimate of how many days until the satellite reenters, if its orbit is decaying.
+    /// This assumes the decay carries on at its current rate, when it speeds up as the orbit
+    /// lowers, so reentry is likely to be sooner.
+    pub days_to_reentry: Option<f64>,
+    /// When the TLE was measured, as a unix timestamp.
+    pub epoch: i64,
+}
+
+impl OrbitSummary {
+    pub fn from_tle(tle: &CachedTle) -> Option<Self> {
+        let mean_motion = tle.mean_motion()?;
+        let eccentricity = tle.eccentricity()?;
+        let semi_major_axis = semi_major_axis(mean_motion);
+        let rate = tle.mean_motion_rate()?;
+
+        let days_to_reentry = (rate > 0.0).then(|| {
+            let reentry_motion = mean_motion_at(EARTH_RADIUS + REENTRY_ALTITUDE);
+            ((reentry_motion - mean_motion) / rate).max(0.0)
+        });
+
+        Some(Self {
+            perigee: semi_major_axis * (1.0 - eccentricity) - EARTH_RADIUS,
+            apogee: semi_major_axis * (1.0 + eccentricity) - EARTH_RADIUS,
+            days_to_reentry,
+            epoch: tle.epoch()?,
+        })
+    }
+
+    /// Whether the satellite is expected to reenter within the given number of days.
+    pub fn reenters_within(&self, days: f64) -> bool {
+        self.perigee < IMMINENT_PERIGEE
+            || self
+                .days_to_reentry
+                .is_some_and(|remaining| remaining < days)
+    }
+
+    /// How many whole days old the TLE is.
+    pub fn age_days(&self) -> i64 {
+        (util::current_utc() - self.epoch) / (24 * 60 * 60)
+    }
+}
+
+/// A new TLE that differs from the one it replaced by enough to make passes predicted with the
+/// old one doubtful.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ElementJump {
+    pub previous: OrbitSummary,
+    pub current: OrbitSummary,
+    /// Whether the orbit changed by more than drag explains, as after a manoeuvre.
+    pub manoeuvred: bool,
+}
+
+impl ElementJump {
+    /// Compares a TLE with the one it replaced, returning the jump if the satellite manoeuvred
+    /// or the epochs are at least `gap_days` apart.
+    pub fn between(previous: &CachedTle, current: &CachedTle, gap_days: f64) -> Option<Self> {
+        let summaries = (
+            OrbitSummary::from_tle(previous)?,
+            OrbitSummary::from_tle(current)?,
+        );
+        let elapsed_days = (summaries.1.epoch - summaries.0.epoch) as f64 / 86400.0;
+        let expected_motion = previous.mean_motion()? + previous.mean_motion_rate()? * elapsed_days;
+        let manoeuvred = (current.mean_motion()? - expected_motion).abs() > MANOEUVRE_MEAN_MOTION;
+
+        (manoeuvred || elapsed_days >= gap_days).then_some(Self {
+            previous: summaries.0,
+            current: summaries.1,
+            manoeuvred,
+        })
+    }
+
+    /// How many whole days apart the two TLEs are.
+    pub fn gap_days(&self) -> i64 {
+        (self.current.epoch - self.previous.epoch) / (24 * 60 * 60)
+    }
+}
+
+/// The semi-major axis of an orbit with a mean motion in revolutions per day, in kilometres.
+fn semi_major_axis(mean_motion: f64) -> f64 {
+    let radians_per_second = mean_motion * std::f64::consts::TAU / 86400.0;
+    (EARTH_MU / radians_per_second.powi(2)).cbrt()
+}
+
+/// The mean motion of a circular orbit of a radius in kilometres, in revolutions per day.
+fn mean_motion_at(radius: f64) -> f64 {
+    (EARTH_MU / radius.powi(3)).sqrt() * 86400.0 / std::f64::consts::TAU
+}
+
+/// Spawns the task that periodically refreshes the TLEs of watched satellites and warns their
+/// watchers of upcoming reentries, stale elements, and large changes in elements.
+pub fn spawn_check_task(
+    http: Arc<Http>,
+    database: Arc<RwLock<Database>>,
+    celestrak_api: Arc<CelesTrakAPI>,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = interval(Duration::from_secs(CHECK_INTERVAL));
+
+        loop {
+            interval.tick().await;
+
+            let satellite_ids = {
+                let database = database.read().await;
+                let mut ids = database
+                    .contents
+                    .watched_satellites
+                    .iter()
+                    .filter(|watched_satellite| watched_satellite.broken.is_none())
+                    .map(|watched_satellite| watched_satellite.satellite_id.0)
+                    .collect::<Vec<_>>();
+                ids.sort_unstable();
+                ids.dedup();
+                ids
+            };
+
+            for satellite_id in satellite_ids {
+                let tle = match celestrak_api.get_tle(satellite_id).await {
+                    Ok(text) => CachedTle::parse(&text, util::current_utc()),
+                    Err(e) => Err(e),
+                };
+
+                match tle {
+                    Ok(tle) => {
+                        let mut database = database.write().await;
+                        database.contents.store_tle(SatelliteId(satellite_id), tle);
+                        database.mark_dirty();
+                    }
+                    Err(e) => warn!("Failed to refresh the TLE of {}: {}", satellite_id, e),
+                }
+            }
+
+            if let Err(e) = check_watches(&*http, &database).await {
+                warn!("Failed to check watched satellites for decay: {}", e);
+            }
+        }
+    })
+}
+
+/// Warns watchers, in the channels their notifications go to, of satellites expected to reenter
+/// soon, whose new TLE follows a long gap or a manoeuvre, or whose TLEs have gone stale. Each
+/// warning is only sent once per watch, and new or stale TLE warnings again only for a later TLE.
+/// A satellite that has been warned about reentering is expected to go stale, so is not warned
+/// about again.
+pub async fn check_watches(
+    notifier: &impl Notifier,
+    database: &RwLock<Database>,
+) -> anyhow::Result<()> {
+    let config = &config::get().decay;
+    let (alert_days, stale_days, gap_days) = (
+        config.alert_days,
+        config.tle_stale_days,
+        config.tle_gap_days,
+    );
+
+    let alerts = {
+        let database = database.read().await;
+        let summaries = database
+            .contents
+            .tles
+            .iter()
+            .filter_map(|(id, tle)| Some((id.0, OrbitSummary::from_tle(tle)?)))
+            .collect::<HashMap<_, _>>();
+        let jumps = database
+            .contents
+            .previous_tles
+            .iter()
+            .filter_map(|(id, previous)| {
+                let current = database.contents.tles.get(id)?;
+                Some((id.0, ElementJump::between(previous, current, gap_days)?))
+            })
+            .collect::<HashMap<_, _>>();
+
+        database
+            .contents
+            .watched_satellites
+            .iter()
+            .enumerate()
+            .filter(|(_, watched_satellite)| watched_satellite.broken.is_none())
+            .filter_map(|(index, watched_satellite)| {
+                let summary = summaries.get(&watched_satellite.satellite_id.0)?;
+                let locale = &watched_satellite.locale;
+                let satellite = watched_satellite.name.as_str();
+
+                if summary.reenters_within(alert_days) && !watched_satellite.reentry_alerted {
+                    Some((
+                        index,
+                        Alert::Reentry,
+                        tr!(locale, "decay-reentry-title", satellite = satellite),
+                        tr!(
+                            locale,
+                            "decay-reentry",
+                            satellite = satellite,
+                            perigee = summary.perigee.round(),
+                            apogee = summary.apogee.round(),
+                            days = summary.days_to_reentry.map_or(0.0, |days| days.round())
+                        ),
+                    ))
+                } else if let Some(jump) = jumps
+                    .get(&watched_satellite.satellite_id.0)
+                    .filter(|jump| watched_satellite.new_tle_alerted < Some(jump.current.epoch))
+                    .filter(|_| !watched_satellite.reentry_alerted)
+                {
+                    let (key, days) = if jump.manoeuvred {
+                        ("decay-manoeuvre", 0)
+                    } else {
+                        ("decay-new-tle", jump.gap_days())
+                    };
+
+                    Some((
+                        index,
+                        Alert::NewTle(jump.current.epoch),
+                        tr!(locale, "decay-new-tle-title", satellite = satellite),
+                        tr!(
+                            locale,
+                            key,
+                            satellite = satellite,
+                            days = days,
+                            perigee = jump.current.perigee.round(),
+                            apogee = jump.current.apogee.round(),
+                            previous_perigee = jump.previous.perigee.round(),
+                            previous_apogee = jump.previous.apogee.round()
+                        ),
+                    ))
+                } else if summary.age_days() >= stale_days
+                    && !watched_satellite.reentry_alerted
+                    && watched_satellite.stale_tle_alerted != Some(summary.epoch)
+                {
+                    Some((
+                        index,
+                        Alert::StaleTle(summary.epoch),
+                        tr!(locale, "decay-stale-title", satellite = satellite),
+                        tr!(
+                            locale,
+                            "decay-stale",
+                            satellite = satellite,
+                            days = summary.age_days()
+                        ),
+                    ))
+                } else {
+                    None
+                }
+            })
+            .map(|(index, alert, title, description)| {
+                (
+                    index,
+                    database.contents.watched_satellites[index].channel.0,
+                    alert,
+                    json!({ "embeds": [{ "title": title, "description": description }] }),
+                )
+            })
+            .collect::<Vec<_>>()
+    };
+
+    for (index, channel, alert, message) in alerts {
+        if let Err(e) = notifier.send_message(channel, &message).await {
+            warn!("Failed to send decay alert to {}: {:?}", channel, e);
+            continue;
+        }
+
+        info!("Sent {:?} alert to {}", alert, channel);
+        let mut database = database.write().await;
+
+        if let Some(watched_satellite) = database.contents.watched_satellites.get_mut(index) {
+            match alert {
+                Alert::Reentry => watched_satellite.reentry_alerted = true,
+                Alert::StaleTle(epoch) => watched_satellite.stale_tle_alerted = Some(epoch),
+                Alert::NewTle(epoch) => watched_satellite.new_tle_alerted = Some(epoch),
+            }
+        }
+
+        database.mark_dirty();
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Alert {
+    Reentry,
+    /// The TLE with this epoch has gone stale.
+    StaleTle(i64),
+    /// The TLE with this epoch followed a long gap or a manoeuvre.
+    NewTle(i64),
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use super::*;
+    use crate::{
+        database::DatabaseContents,
+        testing::{watch, FakeNotifier},
+    };
+
+    /// The ISS, in a healthy orbit.
+    const ISS_TLE: (&str, &str) = (
+        "1 25544U 98067A   23320.54791667  .00016717  00000-0  30164-3 0  9990",
+        "2 25544  51.6416 339.7760 0001038  88.0434 272.0759 15.49815322425140",
+    );
+    /// A satellite days from reentry, with a perigee around 160 km and decaying quickly.
+    const DECAYING_TLE: (&str, &str) = (
+        "1 99999U 20001A   23320.50000000  .05000000  00000-0  30000-3 0  9990",
+        "2 99999  51.6000 100.0000 0005000  90.0000 270.0000 16.20000000 10000",
+    );
+
+    fn tle((line1, line2): (&str, &str)) -> CachedTle {
+        CachedTle {
+            line1: line1.to_string(),
+            line2: line2.to_string(),
+            fetched_at: 0,
+        }
+    }
+
+    /// The ISS TLE, as if measured some days earlier with a different mean motion.
+    fn earlier_iss_tle(days: f64, mean_motion: f64) -> CachedTle {
+        let current = tle(ISS_TLE);
+        CachedTle {
+            line1: format!(
+                "{}{:012.8}{}",
+                &current.line1[..20],
+                320.54791667 - days,
+                &current.line1[32..]
+            ),
+            line2: format!(
+                "{}{:11.8}{}",
+                &current.line2[..52],
+                mean_motion,
+                &current.line2[63..]
+            ),
+            fetched_at: 0,
+        }
+    }
+
+    #[test]
+    fn summarises_orbits() {
+        let iss = OrbitSummary::from_tle(&tle(ISS_TLE)).unwrap();
+
+        assert!((410.0..425.0).contains(&iss.perigee), "{:?}", iss);
+        assert!((415.0..430.0).contains(&iss.apogee), "{:?}", iss);
+        // Day 320.54791667 of 2023 is 13:09 UTC on the 16th of November
+        assert_eq!(iss.epoch, 1_700_140_140);
+        assert!(!iss.reenters_within(30.0));
+
+        let decaying = OrbitSummary::from_tle(&tle(DECAYING_TLE)).unwrap();
+
+        assert!(decaying.days_to_reentry.unwrap() < 30.0, "{:?}", decaying);
+        assert!(decaying.reenters_within(30.0));
+    }
+
+    #[test]
+    fn spots_element_jumps() {
+        let current = tle(ISS_TLE);
+        // The mean motion one day earlier, allowing for drag
+        let drag_only = 15.49815322 - 0.00033434;
+
+        assert_eq!(
+            ElementJump::between(&earlier_iss_tle(1.0, drag_only), &current, 3.0),
+            None
+        );
+
+        let gap = ElementJump::between(&earlier_iss_tle(5.0, 15.49648152), &current, 3.0).unwrap();
+        assert!(!gap.manoeuvred);
+        assert_eq!(gap.gap_days(), 5);
+
+        // A reboost of a couple of kilometres
+        let reboost = ElementJump::between(&earlier_iss_tle(1.0, 15.50500000), &current, 3.0);
+        assert!(reboost.is_some_and(|jump| jump.manoeuvred), "{:?}", reboost);
+    }
+
+    #[tokio::test]
+    async fn alerts_each_watch_once() {
+        let notifier = FakeNotifier::default();
+        let database = RwLock::new(Database::in_memory(DatabaseContents {
+            watched_satellites: vec![watch(25544, 10), watch(99999, 20)],
+            tles: HashMap::from([
+                (SatelliteId(25544), tle(ISS_TLE)),
+                (SatelliteId(99999), tle(DECAYING_TLE)),
+            ]),
+            ..Default::default()
+        }));
+
+        check_watches(&notifier, &database).await.unwrap();
+        check_watches(&notifier, &database).await.unwrap();
+
+        let sent = notifier.sent.lock().unwrap();
+        let titles = sent
+            .iter()
+            .map(|(channel, message)| (*channel, message["embeds"][0]["title"].clone()))
+            .collect::<Vec<_>>();
+
+        // Both test TLEs are long out of date, but reentry is the more pressing warning
+        assert_eq!(
+            titles,
+            vec![
+                (
+                    10,
+                    Value::from("SAT 25544 has out of date orbital elements")
+                ),
+                (20, Value::from("SAT 99999 is expected to reenter soon")),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn alerts_of_new_elements_once() {
+        let notifier = FakeNotifier::default();
+        let mut contents = DatabaseContents {
+            watched_satellites: vec![watch(25544, 10)],
+            ..Default::default()
+        };
+        contents.store_tle(SatelliteId(25544), earlier_iss_tle(5.0, 15.49648152));
+        contents.store_tle(SatelliteId(25544), tle(ISS_TLE));
+        // Refetching the same elements keeps what they replaced
+        contents.store_tle(SatelliteId(25544), tle(ISS_TLE));
+        let database = RwLock::new(Database::in_memory(contents));
+
+        check_watches(&notifier, &database).await.unwrap();
+        check_watches(&notifier, &database).await.unwrap();
+
+        let sent = notifier.sent.lock().unwrap();
+        let titles = sent
+            .iter()
+            .map(|(_, message)| message["embeds"][0]["title"].clone())
+            .collect::<Vec<_>>();
+
+        // The new TLE is itself out of date by now, which is warned about separately
+        assert_eq!(
+            titles,
+            vec![
+                Value::from("SAT 25544 has new orbital elements"),
+                Value::from("SAT 25544 has out of date orbital elements"),
+            ]
+        );
+    }
+}
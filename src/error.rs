@@ -0,0 +1,200 @@
+use std::{
+    collections::HashMap,
+    fmt::{Display, Formatter},
+    sync::{Mutex, OnceLock},
+};
+
+use serenity::http::Http;
+
+use crate::{config, i18n::tr, util};
+
+/// How long the same error is kept from being posted to the ops channel again, in seconds.
+const OPS_REPEAT_SECONDS: i64 = 15 * 60;
+/// The most characters of an error's details posted to the ops channel, which with the rest of
+/// the message stays within Discord's limit of 2000.
+const MAX_OPS_DETAILS: usize = 1800;
+
+/// An error with enough context to decide how it is reported. Commands return these wrapped in
+/// [`anyhow::Error`], and anything that is not one is treated as an unexpected internal error.
+#[derive(Debug)]
+pub enum BotError {
+    /// The user asked for something that cannot be done, such as an unknown location.
+    User(String),
+    /// There is no satellite with the given NORAD ID.
+    UnknownSatellite(usize),
+    /// N2YO could not be reached or refused the request.
+    N2yo(anyhow::Error),
+    /// The database could not be read or written.
+    Database(anyhow::Error),
+    /// Discord rejected a request.
+    Discord(serenity::Error),
+}
+
+impl BotError {
+    pub fn user(message: impl Into<String>) -> Self {
+        Self::User(message.into())
+    }
+}
+
+impl Display for BotError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BotError::User(message) => write!(f, "{}", message),
+            BotError::UnknownSatellite(satellite_id) => {
+                write!(f, "no satellite with NORAD ID {}", satellite_id)
+            }
+            BotError::N2yo(e) => write!(f, "N2YO error: {}", e),
+            BotError::Database(e) => write!(f, "database error: {}", e),
+            BotError::Discord(e) => write!(f, "Discord error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for BotError {}
+
+impl From<serenity::Error> for BotError {
+    fn from(e: serenity::Error) -> Self {
+        Self::Discord(e)
+    }
+}
+
+/// How an error that ended a command is reported.
+pub struct Report {
+    /// What the user is told.
+    pub message: String,
+    /// Whether only the user who ran the command sees the message.
+    pub ephemeral: bool,
+    /// Whether the error is unexpected, and so logged in full rather than as a single line.
+    pub unexpected: bool,
+}
+
+impl Report {
+    /// Describes an error in the given Discord locale.
+    pub fn new(error: &anyhow::Error, locale: &str) -> Self {
+        let bot_error = error.downcast_ref::<BotError>();
+        let discord_error = error.downcast_ref::<serenity::Error>();
+
+        match (bot_error, discord_error) {
+            (Some(BotError::User(message)), _) => Self {
+                message: message.clone(),
+                ephemeral: true,
+                unexpected: false,
+            },
+            (Some(BotError::UnknownSatellite(satellite_id)), _) => Self {
+                message: tr!(
+                    locale,
+                    "error-no-such-satellite",
+                    satellite_id = *satellite_id
+                ),
+                ephemeral: true,
+                unexpected: false,
+            },
+            (Some(BotError::N2yo(e)), _) => Self {
+                message: tr!(locale, "error-n2yo", details = e.to_string()),
+                ephemeral: true,
+                unexpected: false,
+            },
+            (Some(BotError::Database(_)), _) => Self {
+                message: tr!(locale, "error-database"),
+                ephemeral: false,
+                unexpected: true,
+            },
+            (Some(BotError::Discord(e)), _) | (None, Some(e)) => Self {
+                message: tr!(locale, "error-discord", details = e.to_string()),
+                ephemeral: false,
+                unexpected: true,
+            },
+            (None, None) => Self {
+                message: tr!(locale, "error-unknown", details = error.to_string()),
+                ephemeral: false,
+                unexpected: true,
+            },
+        }
+    }
+}
+
+/// Posts an error the operator should know about to the ops channel, if one is configured, with
+/// its causes and the start of its backtrace when one was captured (with `RUST_BACKTRACE=1`). The
+/// same error is posted at most once every 15 minutes, so that an outage does not flood the
+/// channel.
+pub async fn report_to_ops_channel(http: &Http, context: &str, error: &anyhow::Error) {
+    if util::ops_channel().is_none() || !not_recently_reported(&format!("{}: {}", context, error)) {
+        return;
+    }
+
+    util::post_to_ops_channel(http, &ops_message(context, error)).await;
+}
+
+/// Whether a problem summarised by `summary` has not been posted to the ops channel in the last
+/// 15 minutes. If not, it is remembered as posted now.
+pub fn not_recently_reported(summary: &str) -> bool {
+    static LAST_POSTED: OnceLock<Mutex<HashMap<String, i64>>> = OnceLock::new();
+
+    let now = util::current_utc();
+    let mut last_posted = LAST_POSTED.get_or_init(Default::default).lock().unwrap();
+    last_posted.retain(|_, posted_at| now - *posted_at < OPS_REPEAT_SECONDS);
+
+    if last_posted.contains_key(summary) {
+        return false;
+    }
+
+    last_posted.insert(summary.to_string(), now);
+    true
+}
+
+/// Starts sending errors to Sentry when `SENTRY_DSN` is set, tagged with `SENTRY_ENVIRONMENT`.
+/// Anything still queued is sent when the returned guard is dropped, so it must be held until the
+/// bot exits.
+pub fn init_reporting() -> anyhow::Result<Option<sentry::ClientInitGuard>> {
+    let config = &config::get().sentry;
+    let Some(dsn) = &config.dsn else {
+        return Ok(None);
+    };
+    let dsn = dsn
+        .parse::<sentry::types::Dsn>()
+        .map_err(|e| anyhow::anyhow!("invalid SENTRY_DSN: {}", e))?;
+
+    Ok(Some(sentry::init(sentry::ClientOptions {
+        dsn: Some(dsn),
+        release: sentry::release_name!(),
+        environment: config.environment.clone().map(Into::into),
+        ..Default::default()
+    })))
+}
+
+/// Sends an error to Sentry with tags saying where it happened, such as the command and guild. This
+/// does nothing unless reporting was started by [`init_reporting`].
+pub fn capture(error: &anyhow::Error, tags: &[(&str, String)]) {
+    sentry::with_scope(
+        |scope| {
+            for (key, value) in tags {
+                scope.set_tag(key, value);
+            }
+        },
+        || sentry::integrations::anyhow::capture_anyhow(error),
+    );
+}
+
+/// Describes an error for the ops channel.
+fn ops_message(context: &str, error: &anyhow::Error) -> String {
+    format!(
+        "**{}**\n```\n{}\n```",
+        context,
+        util::truncate(&format!("{:?}", error), MAX_OPS_DETAILS)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncates_long_errors_for_the_ops_channel() {
+        let error = anyhow::anyhow!("x".repeat(5000)).context("failed to notify");
+        let message = ops_message("Notification run failed", &error);
+
+        assert!(message.starts_with("**Notification run failed**\n```\nfailed to notify"));
+        assert!(message.ends_with("…\n```"));
+        assert!(message.chars().count() <= 2000);
+    }
+}
@@ -0,0 +1,150 @@
+use poise::{serenity_prelude::Interaction, Event, FrameworkContext};
+use serenity::prelude::Context;
+use tracing::info;
+
+use crate::{
+    config,
+    database::Snowflake,
+    registration::{self, RegistrationMode},
+    rsvp, satcat, tracking, ApplicationContext,
+};
+
+/// Registers commands in newly joined guilds, handles the buttons on notifications, and removes
+/// database entries that reference channels, guilds, or members that no longer exist.
+pub async fn handle_event(
+    ctx: &Context,
+    event: &Event<'_>,
+    framework: FrameworkContext<'_, ApplicationContext, anyhow::Error>,
+    data: &ApplicationContext,
+) -> anyhow::Result<()> {
+    match event {
+        Event::Ready { data_about_bot } => {
+            info!(
+                "Shard {} connected as {} in {} guild(s)",
+                shard_label(ctx),
+                data_about_bot.user.name,
+                data_about_bot.guilds.len()
+            );
+        }
+        Event::Resume { .. } => info!("Shard {} resumed", shard_label(ctx)),
+        Event::GuildCreate { guild, .. }
+            if config::get().discord.command_registration == RegistrationMode::AllGuilds =>
+        {
+            registration::register_in_new_guild(
+                &ctx.http,
+                &data.database,
+                &framework.options().commands,
+                guild.id,
+            )
+            .await?;
+        }
+        Event::InteractionCreate {
+            interaction: Interaction::MessageComponent(component),
+        } if component
+            .data
+            .custom_id
+            .starts_with(tracking::BUTTON_PREFIX) =>
+        {
+            tracking::start(ctx.http.clone(), data, component).await?;
+        }
+        Event::InteractionCreate {
+            interaction: Interaction::MessageComponent(component),
+        } if component.data.custom_id.starts_with(rsvp::BUTTON_PREFIX) => {
+            rsvp::toggle(ctx.http.clone(), data, component).await?;
+        }
+        Event::InteractionCreate {
+            interaction: Interaction::MessageComponent(component),
+        } if component.data.custom_id.starts_with(satcat::BUTTON_PREFIX) => {
+            satcat::handle_button(ctx.http.clone(), data, component).await?;
+        }
+        Event::ChannelDelete { channel } => {
+            let channel = Snowflake(channel.id.0);
+            let mut database = data.database.write().await;
+            let before = database.contents.watched_satellites.len();
+            database
+                .contents
+                .watched_satellites
+                .retain(|watched_satellite| watched_satellite.channel != channel);
+            database
+                .contents
+                .failed_deliveries
+                .retain(|delivery| delivery.channel != channel);
+            database
+                .contents
+                .launch_subscriptions
+                .retain(|subscription| subscription.channel != channel);
+            info!(
+                "Channel {} deleted, removed {} watch(es)",
+                channel.0,
+                before - database.contents.watched_satellites.len()
+            );
+            database.mark_dirty();
+        }
+        Event::GuildDelete { incomplete, .. } if !incomplete.unavailable => {
+            let guild = Some(Snowflake(incomplete.id.0));
+            let mut database = data.database.write().await;
+            database
+                .contents
+                .watched_satellites
+                .retain(|watched_satellite| watched_satellite.guild != guild);
+            database
+                .contents
+                .locations
+                .retain(|location| location.guild != guild);
+            database
+                .contents
+                .launch_subscriptions
+                .retain(|subscription| subscription.guild != guild);
+            database
+                .contents
+                .logged_observations
+                .retain(|observation| observation.guild != guild);
+            // Discord removes the commands along with the bot
+            database
+                .contents
+                .registered_guilds
+                .remove(&Snowflake(incomplete.id.0));
+            info!(
+                "Removed from guild {}, removed its watches, locations, launch subscriptions and \
+                 observations",
+                incomplete.id.0
+            );
+            database.mark_dirty();
+        }
+        Event::GuildMemberRemoval { guild_id, user, .. } => {
+            let guild = Some(Snowflake(guild_id.0));
+            let member = Snowflake(user.id.0);
+            let mut database = data.database.write().await;
+            database
+                .contents
+                .watched_satellites
+                .retain(|watched_satellite| {
+                    watched_satellite.guild != guild || watched_satellite.watcher != member
+                });
+
+            // Locations still used by other members' watches are kept
+            let contents = &mut database.contents;
+            let watched_satellites = &contents.watched_satellites;
+            contents.locations.retain(|location| {
+                location.guild != guild
+                    || location.creator != member
+                    || watched_satellites
+                        .iter()
+                        .any(|watched_satellite| watched_satellite.location == location.name)
+            });
+
+            info!(
+                "Member {} left guild {}, removed their watches and locations",
+                user.id.0, guild_id.0
+            );
+            database.mark_dirty();
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+fn shard_label(ctx: &Context) -> String {
+    format!("{}/{}", ctx.shard_id, ctx.cache.shard_count())
+}
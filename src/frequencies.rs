@@ -0,0 +1,114 @@
+/// A radio transmitter carried by a satellite.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transmitter {
+    pub description: &'static str,
+    /// The downlink frequency, in megahertz.
+    pub downlink: f64,
+    /// The uplink frequency of a transponder or repeater, in megahertz.
+    pub uplink: Option<f64>,
+    pub mode: &'static str,
+    /// The CTCSS tone needed to open a repeater, in hertz.
+    pub tone: Option<f64>,
+}
+
+/// The transmitters of a satellite that is commonly listened to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KnownSatellite {
+    pub id: usize,
+    pub name: &'static str,
+    /// The satellite's transmitters, most useful first.
+    pub transmitters: &'static [Transmitter],
+}
+
+impl KnownSatellite {
+    /// The transmitter most people listen to.
+    pub fn primary(&self) -> &Transmitter {
+        &self.transmitters[0]
+    }
+}
+
+const fn downlink(description: &'static str, downlink: f64, mode: &'static str) -> Transmitter {
+    Transmitter {
+        description,
+        downlink,
+        uplink: None,
+        mode,
+        tone: None,
+    }
+}
+
+const fn repeater(downlink: f64, uplink: f64, tone: f64) -> Transmitter {
+    Transmitter {
+        description: "FM repeater",
+        downlink,
+        uplink: Some(uplink),
+        mode: "FM",
+        tone: Some(tone),
+    }
+}
+
+/// Weather satellites and amateur satellites that are commonly listened to. Frequencies change
+/// rarely enough that bundling them is simpler than looking them up.
+pub const KNOWN_SATELLITES: &[KnownSatellite] = &[
+    KnownSatellite {
+        id: 25338,
+        name: "NOAA 15",
+        transmitters: &[downlink("APT weather images", 137.620, "APT")],
+    },
+    KnownSatellite {
+        id: 28654,
+        name: "NOAA 18",
+        transmitters: &[downlink("APT weather images", 137.9125, "APT")],
+    },
+    KnownSatellite {
+        id: 33591,
+        name: "NOAA 19",
+        transmitters: &[downlink("APT weather images", 137.100, "APT")],
+    },
+    KnownSatellite {
+        id: 57166,
+        name: "METEOR-M2 3",
+        transmitters: &[downlink("LRPT weather images", 137.900, "LRPT (72k)")],
+    },
+    KnownSatellite {
+        id: 59051,
+        name: "METEOR-M2 4",
+        transmitters: &[downlink("LRPT weather images", 137.900, "LRPT (72k)")],
+    },
+    KnownSatellite {
+        id: 25544,
+        name: "ISS",
+        transmitters: &[
+            downlink("Voice and SSTV", 145.800, "FM"),
+            downlink("APRS digipeater", 145.825, "AFSK 1200 baud"),
+            repeater(437.800, 145.990, 67.0),
+        ],
+    },
+    KnownSatellite {
+        id: 27607,
+        name: "SO-50",
+        transmitters: &[repeater(436.795, 145.850, 67.0)],
+    },
+    KnownSatellite {
+        id: 43017,
+        name: "AO-91",
+        transmitters: &[repeater(145.960, 435.250, 67.0)],
+    },
+    KnownSatellite {
+        id: 43678,
+        name: "PO-101",
+        transmitters: &[repeater(145.900, 437.500, 141.3)],
+    },
+];
+
+/// The bundled transmitters of a satellite, if it is one that is commonly listened to.
+pub fn lookup(satellite_id: usize) -> Option<&'static KnownSatellite> {
+    KNOWN_SATELLITES
+        .iter()
+        .find(|satellite| satellite.id == satellite_id)
+}
+
+/// Writes a frequency given in megahertz.
+pub fn format_frequency(megahertz: f64) -> String {
+    format!("{:.3} MHz", megahertz)
+}
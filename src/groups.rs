@@ -0,0 +1,312 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    database::{Database, SatelliteId, Snowflake},
+    error::BotError,
+    i18n::tr,
+    preferences::{GuildSettings, UserPreferences},
+};
+
+/// The group `/get-upcoming-noaa-passes` lists the passes of.
+pub const WEATHER: &str = "weather";
+/// The group `/get-upcoming-meteor-passes` lists the passes of.
+pub const METEOR: &str = "meteor";
+
+/// The name that stands for the user's favorite satellites.
+pub const FAVORITES: &str = "favorites";
+
+/// The most satellites looked up at once, as Discord allows ten embeds in a message.
+pub const MAX_SATELLITES: usize = 10;
+/// The longest name an alias can have.
+pub const MAX_ALIAS_LENGTH: usize = 32;
+
+/// Satellites commonly looked up together, by the name they are given in commands, in lowercase.
+/// They are kept in the database so that operators can change them as satellites fail or launch.
+/// A group set to `null` is deleted, even if it is bundled.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct SatelliteGroups(pub BTreeMap<String, Option<Vec<SatelliteId>>>);
+
+impl Default for SatelliteGroups {
+    fn default() -> Self {
+        let group = |name: &str, ids: &[usize]| {
+            (
+                name.to_string(),
+                Some(ids.iter().copied().map(SatelliteId).collect()),
+            )
+        };
+
+        Self(BTreeMap::from([
+            // NOAA 15, 18, and 19
+            group("noaa", &[25338, 28654, 33591]),
+            // NOAA 15, 18, and 19, and Meteor-M 2-3 and 2-4
+            group(WEATHER, &[25338, 28654, 33591, 57166, 59051]),
+            // Meteor-M 2-3 and 2-4, which still send LRPT images
+            group(METEOR, &[57166, 59051]),
+            // ISS, SO-50, AO-91, and PO-101, FM repeaters that can be heard with a handheld radio
+            group("fm", &[25544, 27607, 43017, 43678]),
+        ]))
+    }
+}
+
+impl SatelliteGroups {
+    /// The NORAD IDs in a group, ignoring case. Groups missing from the database, such as those
+    /// bundled since it was made, are their defaults.
+    pub fn get(&self, name: &str) -> Option<Vec<usize>> {
+        let name = name.trim().to_lowercase();
+        let ids = match self.0.get(&name) {
+            Some(ids) => ids.clone(),
+            None => Self::default().0.remove(&name).flatten(),
+        };
+
+        ids.map(|ids| ids.iter().map(|id| id.0).collect())
+    }
+}
+
+/// Reads a group name, `favorites`, or a comma separated list of NORAD IDs and the server's
+/// aliases, such as `25338, noaa19`, into NORAD IDs, without duplicates.
+pub fn resolve(
+    database: &Database,
+    guild: Option<&Snowflake>,
+    user: &Snowflake,
+    locale: &str,
+    text: &str,
+) -> anyhow::Result<Vec<usize>> {
+    let text = text.trim();
+
+    if let Some(mut ids) = database.contents.groups.get(text) {
+        ids.truncate(MAX_SATELLITES);
+        return Ok(ids);
+    }
+
+    if text.eq_ignore_ascii_case(FAVORITES) {
+        let favorites = UserPreferences::for_user(database, user).favorites;
+
+        if favorites.is_empty() {
+            return Err(BotError::user(tr!(locale, "error-no-favorites")).into());
+        }
+
+        return Ok(favorites.into_iter().map(|id| id.0).collect());
+    }
+
+    let settings = GuildSettings::for_guild(database, guild);
+    let mut ids = Vec::new();
+
+    for part in text
+        .split(',')
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+    {
+        let part_ids = match (part.parse::<usize>(), settings.alias(part)) {
+            (Ok(id), _) => vec![id],
+            (Err(_), Some(alias)) => alias.iter().map(|id| id.0).collect(),
+            (Err(_), None) => {
+                return Err(BotError::user(tr!(
+                    locale,
+                    "error-invalid-satellite",
+                    satellite = part,
+                    groups = names(database, &settings)
+                ))
+                .into())
+            }
+        };
+
+        for id in part_ids {
+            if !ids.contains(&id) {
+                ids.push(id);
+            }
+        }
+    }
+
+    if ids.is_empty() {
+        return Err(BotError::user(tr!(locale, "error-no-satellites")).into());
+    }
+
+    if ids.len() > MAX_SATELLITES {
+        return Err(BotError::user(tr!(
+            locale,
+            "error-too-many-satellites",
+            max = MAX_SATELLITES
+        ))
+        .into());
+    }
+
+    Ok(ids)
+}
+
+/// Reads a NORAD ID, or one of the server's aliases that stands for a single satellite.
+pub fn resolve_one(
+    database: &Database,
+    guild: Option<&Snowflake>,
+    locale: &str,
+    text: &str,
+) -> anyhow::Result<usize> {
+    if let Ok(id) = text.trim().parse::<usize>() {
+        return Ok(id);
+    }
+
+    match GuildSettings::for_guild(database, guild).alias(text) {
+        Some([id]) => Ok(id.0),
+        Some(_) => {
+            Err(BotError::user(tr!(locale, "error-alias-is-group", alias = text.trim())).into())
+        }
+        None => Err(BotError::user(tr!(
+            locale,
+            "error-invalid-satellite-id",
+            satellite = text.trim()
+        ))
+        .into()),
+    }
+}
+
+/// Checks a name can be given to an alias, returning it in lowercase. Names cannot look like NORAD
+/// IDs or lists of them, or hide a group or `favorites`.
+pub fn check_alias_name(database: &Database, locale: &str, name: &str) -> anyhow::Result<String> {
+    let name = name.trim().to_lowercase();
+
+    if name.is_empty()
+        || name.chars().count() > MAX_ALIAS_LENGTH
+        || name.contains(',')
+        || name.parse::<usize>().is_ok()
+    {
+        return Err(
+            BotError::user(tr!(locale, "error-invalid-alias", max = MAX_ALIAS_LENGTH)).into(),
+        );
+    }
+
+    if database.contents.groups.get(&name).is_some() || name == FAVORITES {
+        return Err(BotError::user(tr!(locale, "error-reserved-alias", alias = name)).into());
+    }
+
+    Ok(name)
+}
+
+/// NORAD IDs separated by commas, as they are given in commands.
+pub fn list(ids: &[SatelliteId]) -> String {
+    ids.iter()
+        .map(|id| id.0.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// The names of the groups, `favorites`, and the server's aliases, for listing in help and errors.
+pub fn names(database: &Database, settings: &GuildSettings) -> String {
+    let mut groups = SatelliteGroups::default().0;
+    groups.extend(database.contents.groups.0.clone());
+
+    groups
+        .iter()
+        .filter(|(_, ids)| ids.is_some())
+        .map(|(name, _)| name.as_str())
+        .chain([FAVORITES])
+        .chain(settings.aliases.keys().map(String::as_str))
+        .map(|name| format!("`{}`", name))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeMap, HashMap};
+
+    use super::*;
+    use crate::database::DatabaseContents;
+
+    fn database() -> Database {
+        Database::in_memory(DatabaseContents {
+            preferences: HashMap::from([(
+                Snowflake(1),
+                UserPreferences {
+                    favorites: vec![SatelliteId(43017), SatelliteId(25544)],
+                    ..Default::default()
+                },
+            )]),
+            guild_settings: HashMap::from([(
+                Snowflake(10),
+                GuildSettings {
+                    aliases: BTreeMap::from([
+                        ("noaa19".to_string(), vec![SatelliteId(33591)]),
+                        (
+                            "weather birds".to_string(),
+                            vec![SatelliteId(33591), SatelliteId(40069)],
+                        ),
+                    ]),
+                    ..Default::default()
+                },
+            )]),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn resolves_groups_and_lists() {
+        let database = database();
+        let resolve = |text| {
+            resolve(
+                &database,
+                Some(&Snowflake(10)),
+                &Snowflake(1),
+                "en-GB",
+                text,
+            )
+        };
+
+        assert_eq!(resolve("NOAA").unwrap(), vec![25338, 28654, 33591]);
+        assert_eq!(resolve("fm").unwrap(), vec![25544, 27607, 43017, 43678]);
+        assert_eq!(resolve("favorites").unwrap(), vec![43017, 25544]);
+        assert_eq!(resolve("25544, 33591,25544,").unwrap(), vec![25544, 33591]);
+        assert_eq!(
+            resolve("25544, Weather Birds, noaa19").unwrap(),
+            vec![25544, 33591, 40069]
+        );
+        assert!(resolve("25544, iss").is_err());
+        assert!(resolve(" , ").is_err());
+        // Other users have no favorites, and other servers no aliases
+        assert!(super::resolve(&database, None, &Snowflake(2), "en-GB", "favorites").is_err());
+        assert!(super::resolve(&database, None, &Snowflake(1), "en-GB", "noaa19").is_err());
+    }
+
+    #[test]
+    fn lets_operators_change_and_delete_groups() {
+        let mut database = database();
+        database.contents.groups = SatelliteGroups(BTreeMap::from([
+            ("fm".to_string(), None),
+            ("noaa".to_string(), Some(vec![SatelliteId(33591)])),
+        ]));
+        let resolve = |text| resolve(&database, None, &Snowflake(1), "en-GB", text);
+
+        assert!(resolve("fm").is_err());
+        assert_eq!(resolve("noaa").unwrap(), vec![33591]);
+        assert_eq!(resolve("meteor").unwrap(), vec![57166, 59051]);
+        assert_eq!(
+            names(&database, &GuildSettings::default()),
+            "`meteor`, `noaa`, `weather`, `favorites`"
+        );
+    }
+
+    #[test]
+    fn resolves_single_satellites() {
+        let database = database();
+        let resolve = |text| resolve_one(&database, Some(&Snowflake(10)), "en-GB", text);
+
+        assert_eq!(resolve(" 25544 ").unwrap(), 25544);
+        assert_eq!(resolve("NOAA19").unwrap(), 33591);
+        assert!(resolve("weather birds").is_err());
+        assert!(resolve("iss").is_err());
+    }
+
+    #[test]
+    fn checks_alias_names() {
+        let database = database();
+        let check = |name| check_alias_name(&database, "en-GB", name);
+
+        assert_eq!(check(" NOAA19 ").unwrap(), "noaa19");
+        assert!(check("25544").is_err());
+        assert!(check("a,b").is_err());
+        assert!(check("noaa").is_err());
+        assert!(check("Favorites").is_err());
+        assert!(check("").is_err());
+    }
+}
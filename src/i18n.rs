@@ -0,0 +1,224 @@
+use std::{collections::HashMap, sync::OnceLock};
+
+use fluent_bundle::{concurrent::FluentBundle, FluentArgs, FluentResource};
+use poise::Command;
+use tracing::error;
+use unic_langid::LanguageIdentifier;
+
+use crate::{ApplicationContext, Context};
+
+/// The locale used when a message is not translated into the requested one.
+pub const FALLBACK_LOCALE: &str = "en-GB";
+
+/// The translations bundled into the binary, by Discord locale.
+const LOCALES: [(&str, &str); 3] = [
+    ("en-GB", include_str!("../locales/en-GB.ftl")),
+    ("de", include_str!("../locales/de.ftl")),
+    ("fr", include_str!("../locales/fr.ftl")),
+];
+
+/// The translations of the commands' descriptions, by Discord locale. The commands are written in
+/// British English.
+const COMMAND_LOCALES: [(&str, &str); 2] = [
+    ("de", include_str!("../locales/commands/de.ftl")),
+    ("fr", include_str!("../locales/commands/fr.ftl")),
+];
+
+type Bundle = FluentBundle<FluentResource>;
+
+fn bundles() -> &'static HashMap<&'static str, Bundle> {
+    static BUNDLES: OnceLock<HashMap<&'static str, Bundle>> = OnceLock::new();
+    BUNDLES.get_or_init(|| load(&LOCALES))
+}
+
+fn command_bundles() -> &'static HashMap<&'static str, Bundle> {
+    static BUNDLES: OnceLock<HashMap<&'static str, Bundle>> = OnceLock::new();
+    BUNDLES.get_or_init(|| load(&COMMAND_LOCALES))
+}
+
+fn load(locales: &[(&'static str, &str)]) -> HashMap<&'static str, Bundle> {
+    locales
+        .iter()
+        .map(|(locale, source)| {
+            let language = locale
+                .parse::<LanguageIdentifier>()
+                .expect("bundled locales have valid names");
+            let resource = FluentResource::try_new(source.to_string())
+                .expect("bundled translations are valid Fluent");
+            let mut bundle = FluentBundle::new_concurrent(vec![language]);
+            // Isolation marks show up as stray characters in Discord
+            bundle.set_use_isolating(false);
+            bundle
+                .add_resource(resource)
+                .expect("bundled translations have unique IDs");
+            (*locale, bundle)
+        })
+        .collect()
+}
+
+/// The locale of the user who invoked a command.
+pub fn locale<'a>(ctx: &'a Context<'_>) -> &'a str {
+    ctx.locale().unwrap_or(FALLBACK_LOCALE)
+}
+
+/// Translates a message into a Discord locale, falling back to the language without its region
+/// (so `de` for `de-AT`), then to British English.
+pub fn translate(locale: &str, id: &str, args: Option<&FluentArgs>) -> String {
+    let bundles = bundles();
+    let language = locale.split('-').next().unwrap_or(locale);
+
+    [locale, language, FALLBACK_LOCALE]
+        .into_iter()
+        .filter_map(|locale| bundles.get(locale))
+        .find_map(|bundle| {
+            let pattern = bundle.get_message(id)?.value()?;
+            let mut errors = Vec::new();
+            let message = bundle.format_pattern(pattern, args, &mut errors);
+
+            if !errors.is_empty() {
+                error!("Failed to format message {}: {:?}", id, errors);
+            }
+
+            Some(message.into_owned())
+        })
+        .unwrap_or_else(|| {
+            error!("Missing translation for {}", id);
+            id.to_string()
+        })
+}
+
+/// Fills in the names and descriptions Discord shows in other languages for commands, their
+/// subcommands, and their options, from the command translations.
+pub fn localize_commands(commands: &mut [Command<ApplicationContext, anyhow::Error>]) {
+    for command in commands {
+        localize_command(command, &command.name.clone());
+    }
+}
+
+/// Localizes a command whose translations are under `id`, which is its name, or its parent's ID
+/// and its name joined by `_` for subcommands.
+fn localize_command(command: &mut Command<ApplicationContext, anyhow::Error>, id: &str) {
+    for (locale, bundle) in command_bundles() {
+        let Some(message) = bundle.get_message(id) else {
+            continue;
+        };
+        let format = |pattern| {
+            bundle
+                .format_pattern(pattern, None, &mut Vec::new())
+                .into_owned()
+        };
+
+        if let Some(name) = message.value() {
+            command
+                .name_localizations
+                .insert(locale.to_string(), format(name));
+        }
+
+        if let Some(description) = message.get_attribute("description") {
+            command
+                .description_localizations
+                .insert(locale.to_string(), format(description.value()));
+        }
+
+        for parameter in &mut command.parameters {
+            if let Some(name) = message.get_attribute(&parameter.name) {
+                parameter
+                    .name_localizations
+                    .insert(locale.to_string(), format(name.value()));
+            }
+
+            if let Some(description) =
+                message.get_attribute(&format!("{}-description", parameter.name))
+            {
+                parameter
+                    .description_localizations
+                    .insert(locale.to_string(), format(description.value()));
+            }
+        }
+    }
+
+    for subcommand in &mut command.subcommands {
+        localize_command(subcommand, &format!("{}_{}", id, subcommand.name));
+    }
+}
+
+/// Picks the localization for a Discord locale, falling back to the language without its region,
+/// then to the text given.
+pub fn localized<'a>(
+    localizations: &'a HashMap<String, String>,
+    locale: &str,
+    text: &'a str,
+) -> &'a str {
+    let language = locale.split('-').next().unwrap_or(locale);
+
+    [locale, language]
+        .into_iter()
+        .find_map(|locale| localizations.get(locale))
+        .map_or(text, String::as_str)
+}
+
+/// Translates a message, with optional `name = value` arguments.
+macro_rules! tr {
+    ($locale:expr, $id:expr) => {
+        $crate::i18n::translate($locale, $id, None)
+    };
+    ($locale:expr, $id:expr, $($name:ident = $value:expr),+ $(,)?) => {{
+        let mut args = fluent_bundle::FluentArgs::new();
+        $(args.set(stringify!($name), $value);)+
+        $crate::i18n::translate($locale, $id, Some(&args))
+    }};
+}
+
+pub(crate) use tr;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn localizes_every_command_within_discord_limits() {
+        fn check(command: &Command<ApplicationContext, anyhow::Error>) {
+            let valid_name = |name: &String| {
+                name.chars().count() <= 32
+                    && name
+                        .chars()
+                        .all(|c| c == '-' || c == '_' || c.is_lowercase() || c.is_numeric())
+            };
+
+            for (locale, _) in COMMAND_LOCALES {
+                let description = command.description_localizations.get(locale);
+                assert!(
+                    description.is_some_and(|description| description.chars().count() <= 100),
+                    "{} has no {} description of at most 100 characters",
+                    command.qualified_name,
+                    locale
+                );
+
+                for parameter in &command.parameters {
+                    let description = parameter.description_localizations.get(locale);
+                    assert!(
+                        description.is_some_and(|description| description.chars().count() <= 100),
+                        "{} {} has no {} description of at most 100 characters",
+                        command.qualified_name,
+                        parameter.name,
+                        locale
+                    );
+                    assert!(parameter.name_localizations.values().all(valid_name));
+                }
+            }
+
+            assert!(command.name_localizations.values().all(valid_name));
+            command.subcommands.iter().for_each(check);
+        }
+
+        crate::commands().iter().for_each(check);
+    }
+
+    #[test]
+    fn falls_back_to_the_language_then_the_text() {
+        let localizations = HashMap::from([("de".to_string(), "Hallo".to_string())]);
+
+        assert_eq!(localized(&localizations, "de-AT", "Hello"), "Hallo");
+        assert_eq!(localized(&localizations, "fr", "Hello"), "Hello");
+    }
+}
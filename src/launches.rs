@@ -0,0 +1,452 @@
+use std::{collections::HashMap, sync::Arc, sync::Mutex, time::Duration};
+
+use poise::ChoiceParameter;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use serenity::http::Http;
+use tokio::{sync::RwLock, task::JoinHandle, time::interval};
+use tracing::{info, warn};
+
+use crate::{
+    config,
+    database::{Database, Snowflake},
+    i18n::tr,
+    mock,
+    notify::Notifier,
+    util::{self, TimeStyle},
+};
+
+/// How long upcoming launches are reused for, in seconds. The free tier of Launch Library 2 only
+/// allows 15 requests an hour.
+const LAUNCHES_TTL: i64 = 15 * 60;
+/// How many upcoming launches are asked for.
+const MAX_LAUNCHES: usize = 30;
+/// How far a launch's NET has to move before subscribers are told it has slipped, in seconds.
+/// Small adjustments to the time within a launch window are common and not worth a message.
+const SLIP_THRESHOLD: i64 = 60 * 60;
+
+/// A client for the Launch Library 2 API by The Space Devs, whose free tier needs no key.
+pub struct LaunchLibraryAPI {
+    client: reqwest::Client,
+    /// Upcoming launches, with when they were fetched.
+    cache: Mutex<Option<(i64, Vec<Launch>)>>,
+    /// Whether canned responses are returned instead of calling Launch Library 2.
+    mock: bool,
+}
+
+impl LaunchLibraryAPI {
+    pub fn new() -> anyhow::Result<Self> {
+        info!("Creating Launch Library 2 API client");
+        Ok(Self {
+            client: reqwest::ClientBuilder::new()
+                .user_agent(util::USER_AGENT)
+                .build()?,
+            cache: Mutex::new(None),
+            mock: mock::is_enabled(),
+        })
+    }
+
+    /// Gets the next launches, soonest first.
+    pub async fn get_upcoming_launches(&self) -> anyhow::Result<Vec<Launch>> {
+        if self.mock {
+            return Ok(mock::launches());
+        }
+
+        if let Some((fetched_at, launches)) = &*self.cache.lock().unwrap() {
+            if util::current_utc() - fetched_at < LAUNCHES_TTL {
+                return Ok(launches.clone());
+            }
+        }
+
+        let url = format!(
+            "https://ll.thespacedevs.com/2.2.0/launch/upcoming/?limit={}&mode=normal&format=json",
+            MAX_LAUNCHES
+        );
+
+        info!("Sending request to {}", url);
+
+        let mut launches = self
+            .client
+            .get(&url)
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<LaunchList>()
+            .await?
+            .results;
+        launches.sort_by_key(Launch::net_utc);
+
+        *self.cache.lock().unwrap() = Some((util::current_utc(), launches.clone()));
+        Ok(launches)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct LaunchList {
+    results: Vec<Launch>,
+}
+
+/// A launch listed by Launch Library 2.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Launch {
+    pub id: String,
+    /// The rocket and payload, such as `Falcon 9 Block 5 | Transporter-12`.
+    pub name: String,
+    /// The time the launch will happen no earlier than, in RFC 3339 format.
+    pub net: String,
+    pub status: LaunchStatus,
+    pub launch_service_provider: Option<Named>,
+    pub mission: Option<Mission>,
+    pub pad: Option<Pad>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct LaunchStatus {
+    /// A short form of the status, such as `Go` or `TBD`.
+    pub abbrev: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Named {
+    pub name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Mission {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// The kind of mission, such as `Earth Science` or `Dedicated Rideshare`.
+    #[serde(rename = "type", default)]
+    pub kind: Option<String>,
+    #[serde(default)]
+    pub orbit: Option<MissionOrbit>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct MissionOrbit {
+    /// A short form of the orbit, such as `SSO` or `LEO`.
+    pub abbrev: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Pad {
+    pub name: String,
+    pub location: Named,
+}
+
+impl Launch {
+    pub fn net_utc(&self) -> Option<i64> {
+        chrono::DateTime::parse_from_rfc3339(&self.net)
+            .ok()
+            .map(|net| net.timestamp())
+    }
+
+    /// The launch's name, mission name and description, lowercased for matching against.
+    fn text(&self) -> String {
+        let mut text = self.name.to_lowercase();
+
+        if let Some(mission) = &self.mission {
+            text.push(' ');
+            text.push_str(&mission.name.to_lowercase());
+
+            if let Some(description) = &mission.description {
+                text.push(' ');
+                text.push_str(&description.to_lowercase());
+            }
+        }
+
+        text
+    }
+
+    /// A short description of the launch, for embed fields.
+    pub fn summary(&self, locale: &str) -> String {
+        let mut lines = vec![format!(
+            "{} ({})",
+            self.net_utc().map_or_else(
+                || tr!(locale, "launch-net-unknown"),
+                |net| util::format_time(net, TimeStyle::Discord)
+            ),
+            self.status.abbrev
+        )];
+
+        if let Some(provider) = &self.launch_service_provider {
+            lines.push(provider.name.clone());
+        }
+
+        if let Some(pad) = &self.pad {
+            lines.push(format!("{}, {}", pad.name, pad.location.name));
+        }
+
+        if let Some(mission) = &self.mission {
+            let details = [
+                mission.kind.clone(),
+                mission.orbit.as_ref().map(|orbit| orbit.abbrev.clone()),
+            ]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+
+            if !details.is_empty() {
+                lines.push(details.join(" · "));
+            }
+        }
+
+        lines.join("\n")
+    }
+}
+
+/// The kinds of launch that can be listed and subscribed to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ChoiceParameter)]
+pub enum LaunchCategory {
+    /// Weather and Earth observation satellites, such as NOAA's and the METEORs.
+    #[name = "weather"]
+    Weather,
+    /// Amateur radio satellites and CubeSats, which mostly fly on rideshares.
+    #[name = "amateur"]
+    Amateur,
+    #[name = "all"]
+    All,
+}
+
+impl LaunchCategory {
+    /// Whether a launch is of this kind. Launch Library 2 does not categorise payloads this
+    /// finely, so this is a best guess from the mission type and description.
+    pub fn matches(self, launch: &Launch) -> bool {
+        let kind = launch
+            .mission
+            .as_ref()
+            .and_then(|mission| mission.kind.as_deref());
+        let text = launch.text();
+        let mentions = |words: &[&str]| words.iter().any(|word| text.contains(word));
+
+        match self {
+            Self::Weather => mentions(&[
+                "weather",
+                "meteorolog",
+                "noaa",
+                "jpss",
+                "goes-",
+                "metop",
+                "meteor-m",
+                "fengyun",
+            ]),
+            Self::Amateur => {
+                kind == Some("Dedicated Rideshare")
+                    || mentions(&["amateur", "cubesat", "amsat", "ham radio"])
+            }
+            Self::All => true,
+        }
+    }
+}
+
+/// A channel that is posted to when matching launches are scheduled or slip.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LaunchSubscription {
+    pub channel: Snowflake,
+    pub guild: Option<Snowflake>,
+    pub subscriber: Snowflake,
+    /// The Discord locale of the subscriber, which posts are written in.
+    pub locale: String,
+    pub categories: Vec<LaunchCategory>,
+}
+
+impl LaunchSubscription {
+    pub fn matches(&self, launch: &Launch) -> bool {
+        self.categories
+            .iter()
+            .any(|category| category.matches(launch))
+    }
+}
+
+/// Spawns the task that periodically checks for new and slipped launches and posts them to
+/// subscribed channels.
+pub fn spawn_check_task(
+    http: Arc<Http>,
+    database: Arc<RwLock<Database>>,
+    launch_api: Arc<LaunchLibraryAPI>,
+) -> anyhow::Result<JoinHandle<()>> {
+    let minutes = config::get().launches.check_interval_minutes;
+
+    Ok(tokio::spawn(async move {
+        let mut interval = interval(Duration::from_secs(minutes * 60));
+
+        loop {
+            interval.tick().await;
+
+            match launch_api.get_upcoming_launches().await {
+                Ok(launches) => check_subscriptions(&*http, &database, &launches).await,
+                Err(e) => warn!("Failed to check for upcoming launches: {}", e),
+            }
+        }
+    }))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Change {
+    Scheduled,
+    /// The launch's NET moved from this time.
+    Slipped(i64),
+}
+
+/// Posts launches that are new, or whose NET has moved, since the last check to the channels
+/// subscribed to them. The first check only records the launches, so that subscribers are not
+/// flooded with everything already scheduled.
+pub async fn check_subscriptions(
+    notifier: &impl Notifier,
+    database: &RwLock<Database>,
+    launches: &[Launch],
+) {
+    let (changes, subscriptions) = {
+        let mut database = database.write().await;
+        let contents = &mut database.contents;
+        let first_check = contents.announced_launches.is_empty();
+
+        let changes = launches
+            .iter()
+            .filter_map(|launch| {
+                let net = launch.net_utc()?;
+                let change = match contents.announced_launches.get(&launch.id) {
+                    None => Change::Scheduled,
+                    Some(previous) if (net - previous).abs() >= SLIP_THRESHOLD => {
+                        Change::Slipped(*previous)
+                    }
+                    Some(_) => return None,
+                };
+
+                Some((launch, change))
+            })
+            .filter(|_| !first_check)
+            .collect::<Vec<_>>();
+
+        // Launches that have left the list have flown or been dropped, so are forgotten
+        contents.announced_launches = launches
+            .iter()
+            .filter_map(|launch| Some((launch.id.clone(), launch.net_utc()?)))
+            .collect::<HashMap<_, _>>();
+        database.mark_dirty();
+
+        (changes, database.contents.launch_subscriptions.clone())
+    };
+
+    for (launch, change) in changes {
+        for subscription in subscriptions.iter().filter(|s| s.matches(launch)) {
+            let locale = &subscription.locale;
+            let title = match change {
+                Change::Scheduled => tr!(locale, "launch-scheduled", launch = launch.name.clone()),
+                Change::Slipped(previous) => tr!(
+                    locale,
+                    "launch-slipped",
+                    launch = launch.name.clone(),
+                    previous = util::format_time(previous, TimeStyle::Discord)
+                ),
+            };
+            let message = json!({
+                "embeds": [{
+                    "title": tr!(locale, "launch-alert-title"),
+                    "description": format!("**{}**\n{}", title, launch.summary(locale)),
+                    "footer": { "text": tr!(locale, "launch-alert-footer") },
+                }]
+            });
+
+            match notifier
+                .send_message(subscription.channel.0, &message)
+                .await
+            {
+                Ok(_) => info!(
+                    "Sent {:?} launch alert for {} to {}",
+                    change, launch.name, subscription.channel.0
+                ),
+                Err(e) => warn!(
+                    "Failed to send launch alert to {}: {:?}",
+                    subscription.channel.0, e
+                ),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{database::DatabaseContents, testing::FakeNotifier};
+
+    fn launch(id: &str, name: &str, net: &str) -> Launch {
+        Launch {
+            id: id.to_string(),
+            name: name.to_string(),
+            net: net.to_string(),
+            status: LaunchStatus {
+                abbrev: "Go".to_string(),
+            },
+            launch_service_provider: None,
+            mission: None,
+            pad: None,
+        }
+    }
+
+    fn subscription(channel: u64, category: LaunchCategory) -> LaunchSubscription {
+        LaunchSubscription {
+            channel: Snowflake(channel),
+            guild: None,
+            subscriber: Snowflake(1),
+            locale: "en-GB".to_string(),
+            categories: vec![category],
+        }
+    }
+
+    #[tokio::test]
+    async fn posts_new_and_slipped_launches() {
+        let notifier = FakeNotifier::default();
+        let database = RwLock::new(Database::in_memory(DatabaseContents {
+            launch_subscriptions: vec![
+                subscription(10, LaunchCategory::Weather),
+                subscription(20, LaunchCategory::All),
+            ],
+            ..Default::default()
+        }));
+        let weather = launch("a", "Atlas V | JPSS-4", "2030-01-01T12:00:00Z");
+        let other = launch("b", "Falcon 9 | Starlink", "2030-01-02T12:00:00Z");
+
+        // The first check only records what is already scheduled
+        check_subscriptions(&notifier, &database, std::slice::from_ref(&other)).await;
+        check_subscriptions(&notifier, &database, &[weather.clone(), other.clone()]).await;
+
+        let slipped = Launch {
+            net: "2030-01-03T12:00:00Z".to_string(),
+            ..weather.clone()
+        };
+        let adjusted = Launch {
+            net: "2030-01-02T12:10:00Z".to_string(),
+            ..other.clone()
+        };
+        check_subscriptions(&notifier, &database, &[slipped, adjusted]).await;
+
+        let sent = notifier
+            .sent
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(channel, message)| {
+                let description = message["embeds"][0]["description"].as_str().unwrap();
+                (*channel, description.lines().next().unwrap().to_string())
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            sent,
+            vec![
+                (10, "**Atlas V | JPSS-4 has been scheduled**".to_string()),
+                (20, "**Atlas V | JPSS-4 has been scheduled**".to_string()),
+                (
+                    10,
+                    "**Atlas V | JPSS-4 has slipped from <t:1893499200:R>**".to_string()
+                ),
+                (
+                    20,
+                    "**Atlas V | JPSS-4 has slipped from <t:1893499200:R>**".to_string()
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,45 @@
+use tracing_appender::{
+    non_blocking::WorkerGuard,
+    rolling::{RollingFileAppender, Rotation},
+};
+
+use crate::config::{self, LogFormat};
+
+/// Sets up where logs go: to stdout, or with `LOG_DIRECTORY` set, to a file there that is rotated
+/// daily, keeping the newest `LOG_MAX_FILES`. `LOG_FORMAT=json` writes each event as a line of
+/// JSON, for shipping to something like Loki or Elasticsearch.
+///
+/// File logs are written in the background, and the returned guard flushes them when dropped, so it
+/// must be held until the bot exits.
+pub fn init() -> anyhow::Result<Option<WorkerGuard>> {
+    let config = &config::get().logging;
+    let json = config.format == LogFormat::Json;
+    let subscriber = tracing_subscriber::fmt();
+
+    let Some(directory) = &config.directory else {
+        if json {
+            subscriber.json().flatten_event(true).init();
+        } else {
+            subscriber.init();
+        }
+
+        return Ok(None);
+    };
+
+    let appender = RollingFileAppender::builder()
+        .rotation(Rotation::DAILY)
+        .filename_prefix("sat-bot")
+        .filename_suffix("log")
+        .max_log_files(config.max_files)
+        .build(directory)?;
+    let (writer, guard) = tracing_appender::non_blocking(appender);
+    let subscriber = subscriber.with_writer(writer).with_ansi(false);
+
+    if json {
+        subscriber.json().flatten_event(true).init();
+    } else {
+        subscriber.init();
+    }
+
+    Ok(Some(guard))
+}
@@ -1,62 +1,242 @@
 use std::{future::Future, pin::Pin, sync::Arc, time::Duration};
 
-use database::Database;
+use celestrak::CelesTrakAPI;
+use clap::Parser;
+use cli::{Cli, CliCommand};
+use database::{Database, Snowflake};
+use error::{BotError, Report};
+use launches::LaunchLibraryAPI;
+use metrics::Metrics;
 use n2yo::N2YOAPI;
-use poise::{serenity_prelude::GuildId, FrameworkError};
+use poise::FrameworkError;
+use provider::PassProvider;
+use satcat::Satcat;
+use satnogs::SatnogsAPI;
+use schedule::Schedule;
 use serenity::prelude::*;
-use tokio::{spawn, sync::RwLock, time::interval};
+use swpc::SwpcAPI;
+use tokio::{spawn, sync::RwLock};
 use tracing::{error, info};
+use tracking::LiveTracker;
+use weather::WeatherAPI;
 
+mod astro;
+mod catalog;
+mod celestrak;
+mod cli;
 mod commands;
+mod config;
 mod database;
+mod decay;
+mod error;
+mod events;
+mod frequencies;
+mod groups;
+mod i18n;
+mod launches;
+mod logging;
+mod metrics;
+mod mock;
 mod n2yo;
+mod notify;
+mod passes;
+mod planning;
+mod preferences;
+mod preflight;
+mod propagation;
+mod provider;
+mod registration;
+mod rsvp;
+mod satcat;
+mod satnogs;
+mod schedule;
+mod sharding;
+mod sstv;
+mod starlink;
+mod swpc;
+mod templates;
+#[cfg(test)]
+mod testing;
+mod tracking;
+mod usage;
 mod util;
+mod weather;
+
+/// How long the notification task waits before its first restart after panicking.
+const NOTIFY_RESTART_DELAY: Duration = Duration::from_secs(30);
+/// The longest the notification task waits before being restarted.
+const MAX_NOTIFY_RESTART_DELAY: Duration = Duration::from_secs(30 * 60);
 
 pub struct ApplicationContext {
     pub database: Arc<RwLock<Database>>,
     pub n2yo_api: Arc<N2YOAPI>,
+    pub celestrak_api: Arc<CelesTrakAPI>,
+    pub pass_provider: Arc<PassProvider>,
+    pub launch_api: Arc<LaunchLibraryAPI>,
+    pub satnogs_api: Arc<SatnogsAPI>,
+    pub swpc_api: Arc<SwpcAPI>,
+    pub metrics: Arc<Metrics>,
+    pub live_tracker: Arc<LiveTracker>,
+    pub satcat: Arc<Satcat>,
 }
 
 pub type Context<'a> = poise::Context<'a, ApplicationContext, anyhow::Error>;
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    tracing_subscriber::fmt::init();
-    util::load_env_file()?;
+    let cli = Cli::parse();
+    // Where logs go is itself configured, so until the config is loaded they go to stdout
+    tracing::subscriber::with_default(tracing_subscriber::fmt().finish(), config::load)?;
+    let _log_guard = logging::init()?;
+    let _reporting_guard = error::init_reporting()?;
+
+    match cli.command.unwrap_or(CliCommand::Run) {
+        CliCommand::Run => run().await,
+        CliCommand::ValidateConfig => cli::validate_config(),
+        CliCommand::Preflight => cli::preflight().await,
+        CliCommand::RegisterCommands => cli::register_commands().await,
+        CliCommand::MigrateDb => cli::migrate_db(),
+        CliCommand::Notify { dry_run } => cli::notify(dry_run).await,
+    }
+}
 
-    let database = Arc::new(RwLock::new(Database::open()?));
+fn commands() -> Vec<poise::Command<ApplicationContext, anyhow::Error>> {
+    let mut commands = vec![
+        commands::add_location(),
+        commands::list_locations(),
+        commands::remove_location(),
+        commands::get_upcoming_passes(),
+        commands::get_upcoming_noaa_passes(),
+        commands::get_upcoming_meteor_passes(),
+        commands::get_upcoming_passes_multi(),
+        commands::best_pass(),
+        commands::next_pass(),
+        commands::pass_info(),
+        commands::plan_session(),
+        commands::compare_passes(),
+        commands::export_rotator_schedule(),
+        commands::export_pass(),
+        commands::starlink_train(),
+        commands::watch_satellite(),
+        commands::list_watched_satellites(),
+        commands::unwatch_satellite(),
+        commands::edit_watch(),
+        commands::clone_watch(),
+        commands::move_watch(),
+        commands::mute_notifications(),
+        commands::vacation(),
+        commands::pause_watch(),
+        commands::resume_watch(),
+        commands::update_watched_satellites(),
+        commands::settings(),
+        commands::server_settings(),
+        commands::space_weather(),
+        commands::solar(),
+        commands::moon(),
+        commands::frequencies(),
+        commands::transmitters(),
+        commands::geo_pointing(),
+        commands::satellite_info(),
+        commands::satcat_search(),
+        commands::recent_observations(),
+        commands::log_observation(),
+        commands::gallery(),
+        commands::upcoming_launches(),
+        commands::launch_alerts(),
+        commands::favorite_satellite(),
+        commands::satellite_alias(),
+        commands::stats(),
+        commands::sync_commands(),
+        commands::help(),
+    ];
+
+    i18n::localize_commands(&mut commands);
+    commands
+}
+
+async fn run() -> anyhow::Result<()> {
+    let notify_schedule = notify_schedule();
     let n2yo_api = Arc::new(N2YOAPI::new()?);
+    preflight::check(&n2yo_api).await?;
+
+    let (database, persistence_receiver) = Database::open()?;
+    let database = Arc::new(RwLock::new(database));
+    let persistence_worker =
+        database::spawn_persistence_worker(database.clone(), persistence_receiver);
+    database::spawn_reload_task(database.clone())?;
+    let celestrak_api = Arc::new(CelesTrakAPI::new()?);
+    catalog::spawn_refresh_task(database.clone(), n2yo_api.clone(), celestrak_api.clone())?;
+    sstv::spawn_check_task(database.clone())?;
+    let satcat = Arc::new(Satcat::default());
+    satcat::spawn_refresh_task(satcat.clone(), celestrak_api.clone())?;
+    let satnogs_api = Arc::new(SatnogsAPI::new()?);
+    let pass_provider = Arc::new(PassProvider::new(
+        n2yo_api.clone(),
+        celestrak_api.clone(),
+        Arc::new(WeatherAPI::new()?),
+        satnogs_api.clone(),
+        database.clone(),
+    )?);
+
+    let launch_api = Arc::new(LaunchLibraryAPI::new()?);
+    let metrics = Arc::new(Metrics::new());
 
     let app_ctx = ApplicationContext {
         database: database.clone(),
         n2yo_api: n2yo_api.clone(),
+        celestrak_api: celestrak_api.clone(),
+        pass_provider: pass_provider.clone(),
+        launch_api: launch_api.clone(),
+        satnogs_api,
+        swpc_api: Arc::new(SwpcAPI::new()?),
+        metrics: metrics.clone(),
+        live_tracker: Arc::new(LiveTracker::default()),
+        satcat,
     };
 
     let framework = poise::Framework::builder()
         .options(poise::FrameworkOptions {
-            commands: vec![
-                commands::add_location(),
-                commands::list_locations(),
-                commands::remove_location(),
-                commands::get_upcoming_passes(),
-                commands::get_upcoming_noaa_passes(),
-                commands::watch_satellite(),
-                commands::list_watched_satellites(),
-                commands::unwatch_satellite(),
-                commands::update_watched_satellites(),
-            ],
+            commands: commands(),
             on_error,
+            post_command: |ctx| {
+                Box::pin(async move {
+                    let mut database = ctx.data().database.write().await;
+                    database
+                        .contents
+                        .usage
+                        .record_command(&ctx.command().qualified_name);
+                    database.mark_dirty();
+
+                    if let poise::Context::Application(ctx) = ctx {
+                        commands::remember_choices(
+                            &mut database,
+                            &Snowflake(ctx.author().id.0),
+                            ctx.args.iter().filter_map(|option| {
+                                let value = match option.value.as_ref()? {
+                                    serde_json::Value::String(value) => value.clone(),
+                                    value => value.to_string(),
+                                };
+                                Some((option.name.as_str(), value))
+                            }),
+                        );
+                    }
+                })
+            },
+            event_handler: |ctx, event, framework, data| {
+                Box::pin(events::handle_event(ctx, event, framework, data))
+            },
             ..Default::default()
         })
-        .token(util::env("DISCORD_TOKEN")?)
-        .intents(GatewayIntents::non_privileged())
-        .setup(|ctx, _ready, framework| {
+        .token(&config::get().discord.token)
+        .intents(intents())
+        .setup(|ctx, ready, framework| {
             Box::pin(async move {
                 info!("Registering commands");
-                poise::builtins::register_in_guild::<ApplicationContext, anyhow::Error>(
-                    ctx,
+                registration::register_if_changed(
+                    &ctx.http,
+                    &app_ctx.database,
                     &framework.options().commands,
-                    GuildId(util::env("GUILD_ID")?.parse()?),
+                    ready.guilds.iter().map(|guild| guild.id),
                 )
                 .await?;
 
@@ -67,52 +247,279 @@ async fn main() -> anyhow::Result<()> {
         .await?;
 
     let http = framework.client().cache_and_http.http.clone();
+    let shard_manager = framework.shard_manager().clone();
 
     spawn(async move {
-        let mut interval = interval(Duration::from_secs(60 * 30));
-
-        loop {
-            info!("Waiting for next interval");
-            interval.tick().await;
-            info!("Checking for new passes");
-            let _ = commands::notify_of_new_passes(&http, &database, &n2yo_api).await;
+        if tokio::signal::ctrl_c().await.is_ok() {
+            info!("Received shutdown signal");
+            shard_manager.lock().await.shutdown_all().await;
         }
     });
 
-    info!("Starting bot");
-    framework.start().await?;
+    let sharding = config::get().discord.sharding;
+
+    if sharding.runs_shard_zero() {
+        usage::spawn_summary_task(http.clone(), database.clone());
+        decay::spawn_check_task(http.clone(), database.clone(), celestrak_api.clone());
+        launches::spawn_check_task(http.clone(), database.clone(), launch_api)?;
+        rsvp::spawn_reminder_task(http.clone(), database.clone())?;
+        spawn(supervise_notify_task(http.clone(), metrics.clone(), {
+            let (http, database, pass_provider, metrics) = (
+                http.clone(),
+                database.clone(),
+                pass_provider.clone(),
+                metrics.clone(),
+            );
+            // Shared with each restarted task, so that restarting does not bring the next run forward
+            let schedule = Arc::new(tokio::sync::Mutex::new(notify_schedule));
+
+            move || {
+                notify_loop(
+                    http.clone(),
+                    database.clone(),
+                    pass_provider.clone(),
+                    metrics.clone(),
+                    schedule.clone(),
+                )
+            }
+        }));
+    } else {
+        info!("Not running shard 0, leaving notifications to another process");
+    }
+
+    info!("Starting bot ({:?})", sharding);
+    sharding.start(framework).await?;
+
+    database::flush(&database).await;
+    persistence_worker.abort();
 
     Ok(())
 }
 
+/// The gateway intents to connect with. Member events are privileged, so cleaning up after
+/// members who leave is opt-in.
+fn intents() -> GatewayIntents {
+    let mut intents = GatewayIntents::non_privileged();
+
+    if config::get().features.member_cleanup {
+        intents |= GatewayIntents::GUILD_MEMBERS;
+    }
+
+    intents
+}
+
+/// Runs the notification task, restarting it whenever it panics. Restarts are delayed by
+/// `NOTIFY_RESTART_DELAY` at first, doubling up to `MAX_NOTIFY_RESTART_DELAY` while the task
+/// keeps panicking without completing a run. `start_task` starts each run of the task, which
+/// stops being supervised if it returns.
+async fn supervise_notify_task<F, T>(
+    http: Arc<serenity::http::Http>,
+    metrics: Arc<Metrics>,
+    mut start_task: F,
+) where
+    F: FnMut() -> T,
+    T: Future<Output = ()> + Send + 'static,
+{
+    let mut delay = NOTIFY_RESTART_DELAY;
+
+    loop {
+        let last_run = metrics.last_notify_run();
+        let task = spawn(start_task());
+
+        let Err(e) = task.await else {
+            return;
+        };
+
+        if !e.is_panic() {
+            return;
+        }
+
+        let payload = e.into_panic();
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|message| message.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_default();
+        let error = anyhow::anyhow!("panicked: {}", message);
+
+        if metrics.last_notify_run() != last_run {
+            delay = NOTIFY_RESTART_DELAY;
+        }
+
+        error!(
+            "Notification task panicked, restarting in {} second(s): {}",
+            delay.as_secs(),
+            message
+        );
+        metrics.record_notify_restart();
+        error::capture(&error, &[("task", "notify".to_string())]);
+        error::report_to_ops_channel(&http, "Notification task panicked", &error).await;
+
+        tokio::time::sleep(delay).await;
+        delay = (delay * 2).min(MAX_NOTIFY_RESTART_DELAY);
+    }
+}
+
+/// Checks for new passes whenever the schedule says to.
+async fn notify_loop(
+    http: Arc<serenity::http::Http>,
+    database: Arc<RwLock<Database>>,
+    pass_provider: Arc<PassProvider>,
+    metrics: Arc<Metrics>,
+    schedule: Arc<tokio::sync::Mutex<Schedule>>,
+) {
+    loop {
+        info!("Waiting for next scheduled run");
+        schedule.lock().await.tick().await;
+        info!("Checking for new passes");
+        if let Err(e) =
+            notify::notify_of_new_passes(&*http, &database, &*pass_provider, &metrics, false).await
+        {
+            error!("Notification run failed: {:?}", e);
+            error::capture(&e, &[("task", "notify".to_string())]);
+            error::report_to_ops_channel(&http, "Notification run failed", &e).await;
+        }
+    }
+}
+
+/// Reads when notification runs happen, either from a cron expression or as a fixed interval.
+fn notify_schedule() -> Schedule {
+    let notifications = &config::get().notifications;
+
+    if let Some(cron) = &notifications.cron {
+        if let Some(next) = cron.next_after(chrono::Local::now()) {
+            info!("Checking for new passes on schedule, next run at {}", next);
+        }
+        return Schedule::Cron(cron.clone());
+    }
+
+    let minutes = notifications.interval_minutes;
+    info!("Checking for new passes every {} minute(s)", minutes);
+    Schedule::interval(Duration::from_secs(minutes * 60))
+}
+
+/// Names the command that failed, and where, for the ops channel.
+fn command_context(ctx: Context<'_>) -> String {
+    match ctx.guild_id() {
+        Some(guild) => format!(
+            "/{} failed in guild {}",
+            ctx.command().qualified_name,
+            guild.0
+        ),
+        None => format!("/{} failed in a DM", ctx.command().qualified_name),
+    }
+}
+
+/// Tags errors sent to Sentry with the command that failed, where, and for which satellite.
+fn command_tags(ctx: Context<'_>) -> Vec<(&'static str, String)> {
+    let mut tags = vec![("command", ctx.command().qualified_name.clone())];
+
+    if let Some(guild) = ctx.guild_id() {
+        tags.push(("guild", guild.0.to_string()));
+    }
+
+    if let poise::Context::Application(ctx) = ctx {
+        if let Some(satellite) = ctx
+            .args
+            .iter()
+            .find(|arg| arg.name == "satellite_id")
+            .and_then(|arg| arg.value.as_ref())
+        {
+            let satellite = match satellite.as_str() {
+                Some(satellite) => satellite.to_string(),
+                None => satellite.to_string(),
+            };
+            tags.push(("satellite_id", satellite));
+        }
+    }
+
+    tags
+}
+
 fn on_error<'a>(
     framework_error: FrameworkError<'a, ApplicationContext, anyhow::Error>,
 ) -> Pin<Box<dyn Future<Output = ()> + Send + 'a>> {
     Box::pin(async move {
         info!("Handling an error");
 
+        let locale = framework_error
+            .ctx()
+            .map(|ctx| i18n::locale(&ctx).to_string())
+            .unwrap_or_else(|| i18n::FALLBACK_LOCALE.to_string());
+        let http = &framework_error.serenity_context().http;
+        let mut ephemeral = false;
         let message = match &framework_error {
             FrameworkError::Setup { error, .. } => {
                 error!("Encountered error during setup: {}", error);
+                error::capture(error, &[("task", "setup".to_string())]);
+                error::report_to_ops_channel(http, "Setup failed", error).await;
                 None
             }
-            FrameworkError::EventHandler { error, .. } => {
+            FrameworkError::EventHandler { error, event, .. } => {
                 error!("Encountered error during event handling: {}", error);
+                error::report_to_ops_channel(
+                    http,
+                    &format!("Failed to handle {} event", event.name()),
+                    error,
+                )
+                .await;
                 None
             }
-            FrameworkError::Command { error, .. } => {
-                error!("Encountered error during command handling: {}", error);
-                Some(format!("Error: {}", error))
+            FrameworkError::Command { error, ctx } => {
+                let report = Report::new(error, &locale);
+
+                if report.unexpected {
+                    error!("Encountered error during command handling: {:?}", error);
+                    error::capture(error, &command_tags(*ctx));
+                } else {
+                    info!("Command failed: {}", error);
+                }
+
+                // N2YO outages are not the user's fault, but are worth the operator knowing about
+                if report.unexpected
+                    || matches!(error.downcast_ref::<BotError>(), Some(BotError::N2yo(_)))
+                {
+                    error::report_to_ops_channel(http, &command_context(*ctx), error).await;
+                }
+
+                ephemeral = report.ephemeral;
+                Some(report.message)
+            }
+            FrameworkError::CommandCheckFailed {
+                error: Some(error), ..
+            } => {
+                let report = Report::new(error, &locale);
+                info!("Command check failed: {}", error);
+                ephemeral = report.ephemeral;
+                Some(report.message)
+            }
+            FrameworkError::CommandCheckFailed { error: None, .. } => {
+                info!("Command check failed");
+                None
+            }
+            FrameworkError::CooldownHit {
+                remaining_cooldown, ..
+            } => {
+                ephemeral = true;
+                Some(i18n::tr!(
+                    &locale,
+                    "error-cooldown",
+                    seconds = remaining_cooldown.as_secs_f64().ceil() as u64
+                ))
             }
             FrameworkError::SubcommandRequired { .. } => {
                 error!("Command invoked without a subcommand");
                 Some("Command invoked without a subcommand".to_string())
             }
-            FrameworkError::CommandPanic { payload, .. } => {
+            FrameworkError::CommandPanic { payload, ctx } => {
                 error!(
                     "Command panicked: {}",
                     payload.clone().unwrap_or("".to_string())
                 );
+                let error =
+                    anyhow::anyhow!("panicked: {}", payload.clone().unwrap_or("".to_string()));
+                error::capture(&error, &command_tags(*ctx));
+                error::report_to_ops_channel(http, &command_context(*ctx), &error).await;
                 Some("Command panicked".to_string())
             }
             FrameworkError::ArgumentParse { error, .. } => {
@@ -134,13 +541,65 @@ fn on_error<'a>(
             let _ = ctx
                 .send(|b| {
                     b.embed(|e| {
-                        e.title("An error occurred");
+                        e.title(i18n::tr!(&locale, "error-title"));
                         e.description(message.unwrap_or("Unknown error".to_string()));
                         e
                     })
-                    .ephemeral(false)
+                    .ephemeral(ephemeral)
                 })
                 .await;
         }
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use tokio::time::Instant;
+
+    use super::*;
+
+    #[tokio::test(start_paused = true)]
+    async fn restarts_the_notification_task_with_backoff() {
+        let http = Arc::new(serenity::http::Http::new(""));
+        let metrics = Arc::new(Metrics::new());
+        let starts = Arc::new(Mutex::new(Vec::new()));
+
+        supervise_notify_task(http, metrics.clone(), {
+            let (metrics, starts) = (metrics.clone(), starts.clone());
+
+            move || {
+                let (metrics, starts) = (metrics.clone(), starts.clone());
+
+                async move {
+                    let count = {
+                        let mut starts = starts.lock().unwrap();
+                        starts.push(Instant::now());
+                        starts.len()
+                    };
+
+                    match count {
+                        3 => {
+                            metrics.record_notify_run();
+                            panic!("panicked after a run");
+                        }
+                        5 => {}
+                        _ => panic!("panicked before a run"),
+                    }
+                }
+            }
+        })
+        .await;
+
+        let starts = starts.lock().unwrap();
+        let delays = starts
+            .windows(2)
+            .map(|pair| (pair[1] - pair[0]).as_secs())
+            .collect::<Vec<_>>();
+
+        // Doubling while the task keeps panicking, and starting over once it completes a run
+        assert_eq!(delays, vec![30, 60, 30, 60]);
+        assert_eq!(metrics.notify_restarts(), 4);
+    }
+}
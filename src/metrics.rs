@@ -0,0 +1,56 @@
+use std::sync::atomic::{AtomicI64, AtomicUsize, Ordering};
+
+use crate::util;
+
+/// Counters describing what the bot has done since it started.
+pub struct Metrics {
+    started_at: i64,
+    notifications_sent: AtomicUsize,
+    last_notify_run: AtomicI64,
+    notify_restarts: AtomicUsize,
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        Self {
+            started_at: util::current_utc(),
+            notifications_sent: AtomicUsize::new(0),
+            last_notify_run: AtomicI64::new(0),
+            notify_restarts: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn started_at(&self) -> i64 {
+        self.started_at
+    }
+
+    pub fn notifications_sent(&self) -> usize {
+        self.notifications_sent.load(Ordering::Relaxed)
+    }
+
+    pub fn record_notifications_sent(&self, count: usize) {
+        self.notifications_sent.fetch_add(count, Ordering::Relaxed);
+    }
+
+    /// When the notification task last completed a run, if it has.
+    pub fn last_notify_run(&self) -> Option<i64> {
+        match self.last_notify_run.load(Ordering::Relaxed) {
+            0 => None,
+            time => Some(time),
+        }
+    }
+
+    pub fn record_notify_run(&self) {
+        self.last_notify_run
+            .store(util::current_utc(), Ordering::Relaxed);
+    }
+
+    /// How many times the notification task has been restarted after panicking.
+    pub fn notify_restarts(&self) -> usize {
+        self.notify_restarts.load(Ordering::Relaxed)
+    }
+
+    pub fn record_notify_restart(&self) {
+        self.notify_restarts.fetch_add(1, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,277 @@
+use crate::{
+    config,
+    database::Location,
+    frequencies,
+    launches::{Launch, LaunchStatus, Mission, MissionOrbit, Named, Pad},
+    n2yo::{
+        PassKind, PassSource, SatelliteInfo, SatellitePass, SatellitePassInfo, SatellitePasses,
+        SatelliteTle,
+    },
+    propagation,
+    satnogs::{SatnogsObservation, SatnogsTransmitter},
+    swpc::{AuroraForecast, SolarIndices, SpaceWeather},
+    util,
+    weather::CloudCoverForecast,
+};
+
+/// A real TLE, so that the local propagator also has something sensible to work with.
+const MOCK_TLE: &str = "1 25544U 98067A   23320.54791667  .00016717  00000-0  30164-3 0  9990\n2 25544  51.6416 339.7760 0001038  88.0434 272.0759 15.49815322425140";
+
+/// Whether the bot is running against fake data rather than N2YO and CelesTrak, set with
+/// `SATBOT_MOCK=1`.
+pub fn is_enabled() -> bool {
+    config::get().features.mock
+}
+
+fn name(satellite_id: usize) -> String {
+    match satellite_id {
+        25338 => "NOAA 15".to_string(),
+        25544 => "SPACE STATION".to_string(),
+        28654 => "NOAA 18".to_string(),
+        33591 => "NOAA 19".to_string(),
+        _ => format!("MOCK SAT {}", satellite_id),
+    }
+}
+
+/// Canned passes, two a day at times and elevations derived from the satellite ID, so that the
+/// same request always gets the same answer. Visual passes also get a magnitude.
+pub fn satellite_passes(
+    satellite_id: usize,
+    _location: &Location,
+    days: usize,
+    min_max_elevation: f64,
+    kind: PassKind,
+) -> SatellitePasses {
+    const DAY: i64 = 24 * 60 * 60;
+    let now = util::current_utc();
+    let midnight = now - now.rem_euclid(DAY);
+    let offset = (satellite_id % 60) as i64 * 60;
+
+    let passes = (0..=days as i64 * 2)
+        .map(|i| {
+            let start = midnight + i * DAY / 2 + 6 * 60 * 60 + offset;
+            let end = start + 10 * 60;
+            let start_azimuth = ((satellite_id * 37 + i as usize * 53) % 360) as f64;
+            let end_azimuth = (start_azimuth + 160.0) % 360.0;
+            let max_azimuth = (start_azimuth + 80.0) % 360.0;
+
+            SatellitePass {
+                start_azimuth,
+                start_azimuth_compass: propagation::compass(start_azimuth).to_string(),
+                start_utc: start as usize,
+                max_azimuth,
+                max_azimuth_compass: propagation::compass(max_azimuth).to_string(),
+                max_elevation: (10 + (satellite_id * 7 + i as usize * 13) % 80) as f64,
+                max_utc: (start + 5 * 60) as usize,
+                end_azimuth,
+                end_azimuth_compass: propagation::compass(end_azimuth).to_string(),
+                end_utc: end as usize,
+                visible_fraction: None,
+                cloud_cover: None,
+                moon_separation: None,
+                magnitude: (kind == PassKind::Visual)
+                    .then(|| ((satellite_id + i as usize) % 7) as f64 - 2.5),
+            }
+        })
+        .filter(|pass| {
+            pass.start_utc as i64 >= now
+                && (pass.start_utc as i64) < now + days as i64 * DAY
+                && pass.max_elevation >= min_max_elevation
+        })
+        .collect::<Vec<_>>();
+
+    let info = SatellitePassInfo {
+        id: satellite_id,
+        name: name(satellite_id),
+        transaction_count: 0,
+        passes_count: passes.len(),
+    };
+    // Shaped like a response from N2YO, for the raw attachment of pass commands
+    let raw = serde_json::json!({ "info": info, "passes": passes }).to_string();
+
+    SatellitePasses {
+        info,
+        passes,
+        source: PassSource::N2YO,
+        raw: Some(raw),
+    }
+}
+
+pub fn tle(satellite_id: usize) -> SatelliteTle {
+    SatelliteTle {
+        info: SatelliteInfo {
+            id: satellite_id,
+            name: name(satellite_id),
+            transaction_count: 0,
+        },
+        tle: MOCK_TLE.to_string(),
+    }
+}
+
+/// A TLE with its title line, as CelesTrak returns them.
+pub fn tle_text(satellite_id: usize) -> String {
+    format!("{}\n{}", name(satellite_id), MOCK_TLE)
+}
+
+/// A group of TLEs, made of a pair of Starlink satellites from the same launch.
+pub fn group_tles(_group: &str) -> String {
+    format!("STARLINK-90001\n{}\nSTARLINK-90002\n{}", MOCK_TLE, MOCK_TLE)
+}
+
+/// A handful of well known SATCAT entries, in CelesTrak's CSV format.
+pub fn satcat_csv() -> String {
+    [
+        "OBJECT_NAME,OBJECT_ID,NORAD_CAT_ID,OBJECT_TYPE,OPS_STATUS_CODE,OWNER,LAUNCH_DATE,LAUNCH_SITE,DECAY_DATE,PERIOD,INCLINATION,APOGEE,PERIGEE,RCS,DATA_STATUS_CODE,ORBIT_CENTER,ORBIT_TYPE",
+        "NOAA 15,1998-030A,25338,PAY,+,US,1998-05-13,AFWTR,,101.17,98.56,811,793,0,,EA,ORB",
+        "ISS (ZARYA),1998-067A,25544,PAY,+,ISS,1998-11-20,TYMSC,,92.88,51.64,421,413,0,,EA,ORB",
+        "NOAA 18,2005-018A,28654,PAY,+,US,2005-05-20,AFWTR,,102.04,98.86,860,843,0,,EA,ORB",
+        "NOAA 19,2009-005A,33591,PAY,+,US,2009-02-06,AFWTR,,102.08,99.05,867,847,0,,EA,ORB",
+        "METEOR-M 2,2014-037A,40069,PAY,+,CIS,2014-07-08,TTMTR,,101.36,98.49,822,801,0,,EA,ORB",
+        "\"CZ-4C R/B\",2013-066B,39430,R/B,,PRC,2013-11-21,TAISC,2014-02-11,,,,,,,EA,IMP",
+        "COSMOS 2251 DEB,1993-036AJL,35000,DEB,,CIS,1993-06-16,PKMTR,,99.41,74.05,771,678,0,,EA,ORB",
+    ]
+    .join("\n")
+}
+
+/// A canned cloud cover forecast, cycling through clear and overcast skies over each day.
+pub fn cloud_cover(days: usize) -> CloudCoverForecast {
+    const HOUR: i64 = 60 * 60;
+    let now = util::current_utc();
+    let start = now - now.rem_euclid(HOUR);
+
+    CloudCoverForecast {
+        hours: (0..(days as i64 + 1) * 24)
+            .map(|hour| (start + hour * HOUR, ((hour * 37) % 101) as f64))
+            .collect(),
+    }
+}
+
+/// A canned space weather report, with unsettled conditions building to a minor storm.
+pub fn space_weather() -> SpaceWeather {
+    const THREE_HOURS: i64 = 3 * 60 * 60;
+    let now = util::current_utc();
+    let start = now - now.rem_euclid(THREE_HOURS);
+
+    SpaceWeather {
+        current: (now - 60, 3.67),
+        predicted: [3.33, 4.0, 4.67, 5.33, 5.0, 4.33, 3.67, 3.0]
+            .into_iter()
+            .enumerate()
+            .map(|(period, kp)| (start + period as i64 * THREE_HOURS, kp))
+            .collect(),
+        aurora: AuroraForecast {
+            probabilities: (0..360)
+                .flat_map(|longitude| (-90..=90i32).map(move |latitude| (longitude, latitude)))
+                .map(|(longitude, latitude)| {
+                    let distance_from_oval = latitude.abs().abs_diff(67);
+                    let probability = 60u32.saturating_sub(distance_from_oval * 6);
+                    ((longitude, latitude), probability as u8)
+                })
+                .collect(),
+        },
+    }
+}
+
+/// Canned solar indices, typical of a solar maximum.
+pub fn solar_indices() -> SolarIndices {
+    let now = util::current_utc();
+
+    SolarIndices {
+        solar_flux: Some((now - 4 * 60 * 60, 182.0)),
+        sunspot_number: Some((now - 12 * 60 * 60, 143.0)),
+        a_index: Some((now - 60 * 60, 12.0)),
+        k_index: Some((now - 60 * 60, 3.0)),
+    }
+}
+
+/// Transmitters made from the bundled frequencies of well known satellites.
+pub fn transmitters(satellite_id: usize) -> Vec<SatnogsTransmitter> {
+    frequencies::lookup(satellite_id)
+        .map(|satellite| satellite.transmitters)
+        .unwrap_or_default()
+        .iter()
+        .map(|transmitter| SatnogsTransmitter {
+            description: transmitter.description.to_string(),
+            alive: true,
+            status: "active".to_string(),
+            uplink_low: transmitter
+                .uplink
+                .map(|uplink| (uplink * 1_000_000.0) as u64),
+            downlink_low: Some((transmitter.downlink * 1_000_000.0) as u64),
+            mode: Some(transmitter.mode.to_string()),
+            baud: None,
+        })
+        .collect()
+}
+
+/// A pair of canned good observations from earlier today.
+pub fn observations(satellite_id: usize) -> Vec<SatnogsObservation> {
+    let now = util::current_utc();
+
+    [(1, "Mock Station A", 2), (2, "Mock Station B", 5)]
+        .into_iter()
+        .map(|(id, station_name, hours_ago)| SatnogsObservation {
+            id: satellite_id as u64 * 10 + id,
+            start: chrono::DateTime::from_timestamp(now - hours_ago * 60 * 60, 0)
+                .unwrap_or_default()
+                .to_rfc3339(),
+            station_name: station_name.to_string(),
+            waterfall: None,
+            transmitter_mode: Some("FM".to_string()),
+            observation_frequency: Some(145_800_000),
+        })
+        .collect()
+}
+
+/// A weather satellite launch and a rideshare carrying CubeSats over the coming weeks.
+pub fn launches() -> Vec<Launch> {
+    const DAY: i64 = 24 * 60 * 60;
+    let now = util::current_utc();
+    let start = now - now.rem_euclid(DAY);
+
+    [
+        (
+            "mock-1",
+            "Falcon 9 Block 5 | Transporter-99",
+            5,
+            "Dedicated Rideshare",
+            "SSO",
+        ),
+        (
+            "mock-2",
+            "Soyuz-2.1b/Fregat-M | Meteor-M No.2-5",
+            12,
+            "Earth Science",
+            "SSO",
+        ),
+    ]
+    .into_iter()
+    .map(|(id, name, days, kind, orbit)| Launch {
+        id: id.to_string(),
+        name: name.to_string(),
+        net: chrono::DateTime::from_timestamp(start + days * DAY + 14 * 60 * 60, 0)
+            .unwrap_or_default()
+            .to_rfc3339(),
+        status: LaunchStatus {
+            abbrev: "Go".to_string(),
+        },
+        launch_service_provider: Some(Named {
+            name: "Mock Launch Services".to_string(),
+        }),
+        mission: Some(Mission {
+            name: name.split(" | ").last().unwrap_or(name).to_string(),
+            description: None,
+            kind: Some(kind.to_string()),
+            orbit: Some(MissionOrbit {
+                abbrev: orbit.to_string(),
+            }),
+        }),
+        pad: Some(Pad {
+            name: "Mock Pad 1".to_string(),
+            location: Named {
+                name: "Mock Spaceport".to_string(),
+            },
+        }),
+    })
+    .collect()
+}
@@ -1,95 +1,684 @@
-use serde::{Deserialize, Serialize};
-use tracing::info;
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicI64, AtomicUsize, Ordering},
+        Mutex,
+    },
+    time::{Duration, Instant},
+};
 
-use crate::{database::Location, util};
+use chrono::{NaiveDate, Utc};
+use poise::ChoiceParameter;
+use reqwest::{header::RETRY_AFTER, Response, StatusCode};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use tokio::time::sleep;
+use tracing::{info, warn};
+
+use crate::{config, database::Location, error::BotError, mock, util};
+
+/// The fraction of the hourly transaction limit after which the quota is considered nearly
+/// exhausted.
+const QUOTA_WARNING_THRESHOLD: f64 = 0.9;
+
+/// The default root of the N2YO REST API, overridable with `N2YO_BASE_URL`.
+pub const DEFAULT_BASE_URL: &str = "https://api.n2yo.com/rest/v1";
+/// How many seconds a satellite must be visible for to count as a visual pass.
+const MIN_VISIBILITY: usize = 60;
+/// The magnitude N2YO gives visual passes when it does not know how bright the satellite is.
+const UNKNOWN_MAGNITUDE: f64 = 100_000.0;
 
 pub struct N2YOAPI {
-    api_key: String,
+    base_url: String,
+    keys: Vec<ApiKey>,
+    next_key: AtomicUsize,
     client: reqwest::Client,
+    hourly_limit: usize,
+    retry_policy: RetryPolicy,
+    cache: PassCache,
+    rate_limiter: RateLimiter,
+    /// Whether canned responses are returned instead of calling N2YO.
+    mock: bool,
+}
+
+/// A token bucket limiting how many requests are sent to N2YO, shared by every caller.
+struct RateLimiter {
+    capacity: f64,
+    refill_per_second: f64,
+    state: Mutex<(f64, Instant)>,
+}
+
+impl RateLimiter {
+    fn per_hour(requests: usize) -> Self {
+        Self {
+            capacity: requests as f64,
+            refill_per_second: requests as f64 / (60.0 * 60.0),
+            state: Mutex::new((requests as f64, Instant::now())),
+        }
+    }
+
+    /// Takes a token from the bucket, or returns how long until one is available.
+    fn try_acquire(&self) -> Result<(), Duration> {
+        let mut state = self.state.lock().unwrap();
+        let (tokens, last_refill) = &mut *state;
+        *tokens = (*tokens + last_refill.elapsed().as_secs_f64() * self.refill_per_second)
+            .min(self.capacity);
+        *last_refill = Instant::now();
+
+        if *tokens >= 1.0 {
+            *tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64(
+                (1.0 - *tokens) / self.refill_per_second,
+            ))
+        }
+    }
+}
+
+/// Whether a request may be served from the pass cache.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheMode {
+    /// Serve from the cache if a fresh entry exists.
+    Use,
+    /// Always query N2YO, refreshing the cache with the result.
+    Bypass,
+}
+
+/// The parameters of a pass request, with the location rounded so that nearby locations
+/// share entries.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct PassQuery {
+    satellite_id: usize,
+    latitude: i64,
+    longitude: i64,
+    altitude: i64,
+    days: usize,
+    min_max_elevation: i64,
+    kind: PassKind,
+}
+
+impl PassQuery {
+    fn new(
+        satellite_id: usize,
+        location: &Location,
+        days: usize,
+        min_max_elevation: f64,
+        kind: PassKind,
+    ) -> Self {
+        Self {
+            satellite_id,
+            latitude: (location.latitude * 100.0).round() as i64,
+            longitude: (location.longitude * 100.0).round() as i64,
+            altitude: location.altitude.round() as i64,
+            days,
+            min_max_elevation: (min_max_elevation * 10.0).round() as i64,
+            kind,
+        }
+    }
+}
+
+/// An in-memory cache of pass responses, with entries expiring after a fixed TTL.
+struct PassCache {
+    ttl: Duration,
+    entries: Mutex<HashMap<PassQuery, (Instant, SatellitePasses)>>,
+}
+
+impl PassCache {
+    fn get(&self, query: &PassQuery) -> Option<SatellitePasses> {
+        let entries = self.entries.lock().unwrap();
+        let (inserted_at, passes) = entries.get(query)?;
+
+        if inserted_at.elapsed() < self.ttl {
+            Some(passes.clone())
+        } else {
+            None
+        }
+    }
+
+    fn insert(&self, query: PassQuery, passes: &SatellitePasses) {
+        if self.ttl.is_zero() {
+            return;
+        }
+
+        let mut entries = self.entries.lock().unwrap();
+        entries.retain(|_, (inserted_at, _)| inserted_at.elapsed() < self.ttl);
+        entries.insert(query, (Instant::now(), passes.clone()));
+    }
+}
+
+/// How failed requests are retried.
+pub struct RetryPolicy {
+    /// The total number of attempts made for a request, including the first.
+    pub attempts: usize,
+    /// The delay before the first retry, doubled for each subsequent retry.
+    pub base_delay: Duration,
+    /// The upper bound for any single delay.
+    pub max_delay: Duration,
+}
+
+impl RetryPolicy {
+    pub fn from_config() -> Self {
+        Self {
+            attempts: config::get().n2yo.retry_attempts,
+            base_delay: Duration::from_millis(config::get().n2yo.retry_base_delay_ms),
+            max_delay: Duration::from_secs(30),
+        }
+    }
+
+    /// The delay before the given retry (starting at 0), with up to 50% jitter applied.
+    fn delay(&self, retry: usize) -> Duration {
+        let delay = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(retry as u32))
+            .min(self.max_delay);
+        delay.mul_f64(0.5 + util::jitter() * 0.5)
+    }
+}
+
+/// An N2YO API key, along with its transaction counters.
+pub struct ApiKey {
+    key: String,
+    hourly_transactions: AtomicUsize,
+    hourly_transactions_recorded_at: AtomicI64,
+    total_transactions: AtomicUsize,
+    daily_transactions: Mutex<(NaiveDate, usize)>,
+    rate_limited_until: AtomicI64,
+}
+
+impl ApiKey {
+    fn new(key: &str) -> Self {
+        Self {
+            key: key.to_string(),
+            hourly_transactions: AtomicUsize::new(0),
+            hourly_transactions_recorded_at: AtomicI64::new(0),
+            total_transactions: AtomicUsize::new(0),
+            daily_transactions: Mutex::new((Utc::now().date_naive(), 0)),
+            rate_limited_until: AtomicI64::new(0),
+        }
+    }
+
+    /// The key with all but its last four characters hidden, suitable for display.
+    pub fn masked(&self) -> String {
+        let visible = self.key.len().saturating_sub(4);
+        format!("{}{}", "*".repeat(visible.min(8)), &self.key[visible..])
+    }
+
+    /// The number of transactions N2YO reported as used with this key in the last hour.
+    pub fn hourly_transactions(&self) -> usize {
+        let recorded_at = self.hourly_transactions_recorded_at.load(Ordering::Relaxed);
+
+        if util::current_utc() - recorded_at > 60 * 60 {
+            0
+        } else {
+            self.hourly_transactions.load(Ordering::Relaxed)
+        }
+    }
+
+    /// The number of transactions made with this key since the bot started.
+    pub fn total_transactions(&self) -> usize {
+        self.total_transactions.load(Ordering::Relaxed)
+    }
+
+    /// The number of transactions made with this key since midnight UTC.
+    pub fn transactions_today(&self) -> usize {
+        let (date, count) = *self.daily_transactions.lock().unwrap();
+
+        if date == Utc::now().date_naive() {
+            count
+        } else {
+            0
+        }
+    }
+
+    pub fn is_rate_limited(&self) -> bool {
+        self.rate_limited_until.load(Ordering::Relaxed) > util::current_utc()
+    }
+
+    fn record_transactions(&self, transaction_count: usize) {
+        self.hourly_transactions
+            .store(transaction_count, Ordering::Relaxed);
+        self.hourly_transactions_recorded_at
+            .store(util::current_utc(), Ordering::Relaxed);
+        self.total_transactions.fetch_add(1, Ordering::Relaxed);
+
+        let today = Utc::now().date_naive();
+        let mut daily_transactions = self.daily_transactions.lock().unwrap();
+        if daily_transactions.0 != today {
+            *daily_transactions = (today, 0);
+        }
+        daily_transactions.1 += 1;
+    }
+
+    fn mark_rate_limited(&self, retry_after: Option<Duration>) {
+        let duration = retry_after.unwrap_or(Duration::from_secs(60 * 60));
+        warn!(
+            "N2YO key {} is rate limited, skipping it for {}s",
+            self.masked(),
+            duration.as_secs()
+        );
+        self.rate_limited_until.store(
+            util::current_utc() + duration.as_secs() as i64,
+            Ordering::Relaxed,
+        );
+    }
 }
 
 impl N2YOAPI {
     pub fn new() -> anyhow::Result<Self> {
+        Self::build(mock::is_enabled())
+    }
+
+    /// A client that returns canned responses, whatever the configuration.
+    #[cfg(test)]
+    pub fn mocked() -> Self {
+        Self::build(true).unwrap()
+    }
+
+    fn build(mock: bool) -> anyhow::Result<Self> {
         info!("Creating N2YO API client");
+
+        if mock {
+            info!("Mock mode is enabled, N2YO will not be contacted");
+        }
+
+        let config = &config::get().n2yo;
+        let mut keys = config
+            .keys
+            .iter()
+            .map(|key| key.trim())
+            .filter(|key| !key.is_empty())
+            .map(ApiKey::new)
+            .collect::<Vec<_>>();
+
+        if keys.is_empty() && mock {
+            keys.push(ApiKey::new("mock"));
+        }
+
+        if keys.is_empty() {
+            return Err(anyhow::anyhow!("N2YO_KEY must contain at least one key"));
+        }
+
+        info!("Using {} N2YO API key(s)", keys.len());
+        let hourly_limit = config.hourly_limit;
+        let requests_per_hour = config
+            .requests_per_hour
+            .unwrap_or(hourly_limit * keys.len());
+
+        let mut client = reqwest::ClientBuilder::new()
+            .user_agent(util::USER_AGENT)
+            .timeout(Duration::from_secs(config.timeout_secs));
+
+        if let Some(proxy) = &config.proxy {
+            info!("Using proxy for N2YO requests");
+            client = client.proxy(reqwest::Proxy::all(proxy)?);
+        }
+
         Ok(Self {
-            api_key: util::env("N2YO_KEY")?,
-            client: reqwest::ClientBuilder::new()
-                .user_agent("sat-bot (james@jamalam.tech)")
-                .build()?,
+            base_url: config.base_url.trim_end_matches('/').to_string(),
+            rate_limiter: RateLimiter::per_hour(requests_per_hour),
+            keys,
+            next_key: AtomicUsize::new(0),
+            client: client.build()?,
+            hourly_limit,
+            retry_policy: RetryPolicy::from_config(),
+            cache: PassCache {
+                ttl: Duration::from_secs(config.cache_ttl_secs),
+                entries: Mutex::new(HashMap::new()),
+            },
+            mock,
         })
     }
 
+    pub fn keys(&self) -> &[ApiKey] {
+        &self.keys
+    }
+
+    /// The number of transactions N2YO reported as used in the last hour, across all keys.
+    pub fn hourly_transactions(&self) -> usize {
+        self.keys.iter().map(ApiKey::hourly_transactions).sum()
+    }
+
+    /// The number of transactions made since the bot started, across all keys.
+    pub fn total_transactions(&self) -> usize {
+        self.keys.iter().map(ApiKey::total_transactions).sum()
+    }
+
+    /// The number of transactions made since midnight UTC, across all keys.
+    pub fn transactions_today(&self) -> usize {
+        self.keys.iter().map(ApiKey::transactions_today).sum()
+    }
+
+    /// The hourly transaction limit of a single key.
+    pub fn hourly_limit(&self) -> usize {
+        self.hourly_limit
+    }
+
+    /// The hourly transaction limit across all keys.
+    pub fn total_hourly_limit(&self) -> usize {
+        self.hourly_limit * self.keys.len()
+    }
+
+    /// Whether the hourly transaction limit of every key is nearly exhausted.
+    pub fn is_near_limit(&self) -> bool {
+        self.keys.iter().all(|key| self.is_key_near_limit(key))
+    }
+
+    fn is_key_near_limit(&self, key: &ApiKey) -> bool {
+        key.is_rate_limited()
+            || key.hourly_transactions() as f64
+                >= self.hourly_limit as f64 * QUOTA_WARNING_THRESHOLD
+    }
+
+    /// Gets the passes of a satellite that reach an elevation. Visual passes are those where the
+    /// satellite can be seen with the naked eye, and come with its brightness.
     pub async fn get_satellite_passes(
         &self,
         satellite_id: usize,
         location: &Location,
         days: usize,
         min_max_elevation: f64,
+        kind: PassKind,
+        cache_mode: CacheMode,
     ) -> anyhow::Result<SatellitePasses> {
-        let url = format!(
-            "https://api.n2yo.com/rest/v1/satellite/radiopasses/{}/{}/{}/{}/{}/{}&apiKey={}",
-            satellite_id,
-            location.latitude,
-            location.longitude,
-            location.altitude,
-            days,
-            min_max_elevation,
-            self.api_key
-        );
+        let query = PassQuery::new(satellite_id, location, days, min_max_elevation, kind);
+
+        if cache_mode == CacheMode::Use {
+            if let Some(passes) = self.cache.get(&query) {
+                info!("Serving passes for {} from cache", satellite_id);
+                return Ok(passes);
+            }
+        }
 
-        info!("Sending request to {}", url);
+        if self.mock {
+            return Ok(mock::satellite_passes(
+                satellite_id,
+                location,
+                days,
+                min_max_elevation,
+                kind,
+            ));
+        }
+
+        let path = match kind {
+            PassKind::Radio => format!(
+                "satellite/radiopasses/{}/{}/{}/{}/{}/{}",
+                satellite_id,
+                location.latitude,
+                location.longitude,
+                location.altitude,
+                days,
+                min_max_elevation,
+            ),
+            PassKind::Visual => format!(
+                "satellite/visualpasses/{}/{}/{}/{}/{}/{}",
+                satellite_id,
+                location.latitude,
+                location.longitude,
+                location.altitude,
+                days,
+                MIN_VISIBILITY,
+            ),
+        };
 
-        let response = self
-            .client
-            .get(&url)
-            .send()
-            .await?
-            .json::<JsonSatellitePasses>()
-            .await?;
-        Ok(response.into())
+        let (json, raw) = self
+            .request::<JsonSatellitePasses>(&path)
+            .await
+            .map_err(BotError::N2yo)?;
+        let mut passes = SatellitePasses {
+            raw: Some(raw),
+            ..json.into()
+        };
+        // The visual pass endpoint takes a visible duration rather than an elevation
+        passes
+            .passes
+            .retain(|pass| pass.max_elevation >= min_max_elevation);
+        self.cache.insert(query, &passes);
+        Ok(passes)
     }
 
-    pub async fn get_name_from_norad_id(&self, satellite_id: usize) -> anyhow::Result<String> {
-        let url = format!(
-            "https://api.n2yo.com/rest/v1/satellite/radiopasses/{}/{}/{}/{}/{}/{}&apiKey={}",
-            satellite_id, 12.0, 12.0, 12.0, 12, 1, self.api_key
-        );
+    /// Gets the current TLE of a satellite, which also includes its name.
+    pub async fn get_tle(&self, satellite_id: usize) -> anyhow::Result<SatelliteTle> {
+        if self.mock {
+            return Ok(mock::tle(satellite_id));
+        }
+
+        let path = format!("satellite/tle/{}", satellite_id);
+        let (response, _) = self
+            .request::<SatelliteTle>(&path)
+            .await
+            .map_err(BotError::N2yo)?;
 
-        info!("Sending request to {}", url);
+        if response.tle.trim().is_empty() {
+            return Err(BotError::UnknownSatellite(satellite_id).into());
+        }
 
-        let response = self
-            .client
-            .get(&url)
-            .send()
-            .await?
-            .json::<JsonSatellitePasses>()
-            .await?;
-        Ok(response.info.name.clone())
+        Ok(response)
+    }
+
+    /// Sends a request to the API, rotating through the configured keys and skipping any that
+    /// are rate limited. Returns the response along with its body as it was received.
+    async fn request<T: N2YOResponse>(&self, path: &str) -> anyhow::Result<(T, String)> {
+        if let Err(wait) = self.rate_limiter.try_acquire() {
+            return Err(anyhow::anyhow!(
+                "N2YO request limit reached, try again in {} minute(s)",
+                wait.as_secs().div_ceil(60)
+            ));
+        }
+
+        let start = self.next_key.fetch_add(1, Ordering::Relaxed);
+
+        for offset in 0..self.keys.len() {
+            let key = &self.keys[(start + offset) % self.keys.len()];
+
+            if key.is_rate_limited() {
+                continue;
+            }
+
+            let url = format!("{}/{}&apiKey={}", self.base_url, path, key.key);
+
+            info!(
+                "Sending request to {}",
+                url.replace(&key.key, &key.masked())
+            );
+
+            let response = self.send_with_retry(&url).await?;
+
+            if response.status() == StatusCode::TOO_MANY_REQUESTS {
+                key.mark_rate_limited(retry_after(&response));
+                continue;
+            }
+
+            let body = response.text().await.map_err(reqwest::Error::without_url)?;
+            let response = match serde_json::from_str::<JsonResponse<T>>(&body)? {
+                JsonResponse::Ok(response) => response,
+                JsonResponse::Error { error } if is_rate_limit_error(&error) => {
+                    key.mark_rate_limited(None);
+                    continue;
+                }
+                JsonResponse::Error { error } => {
+                    return Err(anyhow::anyhow!("N2YO returned an error: {}", error))
+                }
+            };
+            key.record_transactions(response.transaction_count());
+
+            if self.is_key_near_limit(key) {
+                warn!(
+                    "N2YO transaction quota nearly exhausted for key {} ({}/{} in the last hour)",
+                    key.masked(),
+                    response.transaction_count(),
+                    self.hourly_limit
+                );
+            }
+
+            return Ok((response, body));
+        }
+
+        Err(anyhow::anyhow!(
+            "all N2YO API keys are rate limited, try again later"
+        ))
+    }
+
+    /// Sends a GET request, retrying transport errors and server errors according to the
+    /// retry policy. The URL, which contains the API key, is left out of any error.
+    async fn send_with_retry(&self, url: &str) -> anyhow::Result<Response> {
+        let mut retry = 0;
+
+        loop {
+            let response = self
+                .client
+                .get(url)
+                .send()
+                .await
+                .map_err(reqwest::Error::without_url);
+            let delay = match response {
+                Ok(response) if !response.status().is_server_error() => return Ok(response),
+                Ok(response) if retry + 1 >= self.retry_policy.attempts => {
+                    return Ok(response
+                        .error_for_status()
+                        .map_err(reqwest::Error::without_url)?)
+                }
+                Err(e) if retry + 1 >= self.retry_policy.attempts => return Err(e.into()),
+                Ok(response) => {
+                    warn!("N2YO responded with {}, retrying", response.status());
+                    retry_after(&response).unwrap_or_else(|| self.retry_policy.delay(retry))
+                }
+                Err(e) => {
+                    warn!("Request to N2YO failed ({}), retrying", e);
+                    self.retry_policy.delay(retry)
+                }
+            };
+
+            sleep(delay.min(self.retry_policy.max_delay)).await;
+            retry += 1;
+        }
     }
 }
 
-#[derive(Debug)]
+/// The delay requested by a response's `Retry-After` header, if it is given in seconds.
+fn retry_after(response: &Response) -> Option<Duration> {
+    response
+        .headers()
+        .get(RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .parse()
+        .ok()
+        .map(Duration::from_secs)
+}
+
+#[derive(Debug, Clone)]
 pub struct SatellitePasses {
     pub info: SatellitePassInfo,
     pub passes: Vec<SatellitePass>,
+    pub source: PassSource,
+    /// The response from N2YO as it was received, if the passes came from it.
+    pub raw: Option<String>,
+}
+
+/// Where a set of pass predictions came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PassSource {
+    N2YO,
+    /// Predicted locally with SGP4 from a cached TLE.
+    Local,
+}
+
+impl std::fmt::Display for PassSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PassSource::N2YO => write!(f, "N2YO"),
+            PassSource::Local => write!(f, "local SGP4 prediction, may differ slightly"),
+        }
+    }
 }
 
 impl From<JsonSatellitePasses> for SatellitePasses {
     fn from(json: JsonSatellitePasses) -> Self {
+        let mut passes = json.passes.unwrap_or_default();
+
+        for pass in passes.iter_mut() {
+            pass.magnitude = pass
+                .magnitude
+                .filter(|magnitude| *magnitude < UNKNOWN_MAGNITUDE);
+        }
+
         Self {
             info: json.info,
-            passes: json.passes.unwrap_or_default(),
+            passes,
+            source: PassSource::N2YO,
+            raw: None,
         }
     }
 }
 
+/// Which kind of passes to find.
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ChoiceParameter,
+)]
+pub enum PassKind {
+    /// Passes above the horizon, for listening to the satellite.
+    #[default]
+    #[name = "radio"]
+    Radio,
+    /// Passes where the satellite can be seen with the naked eye, with how bright it will be.
+    #[name = "visual"]
+    Visual,
+}
+
+/// A response from N2YO, which is either the expected payload or an error message.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum JsonResponse<T> {
+    Error { error: String },
+    Ok(T),
+}
+
+/// Whether an error message returned by N2YO indicates that the key's quota is exhausted.
+fn is_rate_limit_error(error: &str) -> bool {
+    let error = error.to_lowercase();
+    error.contains("exceeded") || error.contains("limit")
+}
+
+/// A successful response from N2YO, all of which report the key's recent transaction count.
+trait N2YOResponse: DeserializeOwned {
+    fn transaction_count(&self) -> usize;
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 struct JsonSatellitePasses {
     info: SatellitePassInfo,
     passes: Option<Vec<SatellitePass>>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+impl N2YOResponse for JsonSatellitePasses {
+    fn transaction_count(&self) -> usize {
+        self.info.transaction_count
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SatelliteTle {
+    pub info: SatelliteInfo,
+    pub tle: String,
+}
+
+impl N2YOResponse for SatelliteTle {
+    fn transaction_count(&self) -> usize {
+        self.info.transaction_count
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SatelliteInfo {
+    #[serde(rename = "satid")]
+    pub id: usize,
+    #[serde(rename = "satname")]
+    pub name: String,
+    #[serde(rename = "transactionscount")]
+    pub transaction_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SatellitePassInfo {
     #[serde(rename = "satid")]
     pub id: usize,
@@ -123,4 +712,50 @@ pub struct SatellitePass {
     pub end_azimuth_compass: String,
     #[serde(rename = "endUTC")]
     pub end_utc: usize,
+    /// The fraction of the pass the satellite can be seen for with the naked eye, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub visible_fraction: Option<f64>,
+    /// The forecast cloud cover at the location during the pass, as a percentage, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cloud_cover: Option<u8>,
+    /// The closest the satellite comes to the Moon while both are up, in degrees, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub moon_separation: Option<f64>,
+    /// The brightest apparent magnitude of the satellite during a visual pass, if known. Lower is
+    /// brighter.
+    #[serde(rename = "mag", default, skip_serializing_if = "Option::is_none")]
+    pub magnitude: Option<f64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn keeps_the_api_key_out_of_errors() {
+        let api = N2YOAPI {
+            // Nothing listens on port 1, so the request fails with the URL in the error
+            base_url: "http://127.0.0.1:1".to_string(),
+            keys: vec![ApiKey::new("SECRET-N2YO-KEY")],
+            next_key: AtomicUsize::new(0),
+            client: reqwest::Client::new(),
+            hourly_limit: 100,
+            retry_policy: RetryPolicy {
+                attempts: 1,
+                base_delay: Duration::ZERO,
+                max_delay: Duration::ZERO,
+            },
+            cache: PassCache {
+                ttl: Duration::ZERO,
+                entries: Mutex::new(HashMap::new()),
+            },
+            rate_limiter: RateLimiter::per_hour(100),
+            mock: false,
+        };
+
+        let error = api.get_tle(25544).await.unwrap_err();
+
+        assert!(!format!("{:#}", error).contains("SECRET-N2YO-KEY"));
+        assert!(!format!("{:?}", error).contains("SECRET-N2YO-KEY"));
+    }
 }
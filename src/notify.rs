@@ -0,0 +1,1933 @@
+use std::collections::{HashMap, HashSet};
+
+use async_trait::async_trait;
+use poise::serenity_prelude::{ButtonStyle, ChannelId, UserId};
+use serde_json::json;
+use serenity::{builder::CreateMessage, http::Http, json::Value};
+use tokio::sync::RwLock;
+use tracing::{error, info, warn};
+
+use crate::{
+    config,
+    database::{
+        AnnouncedPass, ChannelMute, Database, HeldPass, PassThread, PendingDelivery, Snowflake,
+        Vacation, WatchedSatellite,
+    },
+    frequencies,
+    i18n::tr,
+    metrics::Metrics,
+    n2yo::{CacheMode, SatellitePass, SatellitePasses},
+    passes,
+    preferences::UserPreferences,
+    provider::{PassProvider, PassRequest},
+    rsvp, sstv, templates, tracking,
+    util::{self, TimeStyle},
+};
+
+/// Why a message could not be delivered.
+#[derive(Debug)]
+pub enum DeliveryError {
+    /// The channel was deleted or can no longer be accessed, so retrying is pointless.
+    ChannelGone(anyhow::Error),
+    /// Any other failure, which may succeed if retried.
+    Failed(anyhow::Error),
+}
+
+/// Delivers notifications to Discord.
+#[async_trait]
+pub trait Notifier: Send + Sync {
+    /// Sends a message to a channel, returning its ID.
+    async fn send_message(&self, channel: u64, message: &Value) -> Result<u64, DeliveryError>;
+    async fn direct_message(&self, user: u64, message: &str) -> anyhow::Result<()>;
+    async fn post_to_ops_channel(&self, message: &str);
+    /// Starts a public thread on a message, returning the thread's ID.
+    async fn create_thread(&self, channel: u64, message: u64, name: &str) -> anyhow::Result<u64>;
+    /// Archives and locks a thread.
+    async fn archive_thread(&self, thread: u64) -> anyhow::Result<()>;
+}
+
+#[async_trait]
+impl Notifier for Http {
+    async fn send_message(&self, channel: u64, message: &Value) -> Result<u64, DeliveryError> {
+        match Http::send_message(self, channel, message).await {
+            Ok(message) => Ok(message.id.0),
+            Err(e) if util::is_dead_channel_error(&e) => Err(DeliveryError::ChannelGone(e.into())),
+            Err(e) => Err(DeliveryError::Failed(e.into())),
+        }
+    }
+
+    async fn direct_message(&self, user: u64, message: &str) -> anyhow::Result<()> {
+        UserId(user)
+            .create_dm_channel(self)
+            .await?
+            .say(self, message)
+            .await?;
+        Ok(())
+    }
+
+    async fn post_to_ops_channel(&self, message: &str) {
+        util::post_to_ops_channel(self, message).await;
+    }
+
+    async fn create_thread(&self, channel: u64, message: u64, name: &str) -> anyhow::Result<u64> {
+        let thread = ChannelId(channel)
+            .create_public_thread(self, message, |t| {
+                t.name(name)
+                    .auto_archive_duration(THREAD_AUTO_ARCHIVE_MINUTES)
+            })
+            .await?;
+        Ok(thread.id.0)
+    }
+
+    async fn archive_thread(&self, thread: u64) -> anyhow::Result<()> {
+        ChannelId(thread)
+            .edit_thread(self, |t| t.archived(true).locked(true))
+            .await?;
+        Ok(())
+    }
+}
+
+/// How long a thread may go without messages before Discord hides it, in minutes.
+const THREAD_AUTO_ARCHIVE_MINUTES: u16 = 24 * 60;
+/// The longest embed title Discord allows.
+const MAX_TITLE_LENGTH: usize = 256;
+/// The longest embed description Discord allows.
+const MAX_DESCRIPTION_LENGTH: usize = 4096;
+/// How long passes are kept after they end so they can be looked up by ID, in days.
+pub const PASS_HISTORY_DAYS: i64 = 30;
+/// The longest thread name Discord allows.
+const MAX_THREAD_NAME_LENGTH: usize = 100;
+/// The most held passes listed when a mute lifts, to stay within Discord's description limit.
+const MAX_HELD_PASSES: usize = 20;
+/// The lowest quality score of the passes users are reminded of when they return from vacation.
+const VACATION_MIN_QUALITY: u8 = 70;
+/// The most passes users are reminded of when they return from vacation.
+const MAX_VACATION_PASSES: usize = 10;
+
+/// Groups identical pass requests, so that watches of the same satellite from the same place cost
+/// a single lookup. Returns the distinct requests, and for each request which of them answers it.
+fn group_requests(requests: Vec<PassRequest>) -> (Vec<PassRequest>, Vec<usize>) {
+    let mut distinct = Vec::new();
+    let mut indices = HashMap::new();
+    let request_indices = requests
+        .into_iter()
+        .map(|request| {
+            *indices.entry(request.key()).or_insert_with(|| {
+                distinct.push(request);
+                distinct.len() - 1
+            })
+        })
+        .collect();
+
+    (distinct, request_indices)
+}
+
+/// Provides the passes notifications are made for.
+#[async_trait]
+pub trait PassFetcher: Send + Sync {
+    /// A message explaining why notification runs should be skipped for now, if they should.
+    fn quota_warning(&self) -> Option<String>;
+    async fn get_many_satellite_passes(
+        &self,
+        requests: Vec<PassRequest>,
+    ) -> Vec<anyhow::Result<SatellitePasses>>;
+    /// A summary of the main downlink of a satellite, if it is known.
+    async fn primary_transmitter(&self, satellite_id: usize) -> Option<String>;
+}
+
+#[async_trait]
+impl PassFetcher for PassProvider {
+    fn quota_warning(&self) -> Option<String> {
+        let n2yo_api = self.n2yo_api();
+
+        n2yo_api.is_near_limit().then(|| {
+            format!(
+                "N2YO transaction quota nearly exhausted ({}/{} in the last hour), skipping notification run",
+                n2yo_api.hourly_transactions(),
+                n2yo_api.total_hourly_limit()
+            )
+        })
+    }
+
+    async fn get_many_satellite_passes(
+        &self,
+        requests: Vec<PassRequest>,
+    ) -> Vec<anyhow::Result<SatellitePasses>> {
+        PassProvider::get_many_satellite_passes_staggered(self, requests, CacheMode::Bypass).await
+    }
+
+    async fn primary_transmitter(&self, satellite_id: usize) -> Option<String> {
+        PassProvider::primary_transmitter(self, satellite_id).await
+    }
+}
+
+/// Notifies watchers of passes they have not yet been told about. In a dry run, the notifications
+/// are only logged and nothing is recorded. A failure for one watch does not affect the others.
+pub async fn notify_of_new_passes(
+    notifier: &impl Notifier,
+    database: &RwLock<Database>,
+    pass_fetcher: &impl PassFetcher,
+    metrics: &Metrics,
+    dry_run: bool,
+) -> anyhow::Result<()> {
+    if let Some(message) = pass_fetcher.quota_warning() {
+        warn!("{}", message);
+        notifier.post_to_ops_channel(&message).await;
+        return Ok(());
+    }
+
+    if !dry_run {
+        retry_failed_deliveries(notifier, database).await?;
+        archive_old_threads(notifier, database).await?;
+        lift_expired_mutes(notifier, database).await;
+    }
+
+    let returning = if dry_run {
+        HashMap::new()
+    } else {
+        end_vacations(database).await
+    };
+
+    let sstv_events = database.read().await.contents.sstv_events.clone();
+    let muted_channels = database
+        .read()
+        .await
+        .contents
+        .channel_mutes
+        .iter()
+        .filter(|(_, mute)| mute.until > util::current_utc())
+        .map(|(channel, _)| channel.clone())
+        .collect::<HashSet<_>>();
+    let on_vacation = database
+        .read()
+        .await
+        .contents
+        .vacations
+        .keys()
+        .cloned()
+        .collect::<HashSet<_>>();
+    let sstv_min_max_elevation = config::get().sstv.min_max_elevation;
+
+    let (watched_satellites, requests): (Vec<_>, Vec<_>) = {
+        let database = database.read().await;
+        database
+            .contents
+            .watched_satellites
+            .iter()
+            .filter(|watched_satellite| {
+                watched_satellite.broken.is_none()
+                    && !watched_satellite.paused
+                    && !on_vacation.contains(&watched_satellite.watcher)
+            })
+            .filter_map(|watched_satellite| {
+                let Some(location) = database
+                    .contents
+                    .locations
+                    .iter()
+                    .find(|location| location.name.0 == watched_satellite.location.0)
+                else {
+                    warn!(
+                        "Skipping watch of {} at unknown location {}",
+                        watched_satellite.name, watched_satellite.location.0
+                    );
+                    return None;
+                };
+
+                let time_format = watched_satellite.timezone().map(|timezone| {
+                    UserPreferences::for_user(&database, &watched_satellite.watcher)
+                        .time_format(&watched_satellite.locale)
+                        .in_timezone(timezone)
+                });
+
+                Some((
+                    (watched_satellite.clone(), location.clone(), time_format),
+                    PassRequest {
+                        satellite_id: watched_satellite.satellite_id.0,
+                        location: location.clone(),
+                        days: watched_satellite.days_ahead,
+                        min_max_elevation: if sstv_events.iter().any(|event| {
+                            watched_satellite.satellite_id.0 == sstv::ISS
+                                && event.end > util::current_utc()
+                                && event.start
+                                    < util::current_utc()
+                                        + watched_satellite.days_ahead as i64 * 24 * 60 * 60
+                        }) {
+                            watched_satellite
+                                .min_max_elevation
+                                .min(sstv_min_max_elevation)
+                        } else {
+                            watched_satellite.min_max_elevation
+                        },
+                        kind: watched_satellite.kind,
+                    },
+                ))
+            })
+            .unzip()
+    };
+
+    let (requests, request_indices) = group_requests(requests);
+    let fetched = pass_fetcher.get_many_satellite_passes(requests).await;
+    let all_passes = request_indices
+        .into_iter()
+        .map(|index| match &fetched[index] {
+            Ok(passes) => Ok(passes.clone()),
+            Err(e) => Err(anyhow::anyhow!("{:#}", e)),
+        })
+        .collect::<Vec<_>>();
+    let default_notify_within_hours = config::get().notifications.within_hours;
+    let mut successful_notifications = Vec::new();
+    let mut pending_passes = Vec::new();
+    let mut failed_deliveries = Vec::new();
+    let mut broken_watches = Vec::new();
+    let mut delivered = Vec::new();
+    let mut held_passes = Vec::new();
+    let mut returning_passes = HashMap::<Snowflake, Vec<(String, SatellitePass)>>::new();
+    let mut failed_fetches = Vec::new();
+
+    for ((watched_satellite, location, time_format), passes) in
+        watched_satellites.iter().zip(all_passes)
+    {
+        let passes = match passes {
+            Ok(passes) => passes,
+            Err(e) => {
+                error!(
+                    "Failed to get passes of {} at {}: {}",
+                    watched_satellite.name, watched_satellite.location.0, e
+                );
+
+                if !dry_run {
+                    let mut tags = vec![
+                        ("task", "notify".to_string()),
+                        ("satellite_id", watched_satellite.satellite_id.0.to_string()),
+                    ];
+
+                    if let Some(guild) = &watched_satellite.guild {
+                        tags.push(("guild", guild.0.to_string()));
+                    }
+
+                    crate::error::capture(&e, &tags);
+                }
+
+                failed_fetches.push(format!(
+                    "{} at {}: {}",
+                    watched_satellite.name, watched_satellite.location.0, e
+                ));
+                continue;
+            }
+        };
+
+        if returning.contains_key(&watched_satellite.watcher) {
+            returning_passes
+                .entry(watched_satellite.watcher.clone())
+                .or_default()
+                .extend(
+                    passes
+                        .passes
+                        .iter()
+                        .filter(|pass| {
+                            (pass.end_utc as i64) > util::current_utc()
+                                && passes::score(pass) >= VACATION_MIN_QUALITY
+                        })
+                        .map(|pass| (watched_satellite.name.clone(), pass.clone())),
+                );
+        }
+
+        let downlink = match frequencies::lookup(watched_satellite.satellite_id.0) {
+            Some(satellite) => {
+                let transmitter = satellite.primary();
+                Some(format!(
+                    "{} {}",
+                    frequencies::format_frequency(transmitter.downlink),
+                    transmitter.mode
+                ))
+            }
+            None if watched_satellite.show_transmitter => {
+                pass_fetcher
+                    .primary_transmitter(watched_satellite.satellite_id.0)
+                    .await
+            }
+            None => None,
+        };
+        let notify_within_hours = watched_satellite
+            .notify_within_hours
+            .unwrap_or(default_notify_within_hours);
+
+        let mut candidates = watched_satellite.pending_passes.clone();
+        for pass in passes.passes.iter() {
+            if !candidates.iter().any(|other| is_same_pass(other, pass)) {
+                candidates.push(pass.clone());
+            }
+        }
+
+        let mut held_back = Vec::new();
+        let mut b = CreateMessage::default();
+        let mut embeds = 0;
+        let mut pass_buttons = Vec::new();
+        let mut notified_passes = Vec::new();
+
+        for pass in candidates.iter() {
+            let sstv_event =
+                sstv::event_during(&sstv_events, watched_satellite.satellite_id.0, pass);
+            // SSTV is received rather than seen, so events relax the watch's filters
+            let included = match sstv_event {
+                Some(_) => {
+                    pass.max_elevation
+                        >= watched_satellite
+                            .min_max_elevation
+                            .min(sstv_min_max_elevation)
+                }
+                None => {
+                    pass.max_elevation >= watched_satellite.min_max_elevation
+                        && watched_satellite.lighting.includes(location, pass)
+                        && watched_satellite
+                            .min_quality
+                            .is_none_or(|min_quality| passes::score(pass) >= min_quality)
+                        && passes::is_brighter_than(pass, watched_satellite.max_magnitude)
+                }
+            };
+
+            if !included
+                || (pass.end_utc as i64) < util::current_utc()
+                || watched_satellite
+                    .previous_notifications
+                    .iter()
+                    .any(|(start, end)| {
+                        util::are_within_10_seconds(*start as i64, pass.start_utc as i64)
+                            && util::are_within_10_seconds(*end as i64, pass.end_utc as i64)
+                    })
+            {
+                continue;
+            }
+
+            if notify_within_hours > 0
+                && pass.start_utc as i64 - util::current_utc()
+                    > notify_within_hours as i64 * 60 * 60
+            {
+                held_back.push(pass.clone());
+                continue;
+            }
+
+            let id = passes::pass_id(watched_satellite.satellite_id.0, pass.max_utc);
+            successful_notifications.push((watched_satellite.clone(), pass.clone(), id.clone()));
+
+            if muted_channels.contains(&watched_satellite.channel) {
+                held_passes.push((
+                    watched_satellite.channel.clone(),
+                    HeldPass {
+                        satellite: watched_satellite.name.clone(),
+                        location: watched_satellite.location.clone(),
+                        id,
+                        pass: pass.clone(),
+                    },
+                ));
+                continue;
+            }
+
+            embeds += 1;
+            notified_passes.push(pass.clone());
+            pass_buttons.push((
+                rsvp::button_id(
+                    embeds - 1,
+                    watched_satellite.satellite_id.0,
+                    pass,
+                    &watched_satellite.location.0,
+                ),
+                tracking::button_id(
+                    watched_satellite.satellite_id.0,
+                    pass,
+                    &watched_satellite.location.0,
+                ),
+            ));
+
+            b.add_embed(|e| {
+                let locale = &watched_satellite.locale;
+                e.colour(passes::colour(pass.max_elevation));
+                e.title(match &watched_satellite.title_template {
+                    Some(template) => {
+                        // Discord does not render timestamps in titles
+                        let title_format = time_format
+                            .unwrap_or_else(|| UserPreferences::default().time_format(locale));
+                        let title = templates::render(template, |placeholder| {
+                            template_value(
+                                watched_satellite,
+                                pass,
+                                &id,
+                                TimeStyle::Plain(&title_format),
+                                placeholder,
+                            )
+                        });
+                        util::truncate(&title, MAX_TITLE_LENGTH)
+                    }
+                    None => tr!(
+                        locale,
+                        "notification-title",
+                        satellite = watched_satellite.name.as_str(),
+                        location = watched_satellite.location.0.as_str()
+                    ),
+                });
+
+                e.description(match &watched_satellite.description_template {
+                    Some(template) => {
+                        // Placeholders such as {path} can make the description much longer than
+                        // the template
+                        let description = templates::render(template, |placeholder| {
+                            template_value(
+                                watched_satellite,
+                                pass,
+                                &id,
+                                TimeStyle::Discord,
+                                placeholder,
+                            )
+                        });
+                        util::truncate(&description, MAX_DESCRIPTION_LENGTH)
+                    }
+                    None => format!(
+                        "{}{}\n{}\n{}",
+                        util::format_pass_time(
+                            pass.start_utc as i64,
+                            pass.end_utc as i64,
+                            TimeStyle::Discord
+                        ),
+                        time_format
+                            .map(|time_format| format!(
+                                "\n{}",
+                                util::format_pass_time(
+                                    pass.start_utc as i64,
+                                    pass.end_utc as i64,
+                                    TimeStyle::Plain(&time_format)
+                                )
+                            ))
+                            .unwrap_or_default(),
+                        passes::describe_path(locale, pass),
+                        passes::describe(locale, pass)
+                    ),
+                });
+
+                if let Some(downlink) = &downlink {
+                    e.field(tr!(locale, "notification-frequency"), downlink, false);
+                }
+
+                if let Some(event) = sstv_event {
+                    e.field(
+                        tr!(locale, "notification-sstv"),
+                        tr!(
+                            locale,
+                            "notification-sstv-active",
+                            frequency = frequencies::format_frequency(event.frequency),
+                            mode = event.mode.clone().unwrap_or_else(|| "SSTV".to_string())
+                        ),
+                        false,
+                    );
+                }
+
+                e.footer(|f| {
+                    f.text(format!(
+                        "{} · {}",
+                        tr!(locale, "notification-pass-id", id = id.as_str()),
+                        tr!(locale, "data-source", source = passes.source.to_string())
+                    ))
+                });
+                e
+            });
+        }
+
+        pending_passes.push((watched_satellite.clone(), held_back));
+
+        if embeds == 0 {
+            continue;
+        }
+
+        add_pass_buttons(&mut b, &watched_satellite.locale, pass_buttons);
+
+        let mut map = serde_json::Map::new();
+        for (key, value) in b.0 {
+            map.insert(key.to_string(), value);
+        }
+
+        let message = Value::Object(map);
+
+        if dry_run {
+            info!(
+                "Would notify channel {} of {} pass(es) of {}",
+                watched_satellite.channel.0, embeds, watched_satellite.name
+            );
+            continue;
+        }
+
+        match notifier
+            .send_message(watched_satellite.channel.0, &message)
+            .await
+        {
+            Ok(message) => {
+                metrics.record_notifications_sent(embeds);
+                delivered.push((watched_satellite.clone(), embeds));
+
+                if watched_satellite.threads {
+                    start_thread(
+                        notifier,
+                        database,
+                        watched_satellite,
+                        message,
+                        &notified_passes,
+                    )
+                    .await;
+                }
+            }
+            Err(DeliveryError::ChannelGone(e)) => {
+                warn!(
+                    "Channel {} is no longer accessible: {}",
+                    watched_satellite.channel.0, e
+                );
+                broken_watches.push(watched_satellite.clone());
+            }
+            Err(DeliveryError::Failed(e)) => {
+                warn!(
+                    "Failed to notify channel {}, queueing for retry: {}",
+                    watched_satellite.channel.0, e
+                );
+                failed_deliveries.push(PendingDelivery {
+                    channel: watched_satellite.channel.clone(),
+                    message,
+                    attempts: 1,
+                    end: notified_passes.iter().map(|pass| pass.end_utc as i64).max(),
+                });
+            }
+        }
+    }
+
+    if dry_run {
+        return Ok(());
+    }
+
+    if let Some(failed_fetch) = failed_fetches.first().filter(|failed_fetch| {
+        crate::error::not_recently_reported(&format!("Failed to get passes: {}", failed_fetch))
+    }) {
+        notifier
+            .post_to_ops_channel(&format!(
+                "Failed to get passes for {} of {} watches, such as {}",
+                failed_fetches.len(),
+                watched_satellites.len(),
+                failed_fetch
+            ))
+            .await;
+    }
+
+    for broken in broken_watches.iter() {
+        disable_watch(notifier, database, broken).await;
+    }
+
+    for (user, vacation) in returning {
+        welcome_back(
+            notifier,
+            database,
+            &user,
+            &vacation,
+            returning_passes.remove(&user).unwrap_or_default(),
+        )
+        .await;
+    }
+
+    let mut database = database.write().await;
+    database
+        .contents
+        .failed_deliveries
+        .extend(failed_deliveries);
+
+    for (watched_satellite, count) in delivered {
+        database.contents.usage.record_notifications(
+            &watched_satellite.satellite_id,
+            &watched_satellite.channel,
+            count,
+        );
+    }
+
+    for (successful, pass, id) in successful_notifications {
+        if let Some(watched_satellite) = database
+            .contents
+            .watched_satellites
+            .iter_mut()
+            .find(|watched_satellite| watched_satellite.is_same_watch(&successful))
+        {
+            watched_satellite
+                .previous_notifications
+                .push((pass.start_utc, pass.end_utc));
+            watched_satellite.announced_passes.push(AnnouncedPass {
+                id,
+                pass,
+                announced_at: util::current_utc(),
+            });
+        }
+    }
+
+    for (channel, held_pass) in held_passes {
+        if let Some(mute) = database.contents.channel_mutes.get_mut(&channel) {
+            mute.held_passes.push(held_pass);
+        }
+    }
+
+    for (held_back_watch, held_back) in pending_passes {
+        if let Some(watched_satellite) = database
+            .contents
+            .watched_satellites
+            .iter_mut()
+            .find(|watched_satellite| watched_satellite.is_same_watch(&held_back_watch))
+        {
+            watched_satellite.pending_passes = held_back;
+        }
+    }
+
+    database
+        .contents
+        .watched_satellites
+        .iter_mut()
+        .for_each(|ws| {
+            ws.previous_notifications.retain(|(start, end)| {
+                !has_more_than_one_day_passed(*start as i64)
+                    && !has_more_than_one_day_passed(*end as i64)
+            });
+            ws.announced_passes.retain(|announced| {
+                util::current_utc() - announced.pass.end_utc as i64
+                    <= PASS_HISTORY_DAYS * 24 * 60 * 60
+            });
+        });
+
+    database.mark_dirty();
+    metrics.record_notify_run();
+
+    Ok(())
+}
+
+/// What a placeholder in a watch's notification template stands for in a pass, from
+/// [`templates::PLACEHOLDERS`].
+fn template_value(
+    watched_satellite: &WatchedSatellite,
+    pass: &SatellitePass,
+    id: &str,
+    style: TimeStyle,
+    placeholder: &str,
+) -> Option<String> {
+    let time = |time: usize| match style {
+        TimeStyle::Discord => format!("<t:{}:f>", time),
+        TimeStyle::Plain(format) => format.date_time(time as i64),
+    };
+
+    Some(match placeholder {
+        "name" => watched_satellite.name.clone(),
+        "location" => watched_satellite.location.0.clone(),
+        "start" => time(pass.start_utc),
+        "end" => time(pass.end_utc),
+        "duration" => util::duration_between(pass.start_utc as i64, pass.end_utc as i64),
+        "max_el" => format!("{:.0}", pass.max_elevation),
+        "quality" => passes::score(pass).to_string(),
+        "path" => passes::describe_path(&watched_satellite.locale, pass),
+        "id" => id.to_string(),
+        _ => return None,
+    })
+}
+
+/// Starts a thread on a notification, named after the satellite and the time of its first pass,
+/// such as "NOAA 19 – Sat 14:32".
+async fn start_thread(
+    notifier: &impl Notifier,
+    database: &RwLock<Database>,
+    watched_satellite: &WatchedSatellite,
+    message: u64,
+    passes: &[SatellitePass],
+) {
+    let (Some(first), Some(end)) = (
+        passes.first(),
+        passes.iter().map(|pass| pass.end_utc as i64).max(),
+    ) else {
+        return;
+    };
+    let name = thread_name(watched_satellite, first);
+
+    match notifier
+        .create_thread(watched_satellite.channel.0, message, &name)
+        .await
+    {
+        Ok(thread) => {
+            let mut database = database.write().await;
+            database.contents.pass_threads.push(PassThread {
+                thread: Snowflake(thread),
+                end,
+            });
+            database.mark_dirty();
+        }
+        Err(e) => warn!(
+            "Failed to start a thread in channel {}: {}",
+            watched_satellite.channel.0, e
+        ),
+    }
+}
+
+/// The name of the thread for a pass, with its start in the watch's timezone, or UTC if it has
+/// none.
+fn thread_name(watched_satellite: &WatchedSatellite, pass: &SatellitePass) -> String {
+    let start = chrono::DateTime::from_timestamp(pass.start_utc as i64, 0).unwrap_or_default();
+    let time = match watched_satellite.timezone() {
+        Some(timezone) => start
+            .with_timezone(&timezone)
+            .format("%a %H:%M")
+            .to_string(),
+        None => start.format("%a %H:%M UTC").to_string(),
+    };
+
+    format!("{} – {}", watched_satellite.name, time)
+        .chars()
+        .take(MAX_THREAD_NAME_LENGTH)
+        .collect()
+}
+
+/// Archives threads whose passes ended long enough ago that reports have stopped coming in. A
+/// thread that cannot be archived, such as one that has been deleted, is forgotten all the same.
+async fn archive_old_threads(
+    notifier: &impl Notifier,
+    database: &RwLock<Database>,
+) -> anyhow::Result<()> {
+    let hours = config::get().notifications.thread_archive_hours;
+    let cutoff = util::current_utc() - hours * 60 * 60;
+
+    let old = {
+        let mut database = database.write().await;
+        let (old, current) = std::mem::take(&mut database.contents.pass_threads)
+            .into_iter()
+            .partition::<Vec<_>, _>(|thread| thread.end < cutoff);
+        database.contents.pass_threads = current;
+
+        if !old.is_empty() {
+            database.mark_dirty();
+        }
+
+        old
+    };
+
+    for thread in old {
+        if let Err(e) = notifier.archive_thread(thread.thread.0).await {
+            warn!("Failed to archive thread {}: {}", thread.thread.0, e);
+        }
+    }
+
+    Ok(())
+}
+
+/// Resends notifications that previously failed to be delivered, dropping any that have reached
+/// the maximum number of attempts.
+async fn retry_failed_deliveries(
+    notifier: &impl Notifier,
+    database: &RwLock<Database>,
+) -> anyhow::Result<()> {
+    let max_attempts = config::get().notifications.delivery_max_attempts;
+    let now = util::current_utc();
+    let (queued, expired): (Vec<_>, Vec<_>) =
+        std::mem::take(&mut database.write().await.contents.failed_deliveries)
+            .into_iter()
+            .partition(|delivery| delivery.end.is_none_or(|end| end > now));
+
+    if !expired.is_empty() {
+        info!(
+            "Dropping {} failed deliveries for passes that have ended",
+            expired.len()
+        );
+        database.write().await.mark_dirty();
+    }
+
+    if queued.is_empty() {
+        return Ok(());
+    }
+
+    info!("Retrying {} failed deliveries", queued.len());
+    let mut still_failing = Vec::new();
+
+    for mut delivery in queued {
+        match notifier
+            .send_message(delivery.channel.0, &delivery.message)
+            .await
+        {
+            Ok(_) => {}
+            Err(DeliveryError::ChannelGone(e)) => {
+                warn!(
+                    "Dropping delivery to inaccessible channel {}: {}",
+                    delivery.channel.0, e
+                );
+            }
+            Err(DeliveryError::Failed(e)) => {
+                delivery.attempts += 1;
+
+                if delivery.attempts >= max_attempts {
+                    error!(
+                        "Giving up on delivery to channel {} after {} attempts: {}",
+                        delivery.channel.0, delivery.attempts, e
+                    );
+                } else {
+                    still_failing.push(delivery);
+                }
+            }
+        }
+    }
+
+    let mut database = database.write().await;
+    database.contents.failed_deliveries.extend(still_failing);
+    database.mark_dirty();
+
+    Ok(())
+}
+
+/// Ends the mutes that have run out, telling each channel of the passes it was not notified of
+/// that are still to come.
+pub async fn lift_expired_mutes(notifier: &impl Notifier, database: &RwLock<Database>) {
+    let now = util::current_utc();
+    let expired = {
+        let mut database = database.write().await;
+        let (expired, muted): (Vec<_>, Vec<_>) =
+            std::mem::take(&mut database.contents.channel_mutes)
+                .into_iter()
+                .partition(|(_, mute)| mute.until <= now);
+        database.contents.channel_mutes = muted.into_iter().collect();
+
+        if !expired.is_empty() {
+            database.mark_dirty();
+        }
+
+        expired
+    };
+
+    for (channel, mute) in expired {
+        info!("Mute of channel {} lifted", channel.0);
+        let message = json!({
+            "embeds": [{
+                "title": tr!(&mute.locale, "mute-lifted-title"),
+                "description": mute_summary(&mute, now),
+            }]
+        });
+
+        if let Err(e) = notifier.send_message(channel.0, &message).await {
+            warn!(
+                "Failed to tell channel {} its mute was lifted: {:?}",
+                channel.0, e
+            );
+        }
+    }
+}
+
+/// Ends the vacations that have run out, returning whose they were.
+async fn end_vacations(database: &RwLock<Database>) -> HashMap<Snowflake, Vacation> {
+    let now = util::current_utc();
+    let mut database = database.write().await;
+    let (ended, away): (HashMap<_, _>, HashMap<_, _>) =
+        std::mem::take(&mut database.contents.vacations)
+            .into_iter()
+            .partition(|(_, vacation)| vacation.until <= now);
+    database.contents.vacations = away;
+
+    if !ended.is_empty() {
+        info!("{} user(s) returned from vacation", ended.len());
+        database.mark_dirty();
+    }
+
+    ended
+}
+
+/// Tells a user back from vacation that their watches have resumed, with the best of the passes
+/// coming up for them.
+async fn welcome_back(
+    notifier: &impl Notifier,
+    database: &RwLock<Database>,
+    user: &Snowflake,
+    vacation: &Vacation,
+    mut upcoming: Vec<(String, SatellitePass)>,
+) {
+    if !UserPreferences::for_user(&*database.read().await, user).direct_messages {
+        return;
+    }
+
+    let locale = &vacation.locale;
+    upcoming.sort_by_key(|(_, pass)| pass.start_utc);
+    let mut message = tr!(locale, "vacation-over");
+
+    if !upcoming.is_empty() {
+        message.push_str("\n\n");
+        message.push_str(&tr!(
+            locale,
+            "vacation-over-passes",
+            quality = VACATION_MIN_QUALITY
+        ));
+    }
+
+    for (name, pass) in upcoming.iter().take(MAX_VACATION_PASSES) {
+        message.push('\n');
+        message.push_str(&tr!(
+            locale,
+            "vacation-over-pass",
+            satellite = name.as_str(),
+            time = util::format_pass_time(
+                pass.start_utc as i64,
+                pass.end_utc as i64,
+                TimeStyle::Discord
+            ),
+            elevation = format!("{:.0}", pass.max_elevation),
+            quality = passes::score(pass)
+        ));
+    }
+
+    if let Err(e) = notifier.direct_message(user.0, &message).await {
+        warn!("Failed to welcome {} back from vacation: {}", user.0, e);
+    }
+}
+
+/// Lists the passes held during a mute that have not yet ended, soonest first.
+fn mute_summary(mute: &ChannelMute, now: i64) -> String {
+    let locale = &mute.locale;
+    let mut upcoming = mute
+        .held_passes
+        .iter()
+        .filter(|held| held.pass.end_utc as i64 > now)
+        .collect::<Vec<_>>();
+    upcoming.sort_by_key(|held| held.pass.start_utc);
+
+    if upcoming.is_empty() {
+        return tr!(locale, "mute-lifted-none");
+    }
+
+    let mut lines = upcoming
+        .iter()
+        .take(MAX_HELD_PASSES)
+        .map(|held| {
+            tr!(
+                locale,
+                "mute-lifted-pass",
+                satellite = held.satellite.as_str(),
+                location = held.location.0.as_str(),
+                time = util::format_pass_time(
+                    held.pass.start_utc as i64,
+                    held.pass.end_utc as i64,
+                    TimeStyle::Discord
+                ),
+                elevation = format!("{:.0}", held.pass.max_elevation),
+                id = held.id.as_str()
+            )
+        })
+        .collect::<Vec<_>>();
+
+    if upcoming.len() > MAX_HELD_PASSES {
+        lines.push(tr!(
+            locale,
+            "mute-lifted-more",
+            count = upcoming.len() - MAX_HELD_PASSES
+        ));
+    }
+
+    lines.join("\n")
+}
+
+/// Marks a watch whose channel can no longer be posted to as broken, and lets its watcher know
+/// how to repair it.
+async fn disable_watch(
+    notifier: &impl Notifier,
+    database: &RwLock<Database>,
+    watched_satellite: &WatchedSatellite,
+) {
+    warn!(
+        "Disabling watch of {} in inaccessible channel {}",
+        watched_satellite.name, watched_satellite.channel.0
+    );
+
+    let direct_messages = {
+        let mut database = database.write().await;
+
+        if let Some(watch) = database
+            .contents
+            .watched_satellites
+            .iter_mut()
+            .find(|watch| watch.is_same_watch(watched_satellite))
+        {
+            watch.broken = Some("the channel was deleted or is no longer accessible".to_string());
+        }
+
+        database.mark_dirty();
+        UserPreferences::for_user(&database, &watched_satellite.watcher).direct_messages
+    };
+
+    if !direct_messages {
+        return;
+    }
+
+    let message = tr!(
+        &watched_satellite.locale,
+        "notification-watch-disabled",
+        satellite = watched_satellite.name.as_str(),
+        location = watched_satellite.location.0.as_str(),
+        channel = watched_satellite.channel.0.to_string()
+    );
+
+    if let Err(e) = notifier
+        .direct_message(watched_satellite.watcher.0, &message)
+        .await
+    {
+        warn!(
+            "Failed to DM watcher {}: {}",
+            watched_satellite.watcher.0, e
+        );
+    }
+}
+
+/// The most action rows Discord allows in a message.
+const MAX_ROWS: usize = 5;
+/// The most buttons Discord allows in an action row.
+const MAX_BUTTONS_PER_ROW: usize = 5;
+
+/// Adds buttons for each pass in a notification, to say you are going and to track it live,
+/// numbered in the order of its embeds when there are several. Each pass gets its own row when
+/// they fit, and otherwise the buttons are packed into as few rows as possible, leaving out the
+/// last passes' RSVP buttons if there are still too many. Buttons without an ID are skipped.
+fn add_pass_buttons(
+    b: &mut CreateMessage,
+    locale: &str,
+    button_ids: Vec<(Option<String>, Option<String>)>,
+) {
+    let several = button_ids.len() > 1;
+    let passes = button_ids.len();
+    let mut buttons = button_ids
+        .into_iter()
+        .enumerate()
+        .map(|(index, (rsvp_id, track_id))| {
+            let (rsvp_label, track_label) = if several {
+                (
+                    tr!(locale, "notification-rsvp-pass", number = index + 1),
+                    tr!(locale, "notification-track-pass", number = index + 1),
+                )
+            } else {
+                (
+                    tr!(locale, "notification-rsvp"),
+                    tr!(locale, "notification-track-live"),
+                )
+            };
+
+            [
+                rsvp_id.map(|id| (id, rsvp_label, ButtonStyle::Primary)),
+                track_id.map(|id| (id, track_label, ButtonStyle::Secondary)),
+            ]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+        })
+        .filter(|buttons| !buttons.is_empty())
+        .collect::<Vec<_>>();
+
+    let rows = if passes <= MAX_ROWS {
+        buttons
+    } else {
+        let max_buttons = MAX_ROWS * MAX_BUTTONS_PER_ROW;
+        let mut total = buttons.iter().map(Vec::len).sum::<usize>();
+
+        for pass_buttons in buttons.iter_mut().rev() {
+            if total <= max_buttons {
+                break;
+            }
+
+            if pass_buttons.len() > 1 {
+                pass_buttons.remove(0);
+                total -= 1;
+            }
+        }
+
+        buttons
+            .into_iter()
+            .flatten()
+            .take(max_buttons)
+            .collect::<Vec<_>>()
+            .chunks(MAX_BUTTONS_PER_ROW)
+            .map(<[_]>::to_vec)
+            .collect()
+    };
+
+    if rows.is_empty() {
+        return;
+    }
+
+    b.components(|c| {
+        for row in rows {
+            c.create_action_row(|r| {
+                for (id, label, style) in row {
+                    r.create_button(|button| button.custom_id(id).label(label).style(style));
+                }
+                r
+            });
+        }
+        c
+    });
+}
+
+fn is_same_pass(a: &SatellitePass, b: &SatellitePass) -> bool {
+    util::are_within_10_seconds(a.start_utc as i64, b.start_utc as i64)
+        && util::are_within_10_seconds(a.end_utc as i64, b.end_utc as i64)
+}
+
+fn has_more_than_one_day_passed(since: i64) -> bool {
+    util::current_utc() - since > 24 * 60 * 60
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::{
+        astro::Lighting,
+        database::{DatabaseContents, Location, LocationName},
+        n2yo::{PassKind, PassSource, SatellitePassInfo},
+        sstv::SstvEvent,
+        testing::{pass, watch, Failure, FakeNotifier},
+    };
+
+    const HOUR: i64 = 60 * 60;
+
+    #[derive(Default)]
+    struct FakePasses {
+        passes: HashMap<usize, Vec<SatellitePass>>,
+        quota_warning: Option<String>,
+        transmitters: HashMap<usize, String>,
+    }
+
+    #[async_trait]
+    impl PassFetcher for FakePasses {
+        fn quota_warning(&self) -> Option<String> {
+            self.quota_warning.clone()
+        }
+
+        async fn get_many_satellite_passes(
+            &self,
+            requests: Vec<PassRequest>,
+        ) -> Vec<anyhow::Result<SatellitePasses>> {
+            requests
+                .into_iter()
+                .map(|request| {
+                    let passes = self
+                        .passes
+                        .get(&request.satellite_id)
+                        .ok_or_else(|| anyhow::anyhow!("no passes"))?
+                        .clone();
+
+                    Ok(SatellitePasses {
+                        info: SatellitePassInfo {
+                            id: request.satellite_id,
+                            name: format!("SAT {}", request.satellite_id),
+                            transaction_count: 0,
+                            passes_count: passes.len(),
+                        },
+                        passes,
+                        source: PassSource::N2YO,
+                        raw: None,
+                    })
+                })
+                .collect()
+        }
+
+        async fn primary_transmitter(&self, satellite_id: usize) -> Option<String> {
+            self.transmitters.get(&satellite_id).cloned()
+        }
+    }
+
+    fn database(watched_satellites: Vec<WatchedSatellite>) -> RwLock<Database> {
+        RwLock::new(Database::in_memory(DatabaseContents {
+            locations: vec![Location {
+                name: LocationName("home".to_string()),
+                creator: Snowflake(1),
+                latitude: 51.5,
+                longitude: 0.0,
+                altitude: 0.0,
+                guild: None,
+            }],
+            watched_satellites,
+            ..Default::default()
+        }))
+    }
+
+    async fn run(notifier: &FakeNotifier, database: &RwLock<Database>, passes: &FakePasses) {
+        notify_of_new_passes(notifier, database, passes, &Metrics::new(), false)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn notifies_of_each_pass_once() {
+        let now = util::current_utc();
+        let notifier = FakeNotifier::default();
+        let database = database(vec![watch(1, 10)]);
+        let passes = FakePasses {
+            passes: HashMap::from([(1, vec![pass(now + HOUR, 45.0)])]),
+            ..Default::default()
+        };
+
+        run(&notifier, &database, &passes).await;
+        run(&notifier, &database, &passes).await;
+
+        assert_eq!(notifier.sent_embeds(), vec![(10, 1)]);
+        assert_eq!(
+            database.read().await.contents.watched_satellites[0]
+                .previous_notifications
+                .len(),
+            1
+        );
+
+        let id = passes::pass_id(1, (now + HOUR + 300) as usize);
+        assert_eq!(
+            database.read().await.contents.watched_satellites[0]
+                .announced_passes
+                .iter()
+                .map(|announced| announced.id.clone())
+                .collect::<Vec<_>>(),
+            vec![id.clone()]
+        );
+        assert!(
+            notifier.sent.lock().unwrap()[0].1["embeds"][0]["footer"]["text"]
+                .as_str()
+                .unwrap()
+                .contains(&id)
+        );
+    }
+
+    #[tokio::test]
+    async fn skips_paused_watches() {
+        let now = util::current_utc();
+        let notifier = FakeNotifier::default();
+        let database = database(vec![
+            WatchedSatellite {
+                paused: true,
+                ..watch(1, 10)
+            },
+            watch(1, 20),
+        ]);
+        let passes = FakePasses {
+            passes: HashMap::from([(1, vec![pass(now + HOUR, 45.0)])]),
+            ..Default::default()
+        };
+
+        run(&notifier, &database, &passes).await;
+
+        assert_eq!(notifier.sent_embeds(), vec![(20, 1)]);
+    }
+
+    #[tokio::test]
+    async fn holds_passes_in_muted_channels_until_the_mute_lifts() {
+        let now = util::current_utc();
+        let notifier = FakeNotifier::default();
+        let database = database(vec![watch(1, 10), watch(2, 20)]);
+        database.write().await.contents.channel_mutes.insert(
+            Snowflake(10),
+            ChannelMute {
+                until: now + HOUR,
+                locale: "en-GB".to_string(),
+                held_passes: Vec::new(),
+            },
+        );
+        let passes = FakePasses {
+            passes: HashMap::from([
+                (1, vec![pass(now + 2 * HOUR, 45.0)]),
+                (2, vec![pass(now + 2 * HOUR, 45.0)]),
+            ]),
+            ..Default::default()
+        };
+
+        run(&notifier, &database, &passes).await;
+
+        assert_eq!(notifier.sent_embeds(), vec![(20, 1)]);
+        assert_eq!(
+            database.read().await.contents.channel_mutes[&Snowflake(10)]
+                .held_passes
+                .len(),
+            1
+        );
+
+        database
+            .write()
+            .await
+            .contents
+            .channel_mutes
+            .get_mut(&Snowflake(10))
+            .unwrap()
+            .until = now - 1;
+        run(&notifier, &database, &passes).await;
+
+        assert!(database.read().await.contents.channel_mutes.is_empty());
+        let sent = notifier.sent.lock().unwrap();
+        assert_eq!(sent.len(), 2);
+        assert_eq!(sent[1].0, 10);
+        assert!(sent[1].1["embeds"][0]["description"]
+            .as_str()
+            .unwrap()
+            .contains("SAT 1"));
+    }
+
+    #[tokio::test]
+    async fn resumes_watches_after_a_vacation() {
+        let now = util::current_utc();
+        let notifier = FakeNotifier::default();
+        let database = database(vec![watch(1, 10)]);
+        database.write().await.contents.vacations.insert(
+            Snowflake(1010),
+            Vacation {
+                until: now + HOUR,
+                locale: "en-GB".to_string(),
+            },
+        );
+        let passes = FakePasses {
+            passes: HashMap::from([(1, vec![pass(now + 2 * HOUR, 80.0)])]),
+            ..Default::default()
+        };
+
+        run(&notifier, &database, &passes).await;
+
+        assert!(notifier.sent_embeds().is_empty());
+        assert!(notifier.direct_messages.lock().unwrap().is_empty());
+
+        database
+            .write()
+            .await
+            .contents
+            .vacations
+            .get_mut(&Snowflake(1010))
+            .unwrap()
+            .until = now - 1;
+        run(&notifier, &database, &passes).await;
+
+        assert_eq!(notifier.sent_embeds(), vec![(10, 1)]);
+        assert_eq!(*notifier.direct_messages.lock().unwrap(), vec![1010]);
+        assert!(database.read().await.contents.vacations.is_empty());
+    }
+
+    #[tokio::test]
+    async fn renders_notification_templates() {
+        let now = util::current_utc();
+        let notifier = FakeNotifier::default();
+        let mut templated = watch(1, 10);
+        templated.title_template = Some("{name} reaches {max_el}°".to_string());
+        templated.description_template = Some("Pass {id} from {location}".to_string());
+        let database = database(vec![templated]);
+        let passes = FakePasses {
+            passes: HashMap::from([(1, vec![pass(now + HOUR, 45.0)])]),
+            ..Default::default()
+        };
+
+        run(&notifier, &database, &passes).await;
+
+        let embed = &notifier.sent.lock().unwrap()[0].1["embeds"][0];
+        assert_eq!(embed["title"], "SAT 1 reaches 45°");
+        assert_eq!(
+            embed["description"],
+            format!(
+                "Pass {} from home",
+                passes::pass_id(1, (now + HOUR + 300) as usize)
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn truncates_long_templated_descriptions() {
+        let now = util::current_utc();
+        let notifier = FakeNotifier::default();
+        let mut templated = watch(1, 10);
+        templated.description_template = Some("{location} {name} ".repeat(500));
+        let database = database(vec![templated]);
+        let passes = FakePasses {
+            passes: HashMap::from([(1, vec![pass(now + HOUR, 45.0)])]),
+            ..Default::default()
+        };
+
+        run(&notifier, &database, &passes).await;
+
+        let embed = &notifier.sent.lock().unwrap()[0].1["embeds"][0];
+        let description = embed["description"].as_str().unwrap();
+        assert_eq!(description.chars().count(), MAX_DESCRIPTION_LENGTH);
+        assert!(description.ends_with('…'));
+    }
+
+    #[tokio::test]
+    async fn batches_passes_into_one_message_per_watch() {
+        let now = util::current_utc();
+        let notifier = FakeNotifier::default();
+        let database = database(vec![watch(1, 10), watch(2, 20)]);
+        let passes = FakePasses {
+            passes: HashMap::from([
+                (1, vec![pass(now + HOUR, 45.0), pass(now + 3 * HOUR, 60.0)]),
+                (2, vec![pass(now + 2 * HOUR, 30.0)]),
+            ]),
+            ..Default::default()
+        };
+
+        run(&notifier, &database, &passes).await;
+
+        assert_eq!(notifier.sent_embeds(), vec![(10, 2), (20, 1)]);
+    }
+
+    #[tokio::test]
+    async fn offers_to_go_to_and_track_each_pass() {
+        let now = util::current_utc();
+        let notifier = FakeNotifier::default();
+        let database = database(vec![watch(1, 10)]);
+        let passes = FakePasses {
+            passes: HashMap::from([(1, vec![pass(now + HOUR, 45.0), pass(now + 3 * HOUR, 60.0)])]),
+            ..Default::default()
+        };
+
+        run(&notifier, &database, &passes).await;
+
+        let sent = notifier.sent.lock().unwrap();
+        let rows = sent[0].1["components"].as_array().unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(
+            rows[1]["components"][0]["custom_id"],
+            format!("rsvp:1:1:{}:{}:home", now + 3 * HOUR, now + 3 * HOUR + 600)
+        );
+        assert_eq!(
+            rows[1]["components"][1]["custom_id"],
+            format!("track:1:{}:{}:home", now + 3 * HOUR, now + 3 * HOUR + 600)
+        );
+    }
+
+    #[tokio::test]
+    async fn keeps_track_buttons_for_every_pass_when_there_are_many() {
+        let now = util::current_utc();
+        let notifier = FakeNotifier::default();
+        let database = database(vec![watch(1, 10)]);
+        let passes = FakePasses {
+            passes: HashMap::from([(
+                1,
+                (1..=20).map(|hour| pass(now + hour * HOUR, 45.0)).collect(),
+            )]),
+            ..Default::default()
+        };
+
+        run(&notifier, &database, &passes).await;
+
+        let sent = notifier.sent.lock().unwrap();
+        let rows = sent[0].1["components"].as_array().unwrap();
+        let ids = rows
+            .iter()
+            .flat_map(|row| row["components"].as_array().unwrap())
+            .map(|button| button["custom_id"].as_str().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(rows.len(), 5);
+        assert_eq!(ids.len(), 25);
+        assert_eq!(ids.iter().filter(|id| id.starts_with("track:")).count(), 20);
+        assert!(ids[0].starts_with("rsvp:"));
+    }
+
+    #[tokio::test]
+    async fn skips_low_and_finished_passes() {
+        let now = util::current_utc();
+        let notifier = FakeNotifier::default();
+        let database = database(vec![watch(1, 10)]);
+        let passes = FakePasses {
+            passes: HashMap::from([(1, vec![pass(now + HOUR, 10.0), pass(now - HOUR, 45.0)])]),
+            ..Default::default()
+        };
+
+        run(&notifier, &database, &passes).await;
+
+        assert!(notifier.sent_embeds().is_empty());
+    }
+
+    #[tokio::test]
+    async fn prunes_notifications_older_than_a_day() {
+        let now = util::current_utc();
+        let mut watched_satellite = watch(1, 10);
+        watched_satellite.previous_notifications = vec![
+            ((now - 48 * HOUR) as usize, (now - 48 * HOUR + 600) as usize),
+            ((now - HOUR) as usize, (now - HOUR + 600) as usize),
+        ];
+        let notifier = FakeNotifier::default();
+        let database = database(vec![watched_satellite]);
+        let passes = FakePasses {
+            passes: HashMap::from([(1, Vec::new())]),
+            ..Default::default()
+        };
+
+        run(&notifier, &database, &passes).await;
+
+        assert_eq!(
+            database.read().await.contents.watched_satellites[0].previous_notifications,
+            vec![((now - HOUR) as usize, (now - HOUR + 600) as usize)]
+        );
+    }
+
+    #[tokio::test]
+    async fn holds_back_passes_outside_the_notification_window() {
+        let now = util::current_utc();
+        let mut watched_satellite = watch(1, 10);
+        watched_satellite.notify_within_hours = Some(2);
+        let notifier = FakeNotifier::default();
+        let database = database(vec![watched_satellite]);
+        let passes = FakePasses {
+            passes: HashMap::from([(1, vec![pass(now + HOUR, 45.0), pass(now + 5 * HOUR, 45.0)])]),
+            ..Default::default()
+        };
+
+        run(&notifier, &database, &passes).await;
+
+        assert_eq!(notifier.sent_embeds(), vec![(10, 1)]);
+        assert_eq!(
+            database.read().await.contents.watched_satellites[0].pending_passes,
+            vec![pass(now + 5 * HOUR, 45.0)]
+        );
+    }
+
+    #[test]
+    fn groups_identical_pass_requests() {
+        let location = database(Vec::new())
+            .into_inner()
+            .contents
+            .locations
+            .remove(0);
+        let request = |satellite_id: usize, min_max_elevation: f64| PassRequest {
+            satellite_id,
+            location: location.clone(),
+            days: 1,
+            min_max_elevation,
+            kind: PassKind::Radio,
+        };
+
+        let (distinct, indices) = group_requests(vec![
+            request(25544, 20.0),
+            request(33591, 20.0),
+            request(25544, 20.0),
+            request(25544, 10.0),
+        ]);
+
+        assert_eq!(
+            distinct
+                .iter()
+                .map(|request| request.satellite_id)
+                .collect::<Vec<_>>(),
+            vec![25544, 33591, 25544]
+        );
+        assert_eq!(indices, vec![0, 1, 0, 2]);
+    }
+
+    #[tokio::test]
+    async fn isolates_failed_fetches() {
+        let now = util::current_utc();
+        let notifier = FakeNotifier::default();
+        let database = database(vec![watch(1, 10), watch(2, 20)]);
+        let passes = FakePasses {
+            passes: HashMap::from([(2, vec![pass(now + HOUR, 45.0)])]),
+            ..Default::default()
+        };
+
+        run(&notifier, &database, &passes).await;
+
+        assert_eq!(notifier.sent_embeds(), vec![(20, 1)]);
+    }
+
+    #[test]
+    fn names_threads_after_the_first_pass() {
+        // 2023-11-04 14:32 UTC was a Saturday
+        let pass = pass(1699108320, 45.0);
+        let mut watched_satellite = watch(1, 10);
+
+        assert_eq!(
+            thread_name(&watched_satellite, &pass),
+            "SAT 1 – Sat 14:32 UTC"
+        );
+
+        watched_satellite.timezone = Some("Europe/Berlin".to_string());
+        assert_eq!(thread_name(&watched_satellite, &pass), "SAT 1 – Sat 15:32");
+    }
+
+    #[tokio::test]
+    async fn starts_threads_and_archives_old_ones() {
+        let now = util::current_utc();
+        let notifier = FakeNotifier::default();
+        let mut threaded = watch(1, 10);
+        threaded.threads = true;
+        threaded.name = "NOAA 19".to_string();
+        let database = database(vec![threaded, watch(2, 20)]);
+        database.write().await.contents.pass_threads = vec![
+            PassThread {
+                thread: Snowflake(500),
+                end: now - 2 * 24 * HOUR,
+            },
+            PassThread {
+                thread: Snowflake(501),
+                end: now - HOUR,
+            },
+        ];
+        let passes = FakePasses {
+            passes: HashMap::from([
+                (1, vec![pass(now + HOUR, 45.0)]),
+                (2, vec![pass(now + HOUR, 45.0)]),
+            ]),
+            ..Default::default()
+        };
+
+        run(&notifier, &database, &passes).await;
+
+        let threads = notifier.threads.lock().unwrap().clone();
+        assert_eq!(threads.len(), 1);
+        assert_eq!(threads[0].0, 10);
+        assert!(threads[0].2.starts_with("NOAA 19 – "));
+        assert_eq!(*notifier.archived_threads.lock().unwrap(), vec![500]);
+        assert_eq!(
+            database
+                .read()
+                .await
+                .contents
+                .pass_threads
+                .iter()
+                .map(|thread| thread.thread.0)
+                .collect::<Vec<_>>(),
+            vec![501, 1001]
+        );
+    }
+
+    #[tokio::test]
+    async fn queues_failed_deliveries_without_affecting_other_watches() {
+        let now = util::current_utc();
+        let notifier = FakeNotifier {
+            failures: HashMap::from([(10, Failure::Transient)]),
+            ..Default::default()
+        };
+        let database = database(vec![watch(1, 10), watch(2, 20)]);
+        let passes = FakePasses {
+            passes: HashMap::from([
+                (1, vec![pass(now + HOUR, 45.0)]),
+                (2, vec![pass(now + HOUR, 45.0)]),
+            ]),
+            ..Default::default()
+        };
+
+        run(&notifier, &database, &passes).await;
+
+        assert_eq!(notifier.sent_embeds(), vec![(20, 1)]);
+        let database = database.read().await;
+        assert_eq!(database.contents.failed_deliveries.len(), 1);
+        assert_eq!(
+            database.contents.failed_deliveries[0].channel,
+            Snowflake(10)
+        );
+    }
+
+    #[tokio::test]
+    async fn drops_failed_deliveries_for_passes_that_have_ended() {
+        let now = util::current_utc();
+        let notifier = FakeNotifier::default();
+        let database = database(Vec::new());
+        let delivery = |channel, end| PendingDelivery {
+            channel: Snowflake(channel),
+            message: serde_json::json!({ "content": "pass" }),
+            attempts: 1,
+            end,
+        };
+        database.write().await.contents.failed_deliveries = vec![
+            delivery(10, Some(now - HOUR)),
+            delivery(20, Some(now + HOUR)),
+            delivery(30, None),
+        ];
+
+        retry_failed_deliveries(&notifier, &database).await.unwrap();
+
+        assert!(database.read().await.contents.failed_deliveries.is_empty());
+        let sent = notifier.sent.lock().unwrap();
+        assert_eq!(
+            sent.iter().map(|(channel, _)| *channel).collect::<Vec<_>>(),
+            vec![20, 30]
+        );
+    }
+
+    #[tokio::test]
+    async fn disables_watches_in_inaccessible_channels() {
+        let now = util::current_utc();
+        let notifier = FakeNotifier {
+            failures: HashMap::from([(10, Failure::Gone)]),
+            ..Default::default()
+        };
+        let database = database(vec![watch(1, 10)]);
+        let passes = FakePasses {
+            passes: HashMap::from([(1, vec![pass(now + HOUR, 45.0)])]),
+            ..Default::default()
+        };
+
+        run(&notifier, &database, &passes).await;
+
+        assert!(database.read().await.contents.watched_satellites[0]
+            .broken
+            .is_some());
+        assert!(database.read().await.contents.failed_deliveries.is_empty());
+        assert_eq!(*notifier.direct_messages.lock().unwrap(), vec![1010]);
+    }
+
+    #[tokio::test]
+    async fn respects_direct_message_opt_outs() {
+        let now = util::current_utc();
+        let notifier = FakeNotifier {
+            failures: HashMap::from([(10, Failure::Gone)]),
+            ..Default::default()
+        };
+        let database = database(vec![watch(1, 10)]);
+        database.write().await.contents.preferences.insert(
+            Snowflake(1010),
+            UserPreferences {
+                direct_messages: false,
+                ..Default::default()
+            },
+        );
+        let passes = FakePasses {
+            passes: HashMap::from([(1, vec![pass(now + HOUR, 45.0)])]),
+            ..Default::default()
+        };
+
+        run(&notifier, &database, &passes).await;
+
+        assert!(database.read().await.contents.watched_satellites[0]
+            .broken
+            .is_some());
+        assert!(notifier.direct_messages.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn skips_the_run_when_the_quota_is_nearly_exhausted() {
+        let now = util::current_utc();
+        let notifier = FakeNotifier::default();
+        let database = database(vec![watch(1, 10)]);
+        let passes = FakePasses {
+            passes: HashMap::from([(1, vec![pass(now + HOUR, 45.0)])]),
+            quota_warning: Some("quota".to_string()),
+            ..Default::default()
+        };
+
+        run(&notifier, &database, &passes).await;
+
+        assert!(notifier.sent_embeds().is_empty());
+        assert_eq!(*notifier.ops_messages.lock().unwrap(), vec!["quota"]);
+    }
+
+    #[tokio::test]
+    async fn reports_failed_pass_requests_to_the_ops_channel_once() {
+        let now = util::current_utc();
+        let notifier = FakeNotifier::default();
+        let database = database(vec![watch(1, 10), watch(43017, 10)]);
+        let passes = FakePasses {
+            passes: HashMap::from([(1, vec![pass(now + HOUR, 45.0)])]),
+            ..Default::default()
+        };
+
+        run(&notifier, &database, &passes).await;
+        run(&notifier, &database, &passes).await;
+
+        assert_eq!(notifier.sent_embeds().len(), 1);
+        let ops_messages = notifier.ops_messages.lock().unwrap();
+        assert_eq!(ops_messages.len(), 1);
+        assert!(ops_messages[0].starts_with("Failed to get passes for 1 of 2 watches"));
+    }
+
+    #[tokio::test]
+    async fn dry_runs_send_and_record_nothing() {
+        let now = util::current_utc();
+        let notifier = FakeNotifier::default();
+        let database = database(vec![watch(1, 10)]);
+        let passes = FakePasses {
+            passes: HashMap::from([(1, vec![pass(now + HOUR, 45.0)])]),
+            ..Default::default()
+        };
+
+        notify_of_new_passes(&notifier, &database, &passes, &Metrics::new(), true)
+            .await
+            .unwrap();
+
+        assert!(notifier.sent_embeds().is_empty());
+        assert!(database.read().await.contents.watched_satellites[0]
+            .previous_notifications
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn includes_downlinks_when_known_or_asked_for() {
+        const NOAA_19: usize = 33591;
+        let now = util::current_utc();
+        let notifier = FakeNotifier::default();
+        let database = database(vec![
+            watch(NOAA_19, 10),
+            watch(1, 20),
+            WatchedSatellite {
+                show_transmitter: true,
+                ..watch(2, 30)
+            },
+        ]);
+        let passes = FakePasses {
+            passes: HashMap::from([
+                (NOAA_19, vec![pass(now + HOUR, 45.0)]),
+                (1, vec![pass(now + HOUR, 45.0)]),
+                (2, vec![pass(now + HOUR, 45.0)]),
+            ]),
+            transmitters: HashMap::from([
+                (1, "435.000 MHz FM".to_string()),
+                (2, "436.000 MHz BPSK".to_string()),
+            ]),
+            ..Default::default()
+        };
+
+        run(&notifier, &database, &passes).await;
+
+        let downlinks = notifier
+            .sent
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(channel, message)| {
+                (
+                    *channel,
+                    message["embeds"][0]["fields"][0]["value"]
+                        .as_str()
+                        .map(str::to_string),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            downlinks,
+            vec![
+                (10, Some("137.100 MHz APT".to_string())),
+                (20, None),
+                (30, Some("436.000 MHz BPSK".to_string())),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn relaxes_iss_watches_during_sstv_events() {
+        let now = util::current_utc();
+        let notifier = FakeNotifier::default();
+        let database = database(vec![
+            WatchedSatellite {
+                lighting: Lighting::Night,
+                ..watch(sstv::ISS, 10)
+            },
+            watch(1, 20),
+        ]);
+        database.write().await.contents.sstv_events = vec![SstvEvent {
+            start: now,
+            end: now + 2 * HOUR,
+            frequency: 145.8,
+            mode: Some("PD120".to_string()),
+        }];
+        let passes = FakePasses {
+            passes: HashMap::from([
+                (
+                    sstv::ISS,
+                    vec![pass(now + HOUR, 15.0), pass(now + 3 * HOUR, 15.0)],
+                ),
+                (1, vec![pass(now + HOUR, 15.0)]),
+            ]),
+            ..Default::default()
+        };
+
+        run(&notifier, &database, &passes).await;
+
+        assert_eq!(notifier.sent_embeds(), vec![(10, 1)]);
+        let sent = notifier.sent.lock().unwrap();
+        assert_eq!(
+            sent[0].1["embeds"][0]["fields"][1]["value"],
+            "📡 SSTV active on 145.800 MHz (PD120)"
+        );
+    }
+}
@@ -0,0 +1,400 @@
+use serenity::utils::Colour;
+
+use crate::{astro, i18n::tr, n2yo::SatellitePass, preferences::TimeFormat, util};
+
+/// How close a pass must come to the Moon for it to be pointed out, in degrees. Within this, the
+/// Moon's glare makes a satellite harder to spot.
+const NEAR_MOON: f64 = 10.0;
+/// The length of pass that scores full marks for duration, in seconds. Few low Earth orbit passes
+/// last longer.
+const FULL_DURATION: f64 = 15.0 * 60.0;
+
+/// The elevation below which passes are coloured red, in degrees.
+const LOW_ELEVATION: f64 = 20.0;
+/// The elevation above which passes are coloured green, in degrees.
+const HIGH_ELEVATION: f64 = 50.0;
+
+/// How good a pass is to observe or receive, from 0 to 100.
+///
+/// Higher passes are better, as they are closer, clear of obstructions near the horizon, and cross
+/// less atmosphere. Longer passes are better as they give more time to receive. When the pass is
+/// to be watched rather than listened to, the fraction of it for which the satellite is lit by the
+/// Sun matters too, since it cannot be seen otherwise.
+pub fn quality(pass: &SatellitePass, illumination: Option<f64>) -> u8 {
+    let elevation = (pass.max_elevation / 90.0).clamp(0.0, 1.0);
+    let duration =
+        (pass.end_utc.saturating_sub(pass.start_utc) as f64 / FULL_DURATION).clamp(0.0, 1.0);
+
+    let score = match illumination {
+        Some(illumination) => {
+            0.45 * elevation + 0.25 * duration + 0.3 * illumination.clamp(0.0, 1.0)
+        }
+        None => 0.6 * elevation + 0.4 * duration,
+    };
+
+    (score * 100.0).round() as u8
+}
+
+/// The quality of a pass. Being visible counts in its favour, but as most passes are listened to
+/// rather than watched, being partly in shadow never counts against it.
+pub fn score(pass: &SatellitePass) -> u8 {
+    let radio = quality(pass, None);
+
+    match pass.visible_fraction.filter(|fraction| *fraction > 0.0) {
+        Some(fraction) => quality(pass, Some(fraction)).max(radio),
+        None => radio,
+    }
+}
+
+/// The colour of a pass's embed by its highest elevation, so that good passes stand out: red below
+/// 20°, yellow up to 50°, and green above.
+pub fn colour(max_elevation: f64) -> Colour {
+    if max_elevation < LOW_ELEVATION {
+        Colour::from_rgb(237, 66, 69)
+    } else if max_elevation <= HIGH_ELEVATION {
+        Colour::from_rgb(254, 231, 92)
+    } else {
+        Colour::from_rgb(87, 242, 135)
+    }
+}
+
+/// Whether a pass is at least as bright as a magnitude, if one is given. Passes of unknown
+/// brightness are left out, as they may be too dim to see.
+pub fn is_brighter_than(pass: &SatellitePass, max_magnitude: Option<f64>) -> bool {
+    max_magnitude.is_none_or(|max_magnitude| {
+        pass.magnitude
+            .is_some_and(|magnitude| magnitude < max_magnitude)
+    })
+}
+
+/// Describes a pass's elevation, quality, whether it can be seen, how bright it is, and if the
+/// Moon is in the way, and the forecast cloud cover, for embeds.
+pub fn describe(locale: &str, pass: &SatellitePass) -> String {
+    let details = tr!(
+        locale,
+        "pass-details",
+        elevation = pass.max_elevation,
+        quality = score(pass)
+    );
+
+    let mut description = match pass.visible_fraction {
+        Some(fraction) if fraction > 0.0 => format!("{}\n{}", details, tr!(locale, "pass-visible")),
+        Some(_) => format!("{}\n{}", details, tr!(locale, "pass-radio-only")),
+        None => details,
+    };
+
+    if let Some(magnitude) = pass.magnitude {
+        description.push('\n');
+        description.push_str(&tr!(locale, "pass-magnitude", magnitude = magnitude));
+    }
+
+    if let Some(separation) = pass
+        .moon_separation
+        .filter(|separation| *separation < NEAR_MOON)
+    {
+        description.push('\n');
+        description.push_str(&tr!(
+            locale,
+            "pass-near-moon",
+            separation = separation.round(),
+            illumination = (astro::moon_illumination(pass.max_utc as i64) * 100.0).round()
+        ));
+    }
+
+    if let Some(cover) = pass.cloud_cover {
+        description.push('\n');
+        description.push_str(&tr!(locale, "pass-cloud-cover", cover = cover));
+    }
+
+    description
+}
+
+/// Where a pass rises, how high it gets, and where it sets, such as
+/// "AOS NNE (22°) → max 81° → LOS S (174°)", for pointing an antenna.
+pub fn describe_path(locale: &str, pass: &SatellitePass) -> String {
+    tr!(
+        locale,
+        "pass-path",
+        aos = format!(
+            "{} ({:.0}°)",
+            pass.start_azimuth_compass, pass.start_azimuth
+        ),
+        elevation = pass.max_elevation.round(),
+        los = format!("{} ({:.0}°)", pass.end_azimuth_compass, pass.end_azimuth)
+    )
+}
+
+/// A table of when and where a pass rises, peaks, and sets, and how long it lasts, for those
+/// aligning antennas by hand. Times are plain, as Discord does not render timestamps in code
+/// blocks.
+pub fn table(locale: &str, time_format: &TimeFormat, pass: &SatellitePass) -> String {
+    let point = |azimuth: f64, compass: &str| format!("{:.0}° {}", azimuth, compass);
+    let rows = [
+        [
+            String::new(),
+            tr!(locale, "pass-table-time"),
+            tr!(locale, "pass-table-azimuth"),
+            tr!(locale, "pass-table-elevation"),
+        ],
+        [
+            "AOS".to_string(),
+            time_format.time(pass.start_utc as i64),
+            point(pass.start_azimuth, &pass.start_azimuth_compass),
+            String::new(),
+        ],
+        [
+            "TCA".to_string(),
+            time_format.time(pass.max_utc as i64),
+            point(pass.max_azimuth, &pass.max_azimuth_compass),
+            format!("{:.0}°", pass.max_elevation),
+        ],
+        [
+            "LOS".to_string(),
+            time_format.time(pass.end_utc as i64),
+            point(pass.end_azimuth, &pass.end_azimuth_compass),
+            String::new(),
+        ],
+    ];
+    let widths = (0..4)
+        .map(|column| {
+            rows.iter()
+                .map(|row| row[column].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect::<Vec<_>>();
+
+    let mut table = "```\n".to_string();
+
+    for row in &rows {
+        let line = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect::<Vec<_>>()
+            .join("  ");
+        table.push_str(line.trim_end());
+        table.push('\n');
+    }
+
+    table.push_str(&tr!(
+        locale,
+        "pass-table-duration",
+        duration = util::duration_between(pass.start_utc as i64, pass.end_utc as i64)
+    ));
+    table.push_str("\n```");
+    table
+}
+
+/// How far apart, in seconds, the closest approaches of a pass seen from different locations may
+/// be. Places a few hundred kilometres apart see it peak well within this.
+const SAME_PASS_TOLERANCE: i64 = 2 * 60;
+
+/// A short ID for a pass, such as `33591-guyo8`, made from the satellite and the minute of its
+/// closest approach. Nearby locations usually give a pass the same ID, but not always, as where
+/// it rises depends on the horizon and its peak may fall either side of a minute; use
+/// [`is_same_pass`] to match a pass seen from elsewhere.
+pub fn pass_id(satellite_id: usize, max_utc: usize) -> String {
+    const DIGITS: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+    let mut minutes = max_utc / 60;
+    let mut digits = Vec::new();
+
+    loop {
+        digits.push(DIGITS[minutes % 36]);
+        minutes /= 36;
+
+        if minutes == 0 {
+            break;
+        }
+    }
+
+    digits.reverse();
+    format!(
+        "{}-{}",
+        satellite_id,
+        String::from_utf8(digits).unwrap_or_default()
+    )
+}
+
+/// Whether two passes of a satellite, possibly seen from different locations, are the same pass.
+pub fn is_same_pass(a: &SatellitePass, b: &SatellitePass) -> bool {
+    (a.max_utc as i64 - b.max_utc as i64).abs() <= SAME_PASS_TOLERANCE
+}
+
+/// A link that adds a pass to Google Calendar, which also offers to save it for other calendars.
+pub fn calendar_link(title: &str, details: &str, pass: &SatellitePass) -> anyhow::Result<String> {
+    let dates = format!(
+        "{}/{}",
+        util::calendar_time(pass.start_utc as i64)?,
+        util::calendar_time(pass.end_utc as i64)?
+    );
+
+    Ok(reqwest::Url::parse_with_params(
+        "https://calendar.google.com/calendar/render",
+        &[
+            ("action", "TEMPLATE"),
+            ("text", title),
+            ("details", details),
+            ("dates", &dates),
+        ],
+    )?
+    .to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing;
+
+    fn pass(max_elevation: f64, duration: usize) -> SatellitePass {
+        SatellitePass {
+            max_utc: 1_000_000 + duration / 2,
+            end_utc: 1_000_000 + duration,
+            ..testing::pass(1_000_000, max_elevation)
+        }
+    }
+
+    #[test]
+    fn scores_overhead_long_passes_fully() {
+        assert_eq!(quality(&pass(90.0, 900), None), 100);
+        assert_eq!(quality(&pass(90.0, 900), Some(1.0)), 100);
+    }
+
+    #[test]
+    fn does_not_mark_down_partly_visible_passes() {
+        let unlit = pass(60.0, 600);
+        let mut partly_visible = unlit.clone();
+        partly_visible.visible_fraction = Some(0.2);
+        let mut fully_visible = unlit.clone();
+        fully_visible.visible_fraction = Some(1.0);
+
+        assert_eq!(score(&partly_visible), score(&unlit));
+        assert!(score(&fully_visible) > score(&unlit));
+    }
+
+    #[test]
+    fn scores_passes_that_never_rise_as_zero() {
+        assert_eq!(quality(&pass(0.0, 0), None), 0);
+        assert_eq!(quality(&pass(0.0, 0), Some(0.0)), 0);
+    }
+
+    #[test]
+    fn prefers_higher_passes() {
+        assert!(quality(&pass(60.0, 600), None) > quality(&pass(30.0, 600), None));
+    }
+
+    #[test]
+    fn prefers_longer_passes() {
+        assert!(quality(&pass(45.0, 720), None) > quality(&pass(45.0, 360), None));
+    }
+
+    #[test]
+    fn caps_duration() {
+        assert_eq!(
+            quality(&pass(45.0, 900), None),
+            quality(&pass(45.0, 1800), None)
+        );
+    }
+
+    #[test]
+    fn prefers_sunlit_visual_passes() {
+        assert!(quality(&pass(45.0, 600), Some(1.0)) > quality(&pass(45.0, 600), Some(0.2)));
+    }
+
+    #[test]
+    fn clamps_out_of_range_inputs() {
+        assert_eq!(quality(&pass(120.0, 900), Some(2.0)), 100);
+        assert_eq!(quality(&pass(-10.0, 0), Some(-1.0)), 0);
+    }
+
+    #[test]
+    fn filters_by_magnitude() {
+        let bright = SatellitePass {
+            magnitude: Some(-1.5),
+            ..pass(45.0, 600)
+        };
+        let dim = SatellitePass {
+            magnitude: Some(3.0),
+            ..pass(45.0, 600)
+        };
+        let unknown = pass(45.0, 600);
+
+        assert!(is_brighter_than(&bright, Some(2.0)));
+        assert!(!is_brighter_than(&dim, Some(2.0)));
+        assert!(!is_brighter_than(&unknown, Some(2.0)));
+        assert!(is_brighter_than(&unknown, None));
+    }
+
+    #[test]
+    fn links_passes_to_calendars() {
+        let link = calendar_link("ISS pass", "Over home", &pass(45.0, 600)).unwrap();
+
+        assert_eq!(
+            link,
+            "https://calendar.google.com/calendar/render?action=TEMPLATE&text=ISS+pass&details=Over+home&dates=19700112T134640Z%2F19700112T135640Z"
+        );
+    }
+
+    #[test]
+    fn identifies_passes_by_their_minute() {
+        assert_eq!(pass_id(33591, 1699108320), "33591-guyo8");
+        assert_eq!(pass_id(33591, 1699108379), "33591-guyo8");
+        assert_ne!(pass_id(33591, 1699108380), "33591-guyo8");
+        assert_eq!(pass_id(25544, 0), "25544-0");
+    }
+
+    #[test]
+    fn matches_passes_seen_from_nearby_locations() {
+        let here = testing::pass(1_000_000, 60.0);
+        // Rising later over a hill, and peaking a few seconds later
+        let nearby = SatellitePass {
+            start_utc: here.start_utc + 90,
+            max_utc: here.max_utc + 20,
+            ..here.clone()
+        };
+        let next_orbit = testing::pass(1_000_000 + 95 * 60, 60.0);
+
+        assert!(is_same_pass(&here, &nearby));
+        assert!(!is_same_pass(&here, &next_orbit));
+    }
+
+    #[test]
+    fn describes_paths_across_the_sky() {
+        let pass = SatellitePass {
+            start_azimuth: 22.4,
+            start_azimuth_compass: "NNE".to_string(),
+            end_azimuth: 174.0,
+            ..pass(80.6, 600)
+        };
+
+        assert_eq!(
+            describe_path("en-GB", &pass),
+            "AOS NNE (22°) → max 81° → LOS S (174°)"
+        );
+    }
+
+    #[test]
+    fn tabulates_passes() {
+        use crate::preferences::{ClockFormat, DateFormat};
+
+        let time_format = TimeFormat {
+            clock: ClockFormat::TwentyFourHour,
+            date: DateFormat::DayMonthYear,
+            timezone: chrono_tz::UTC,
+        };
+
+        assert_eq!(
+            table("en-GB", &time_format, &pass(45.0, 600)),
+            "```\n     Time   Azimuth  Elevation\nAOS  13:46  0° N\nTCA  13:51  90° E    45°\nLOS  13:56  180° S\nDuration: 10m 0s\n```"
+        );
+    }
+
+    #[test]
+    fn colours_passes_by_elevation() {
+        assert_eq!(colour(19.9), colour(5.0));
+        assert_eq!(colour(20.0), colour(50.0));
+        assert_eq!(colour(50.1), colour(90.0));
+        assert_ne!(colour(10.0), colour(30.0));
+        assert_ne!(colour(30.0), colour(60.0));
+    }
+}
@@ -0,0 +1,161 @@
+use crate::{n2yo::SatellitePass, passes, util};
+
+/// The time left between passes in a session to retune and turn the antenna, in seconds.
+const SWITCH_TIME: i64 = 60;
+
+/// A pass that may go into a session.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlannedPass {
+    pub satellite_id: usize,
+    pub satellite: String,
+    pub pass: SatellitePass,
+    /// The quality of the pass, from 0 to 100.
+    pub quality: u8,
+}
+
+impl PlannedPass {
+    pub fn new(satellite_id: usize, satellite: String, pass: SatellitePass) -> Self {
+        Self {
+            satellite_id,
+            satellite,
+            quality: passes::score(&pass),
+            pass,
+        }
+    }
+
+    fn overlaps(&self, other: &Self) -> bool {
+        self.pass.start_utc as i64 - SWITCH_TIME < other.pass.end_utc as i64
+            && other.pass.start_utc as i64 - SWITCH_TIME < self.pass.end_utc as i64
+    }
+}
+
+/// The passes chosen for an observing session, in the order they happen.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionPlan {
+    pub passes: Vec<PlannedPass>,
+    /// The number of passes left out because they clashed with a better one.
+    pub conflicts: usize,
+}
+
+/// Chooses passes to observe one after another between two unix timestamps. The best passes are
+/// picked first, and any pass that would overlap one already picked is left out, so that a single
+/// station can take every pass in the plan.
+pub fn plan(mut candidates: Vec<PlannedPass>, start: i64, end: i64) -> SessionPlan {
+    candidates.retain(|candidate| {
+        candidate.pass.start_utc as i64 >= start && (candidate.pass.end_utc as i64) <= end
+    });
+    candidates.sort_by_key(|candidate| {
+        (
+            std::cmp::Reverse(candidate.quality),
+            candidate.pass.start_utc,
+        )
+    });
+
+    let mut passes = Vec::<PlannedPass>::new();
+    let mut conflicts = 0;
+
+    for candidate in candidates {
+        if passes.iter().any(|chosen| chosen.overlaps(&candidate)) {
+            conflicts += 1;
+        } else {
+            passes.push(candidate);
+        }
+    }
+
+    passes.sort_by_key(|chosen| chosen.pass.start_utc);
+    SessionPlan { passes, conflicts }
+}
+
+impl SessionPlan {
+    /// The plan as a CSV table, for spreadsheets and station software.
+    pub fn to_csv(&self) -> anyhow::Result<String> {
+        let mut csv = "satellite,norad_id,aos,tca,los,max_elevation,quality\n".to_string();
+
+        for planned in &self.passes {
+            csv.push_str(&format!(
+                "{},{},{},{},{},{:.1},{}\n",
+                planned.satellite.replace(',', " "),
+                planned.satellite_id,
+                util::iso_time(planned.pass.start_utc as i64)?,
+                util::iso_time(planned.pass.max_utc as i64)?,
+                util::iso_time(planned.pass.end_utc as i64)?,
+                planned.pass.max_elevation,
+                planned.quality
+            ));
+        }
+
+        Ok(csv)
+    }
+
+    /// The plan as an iCalendar file, with an event for each pass.
+    pub fn to_ics(&self, location: &str) -> anyhow::Result<String> {
+        let now = util::calendar_time(util::current_utc())?;
+        let mut ics =
+            "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:-//sat-bot//Observation session//EN\r\n"
+                .to_string();
+
+        for planned in &self.passes {
+            ics.push_str(&format!(
+                "BEGIN:VEVENT\r\nUID:{}-{}@sat-bot\r\nDTSTAMP:{}\r\nDTSTART:{}\r\nDTEND:{}\r\nSUMMARY:{} pass\r\nLOCATION:{}\r\nDESCRIPTION:Max elevation {:.0}°\\, quality {}/100\r\nEND:VEVENT\r\n",
+                planned.satellite_id,
+                planned.pass.start_utc,
+                now,
+                util::calendar_time(planned.pass.start_utc as i64)?,
+                util::calendar_time(planned.pass.end_utc as i64)?,
+                escape_ics(&planned.satellite),
+                escape_ics(location),
+                planned.pass.max_elevation,
+                planned.quality
+            ));
+        }
+
+        ics.push_str("END:VCALENDAR\r\n");
+        Ok(ics)
+    }
+}
+
+fn escape_ics(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing;
+
+    fn candidate(satellite_id: usize, start: usize, quality: u8) -> PlannedPass {
+        PlannedPass {
+            satellite_id,
+            satellite: format!("SAT {}", satellite_id),
+            pass: testing::pass(start as i64, 45.0),
+            quality,
+        }
+    }
+
+    #[test]
+    fn keeps_the_best_of_overlapping_passes() {
+        let plan = plan(
+            vec![
+                candidate(1, 1000, 40),
+                candidate(2, 1300, 80),
+                candidate(3, 1930, 50),
+                candidate(4, 2620, 90),
+                candidate(5, 9000, 100),
+            ],
+            0,
+            5000,
+        );
+
+        // Pass 3 starts 30 seconds after pass 2 ends, too soon to turn the antenna
+        assert_eq!(
+            plan.passes
+                .iter()
+                .map(|planned| planned.satellite_id)
+                .collect::<Vec<_>>(),
+            vec![2, 4]
+        );
+        assert_eq!(plan.conflicts, 2);
+    }
+}
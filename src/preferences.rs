@@ -0,0 +1,299 @@
+use std::collections::BTreeMap;
+
+use chrono::TimeZone;
+use chrono_tz::Tz;
+use poise::ChoiceParameter;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    config,
+    database::{Database, LocationName, SatelliteId, Snowflake},
+};
+
+/// Whether times are written with a 12 or 24-hour clock.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ChoiceParameter)]
+pub enum ClockFormat {
+    #[name = "12-hour (7:30 PM)"]
+    TwelveHour,
+    #[name = "24-hour (19:30)"]
+    TwentyFourHour,
+}
+
+impl ClockFormat {
+    /// The usual clock for a Discord locale.
+    pub fn for_locale(locale: &str) -> Self {
+        match locale {
+            "en-US" => Self::TwelveHour,
+            _ => Self::TwentyFourHour,
+        }
+    }
+
+    fn pattern(self) -> &'static str {
+        match self {
+            Self::TwelveHour => "%-I:%M %p",
+            Self::TwentyFourHour => "%H:%M",
+        }
+    }
+}
+
+/// The order dates are written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ChoiceParameter)]
+pub enum DateFormat {
+    #[name = "Day first (31/12/2026)"]
+    DayMonthYear,
+    #[name = "Month first (12/31/2026)"]
+    MonthDayYear,
+    #[name = "ISO 8601 (2026-12-31)"]
+    Iso,
+}
+
+impl DateFormat {
+    /// The usual date order for a Discord locale.
+    pub fn for_locale(locale: &str) -> Self {
+        match locale {
+            "en-US" => Self::MonthDayYear,
+            _ => Self::DayMonthYear,
+        }
+    }
+
+    fn pattern(self) -> &'static str {
+        match self {
+            Self::DayMonthYear => "%d/%m/%Y",
+            Self::MonthDayYear => "%m/%d/%Y",
+            Self::Iso => "%Y-%m-%d",
+        }
+    }
+}
+
+/// The units lengths are written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ChoiceParameter)]
+pub enum Units {
+    #[name = "Metric (metres)"]
+    Metric,
+    #[name = "Imperial (feet)"]
+    Imperial,
+}
+
+impl Units {
+    /// The usual units for a Discord locale.
+    pub fn for_locale(locale: &str) -> Self {
+        match locale {
+            "en-US" => Self::Imperial,
+            _ => Self::Metric,
+        }
+    }
+
+    /// Writes a length given in metres.
+    pub fn length(self, metres: f64) -> String {
+        match self {
+            Self::Metric => format!("{:.0}m", metres),
+            Self::Imperial => format!("{:.0}ft", metres * 3.28084),
+        }
+    }
+}
+
+/// The categories of commands in `/help`, which can each have their own preferences.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ChoiceParameter)]
+pub enum CommandCategory {
+    #[name = "Passes"]
+    Passes,
+    #[name = "Satellites"]
+    Satellites,
+    #[name = "Conditions"]
+    Conditions,
+    #[name = "Observations"]
+    Observations,
+    #[name = "Locations"]
+    Locations,
+    #[name = "Watches"]
+    Watches,
+    #[name = "Other"]
+    Other,
+}
+
+/// Preferences a user has chosen through `/settings`. Unset formats follow their Discord locale.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UserPreferences {
+    /// The location used by commands when none is given.
+    #[serde(default)]
+    pub default_location: Option<LocationName>,
+    #[serde(default)]
+    pub units: Option<Units>,
+    #[serde(default)]
+    pub clock: Option<ClockFormat>,
+    #[serde(default)]
+    pub date: Option<DateFormat>,
+    /// Whether lookups are replied to so that only the user sees them.
+    #[serde(default)]
+    pub ephemeral: bool,
+    /// Overrides of `ephemeral` for categories of commands, by the category's name.
+    #[serde(default)]
+    pub ephemeral_categories: BTreeMap<String, bool>,
+    /// Whether the bot may send the user direct messages, such as when a watch is disabled.
+    #[serde(default = "default_direct_messages")]
+    pub direct_messages: bool,
+    /// Satellites added with `/favorite-satellite`, in the order they were added.
+    #[serde(default)]
+    pub favorites: Vec<SatelliteId>,
+}
+
+impl Default for UserPreferences {
+    fn default() -> Self {
+        Self {
+            default_location: None,
+            units: None,
+            clock: None,
+            date: None,
+            ephemeral: false,
+            ephemeral_categories: BTreeMap::new(),
+            direct_messages: default_direct_messages(),
+            favorites: Vec::new(),
+        }
+    }
+}
+
+fn default_direct_messages() -> bool {
+    true
+}
+
+impl UserPreferences {
+    /// The preferences of a user, or the defaults if they have not set any.
+    pub fn for_user(database: &Database, user: &Snowflake) -> Self {
+        database
+            .contents
+            .preferences
+            .get(user)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Whether replies to commands in a category are only shown to the user.
+    pub fn ephemeral_in(&self, category: Option<&str>) -> bool {
+        category
+            .and_then(|category| self.ephemeral_categories.get(category))
+            .copied()
+            .unwrap_or(self.ephemeral)
+    }
+
+    pub fn units(&self, locale: &str) -> Units {
+        self.units.unwrap_or_else(|| Units::for_locale(locale))
+    }
+
+    pub fn time_format(&self, locale: &str) -> TimeFormat {
+        TimeFormat {
+            clock: self
+                .clock
+                .unwrap_or_else(|| ClockFormat::for_locale(locale)),
+            date: self.date.unwrap_or_else(|| DateFormat::for_locale(locale)),
+            timezone: Tz::UTC,
+        }
+    }
+}
+
+/// Settings chosen for a whole guild through `/server-settings`.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GuildSettings {
+    /// The minimum elevation used by commands when none is given.
+    #[serde(default)]
+    pub default_min_max_elevation: Option<f64>,
+    /// Names given to satellites, or groups of them, through `/satellite-alias`, in lowercase.
+    #[serde(default)]
+    pub aliases: BTreeMap<String, Vec<SatelliteId>>,
+    /// The most watches each member can have in the server.
+    #[serde(default)]
+    pub max_watches_per_user: Option<usize>,
+    /// How long each member waits between commands that request passes from N2YO, in seconds.
+    #[serde(default)]
+    pub user_cooldown_seconds: Option<u64>,
+    /// How long each channel waits between commands that request passes from N2YO, in seconds.
+    #[serde(default)]
+    pub channel_cooldown_seconds: Option<u64>,
+    /// Roles whose members can use the admin commands without being able to manage the server.
+    #[serde(default)]
+    pub admin_roles: Vec<Snowflake>,
+}
+
+impl GuildSettings {
+    /// The settings of a guild, or the defaults outside of guilds or if none have been set.
+    pub fn for_guild(database: &Database, guild: Option<&Snowflake>) -> Self {
+        guild
+            .and_then(|guild| database.contents.guild_settings.get(guild))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// The satellites an alias stands for, ignoring case.
+    pub fn alias(&self, name: &str) -> Option<&[SatelliteId]> {
+        self.aliases
+            .get(&name.trim().to_lowercase())
+            .map(Vec::as_slice)
+    }
+
+    /// The most watches each member can have, falling back to the configured default.
+    pub fn max_watches_per_user(&self) -> usize {
+        self.max_watches_per_user
+            .unwrap_or(config::get().defaults.max_watches_per_user)
+    }
+
+    /// How long each member waits between commands that request passes, falling back to the
+    /// configured default. Zero turns the cooldown off.
+    pub fn user_cooldown_seconds(&self) -> u64 {
+        self.user_cooldown_seconds
+            .unwrap_or(config::get().defaults.user_cooldown_seconds)
+    }
+
+    /// How long each channel waits between commands that request passes, falling back to the
+    /// configured default. Zero turns the cooldown off.
+    pub fn channel_cooldown_seconds(&self) -> u64 {
+        self.channel_cooldown_seconds
+            .unwrap_or(config::get().defaults.channel_cooldown_seconds)
+    }
+
+    /// The minimum elevation to use when none is given, falling back to the configured default.
+    pub fn default_min_max_elevation(&self) -> f64 {
+        self.default_min_max_elevation
+            .unwrap_or(config::get().defaults.min_max_elevation)
+    }
+}
+
+/// How times are written for a user in plain text, where Discord timestamps are not rendered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeFormat {
+    pub clock: ClockFormat,
+    pub date: DateFormat,
+    /// The timezone times are converted to, UTC unless an observer's timezone is known.
+    pub timezone: Tz,
+}
+
+impl TimeFormat {
+    /// The same format, converting times to another timezone.
+    pub fn in_timezone(self, timezone: Tz) -> Self {
+        Self { timezone, ..self }
+    }
+
+    /// Writes the time of day of a UTC timestamp.
+    pub fn time(&self, timestamp: i64) -> String {
+        self.format(timestamp, self.clock.pattern())
+    }
+
+    /// Writes the date and time of a UTC timestamp, with the timezone's abbreviation.
+    pub fn date_time(&self, timestamp: i64) -> String {
+        self.format(
+            timestamp,
+            &format!("{} {} %Z", self.date.pattern(), self.clock.pattern()),
+        )
+    }
+
+    fn format(&self, timestamp: i64, pattern: &str) -> String {
+        self.timezone
+            .timestamp_opt(timestamp, 0)
+            .single()
+            .map(|time| time.format(pattern).to_string())
+            .unwrap_or_else(|| timestamp.to_string())
+    }
+}
+
+/// Parses an IANA timezone name such as `Europe/London`.
+pub fn parse_timezone(name: &str) -> Option<Tz> {
+    name.parse().ok()
+}
@@ -0,0 +1,105 @@
+use std::{fs::OpenOptions, path::Path};
+
+use serenity::http::Http;
+use tracing::info;
+
+use crate::{config, n2yo::N2YOAPI};
+
+/// The satellite whose TLE is requested to check that N2YO accepts the API key.
+const CHECK_SATELLITE: usize = 25544;
+
+/// Checks, before connecting to the gateway, that the database can be written and that N2YO and
+/// Discord accept their credentials, reporting every problem found at once.
+pub async fn check(n2yo_api: &N2YOAPI) -> anyhow::Result<()> {
+    info!("Running preflight checks");
+    let database = check_database(&config::get().database.path);
+    let (n2yo, discord) = tokio::join!(check_n2yo(n2yo_api), check_discord());
+    let problems = [database, n2yo, discord]
+        .into_iter()
+        .filter_map(Result::err)
+        .collect::<Vec<_>>();
+
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(anyhow::anyhow!(
+            "preflight checks failed:\n  {}",
+            problems.join("\n  ")
+        ))
+    }
+}
+
+/// Checks that the database, or the directory it is to be created in, can be written to.
+fn check_database(path: &Path) -> Result<(), String> {
+    let directory = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    let probe = directory.join(".sat-bot-preflight");
+
+    std::fs::write(&probe, b"")
+        .and_then(|_| std::fs::remove_file(&probe))
+        .map_err(|e| {
+            format!(
+                "cannot write to {} for DATABASE_PATH ({}); check that it exists and is writable",
+                directory.display(),
+                e
+            )
+        })?;
+
+    if path.exists() {
+        OpenOptions::new().append(true).open(path).map_err(|e| {
+            format!(
+                "cannot write to the database at {} ({}); check its permissions",
+                path.display(),
+                e
+            )
+        })?;
+    }
+
+    Ok(())
+}
+
+/// Makes one cheap request to N2YO, which also checks the API key. In mock mode nothing is sent.
+async fn check_n2yo(n2yo_api: &N2YOAPI) -> Result<(), String> {
+    n2yo_api
+        .get_tle(CHECK_SATELLITE)
+        .await
+        .map(|_| ())
+        .map_err(|e| {
+            format!(
+                "N2YO request failed ({}); check N2YO_KEY, and that N2YO can be reached",
+                e
+            )
+        })
+}
+
+/// Checks that Discord accepts the bot token.
+async fn check_discord() -> Result<(), String> {
+    Http::new(&config::get().discord.token)
+        .get_current_user()
+        .await
+        .map(|user| info!("Discord token belongs to {}", user.tag()))
+        .map_err(|e| {
+            format!(
+                "Discord rejected DISCORD_TOKEN ({}); check that it is the bot token from the \
+                 Developer Portal",
+                e
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checks_the_database_can_be_written() {
+        let directory = std::env::temp_dir();
+
+        assert!(check_database(&directory.join("sat_bot.json")).is_ok());
+
+        let problem = check_database(&directory.join("missing").join("sat_bot.json")).unwrap_err();
+        assert!(problem.contains("DATABASE_PATH"));
+    }
+}
@@ -0,0 +1,405 @@
+use std::ops::RangeInclusive;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{database::Location, n2yo::SatellitePass};
+
+/// The equatorial radius of the Earth in the WGS84 model, in kilometres.
+pub const EARTH_RADIUS: f64 = 6378.137;
+/// The flattening of the Earth in the WGS84 model.
+const EARTH_FLATTENING: f64 = 1.0 / 298.257223563;
+/// The time step used when searching for passes, in seconds.
+const SEARCH_STEP: i64 = 30;
+/// The mean motions, in revolutions per day, of satellites that orbit once a sidereal day and so
+/// stay over one point of the equator.
+const GEOSTATIONARY_MEAN_MOTION: RangeInclusive<f64> = 0.99..=1.01;
+
+/// A two-line element set, as cached in the database.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CachedTle {
+    pub line1: String,
+    pub line2: String,
+    pub fetched_at: i64,
+}
+
+impl CachedTle {
+    /// Parses a TLE from text containing its two lines, ignoring any title line.
+    pub fn parse(text: &str, fetched_at: i64) -> anyhow::Result<Self> {
+        let lines = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| line.starts_with("1 ") || line.starts_with("2 "))
+            .collect::<Vec<_>>();
+
+        match lines.as_slice() {
+            [line1, line2] => Ok(Self {
+                line1: line1.to_string(),
+                line2: line2.to_string(),
+                fetched_at,
+            }),
+            _ => Err(anyhow::anyhow!("malformed TLE")),
+        }
+    }
+
+    /// Parses every TLE in text such as a CelesTrak group, with the title line before each,
+    /// skipping any that are malformed.
+    pub fn parse_all(text: &str, fetched_at: i64) -> Vec<(String, Self)> {
+        let lines = text.lines().map(str::trim).collect::<Vec<_>>();
+
+        lines
+            .windows(3)
+            .filter(|window| window[1].starts_with("1 ") && window[2].starts_with("2 "))
+            .map(|window| {
+                (
+                    window[0].to_string(),
+                    Self {
+                        line1: window[1].to_string(),
+                        line2: window[2].to_string(),
+                        fetched_at,
+                    },
+                )
+            })
+            .collect()
+    }
+
+    /// The launch part of the international designator, such as `2024-001`.
+    pub fn launch(&self) -> Option<String> {
+        let year = self.line1.get(9..11)?.trim().parse::<i32>().ok()?;
+        let number = self.line1.get(11..14)?;
+        let year = if year < 57 { 2000 + year } else { 1900 + year };
+
+        Some(format!("{}-{}", year, number))
+    }
+
+    /// When the elements were measured, as a unix timestamp.
+    pub fn epoch(&self) -> Option<i64> {
+        let year = self.line1.get(18..20)?.trim().parse::<i32>().ok()?;
+        let day = self.line1.get(20..32)?.trim().parse::<f64>().ok()?;
+        // Two digit years from 57 onwards are in the 1900s, the first satellite being in 1957
+        let year = if year < 57 { 2000 + year } else { 1900 + year };
+        let start = NaiveDate::from_ymd_opt(year, 1, 1)?
+            .and_hms_opt(0, 0, 0)?
+            .and_utc()
+            .timestamp();
+
+        Some(start + ((day - 1.0) * 86400.0) as i64)
+    }
+
+    /// The mean motion, in revolutions per day.
+    pub fn mean_motion(&self) -> Option<f64> {
+        self.line2.get(52..63)?.trim().parse().ok()
+    }
+
+    /// How quickly the mean motion is increasing as drag lowers the orbit, in revolutions per day
+    /// per day.
+    pub fn mean_motion_rate(&self) -> Option<f64> {
+        // The TLE gives half of the first derivative
+        Some(2.0 * self.line1.get(33..43)?.trim().parse::<f64>().ok()?)
+    }
+
+    /// The inclination, in degrees.
+    pub fn inclination(&self) -> Option<f64> {
+        self.line2.get(8..16)?.trim().parse().ok()
+    }
+
+    /// The orbital period, in minutes.
+    pub fn period(&self) -> Option<f64> {
+        Some(24.0 * 60.0 / self.mean_motion()?)
+    }
+
+    pub fn eccentricity(&self) -> Option<f64> {
+        format!("0.{}", self.line2.get(26..33)?.trim()).parse().ok()
+    }
+
+    /// Whether the satellite is geostationary, staying in the same place in the sky.
+    pub fn is_geostationary(&self) -> bool {
+        self.mean_motion()
+            .is_some_and(|mean_motion| GEOSTATIONARY_MEAN_MOTION.contains(&mean_motion))
+    }
+}
+
+/// A satellite orbit that can be propagated locally with SGP4.
+pub struct Orbit {
+    elements: sgp4::Elements,
+    constants: sgp4::Constants,
+}
+
+/// The position of a satellite as seen by an observer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LookAngles {
+    /// Degrees clockwise from north.
+    pub azimuth: f64,
+    /// Degrees above the horizon.
+    pub elevation: f64,
+    /// Kilometres from the observer.
+    pub range: f64,
+}
+
+impl Orbit {
+    pub fn from_tle(tle: &CachedTle) -> anyhow::Result<Self> {
+        let elements = sgp4::Elements::from_tle(None, tle.line1.as_bytes(), tle.line2.as_bytes())?;
+        let constants = sgp4::Constants::from_elements(&elements)?;
+        Ok(Self {
+            elements,
+            constants,
+        })
+    }
+
+    /// The position of the satellite in the TEME frame at a unix timestamp, in kilometres.
+    pub fn position(&self, time: i64) -> anyhow::Result<[f64; 3]> {
+        let datetime = DateTime::<Utc>::from_timestamp(time, 0)
+            .ok_or_else(|| anyhow::anyhow!("invalid timestamp"))?
+            .naive_utc();
+        let minutes = self.elements.datetime_to_minutes_since_epoch(&datetime)?;
+        Ok(self.constants.propagate(minutes)?.position)
+    }
+
+    /// The position of the satellite relative to an observer at a unix timestamp.
+    pub fn look_angles(&self, location: &Location, time: i64) -> anyhow::Result<LookAngles> {
+        let position = teme_to_ecef(self.position(time)?, time);
+        Ok(topocentric(location, position))
+    }
+}
+
+/// Where a satellite is in the sky at a location at each step between two unix timestamps,
+/// including the end.
+pub fn track(
+    orbit: &Orbit,
+    location: &Location,
+    start: i64,
+    end: i64,
+    step: i64,
+) -> anyhow::Result<Vec<(i64, LookAngles)>> {
+    let mut times = (start..end)
+        .step_by(step.max(1) as usize)
+        .collect::<Vec<_>>();
+    times.push(end);
+
+    times
+        .into_iter()
+        .map(|time| Ok((time, orbit.look_angles(location, time)?)))
+        .collect()
+}
+
+/// Predicts the passes of a satellite over a location between two unix timestamps, keeping only
+/// those that reach the given elevation.
+pub fn predict_passes(
+    orbit: &Orbit,
+    location: &Location,
+    start: i64,
+    end: i64,
+    min_max_elevation: f64,
+) -> anyhow::Result<Vec<SatellitePass>> {
+    let mut passes = Vec::new();
+    let mut time = start;
+    let mut above = orbit.look_angles(location, time)?.elevation > 0.0;
+    let mut aos = above.then_some(start);
+
+    while time < end {
+        let next = (time + SEARCH_STEP).min(end);
+        let next_above = orbit.look_angles(location, next)?.elevation > 0.0;
+
+        if next_above && !above {
+            aos = Some(find_horizon_crossing(orbit, location, time, next)?);
+        } else if !next_above && above {
+            if let Some(start) = aos.take() {
+                let los = find_horizon_crossing(orbit, location, time, next)?;
+                let pass = build_pass(orbit, location, start, los)?;
+
+                if pass.max_elevation >= min_max_elevation {
+                    passes.push(pass);
+                }
+            }
+        }
+
+        above = next_above;
+        time = next;
+    }
+
+    Ok(passes)
+}
+
+/// Finds the second at which the satellite crosses the horizon, given a range that contains one
+/// crossing.
+fn find_horizon_crossing(
+    orbit: &Orbit,
+    location: &Location,
+    mut low: i64,
+    mut high: i64,
+) -> anyhow::Result<i64> {
+    let low_above = orbit.look_angles(location, low)?.elevation > 0.0;
+
+    while high - low > 1 {
+        let middle = (low + high) / 2;
+
+        if (orbit.look_angles(location, middle)?.elevation > 0.0) == low_above {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+
+    Ok(high)
+}
+
+fn build_pass(
+    orbit: &Orbit,
+    location: &Location,
+    start: i64,
+    end: i64,
+) -> anyhow::Result<SatellitePass> {
+    let start_angles = orbit.look_angles(location, start)?;
+    let end_angles = orbit.look_angles(location, end)?;
+    let (max_time, max_angles) = (start..=end)
+        .step_by(5)
+        .map(|time| {
+            orbit
+                .look_angles(location, time)
+                .map(|angles| (time, angles))
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?
+        .into_iter()
+        .max_by(|(_, a), (_, b)| a.elevation.total_cmp(&b.elevation))
+        .unwrap_or((start, start_angles));
+
+    Ok(SatellitePass {
+        start_azimuth: round(start_angles.azimuth),
+        start_azimuth_compass: compass(start_angles.azimuth).to_string(),
+        start_utc: start as usize,
+        max_azimuth: round(max_angles.azimuth),
+        max_azimuth_compass: compass(max_angles.azimuth).to_string(),
+        max_elevation: round(max_angles.elevation),
+        max_utc: max_time as usize,
+        end_azimuth: round(end_angles.azimuth),
+        end_azimuth_compass: compass(end_angles.azimuth).to_string(),
+        end_utc: end as usize,
+        visible_fraction: None,
+        cloud_cover: None,
+        moon_separation: None,
+        magnitude: None,
+    })
+}
+
+/// The 16-point compass direction of an azimuth, in the style used by N2YO.
+pub fn compass(azimuth: f64) -> &'static str {
+    const POINTS: [&str; 16] = [
+        "N", "NNE", "NE", "ENE", "E", "ESE", "SE", "SSE", "S", "SSW", "SW", "WSW", "W", "WNW",
+        "NW", "NNW",
+    ];
+    POINTS[((azimuth.rem_euclid(360.0) / 22.5).round() as usize) % 16]
+}
+
+fn round(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+/// The Greenwich mean sidereal time at a unix timestamp, in radians.
+pub fn gmst(time: i64) -> f64 {
+    let julian_date = time as f64 / 86400.0 + 2440587.5;
+    (280.46061837 + 360.98564736629 * (julian_date - 2451545.0))
+        .rem_euclid(360.0)
+        .to_radians()
+}
+
+/// Rotates a TEME position into the Earth-fixed frame, ignoring polar motion.
+pub fn teme_to_ecef(position: [f64; 3], time: i64) -> [f64; 3] {
+    let theta = gmst(time);
+    [
+        theta.cos() * position[0] + theta.sin() * position[1],
+        -theta.sin() * position[0] + theta.cos() * position[1],
+        position[2],
+    ]
+}
+
+/// The longitude below an Earth-fixed position, in degrees east.
+pub fn longitude(position: [f64; 3]) -> f64 {
+    position[1].atan2(position[0]).to_degrees()
+}
+
+/// The Earth-fixed position of a location, in kilometres.
+pub fn location_to_ecef(location: &Location) -> [f64; 3] {
+    let latitude = location.latitude.to_radians();
+    let longitude = location.longitude.to_radians();
+    let altitude = location.altitude / 1000.0;
+    let e2 = EARTH_FLATTENING * (2.0 - EARTH_FLATTENING);
+    let n = EARTH_RADIUS / (1.0 - e2 * latitude.sin().powi(2)).sqrt();
+
+    [
+        (n + altitude) * latitude.cos() * longitude.cos(),
+        (n + altitude) * latitude.cos() * longitude.sin(),
+        (n * (1.0 - e2) + altitude) * latitude.sin(),
+    ]
+}
+
+/// The look angles from a location to an Earth-fixed position.
+pub fn topocentric(location: &Location, position: [f64; 3]) -> LookAngles {
+    let observer = location_to_ecef(location);
+    let range = [
+        position[0] - observer[0],
+        position[1] - observer[1],
+        position[2] - observer[2],
+    ];
+    let latitude = location.latitude.to_radians();
+    let longitude = location.longitude.to_radians();
+
+    let east = -longitude.sin() * range[0] + longitude.cos() * range[1];
+    let north = -latitude.sin() * longitude.cos() * range[0]
+        - latitude.sin() * longitude.sin() * range[1]
+        + latitude.cos() * range[2];
+    let up = latitude.cos() * longitude.cos() * range[0]
+        + latitude.cos() * longitude.sin() * range[1]
+        + latitude.sin() * range[2];
+    let distance = (east * east + north * north + up * up).sqrt();
+
+    LookAngles {
+        azimuth: east.atan2(north).to_degrees().rem_euclid(360.0),
+        elevation: (up / distance).asin().to_degrees(),
+        range: distance,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::{LocationName, Snowflake};
+
+    /// Es'hail 2, which carries QO-100, over 25.9° E.
+    const GEOSTATIONARY_LINE1: &str =
+        "1 43700U 18090A   24010.50000000  .00000150  00000-0  00000-0 0  9990";
+    const GEOSTATIONARY_LINE2: &str =
+        "2 43700   0.0300 270.0000 0001500 200.0000 205.4163  1.00270000 19007";
+
+    #[test]
+    fn points_at_geostationary_satellites() {
+        let tle = CachedTle {
+            line1: GEOSTATIONARY_LINE1.to_string(),
+            line2: GEOSTATIONARY_LINE2.to_string(),
+            fetched_at: 0,
+        };
+        let london = Location {
+            name: LocationName("london".to_string()),
+            creator: Snowflake(1),
+            latitude: 51.5,
+            longitude: -0.1,
+            altitude: 0.0,
+            guild: None,
+        };
+        let orbit = Orbit::from_tle(&tle).unwrap();
+        let epoch = tle.epoch().unwrap();
+
+        assert!(tle.is_geostationary());
+        assert_eq!(tle.inclination(), Some(0.03));
+        assert!((tle.period().unwrap() - 1436.1).abs() < 0.1);
+
+        // It stays put through the day
+        for time in [epoch, epoch + 6 * 60 * 60, epoch + 12 * 60 * 60] {
+            let position = teme_to_ecef(orbit.position(time).unwrap(), time);
+            let look_angles = topocentric(&london, position);
+
+            assert!((longitude(position) - 25.9).abs() < 1.0);
+            assert!((look_angles.azimuth - 148.5).abs() < 1.0);
+            assert!((look_angles.elevation - 26.2).abs() < 1.0);
+        }
+    }
+}
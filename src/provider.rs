@@ -0,0 +1,427 @@
+use std::{sync::Arc, time::Duration};
+
+use serenity::futures::{stream, StreamExt};
+use tokio::{
+    sync::RwLock,
+    time::{sleep_until, Instant},
+};
+use tracing::{debug, info, warn};
+
+use crate::{
+    astro,
+    celestrak::CelesTrakAPI,
+    config,
+    database::{Database, Location, SatelliteId},
+    error::BotError,
+    n2yo::{
+        CacheMode, PassKind, PassSource, SatellitePass, SatellitePassInfo, SatellitePasses, N2YOAPI,
+    },
+    propagation::{self, CachedTle, Orbit},
+    satnogs::SatnogsAPI,
+    util,
+    weather::WeatherAPI,
+};
+
+/// The parameters of a single pass lookup, for fetching several at once.
+#[derive(Debug, Clone)]
+pub struct PassRequest {
+    pub satellite_id: usize,
+    pub location: Location,
+    pub days: usize,
+    pub min_max_elevation: f64,
+    pub kind: PassKind,
+}
+
+impl PassRequest {
+    /// Identifies what is asked for, so that identical requests can share a single lookup.
+    pub fn key(&self) -> (usize, [u64; 3], usize, u64, PassKind) {
+        (
+            self.satellite_id,
+            [
+                self.location.latitude.to_bits(),
+                self.location.longitude.to_bits(),
+                self.location.altitude.to_bits(),
+            ],
+            self.days,
+            self.min_max_elevation.to_bits(),
+            self.kind,
+        )
+    }
+}
+
+/// Provides pass predictions, preferring N2YO and falling back to local SGP4 propagation from
+/// cached TLEs when N2YO is unavailable.
+pub struct PassProvider {
+    n2yo_api: Arc<N2YOAPI>,
+    celestrak_api: Arc<CelesTrakAPI>,
+    weather_api: Arc<WeatherAPI>,
+    satnogs_api: Arc<SatnogsAPI>,
+    database: Arc<RwLock<Database>>,
+    concurrency: usize,
+    /// How long notification runs leave between starting requests.
+    request_delay: Duration,
+    /// The most that is randomly added to each delay between requests in notification runs.
+    request_jitter: Duration,
+}
+
+impl PassProvider {
+    pub fn new(
+        n2yo_api: Arc<N2YOAPI>,
+        celestrak_api: Arc<CelesTrakAPI>,
+        weather_api: Arc<WeatherAPI>,
+        satnogs_api: Arc<SatnogsAPI>,
+        database: Arc<RwLock<Database>>,
+    ) -> anyhow::Result<Self> {
+        Ok(Self {
+            n2yo_api,
+            celestrak_api,
+            weather_api,
+            satnogs_api,
+            database,
+            concurrency: config::get().n2yo.concurrency,
+            request_delay: Duration::from_millis(config::get().notifications.request_delay_ms),
+            request_jitter: Duration::from_millis(config::get().notifications.request_jitter_ms),
+        })
+    }
+
+    pub fn n2yo_api(&self) -> &N2YOAPI {
+        &self.n2yo_api
+    }
+
+    /// A summary of the main downlink of a satellite from SatNOGS DB. This is best effort, as
+    /// notifications are still worth sending without it.
+    pub async fn primary_transmitter(&self, satellite_id: usize) -> Option<String> {
+        match self.satnogs_api.get_active_transmitters(satellite_id).await {
+            Ok(transmitters) => transmitters
+                .first()
+                .and_then(|transmitter| transmitter.summary()),
+            Err(e) => {
+                warn!(
+                    "Failed to get the transmitters of {} from SatNOGS DB: {}",
+                    satellite_id, e
+                );
+                None
+            }
+        }
+    }
+
+    pub async fn get_satellite_passes(
+        &self,
+        satellite_id: usize,
+        location: &Location,
+        days: usize,
+        min_max_elevation: f64,
+        kind: PassKind,
+        cache_mode: CacheMode,
+    ) -> anyhow::Result<SatellitePasses> {
+        let mut passes = match self
+            .n2yo_api
+            .get_satellite_passes(
+                satellite_id,
+                location,
+                days,
+                min_max_elevation,
+                kind,
+                cache_mode,
+            )
+            .await
+        {
+            Ok(mut passes) => {
+                self.add_visibility(&mut passes, location).await;
+                passes
+            }
+            Err(error) => {
+                warn!(
+                    "N2YO failed to provide passes for {} ({}), falling back to local prediction",
+                    satellite_id, error
+                );
+
+                self.predict_locally(satellite_id, location, days, min_max_elevation, kind)
+                    .await
+                    .map_err(|fallback_error| {
+                        BotError::N2yo(anyhow::anyhow!(
+                            "{} (local prediction also failed: {})",
+                            error,
+                            fallback_error
+                        ))
+                    })?
+            }
+        };
+
+        self.add_cloud_cover(&mut passes, location, days).await;
+        Ok(passes)
+    }
+
+    /// Fetches passes for several requests concurrently, with at most the configured number of
+    /// requests in flight. Results are returned in the same order as the requests.
+    pub async fn get_many_satellite_passes(
+        &self,
+        requests: Vec<PassRequest>,
+        cache_mode: CacheMode,
+    ) -> Vec<anyhow::Result<SatellitePasses>> {
+        self.fetch_many(requests, cache_mode, Duration::ZERO, Duration::ZERO)
+            .await
+    }
+
+    /// Fetches passes like [`Self::get_many_satellite_passes`], but spreads the requests out by
+    /// `NOTIFY_REQUEST_DELAY_MS` plus up to `NOTIFY_REQUEST_JITTER_MS` each, so that notification
+    /// runs with many watches do not trip N2YO's throttling.
+    pub async fn get_many_satellite_passes_staggered(
+        &self,
+        requests: Vec<PassRequest>,
+        cache_mode: CacheMode,
+    ) -> Vec<anyhow::Result<SatellitePasses>> {
+        self.fetch_many(
+            requests,
+            cache_mode,
+            self.request_delay,
+            self.request_jitter,
+        )
+        .await
+    }
+
+    /// Fetches passes for several requests, starting each at least `delay` after the one before
+    /// it, plus up to `jitter`.
+    async fn fetch_many(
+        &self,
+        requests: Vec<PassRequest>,
+        cache_mode: CacheMode,
+        delay: Duration,
+        jitter: Duration,
+    ) -> Vec<anyhow::Result<SatellitePasses>> {
+        let mut next = Instant::now();
+
+        stream::iter(requests)
+            .map(|request| {
+                let starts_at = next;
+                next += delay + jitter.mul_f64(util::jitter());
+
+                async move {
+                    sleep_until(starts_at).await;
+                    self.get_satellite_passes(
+                        request.satellite_id,
+                        &request.location,
+                        request.days,
+                        request.min_max_elevation,
+                        request.kind,
+                        cache_mode,
+                    )
+                    .await
+                }
+            })
+            .buffered(self.concurrency)
+            .collect()
+            .await
+    }
+
+    /// Predicts passes from the satellite's TLE. Visual passes are those with any of the pass
+    /// visible, but without a magnitude, as that needs the satellite's size and shape.
+    async fn predict_locally(
+        &self,
+        satellite_id: usize,
+        location: &Location,
+        days: usize,
+        min_max_elevation: f64,
+        kind: PassKind,
+    ) -> anyhow::Result<SatellitePasses> {
+        let (orbit, name) = self.orbit(satellite_id).await?;
+        let start = util::current_utc();
+        let mut passes = propagation::predict_passes(
+            &orbit,
+            location,
+            start,
+            start + days as i64 * 24 * 60 * 60,
+            min_max_elevation,
+        )?;
+
+        for pass in passes.iter_mut() {
+            observe(&orbit, location, pass);
+        }
+
+        if kind == PassKind::Visual {
+            passes.retain(|pass| pass.visible_fraction.is_some_and(|fraction| fraction > 0.0));
+        }
+
+        Ok(SatellitePasses {
+            info: SatellitePassInfo {
+                id: satellite_id,
+                name,
+                transaction_count: 0,
+                passes_count: passes.len(),
+            },
+            passes,
+            source: PassSource::Local,
+            raw: None,
+        })
+    }
+
+    /// Works out how much of each pass can be seen with the naked eye. This is best effort, as
+    /// N2YO does not give the orbit needed, so passes are left unmarked if there is no TLE.
+    async fn add_visibility(&self, passes: &mut SatellitePasses, location: &Location) {
+        let orbit = match self.tle(passes.info.id).await {
+            Ok((tle, _)) => Orbit::from_tle(&tle),
+            Err(e) => Err(e),
+        };
+
+        let orbit = match orbit {
+            Ok(orbit) => orbit,
+            Err(e) => {
+                debug!(
+                    "No orbit to check the visibility of passes of {}: {}",
+                    passes.info.id, e
+                );
+                return;
+            }
+        };
+
+        for pass in passes.passes.iter_mut() {
+            observe(&orbit, location, pass);
+        }
+    }
+
+    /// Adds the forecast cloud cover to each pass. This is best effort, as passes are still worth
+    /// knowing about without it.
+    async fn add_cloud_cover(
+        &self,
+        passes: &mut SatellitePasses,
+        location: &Location,
+        days: usize,
+    ) {
+        if passes.passes.is_empty() {
+            return;
+        }
+
+        match self.weather_api.get_cloud_cover(location, days).await {
+            Ok(forecast) => {
+                for pass in passes.passes.iter_mut() {
+                    pass.cloud_cover = forecast.at(pass.max_utc as i64);
+                }
+            }
+            Err(e) => warn!("Failed to get the cloud cover forecast: {}", e),
+        }
+    }
+
+    /// Gets the orbit and name of a satellite, for working out its exact position.
+    pub async fn orbit(&self, satellite_id: usize) -> anyhow::Result<(Orbit, String)> {
+        let (tle, name) = self.tle(satellite_id).await?;
+        Ok((Orbit::from_tle(&tle)?, name))
+    }
+
+    /// Gets the TLE and name of a satellite, from the database if it was cached within the
+    /// catalog's maximum age and otherwise from CelesTrak. An older cached TLE is only used if
+    /// CelesTrak cannot be reached.
+    pub async fn tle(&self, satellite_id: usize) -> anyhow::Result<(CachedTle, String)> {
+        let stale = {
+            let database = self.database.read().await;
+            let cached = database.contents.tles.get(&SatelliteId(satellite_id));
+            let name = database
+                .contents
+                .satellites
+                .get(&SatelliteId(satellite_id))
+                .map(|metadata| metadata.name.clone())
+                .unwrap_or(satellite_id.to_string());
+
+            match cached {
+                Some(tle)
+                    if util::current_utc() - tle.fetched_at
+                        <= config::get().catalog.max_age_hours * 60 * 60 =>
+                {
+                    return Ok((tle.clone(), name));
+                }
+                Some(tle) => Some((tle.clone(), name)),
+                None => None,
+            }
+        };
+
+        match (self.fetch_tle(satellite_id).await, stale) {
+            (Ok(fetched), _) => Ok(fetched),
+            (Err(e), Some(stale)) => {
+                warn!(
+                    "Failed to refresh the TLE of {}, using the one fetched at {}: {}",
+                    satellite_id, stale.0.fetched_at, e
+                );
+                Ok(stale)
+            }
+            (Err(e), None) => Err(e),
+        }
+    }
+
+    /// Fetches the TLE and name of a satellite from CelesTrak, and caches the TLE.
+    async fn fetch_tle(&self, satellite_id: usize) -> anyhow::Result<(CachedTle, String)> {
+        info!("Fetching TLE for {} from CelesTrak", satellite_id);
+        let text = self.celestrak_api.get_tle(satellite_id).await?;
+        let tle = CachedTle::parse(&text, util::current_utc())?;
+        let name = text
+            .lines()
+            .next()
+            .filter(|line| !line.starts_with("1 "))
+            .map(|line| line.trim().to_string())
+            .unwrap_or(satellite_id.to_string());
+
+        let mut database = self.database.write().await;
+        database
+            .contents
+            .store_tle(SatelliteId(satellite_id), tle.clone());
+        database.mark_dirty();
+
+        Ok((tle, name))
+    }
+}
+
+/// Works out how much of a pass can be seen with the naked eye and, if any of it can, how close
+/// it comes to the Moon, whose glare can hide it.
+fn observe(orbit: &Orbit, location: &Location, pass: &mut SatellitePass) {
+    pass.visible_fraction = astro::visible_fraction(orbit, location, pass).ok();
+
+    if pass.visible_fraction.is_some_and(|fraction| fraction > 0.0) {
+        pass.moon_separation = astro::moon_separation(orbit, location, pass).ok().flatten();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::{database::DatabaseContents, mock};
+
+    fn provider(tles: HashMap<SatelliteId, CachedTle>) -> PassProvider {
+        PassProvider::new(
+            Arc::new(N2YOAPI::mocked()),
+            Arc::new(CelesTrakAPI::mocked()),
+            Arc::new(WeatherAPI::new().unwrap()),
+            Arc::new(SatnogsAPI::new().unwrap()),
+            Arc::new(RwLock::new(Database::in_memory(DatabaseContents {
+                tles,
+                ..Default::default()
+            }))),
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn refetches_stale_tles() {
+        let max_age = config::get().catalog.max_age_hours * 60 * 60;
+        let now = util::current_utc();
+        let cached = |fetched_at| {
+            let mut tle = CachedTle::parse(&mock::tle_text(25544), fetched_at).unwrap();
+            tle.line1 = "cached".to_string();
+            tle
+        };
+        let provider = provider(HashMap::from([
+            (SatelliteId(25544), cached(now - max_age + 60)),
+            (SatelliteId(43017), cached(now - max_age - 60)),
+        ]));
+
+        let (fresh, _) = provider.tle(25544).await.unwrap();
+        assert_eq!(fresh.line1, "cached");
+
+        let (refetched, _) = provider.tle(43017).await.unwrap();
+        assert_ne!(refetched.line1, "cached");
+        assert!(refetched.fetched_at >= now);
+        assert_eq!(
+            provider.database.read().await.contents.tles[&SatelliteId(43017)],
+            refetched
+        );
+    }
+}
@@ -0,0 +1,191 @@
+use std::{
+    collections::{hash_map::DefaultHasher, HashSet},
+    hash::{Hash, Hasher},
+    str::FromStr,
+};
+
+use poise::{serenity_prelude::GuildId, Command};
+use serenity::http::{GuildPagination, Http};
+use tokio::sync::RwLock;
+use tracing::info;
+
+use crate::{
+    config,
+    database::{Database, Snowflake},
+    ApplicationContext,
+};
+
+/// Where slash commands are registered.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RegistrationMode {
+    /// Only in the guild given by `GUILD_ID`.
+    #[default]
+    Guild,
+    /// Globally, in every guild the bot is in.
+    Global,
+    /// Separately in every guild the bot joins, which takes effect immediately unlike global
+    /// registration.
+    AllGuilds,
+}
+
+impl FromStr for RegistrationMode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "guild" => Ok(Self::Guild),
+            "global" => Ok(Self::Global),
+            "all-guilds" => Ok(Self::AllGuilds),
+            _ => Err(anyhow::anyhow!(
+                "expected one of guild, global, or all-guilds, got {}",
+                s
+            )),
+        }
+    }
+}
+
+/// Registers commands according to the registration mode, given the guilds the bot is in.
+pub async fn register(
+    http: &Http,
+    commands: &[Command<ApplicationContext, anyhow::Error>],
+    guilds: impl Iterator<Item = GuildId>,
+) -> anyhow::Result<()> {
+    match config::get().discord.command_registration {
+        RegistrationMode::Guild => {
+            let guild = config::get().discord.guild_id.ok_or_else(|| {
+                anyhow::anyhow!(
+                    "discord.guild_id (GUILD_ID) must be set to register commands in a guild"
+                )
+            })?;
+            register_in_guild(http, commands, GuildId(guild)).await?
+        }
+        RegistrationMode::Global => {
+            info!("Registering commands globally");
+            poise::builtins::register_globally(http, commands).await?;
+        }
+        RegistrationMode::AllGuilds => {
+            for guild in guilds {
+                register_in_guild(http, commands, guild).await?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Registers commands at startup, unless the same commands were last registered in the same way.
+/// In `all-guilds` mode, guilds joined while the bot was offline are registered in regardless.
+pub async fn register_if_changed(
+    http: &Http,
+    database: &RwLock<Database>,
+    commands: &[Command<ApplicationContext, anyhow::Error>],
+    guilds: impl Iterator<Item = GuildId>,
+) -> anyhow::Result<()> {
+    if database.read().await.contents.registered_commands != Some(fingerprint(commands)?) {
+        return sync(http, database, commands, guilds).await;
+    }
+
+    info!("Commands are unchanged since they were last registered");
+
+    if config::get().discord.command_registration == RegistrationMode::AllGuilds {
+        for guild in guilds {
+            register_in_new_guild(http, database, commands, guild).await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Registers commands, and remembers what was registered so that startup can tell whether they
+/// have changed.
+pub async fn sync(
+    http: &Http,
+    database: &RwLock<Database>,
+    commands: &[Command<ApplicationContext, anyhow::Error>],
+    guilds: impl Iterator<Item = GuildId>,
+) -> anyhow::Result<()> {
+    let guilds = guilds.collect::<Vec<_>>();
+    register(http, commands, guilds.iter().copied()).await?;
+
+    let mut database = database.write().await;
+    database.contents.registered_commands = Some(fingerprint(commands)?);
+    database.contents.registered_guilds =
+        if config::get().discord.command_registration == RegistrationMode::AllGuilds {
+            guilds.iter().map(|guild| Snowflake(guild.0)).collect()
+        } else {
+            HashSet::new()
+        };
+    database.mark_dirty();
+    Ok(())
+}
+
+/// Registers commands in a guild in `all-guilds` mode, unless they already are. Guilds become
+/// available again after every reconnect, so this is called for guilds the bot has long been in.
+pub async fn register_in_new_guild(
+    http: &Http,
+    database: &RwLock<Database>,
+    commands: &[Command<ApplicationContext, anyhow::Error>],
+    guild: GuildId,
+) -> anyhow::Result<()> {
+    let id = Snowflake(guild.0);
+
+    if database
+        .read()
+        .await
+        .contents
+        .registered_guilds
+        .contains(&id)
+    {
+        return Ok(());
+    }
+
+    register_in_guild(http, commands, guild).await?;
+
+    let mut database = database.write().await;
+    database.contents.registered_guilds.insert(id);
+    database.mark_dirty();
+    Ok(())
+}
+
+/// A hash of the commands as they are sent to Discord and where they are registered.
+fn fingerprint(commands: &[Command<ApplicationContext, anyhow::Error>]) -> anyhow::Result<u64> {
+    let mut hasher = DefaultHasher::new();
+    serde_json::to_string(&poise::builtins::create_application_commands(commands).0)?
+        .hash(&mut hasher);
+    config::get().discord.command_registration.hash(&mut hasher);
+    config::get().discord.guild_id.hash(&mut hasher);
+    Ok(hasher.finish())
+}
+
+pub async fn register_in_guild(
+    http: &Http,
+    commands: &[Command<ApplicationContext, anyhow::Error>],
+    guild: GuildId,
+) -> anyhow::Result<()> {
+    info!("Registering commands in guild {}", guild.0);
+    poise::builtins::register_in_guild(http, commands, guild).await?;
+    Ok(())
+}
+
+/// Fetches every guild the bot is in, for when there is no gateway connection to learn them from.
+pub async fn fetch_guilds(http: &Http) -> anyhow::Result<Vec<GuildId>> {
+    let mut guilds = Vec::new();
+
+    loop {
+        let page = http
+            .get_guilds(
+                guilds
+                    .last()
+                    .map(|&guild| GuildPagination::After(guild))
+                    .as_ref(),
+                Some(200),
+            )
+            .await?;
+        let done = page.len() < 200;
+        guilds.extend(page.into_iter().map(|guild| guild.id));
+
+        if done {
+            return Ok(guilds);
+        }
+    }
+}
@@ -0,0 +1,396 @@
+use std::{sync::Arc, time::Duration};
+
+use serde::{Deserialize, Serialize};
+use serenity::{
+    builder::CreateEmbed,
+    http::Http,
+    model::application::interaction::{
+        message_component::MessageComponentInteraction, InteractionResponseType,
+    },
+};
+use tokio::{sync::RwLock, task::JoinHandle, time::interval};
+use tracing::{info, warn};
+
+use crate::{
+    config,
+    database::{Database, Snowflake},
+    i18n::tr,
+    n2yo::SatellitePass,
+    notify::Notifier,
+    preferences::UserPreferences,
+    util::{self, TimeStyle},
+    ApplicationContext,
+};
+
+/// The start of the custom ID of "I'm in" buttons.
+pub const BUTTON_PREFIX: &str = "rsvp:";
+/// The longest custom ID Discord allows.
+const MAX_CUSTOM_ID_LENGTH: usize = 100;
+/// The most attendees named in a notification's footer.
+const MAX_NAMED_ATTENDEES: usize = 10;
+
+/// The people going to a notified pass, who are reminded before it starts.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PassRsvp {
+    /// The notification the pass is in.
+    pub message: Snowflake,
+    pub channel: Snowflake,
+    pub satellite: String,
+    pub start: i64,
+    pub end: i64,
+    /// The locale of the watch that notified of the pass, which the footer and reminders are
+    /// written in.
+    pub locale: String,
+    /// The footer of the pass's embed before anyone said they were going.
+    pub footer: String,
+    pub attendees: Vec<Attendee>,
+    pub reminded: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Attendee {
+    pub user: Snowflake,
+    /// The name shown in the footer, as Discord does not render mentions there.
+    pub name: String,
+}
+
+/// The pass an "I'm in" button was pressed for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct RsvpButton {
+    /// The position of the pass's embed in the notification.
+    embed: usize,
+    satellite_id: usize,
+    start: i64,
+    end: i64,
+    location: String,
+}
+
+impl RsvpButton {
+    fn parse(custom_id: &str) -> Option<Self> {
+        let mut parts = custom_id.strip_prefix(BUTTON_PREFIX)?.splitn(5, ':');
+
+        Some(Self {
+            embed: parts.next()?.parse().ok()?,
+            satellite_id: parts.next()?.parse().ok()?,
+            start: parts.next()?.parse().ok()?,
+            end: parts.next()?.parse().ok()?,
+            location: parts.next()?.to_string(),
+        })
+    }
+}
+
+/// The custom ID of an "I'm in" button for the pass in an embed of a notification, if it fits in
+/// one.
+pub fn button_id(
+    embed: usize,
+    satellite_id: usize,
+    pass: &SatellitePass,
+    location: &str,
+) -> Option<String> {
+    let id = format!(
+        "{}{}:{}:{}:{}:{}",
+        BUTTON_PREFIX, embed, satellite_id, pass.start_utc, pass.end_utc, location
+    );
+
+    (id.len() <= MAX_CUSTOM_ID_LENGTH).then_some(id)
+}
+
+/// Adds or removes the presser of an "I'm in" button from the pass's attendees, and updates the
+/// footer of its embed to list them.
+pub async fn toggle(
+    http: Arc<Http>,
+    data: &ApplicationContext,
+    component: &MessageComponentInteraction,
+) -> anyhow::Result<()> {
+    let Some(button) = RsvpButton::parse(&component.data.custom_id) else {
+        return Ok(());
+    };
+
+    if button.end < util::current_utc() {
+        component
+            .create_interaction_response(&http, |r| {
+                r.kind(InteractionResponseType::ChannelMessageWithSource)
+                    .interaction_response_data(|d| {
+                        d.content(tr!(&component.locale, "error-pass-over"))
+                            .ephemeral(true)
+                    })
+            })
+            .await?;
+        return Ok(());
+    }
+
+    let message = Snowflake(component.message.id.0);
+    let channel = Snowflake(component.channel_id.0);
+    let user = Snowflake(component.user.id.0);
+    let name = component
+        .member
+        .as_ref()
+        .and_then(|member| member.nick.clone())
+        .unwrap_or_else(|| component.user.name.clone());
+    let Some(embed) = component.message.embeds.get(button.embed) else {
+        return Ok(());
+    };
+
+    let rsvp = {
+        let mut database = data.database.write().await;
+        let contents = &mut database.contents;
+
+        let index = match contents
+            .pass_rsvps
+            .iter()
+            .position(|rsvp| rsvp.message == message && rsvp.start == button.start)
+        {
+            Some(index) => index,
+            None => {
+                let watched_satellite = contents.watched_satellites.iter().find(|watched| {
+                    watched.channel == channel
+                        && watched.satellite_id.0 == button.satellite_id
+                        && watched.location.0 == button.location
+                });
+
+                contents.pass_rsvps.push(PassRsvp {
+                    message: message.clone(),
+                    channel,
+                    satellite: watched_satellite
+                        .map(|watched| watched.name.clone())
+                        .unwrap_or_else(|| button.satellite_id.to_string()),
+                    start: button.start,
+                    end: button.end,
+                    locale: watched_satellite
+                        .map(|watched| watched.locale.clone())
+                        .unwrap_or_else(|| component.locale.clone()),
+                    footer: embed
+                        .footer
+                        .as_ref()
+                        .map(|footer| footer.text.clone())
+                        .unwrap_or_default(),
+                    attendees: Vec::new(),
+                    reminded: false,
+                });
+                contents.pass_rsvps.len() - 1
+            }
+        };
+
+        let rsvp = &mut contents.pass_rsvps[index];
+        match rsvp
+            .attendees
+            .iter()
+            .position(|attendee| attendee.user == user)
+        {
+            Some(position) => {
+                rsvp.attendees.remove(position);
+            }
+            None => rsvp.attendees.push(Attendee {
+                user: user.clone(),
+                name,
+            }),
+        }
+
+        let rsvp = rsvp.clone();
+        database.mark_dirty();
+        rsvp
+    };
+
+    info!(
+        "{} going to the pass of {} at {}",
+        rsvp.attendees.len(),
+        rsvp.satellite,
+        rsvp.start
+    );
+
+    let embeds = component
+        .message
+        .embeds
+        .iter()
+        .enumerate()
+        .map(|(index, embed)| {
+            let mut embed = CreateEmbed::from(embed.clone());
+
+            if index == button.embed {
+                embed.footer(|f| f.text(footer(&rsvp)));
+            }
+
+            embed
+        })
+        .collect::<Vec<_>>();
+
+    component
+        .create_interaction_response(&http, |r| {
+            r.kind(InteractionResponseType::UpdateMessage)
+                .interaction_response_data(|d| d.set_embeds(embeds))
+        })
+        .await?;
+
+    Ok(())
+}
+
+/// The footer of a pass's embed, listing who is going.
+fn footer(rsvp: &PassRsvp) -> String {
+    if rsvp.attendees.is_empty() {
+        return rsvp.footer.clone();
+    }
+
+    let mut names = rsvp
+        .attendees
+        .iter()
+        .take(MAX_NAMED_ATTENDEES)
+        .map(|attendee| attendee.name.as_str())
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    if rsvp.attendees.len() > MAX_NAMED_ATTENDEES {
+        names.push_str(&tr!(
+            &rsvp.locale,
+            "rsvp-more",
+            count = rsvp.attendees.len() - MAX_NAMED_ATTENDEES
+        ));
+    }
+
+    format!(
+        "{}\n{}",
+        tr!(
+            &rsvp.locale,
+            "rsvp-going",
+            count = rsvp.attendees.len(),
+            names = names
+        ),
+        rsvp.footer
+    )
+}
+
+/// Spawns a task that reminds attendees shortly before their passes start.
+pub fn spawn_reminder_task(
+    http: Arc<Http>,
+    database: Arc<RwLock<Database>>,
+) -> anyhow::Result<JoinHandle<()>> {
+    let minutes = config::get().notifications.rsvp_reminder_minutes;
+
+    Ok(tokio::spawn(async move {
+        let mut interval = interval(Duration::from_secs(60));
+
+        loop {
+            interval.tick().await;
+            send_reminders(&*http, &database, minutes).await;
+        }
+    }))
+}
+
+/// Messages the attendees of passes starting within a number of minutes, once, unless they have
+/// turned off direct messages. Passes that have ended are forgotten.
+pub async fn send_reminders(notifier: &impl Notifier, database: &RwLock<Database>, minutes: i64) {
+    let now = util::current_utc();
+
+    let due = {
+        let mut database = database.write().await;
+        let before = database.contents.pass_rsvps.len();
+        database.contents.pass_rsvps.retain(|rsvp| rsvp.end > now);
+        let mut changed = database.contents.pass_rsvps.len() != before;
+
+        let mut due = Vec::new();
+
+        for rsvp in database.contents.pass_rsvps.iter_mut() {
+            if rsvp.reminded || rsvp.start - minutes * 60 > now {
+                continue;
+            }
+
+            rsvp.reminded = true;
+            changed = true;
+            due.extend(
+                rsvp.attendees
+                    .iter()
+                    .map(|attendee| (attendee.user.clone(), rsvp.clone())),
+            );
+        }
+
+        due.retain(|(user, _)| UserPreferences::for_user(&database, user).direct_messages);
+
+        if changed {
+            database.mark_dirty();
+        }
+
+        due
+    };
+
+    for (user, rsvp) in due {
+        let message = tr!(
+            &rsvp.locale,
+            "rsvp-reminder",
+            satellite = rsvp.satellite.as_str(),
+            time = util::format_time(rsvp.start, TimeStyle::Discord),
+            channel = rsvp.channel.0.to_string()
+        );
+
+        if let Err(e) = notifier.direct_message(user.0, &message).await {
+            warn!("Failed to remind {} of a pass: {}", user.0, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::{database::DatabaseContents, testing::FakeNotifier};
+
+    fn rsvp(start: i64, attendees: &[u64]) -> PassRsvp {
+        PassRsvp {
+            message: Snowflake(1),
+            channel: Snowflake(10),
+            satellite: "NOAA 19".to_string(),
+            start,
+            end: start + 600,
+            locale: "en-GB".to_string(),
+            footer: "Data from N2YO".to_string(),
+            attendees: attendees
+                .iter()
+                .map(|user| Attendee {
+                    user: Snowflake(*user),
+                    name: format!("user{}", user),
+                })
+                .collect(),
+            reminded: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn reminds_attendees_once_before_the_pass() {
+        let now = util::current_utc();
+        let notifier = FakeNotifier::default();
+        let contents = DatabaseContents {
+            pass_rsvps: vec![
+                rsvp(now + 5 * 60, &[1, 2]),
+                rsvp(now + 2 * 60 * 60, &[3]),
+                rsvp(now - 60 * 60, &[4]),
+            ],
+            preferences: HashMap::from([(
+                Snowflake(2),
+                UserPreferences {
+                    direct_messages: false,
+                    ..Default::default()
+                },
+            )]),
+            ..Default::default()
+        };
+        let database = RwLock::new(Database::in_memory(contents));
+
+        send_reminders(&notifier, &database, 15).await;
+        send_reminders(&notifier, &database, 15).await;
+
+        assert_eq!(*notifier.direct_messages.lock().unwrap(), vec![1]);
+        assert_eq!(database.read().await.contents.pass_rsvps.len(), 2);
+    }
+
+    #[test]
+    fn lists_attendees_in_the_footer() {
+        let names = (1..=12).collect::<Vec<_>>();
+
+        assert_eq!(footer(&rsvp(0, &[])), "Data from N2YO");
+        assert_eq!(
+            footer(&rsvp(0, &[1, 2])),
+            "✋ 2 going: user1, user2\nData from N2YO"
+        );
+        assert!(footer(&rsvp(0, &names)).starts_with("✋ 12 going: user1, user2, user3"));
+        assert!(footer(&rsvp(0, &names)).contains("user10 and 2 more"));
+    }
+}
@@ -0,0 +1,386 @@
+use std::{sync::Arc, time::Duration};
+
+use poise::serenity_prelude::{
+    ButtonStyle, CreateComponents, Http, InteractionResponseType, MessageComponentInteraction,
+};
+use tokio::{sync::RwLock, task::JoinHandle, time::interval};
+use tracing::{error, info};
+
+use crate::{
+    celestrak::{CelesTrakAPI, SatcatRecord},
+    commands, config,
+    database::Snowflake,
+    error::Report,
+    i18n::tr,
+    util, ApplicationContext,
+};
+
+/// The start of the custom ID of the buttons on SATCAT search results.
+pub const BUTTON_PREFIX: &str = "satcat:";
+/// The longest button label Discord allows.
+const MAX_LABEL_LENGTH: usize = 80;
+
+/// CelesTrak's catalog of every object tracked in orbit, kept in memory so that it can be searched
+/// without a request per search.
+#[derive(Default)]
+pub struct Satcat {
+    records: RwLock<Vec<SatcatRecord>>,
+}
+
+impl Satcat {
+    /// Whether the catalog has been downloaded yet.
+    pub async fn is_loaded(&self) -> bool {
+        !self.records.read().await.is_empty()
+    }
+
+    /// The best matches for a search, at most `limit` of them.
+    pub async fn search(&self, filter: &SatcatFilter, limit: usize) -> Vec<SatcatRecord> {
+        search(&self.records.read().await, filter, limit)
+            .into_iter()
+            .cloned()
+            .collect()
+    }
+}
+
+/// What to search the SATCAT for. Every filter that is given must match.
+#[derive(Debug, Default)]
+pub struct SatcatFilter {
+    /// Matched against the name, international designator, and NORAD ID. Empty matches everything.
+    pub text: String,
+    pub launch_year: Option<i32>,
+    /// The owner's SATCAT code, such as `US` or `PRC`.
+    pub owner: Option<String>,
+    /// The object type's SATCAT code, such as `PAY` or `R/B`.
+    pub object_type: Option<&'static str>,
+}
+
+impl SatcatFilter {
+    /// How well a record matches, lower being better, or `None` if it does not.
+    fn rank(&self, record: &SatcatRecord) -> Option<u8> {
+        if self
+            .object_type
+            .is_some_and(|object_type| record.object_type != object_type)
+        {
+            return None;
+        }
+
+        if self.owner.as_ref().is_some_and(|owner| {
+            !record
+                .owner
+                .as_ref()
+                .is_some_and(|other| other.eq_ignore_ascii_case(owner))
+        }) {
+            return None;
+        }
+
+        if self.launch_year.is_some_and(|year| {
+            record
+                .launch_date
+                .as_ref()
+                .and_then(|date| date.get(..4)?.parse::<i32>().ok())
+                != Some(year)
+        }) {
+            return None;
+        }
+
+        let text = self.text.trim().to_uppercase();
+        let name = record.name.to_uppercase();
+
+        if text.is_empty() {
+            Some(2)
+        } else if record.id.to_string() == text || record.international_designator == text {
+            Some(0)
+        } else if name.starts_with(&text) {
+            Some(1)
+        } else if name.contains(&text) || record.international_designator.starts_with(&text) {
+            Some(2)
+        } else {
+            None
+        }
+    }
+}
+
+/// The records matching a search, best first and otherwise in catalog order, at most `limit` of
+/// them.
+pub fn search<'a>(
+    records: &'a [SatcatRecord],
+    filter: &SatcatFilter,
+    limit: usize,
+) -> Vec<&'a SatcatRecord> {
+    let mut matches = records
+        .iter()
+        .filter_map(|record| Some((filter.rank(record)?, record)))
+        .collect::<Vec<_>>();
+
+    matches.sort_by_key(|(rank, _)| *rank);
+    matches
+        .into_iter()
+        .take(limit)
+        .map(|(_, record)| record)
+        .collect()
+}
+
+/// Reads CelesTrak's SATCAT CSV, skipping any rows that cannot be understood.
+pub fn parse(csv: &str) -> anyhow::Result<Vec<SatcatRecord>> {
+    let mut lines = csv.lines();
+    let header = split_row(
+        lines
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("the SATCAT is empty"))?,
+    );
+    let column = |name: &str| header.iter().position(|column| column == name);
+    let required = |name: &str| {
+        column(name).ok_or_else(|| anyhow::anyhow!("the SATCAT has no {} column", name))
+    };
+
+    let name = required("OBJECT_NAME")?;
+    let designator = required("OBJECT_ID")?;
+    let id = required("NORAD_CAT_ID")?;
+    let object_type = required("OBJECT_TYPE")?;
+    let owner = column("OWNER");
+    let launch_date = column("LAUNCH_DATE");
+    let decay_date = column("DECAY_DATE");
+    let period = column("PERIOD");
+    let inclination = column("INCLINATION");
+    let apogee = column("APOGEE");
+    let perigee = column("PERIGEE");
+
+    Ok(lines
+        .filter_map(|line| {
+            let fields = split_row(line);
+            let text = |column: Option<usize>| {
+                Some(fields.get(column?)?.trim())
+                    .filter(|field| !field.is_empty())
+                    .map(str::to_string)
+            };
+            let number = |column: Option<usize>| text(column)?.parse().ok();
+
+            Some(SatcatRecord {
+                name: text(Some(name))?,
+                international_designator: text(Some(designator)).unwrap_or_default(),
+                id: text(Some(id))?.parse().ok()?,
+                object_type: text(Some(object_type)).unwrap_or_default(),
+                owner: text(owner),
+                launch_date: text(launch_date),
+                decay_date: text(decay_date),
+                period: number(period),
+                inclination: number(inclination),
+                apogee: number(apogee),
+                perigee: number(perigee),
+            })
+        })
+        .collect())
+}
+
+/// Splits a row of CSV into its fields, unquoting those in double quotes.
+fn split_row(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+
+    fields.push(field);
+    fields
+}
+
+/// Spawns the task that downloads the SATCAT at startup, and again whenever it is older than
+/// `SATCAT_REFRESH_HOURS`. Failed downloads are retried hourly.
+pub fn spawn_refresh_task(
+    satcat: Arc<Satcat>,
+    celestrak_api: Arc<CelesTrakAPI>,
+) -> anyhow::Result<JoinHandle<()>> {
+    let max_age = config::get().satcat.refresh_hours * 60 * 60;
+
+    Ok(tokio::spawn(async move {
+        let mut interval = interval(Duration::from_secs(60 * 60));
+        let mut fetched_at = None;
+
+        loop {
+            interval.tick().await;
+
+            if fetched_at.is_some_and(|fetched_at| util::current_utc() - fetched_at < max_age) {
+                continue;
+            }
+
+            match celestrak_api
+                .get_satcat_csv()
+                .await
+                .and_then(|csv| parse(&csv))
+            {
+                Ok(records) => {
+                    info!("Loaded {} SATCAT records", records.len());
+                    *satcat.records.write().await = records;
+                    fetched_at = Some(util::current_utc());
+                }
+                Err(e) => error!("Failed to refresh the SATCAT: {}", e),
+            }
+        }
+    }))
+}
+
+/// Adds a row of buttons for each search result, to show its `/satellite-info` or how to watch
+/// it.
+pub fn create_buttons(components: &mut CreateComponents, locale: &str, records: &[SatcatRecord]) {
+    for record in records {
+        let label = format!("{} – {}", record.id, record.name);
+
+        components.create_action_row(|r| {
+            r.create_button(|b| {
+                b.custom_id(format!("{}info:{}", BUTTON_PREFIX, record.id))
+                    .label(label.chars().take(MAX_LABEL_LENGTH).collect::<String>())
+                    .style(ButtonStyle::Primary)
+            })
+            .create_button(|b| {
+                b.custom_id(format!("{}watch:{}", BUTTON_PREFIX, record.id))
+                    .label(tr!(locale, "satcat-search-watch"))
+                    .style(ButtonStyle::Secondary)
+            })
+        });
+    }
+}
+
+/// Replies to a button on a search result, only to whoever pressed it, with the satellite's
+/// information or the command to watch it.
+pub async fn handle_button(
+    http: Arc<Http>,
+    data: &ApplicationContext,
+    component: &MessageComponentInteraction,
+) -> anyhow::Result<()> {
+    let locale = component.locale.as_str();
+    let Some((action, satellite_id)) = component
+        .data
+        .custom_id
+        .strip_prefix(BUTTON_PREFIX)
+        .and_then(|button| button.split_once(':'))
+    else {
+        return Ok(());
+    };
+    let Ok(satellite_id) = satellite_id.parse::<usize>() else {
+        return Ok(());
+    };
+
+    if action == "watch" {
+        component
+            .create_interaction_response(&http, |r| {
+                r.kind(InteractionResponseType::ChannelMessageWithSource)
+                    .interaction_response_data(|d| {
+                        d.content(tr!(locale, "satcat-search-watch-hint", id = satellite_id))
+                            .ephemeral(true)
+                    })
+            })
+            .await?;
+        return Ok(());
+    }
+
+    // Fetching the TLE and SATCAT record can take longer than Discord waits for a response
+    component
+        .create_interaction_response(&http, |r| {
+            r.kind(InteractionResponseType::DeferredChannelMessageWithSource)
+                .interaction_response_data(|d| d.ephemeral(true))
+        })
+        .await?;
+
+    let embed = commands::satellite_info_embed(
+        &http,
+        data,
+        locale,
+        component.guild_id.map(|guild| Snowflake(guild.0)),
+        Snowflake(component.channel_id.0),
+        satellite_id,
+    )
+    .await;
+
+    component
+        .create_followup_message(&http, |m| {
+            match embed {
+                Ok(embed) => m.add_embed(embed),
+                Err(e) => m.content(Report::new(&e, locale).message),
+            }
+            .ephemeral(true)
+        })
+        .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::mock;
+
+    fn ids(records: Vec<&SatcatRecord>) -> Vec<usize> {
+        records.into_iter().map(|record| record.id).collect()
+    }
+
+    #[test]
+    fn splits_quoted_fields() {
+        assert_eq!(
+            split_row(r#"a,"b, c","say ""hi""",,"#),
+            vec!["a", "b, c", "say \"hi\"", "", ""]
+        );
+    }
+
+    #[test]
+    fn parses_the_satcat() {
+        let records = parse(&mock::satcat_csv()).unwrap();
+        let rocket_body = records.iter().find(|record| record.id == 39430).unwrap();
+
+        assert_eq!(records.len(), 7);
+        assert_eq!(rocket_body.name, "CZ-4C R/B");
+        assert_eq!(rocket_body.owner.as_deref(), Some("PRC"));
+        assert_eq!(rocket_body.decay_date.as_deref(), Some("2014-02-11"));
+        assert_eq!(rocket_body.period, None);
+        assert_eq!(records[0].inclination, Some(98.56));
+    }
+
+    #[test]
+    fn ranks_exact_then_prefix_then_other_matches() {
+        let records = parse(&mock::satcat_csv()).unwrap();
+        let filter = |text: &str| SatcatFilter {
+            text: text.to_string(),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            ids(search(&records, &filter("noaa"), 10)),
+            vec![25338, 28654, 33591]
+        );
+        assert_eq!(ids(search(&records, &filter("25544"), 10)), vec![25544]);
+        assert_eq!(ids(search(&records, &filter("r/b"), 10)), vec![39430]);
+        assert_eq!(ids(search(&records, &filter("2009-005"), 10)), vec![33591]);
+        assert_eq!(search(&records, &filter(""), 2).len(), 2);
+    }
+
+    #[test]
+    fn applies_every_filter() {
+        let records = parse(&mock::satcat_csv()).unwrap();
+
+        let filter = SatcatFilter {
+            text: "noaa".to_string(),
+            launch_year: Some(2005),
+            owner: Some("us".to_string()),
+            object_type: Some("PAY"),
+        };
+        assert_eq!(ids(search(&records, &filter, 10)), vec![28654]);
+
+        let filter = SatcatFilter {
+            owner: Some("CIS".to_string()),
+            object_type: Some("DEB"),
+            ..Default::default()
+        };
+        assert_eq!(ids(search(&records, &filter, 10)), vec![35000]);
+    }
+}
@@ -0,0 +1,192 @@
+use std::{collections::HashMap, sync::Mutex};
+
+use serde::Deserialize;
+use tracing::info;
+
+use crate::{frequencies, mock, util};
+
+/// How long a satellite's transmitters are reused for, in seconds. SatNOGS DB changes slowly, and
+/// is run by volunteers, so it should not be asked more than needed.
+const TRANSMITTERS_TTL: i64 = 6 * 60 * 60;
+/// How long a satellite's recent observations are reused for, in seconds.
+const OBSERVATIONS_TTL: i64 = 10 * 60;
+/// The most observations asked for at once.
+const MAX_OBSERVATIONS: usize = 5;
+
+/// A client for the SatNOGS DB and Network APIs, whose read endpoints need no key.
+pub struct SatnogsAPI {
+    client: reqwest::Client,
+    /// Transmitters by NORAD ID, with when they were fetched.
+    cache: Mutex<HashMap<usize, (i64, Vec<SatnogsTransmitter>)>>,
+    /// Recent good observations by NORAD ID, with when they were fetched.
+    observations_cache: Mutex<HashMap<usize, (i64, Vec<SatnogsObservation>)>>,
+    /// Whether canned responses are returned instead of calling SatNOGS.
+    mock: bool,
+}
+
+impl SatnogsAPI {
+    pub fn new() -> anyhow::Result<Self> {
+        info!("Creating SatNOGS API client");
+        Ok(Self {
+            client: reqwest::ClientBuilder::new()
+                .user_agent(util::USER_AGENT)
+                .build()?,
+            cache: Mutex::new(HashMap::new()),
+            observations_cache: Mutex::new(HashMap::new()),
+            mock: mock::is_enabled(),
+        })
+    }
+
+    /// Gets every transmitter SatNOGS DB knows of on a satellite, including inactive ones.
+    pub async fn get_transmitters(
+        &self,
+        satellite_id: usize,
+    ) -> anyhow::Result<Vec<SatnogsTransmitter>> {
+        if self.mock {
+            return Ok(mock::transmitters(satellite_id));
+        }
+
+        if let Some((fetched_at, transmitters)) = self.cache.lock().unwrap().get(&satellite_id) {
+            if util::current_utc() - fetched_at < TRANSMITTERS_TTL {
+                return Ok(transmitters.clone());
+            }
+        }
+
+        let url = format!(
+            "https://db.satnogs.org/api/transmitters/?satellite__norad_cat_id={}&format=json",
+            satellite_id
+        );
+
+        info!("Sending request to {}", url);
+
+        let transmitters = self
+            .client
+            .get(&url)
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<Vec<SatnogsTransmitter>>()
+            .await?;
+
+        self.cache
+            .lock()
+            .unwrap()
+            .insert(satellite_id, (util::current_utc(), transmitters.clone()));
+        Ok(transmitters)
+    }
+
+    /// Gets the active transmitters of a satellite, those with a downlink first.
+    pub async fn get_active_transmitters(
+        &self,
+        satellite_id: usize,
+    ) -> anyhow::Result<Vec<SatnogsTransmitter>> {
+        let mut transmitters = self
+            .get_transmitters(satellite_id)
+            .await?
+            .into_iter()
+            .filter(SatnogsTransmitter::is_active)
+            .collect::<Vec<_>>();
+
+        transmitters.sort_by_key(|transmitter| transmitter.downlink_low.is_none());
+        Ok(transmitters)
+    }
+
+    /// Gets the latest observations of a satellite that SatNOGS Network has vetted as good,
+    /// newest first.
+    pub async fn get_recent_observations(
+        &self,
+        satellite_id: usize,
+    ) -> anyhow::Result<Vec<SatnogsObservation>> {
+        if self.mock {
+            return Ok(mock::observations(satellite_id));
+        }
+
+        if let Some((fetched_at, observations)) =
+            self.observations_cache.lock().unwrap().get(&satellite_id)
+        {
+            if util::current_utc() - fetched_at < OBSERVATIONS_TTL {
+                return Ok(observations.clone());
+            }
+        }
+
+        let url = format!(
+            "https://network.satnogs.org/api/observations/?satellite__norad_cat_id={}&status=good&format=json",
+            satellite_id
+        );
+
+        info!("Sending request to {}", url);
+
+        let mut observations = self
+            .client
+            .get(&url)
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<Vec<SatnogsObservation>>()
+            .await?;
+        observations.sort_by_key(|observation| std::cmp::Reverse(observation.start_utc()));
+        observations.truncate(MAX_OBSERVATIONS);
+
+        self.observations_cache
+            .lock()
+            .unwrap()
+            .insert(satellite_id, (util::current_utc(), observations.clone()));
+        Ok(observations)
+    }
+}
+
+/// A transmitter, transceiver, or transponder listed in SatNOGS DB. Frequencies are in hertz.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct SatnogsTransmitter {
+    pub description: String,
+    pub alive: bool,
+    /// `active`, `inactive`, or `invalid`.
+    pub status: String,
+    pub uplink_low: Option<u64>,
+    pub downlink_low: Option<u64>,
+    pub mode: Option<String>,
+    pub baud: Option<f64>,
+}
+
+impl SatnogsTransmitter {
+    pub fn is_active(&self) -> bool {
+        self.alive && self.status == "active"
+    }
+
+    /// A one line summary of the downlink, such as `145.800 MHz FM`.
+    pub fn summary(&self) -> Option<String> {
+        let downlink = frequencies::format_frequency(self.downlink_low? as f64 / 1_000_000.0);
+
+        Some(match &self.mode {
+            Some(mode) => format!("{} {}", downlink, mode),
+            None => downlink,
+        })
+    }
+}
+
+/// An observation scheduled on a SatNOGS Network ground station.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct SatnogsObservation {
+    pub id: u64,
+    /// When the observation started, in RFC 3339 format.
+    pub start: String,
+    pub station_name: String,
+    /// A link to the waterfall image, once it has been uploaded.
+    pub waterfall: Option<String>,
+    pub transmitter_mode: Option<String>,
+    /// The frequency observed, in hertz.
+    pub observation_frequency: Option<u64>,
+}
+
+impl SatnogsObservation {
+    pub fn start_utc(&self) -> Option<i64> {
+        chrono::DateTime::parse_from_rfc3339(&self.start)
+            .ok()
+            .map(|start| start.timestamp())
+    }
+
+    /// The observation's page on SatNOGS Network.
+    pub fn url(&self) -> String {
+        format!("https://network.satnogs.org/observations/{}/", self.id)
+    }
+}
@@ -0,0 +1,276 @@
+use std::{str::FromStr, time::Duration};
+
+use chrono::{DateTime, Datelike, Local, Timelike};
+use tokio::time::{interval, sleep, Interval};
+
+/// How far ahead a cron expression is searched for its next match. Five years always includes a
+/// 29th of February.
+const HORIZON_DAYS: i64 = 5 * 366;
+
+/// When the notification task runs.
+pub enum Schedule {
+    /// Every fixed interval, starting immediately.
+    Interval(Interval),
+    /// Whenever a cron expression matches, in the server's local time.
+    Cron(CronExpression),
+}
+
+impl Schedule {
+    pub fn interval(period: Duration) -> Self {
+        Self::Interval(interval(period))
+    }
+
+    /// Waits until the next scheduled run.
+    pub async fn tick(&mut self) {
+        match self {
+            Schedule::Interval(interval) => {
+                interval.tick().await;
+            }
+            Schedule::Cron(expression) => {
+                let now = Local::now();
+                // Expressions that never match are rejected when they are parsed
+                let Some(next) = expression.next_after(now) else {
+                    return std::future::pending().await;
+                };
+                sleep((next - now).to_std().unwrap_or_default()).await;
+            }
+        }
+    }
+}
+
+/// A standard five field cron expression (minute, hour, day of month, month, day of week).
+#[derive(Debug, Clone, PartialEq)]
+pub struct CronExpression {
+    minutes: Field,
+    hours: Field,
+    days_of_month: Field,
+    months: Field,
+    days_of_week: Field,
+}
+
+/// The values a single cron field matches, as a bitmask.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Field {
+    values: u64,
+    restricted: bool,
+}
+
+impl Field {
+    fn parse(field: &str, min: u32, max: u32) -> anyhow::Result<Self> {
+        let mut values = 0u64;
+
+        for part in field.split(',') {
+            let (range, step) = match part.split_once('/') {
+                Some((range, step)) => (range, step.parse::<u32>()?),
+                None => (part, 1),
+            };
+
+            if step == 0 {
+                return Err(anyhow::anyhow!("step must not be zero in '{}'", part));
+            }
+
+            let (start, end) = if range == "*" {
+                (min, max)
+            } else if let Some((start, end)) = range.split_once('-') {
+                (start.parse()?, end.parse()?)
+            } else {
+                let value = range.parse()?;
+                (value, if part.contains('/') { max } else { value })
+            };
+
+            if start < min || end > max || start > end {
+                return Err(anyhow::anyhow!(
+                    "'{}' is outside of the range {}-{}",
+                    part,
+                    min,
+                    max
+                ));
+            }
+
+            for value in (start..=end).step_by(step as usize) {
+                values |= 1 << value;
+            }
+        }
+
+        Ok(Self {
+            values,
+            restricted: field != "*",
+        })
+    }
+
+    fn matches(&self, value: u32) -> bool {
+        self.values & (1 << value) != 0
+    }
+}
+
+impl FromStr for CronExpression {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl CronExpression {
+    pub fn parse(expression: &str) -> anyhow::Result<Self> {
+        let fields = expression.split_whitespace().collect::<Vec<_>>();
+
+        let [minutes, hours, days_of_month, months, days_of_week] = fields.as_slice() else {
+            return Err(anyhow::anyhow!(
+                "cron expression must have five fields, got {}",
+                fields.len()
+            ));
+        };
+
+        let mut days_of_week = Field::parse(days_of_week, 0, 7)?;
+
+        // Both 0 and 7 mean Sunday
+        if days_of_week.matches(7) {
+            days_of_week.values |= 1;
+        }
+
+        let cron = Self {
+            minutes: Field::parse(minutes, 0, 59)?,
+            hours: Field::parse(hours, 0, 23)?,
+            days_of_month: Field::parse(days_of_month, 1, 31)?,
+            months: Field::parse(months, 1, 12)?,
+            days_of_week,
+        };
+
+        if cron.next_after(Local::now()).is_none() {
+            return Err(anyhow::anyhow!(
+                "'{}' never matches, such as a day that no month has",
+                expression
+            ));
+        }
+
+        Ok(cron)
+    }
+
+    /// Whether the expression matches the day and hour of a time, ignoring the minute.
+    fn matches_hour(&self, time: &DateTime<Local>) -> bool {
+        let day_of_month = self.days_of_month.matches(time.day());
+        let day_of_week = self
+            .days_of_week
+            .matches(time.weekday().num_days_from_sunday());
+
+        // As in standard cron, a restricted day of month or day of week either matches
+        let day = match (self.days_of_month.restricted, self.days_of_week.restricted) {
+            (true, true) => day_of_month || day_of_week,
+            _ => day_of_month && day_of_week,
+        };
+
+        day && self.hours.matches(time.hour()) && self.months.matches(time.month())
+    }
+
+    /// The first minute after the given time that matches the expression, or `None` if there is
+    /// none within [`HORIZON_DAYS`].
+    pub fn next_after(&self, time: DateTime<Local>) -> Option<DateTime<Local>> {
+        let mut candidate = time
+            .with_second(0)
+            .and_then(|time| time.with_nanosecond(0))
+            .unwrap_or(time)
+            + chrono::Duration::minutes(1);
+        let horizon = candidate + chrono::Duration::days(HORIZON_DAYS);
+
+        while candidate < horizon {
+            if !self.matches_hour(&candidate) {
+                candidate += chrono::Duration::minutes(60 - candidate.minute() as i64);
+            } else if self.minutes.matches(candidate.minute()) {
+                return Some(candidate);
+            } else {
+                candidate += chrono::Duration::minutes(1);
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn local(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> DateTime<Local> {
+        Local
+            .with_ymd_and_hms(year, month, day, hour, minute, 0)
+            .unwrap()
+    }
+
+    fn next(expression: &str, after: DateTime<Local>) -> Option<DateTime<Local>> {
+        CronExpression::parse(expression).unwrap().next_after(after)
+    }
+
+    #[test]
+    fn parses_fields() {
+        let cron = CronExpression::parse("*/15 6-8 * 1,7 0").unwrap();
+
+        assert_eq!(cron.minutes.values, 1 | 1 << 15 | 1 << 30 | 1 << 45);
+        assert_eq!(cron.hours.values, 1 << 6 | 1 << 7 | 1 << 8);
+        assert_eq!(cron.months.values, 1 << 1 | 1 << 7);
+        assert!(!cron.days_of_month.restricted);
+        // 7 is also Sunday
+        assert!(CronExpression::parse("0 0 * * 7")
+            .unwrap()
+            .days_of_week
+            .matches(0));
+    }
+
+    #[test]
+    fn rejects_malformed_expressions() {
+        assert!(CronExpression::parse("0 6 * *").is_err());
+        assert!(CronExpression::parse("60 * * * *").is_err());
+        assert!(CronExpression::parse("*/0 * * * *").is_err());
+        assert!(CronExpression::parse("0 8-6 * * *").is_err());
+        assert!(CronExpression::parse("0 0 0 * *").is_err());
+    }
+
+    #[test]
+    fn rejects_expressions_that_never_match() {
+        let problem = CronExpression::parse("0 0 30 2 *").unwrap_err().to_string();
+
+        assert!(problem.contains("never matches"), "{}", problem);
+        assert!(CronExpression::parse("0 0 31 4,6,9,11 *").is_err());
+        assert!(CronExpression::parse("0 0 29 2 *").is_ok());
+    }
+
+    #[test]
+    fn finds_the_next_matching_minute() {
+        // The 10th of January 2026 is a Saturday
+        let saturday = local(2026, 1, 10, 7, 30);
+
+        assert_eq!(
+            next("0 6,18 * * *", saturday),
+            Some(local(2026, 1, 10, 18, 0))
+        );
+        assert_eq!(
+            next("0 6,18 * * *", local(2026, 1, 10, 18, 0)),
+            Some(local(2026, 1, 11, 6, 0))
+        );
+        assert_eq!(
+            next("30 9 * * 1", saturday),
+            Some(local(2026, 1, 12, 9, 30))
+        );
+        assert_eq!(
+            next("*/20 * * * *", saturday),
+            Some(local(2026, 1, 10, 7, 40))
+        );
+        assert_eq!(next("0 0 29 2 *", saturday), Some(local(2028, 2, 29, 0, 0)));
+    }
+
+    #[test]
+    fn matches_either_restricted_day() {
+        // The 13th is a Tuesday, before the following Friday
+        assert_eq!(
+            next("0 12 13 * 5", local(2026, 1, 10, 0, 0)),
+            Some(local(2026, 1, 13, 12, 0))
+        );
+        // After the 13th, the Friday that follows
+        assert_eq!(
+            next("0 12 13 * 5", local(2026, 1, 13, 13, 0)),
+            Some(local(2026, 1, 16, 12, 0))
+        );
+    }
+}
@@ -0,0 +1,67 @@
+use std::{str::FromStr, sync::Arc};
+
+use poise::Framework;
+
+use crate::ApplicationContext;
+
+/// How this process connects to the gateway.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Sharding {
+    /// A single shard.
+    #[default]
+    None,
+    /// As many shards as Discord recommends.
+    Auto,
+    /// A range of shards (inclusive) out of a total, for splitting shards across processes.
+    Range { start: u64, end: u64, total: u64 },
+}
+
+impl FromStr for Sharding {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "auto" {
+            return Ok(Self::Auto);
+        }
+
+        let (range, total) = s
+            .split_once('/')
+            .ok_or_else(|| anyhow::anyhow!("expected auto or start-end/total, got {}", s))?;
+        let (start, end) = range.split_once('-').unwrap_or((range, range));
+        let (start, end, total) = (start.parse()?, end.parse()?, total.parse()?);
+
+        if start > end || end >= total {
+            return Err(anyhow::anyhow!("invalid shard range {}", s));
+        }
+
+        Ok(Self::Range { start, end, total })
+    }
+}
+
+impl Sharding {
+    /// Whether this process runs shard 0, which is responsible for the notification task so
+    /// that notifications are not duplicated across processes.
+    pub fn runs_shard_zero(&self) -> bool {
+        match self {
+            Sharding::None | Sharding::Auto => true,
+            Sharding::Range { start, .. } => *start == 0,
+        }
+    }
+
+    pub async fn start(
+        self,
+        framework: Arc<Framework<ApplicationContext, anyhow::Error>>,
+    ) -> Result<(), serenity::Error> {
+        match self {
+            Sharding::None => framework.start().await,
+            Sharding::Auto => framework.start_autosharded().await,
+            Sharding::Range { start, end, total } => {
+                framework
+                    .start_with(|mut client| async move {
+                        client.start_shard_range([start, end], total).await
+                    })
+                    .await
+            }
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use std::{sync::Arc, time::Duration};
+
+use serde::{Deserialize, Serialize};
+use tokio::{sync::RwLock, task::JoinHandle, time::interval};
+use tracing::{error, info};
+
+use crate::{config, database::Database, n2yo::SatellitePass, util};
+
+/// The NORAD ID of the ISS.
+pub const ISS: usize = 25544;
+/// The frequency ARISS SSTV events are transmitted on, in megahertz.
+const DEFAULT_FREQUENCY: f64 = 145.800;
+
+/// A window in which the ISS is announced to be transmitting SSTV images.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SstvEvent {
+    pub start: i64,
+    pub end: i64,
+    /// The downlink frequency, in megahertz.
+    pub frequency: f64,
+    /// The SSTV mode, such as `PD120`, if announced.
+    pub mode: Option<String>,
+}
+
+impl SstvEvent {
+    /// Whether any of a pass falls within the event.
+    pub fn covers(&self, pass: &SatellitePass) -> bool {
+        (pass.start_utc as i64) < self.end && (pass.end_utc as i64) > self.start
+    }
+}
+
+/// An event as announced in the feed, with RFC 3339 times.
+#[derive(Debug, Deserialize)]
+struct AnnouncedEvent {
+    start: String,
+    end: String,
+    frequency: Option<f64>,
+    mode: Option<String>,
+}
+
+impl AnnouncedEvent {
+    fn parse(self) -> anyhow::Result<SstvEvent> {
+        Ok(SstvEvent {
+            start: chrono::DateTime::parse_from_rfc3339(&self.start)?.timestamp(),
+            end: chrono::DateTime::parse_from_rfc3339(&self.end)?.timestamp(),
+            frequency: self.frequency.unwrap_or(DEFAULT_FREQUENCY),
+            mode: self.mode,
+        })
+    }
+}
+
+/// The SSTV event covering an ISS pass, if there is one.
+pub fn event_during<'a>(
+    events: &'a [SstvEvent],
+    satellite_id: usize,
+    pass: &SatellitePass,
+) -> Option<&'a SstvEvent> {
+    if satellite_id != ISS {
+        return None;
+    }
+
+    events.iter().find(|event| event.covers(pass))
+}
+
+/// Spawns the task that periodically fetches announced SSTV events from `SSTV_EVENTS_URL`, if it
+/// is set. The feed is a JSON array of objects with `start` and `end` times, and optionally the
+/// `frequency` in megahertz and `mode`.
+pub fn spawn_check_task(database: Arc<RwLock<Database>>) -> anyhow::Result<Option<JoinHandle<()>>> {
+    let config = &config::get().sstv;
+    let Some(url) = config.events_url.clone() else {
+        return Ok(None);
+    };
+    let minutes = config.check_interval_minutes;
+    let client = reqwest::ClientBuilder::new()
+        .user_agent(util::USER_AGENT)
+        .build()?;
+
+    Ok(Some(tokio::spawn(async move {
+        let mut interval = interval(Duration::from_secs(minutes * 60));
+
+        loop {
+            interval.tick().await;
+
+            match fetch(&client, &url).await {
+                Ok(events) => {
+                    let mut database = database.write().await;
+
+                    if database.contents.sstv_events != events {
+                        info!("{} upcoming ISS SSTV event(s) announced", events.len());
+                        database.contents.sstv_events = events;
+                        database.mark_dirty();
+                    }
+                }
+                Err(e) => error!("Failed to check for ISS SSTV events: {}", e),
+            }
+        }
+    })))
+}
+
+/// Fetches the announced events that have not yet ended.
+async fn fetch(client: &reqwest::Client, url: &str) -> anyhow::Result<Vec<SstvEvent>> {
+    info!("Sending request to {}", url);
+
+    let now = util::current_utc();
+
+    client
+        .get(url)
+        .send()
+        .await?
+        .error_for_status()?
+        .json::<Vec<AnnouncedEvent>>()
+        .await?
+        .into_iter()
+        .map(AnnouncedEvent::parse)
+        .filter(|event| event.as_ref().map_or(true, |event| event.end > now))
+        .collect()
+}
@@ -0,0 +1,139 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    astro,
+    database::Location,
+    decay::OrbitSummary,
+    n2yo::SatellitePass,
+    propagation::{self, CachedTle, Orbit},
+    util,
+};
+
+/// The CelesTrak group of objects launched in the last 30 days.
+pub const RECENT_LAUNCHES: &str = "last-30-days";
+/// The mean altitude below which a batch is taken to still be raising its orbit, in kilometres.
+/// Batches are released around 300 km, and are seen as a bright train until they spread out on
+/// the way up to their operational shells above 500 km.
+const TRAIN_MAX_ALTITUDE: f64 = 450.0;
+/// The lowest elevation of the train passes found, in degrees.
+const MIN_ELEVATION: f64 = 10.0;
+
+/// The Starlink satellites from one launch.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StarlinkBatch {
+    /// The launch part of the international designator, such as `2024-001`.
+    pub launch: String,
+    pub satellites: Vec<(String, CachedTle)>,
+    /// The mean altitude of the satellites, in kilometres.
+    pub altitude: f64,
+}
+
+impl StarlinkBatch {
+    /// Predicts the passes of the train that can be seen with the naked eye between two unix
+    /// timestamps. The satellites follow each other closely, so the passes of the first stand in
+    /// for the whole train.
+    pub fn visible_passes(
+        &self,
+        location: &Location,
+        start: i64,
+        end: i64,
+    ) -> anyhow::Result<Vec<SatellitePass>> {
+        let Some((_, tle)) = self.satellites.first() else {
+            return Ok(Vec::new());
+        };
+        let orbit = Orbit::from_tle(tle)?;
+
+        let mut passes = Vec::new();
+
+        for mut pass in propagation::predict_passes(&orbit, location, start, end, MIN_ELEVATION)? {
+            let fraction = astro::visible_fraction(&orbit, location, &pass)?;
+
+            if fraction > 0.0 {
+                pass.visible_fraction = Some(fraction);
+                passes.push(pass);
+            }
+        }
+
+        Ok(passes)
+    }
+}
+
+/// Finds the batches of Starlink satellites in a group of TLEs that are still low enough to be
+/// seen as a train, newest launch first.
+pub fn find_batches(text: &str) -> Vec<StarlinkBatch> {
+    let mut launches = BTreeMap::<String, Vec<(String, CachedTle)>>::new();
+
+    for (name, tle) in CachedTle::parse_all(text, util::current_utc()) {
+        if !name.starts_with("STARLINK") {
+            continue;
+        }
+
+        if let Some(launch) = tle.launch() {
+            launches.entry(launch).or_default().push((name, tle));
+        }
+    }
+
+    launches
+        .into_iter()
+        .rev()
+        .filter_map(|(launch, satellites)| {
+            let altitudes = satellites
+                .iter()
+                .filter_map(|(_, tle)| OrbitSummary::from_tle(tle))
+                .map(|summary| (summary.perigee + summary.apogee) / 2.0)
+                .collect::<Vec<_>>();
+            let altitude = altitudes.iter().sum::<f64>() / altitudes.len().max(1) as f64;
+
+            (!altitudes.is_empty() && altitude < TRAIN_MAX_ALTITUDE).then_some(StarlinkBatch {
+                launch,
+                satellites,
+                altitude,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOW_LINE1: &str = "1 58001U 24001A   24010.50000000  .00100000  00000-0  30000-3 0  9990";
+    const LOW_LINE2: &str = "2 58001  43.0000 100.0000 0001000  90.0000 270.0000 15.95000000  1000";
+    /// A batch that has reached its operational shell, around 550 km.
+    const RAISED_LINE1: &str =
+        "1 57001U 23150A   24010.50000000  .00001000  00000-0  30000-4 0  9990";
+    const RAISED_LINE2: &str =
+        "2 57001  43.0000 100.0000 0001000  90.0000 270.0000 15.05000000  1000";
+
+    #[test]
+    fn finds_low_batches() {
+        let text = [
+            ("STARLINK-31001", LOW_LINE1, LOW_LINE2),
+            ("STARLINK-31002", LOW_LINE1, LOW_LINE2),
+            ("FALCON 9 R/B", LOW_LINE1, LOW_LINE2),
+            ("STARLINK-30001", RAISED_LINE1, RAISED_LINE2),
+        ]
+        .iter()
+        .map(|(name, line1, line2)| format!("{}\n{}\n{}", name, line1, line2))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+        let batches = find_batches(&text);
+
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].launch, "2024-001");
+        assert_eq!(
+            batches[0]
+                .satellites
+                .iter()
+                .map(|(name, _)| name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["STARLINK-31001", "STARLINK-31002"]
+        );
+        assert!(
+            (250.0..350.0).contains(&batches[0].altitude),
+            "{:?}",
+            batches[0].altitude
+        );
+    }
+}
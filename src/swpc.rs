@@ -0,0 +1,269 @@
+use std::{collections::HashMap, sync::Mutex};
+
+use chrono::NaiveDateTime;
+use serde::Deserialize;
+use serde_json::Value;
+use tracing::info;
+
+use crate::{database::Location, mock, util};
+
+const ROOT: &str = "https://services.swpc.noaa.gov";
+/// How long a report is reused before the feeds are fetched again, in seconds. The aurora forecast
+/// is around a megabyte, and none of the feeds change more than every few minutes.
+const REPORT_TTL: i64 = 5 * 60;
+/// How far ahead predicted Kp values are reported, in seconds.
+const PREDICTION_WINDOW: i64 = 24 * 60 * 60;
+
+/// A client for the NOAA Space Weather Prediction Center's JSON feeds, which need no key.
+pub struct SwpcAPI {
+    client: reqwest::Client,
+    /// The last report, with when it was fetched.
+    cache: Mutex<Option<(i64, SpaceWeather)>>,
+    /// The last solar indices, with when they were fetched.
+    solar_cache: Mutex<Option<(i64, SolarIndices)>>,
+    /// Whether a canned report is returned instead of calling SWPC.
+    mock: bool,
+}
+
+impl SwpcAPI {
+    pub fn new() -> anyhow::Result<Self> {
+        info!("Creating SWPC API client");
+        Ok(Self {
+            client: reqwest::ClientBuilder::new()
+                .user_agent(util::USER_AGENT)
+                .build()?,
+            cache: Mutex::new(None),
+            solar_cache: Mutex::new(None),
+            mock: mock::is_enabled(),
+        })
+    }
+
+    /// Gets the current and predicted geomagnetic conditions, and the aurora forecast.
+    pub async fn get_space_weather(&self) -> anyhow::Result<SpaceWeather> {
+        if self.mock {
+            return Ok(mock::space_weather());
+        }
+
+        if let Some((fetched_at, report)) = self.cache.lock().unwrap().as_ref() {
+            if util::current_utc() - fetched_at < REPORT_TTL {
+                return Ok(report.clone());
+            }
+        }
+
+        let (current, forecast, aurora) = tokio::try_join!(
+            self.get::<Vec<KpReading>>("/json/planetary_k_index_1m.json"),
+            self.get::<Value>("/products/noaa-planetary-k-index-forecast.json"),
+            self.get::<OvationResponse>("/json/ovation_aurora_latest.json"),
+        )?;
+
+        let current = current
+            .into_iter()
+            .filter_map(|reading| Some((parse_time(&reading.time_tag)?, reading.estimated_kp)))
+            .max_by_key(|(time, _)| *time)
+            .ok_or_else(|| anyhow::anyhow!("SWPC returned no Kp readings"))?;
+
+        let now = util::current_utc();
+        let predicted = rows(&forecast)
+            .into_iter()
+            .filter(|row| {
+                row.get("observed").and_then(|observed| observed.as_str()) == Some("predicted")
+            })
+            .filter_map(|row| {
+                let time = parse_time(row.get("time_tag")?.as_str()?)?;
+                Some((time, number(row.get("kp")?)?))
+            })
+            .filter(|(time, _)| *time >= now - 3 * 60 * 60 && *time < now + PREDICTION_WINDOW)
+            .collect();
+
+        let report = SpaceWeather {
+            current,
+            predicted,
+            aurora: AuroraForecast {
+                probabilities: aurora
+                    .coordinates
+                    .into_iter()
+                    .map(|[longitude, latitude, probability]| {
+                        (
+                            (longitude.round() as i32, latitude.round() as i32),
+                            probability.round().clamp(0.0, 100.0) as u8,
+                        )
+                    })
+                    .collect(),
+            },
+        };
+
+        *self.cache.lock().unwrap() = Some((util::current_utc(), report.clone()));
+        Ok(report)
+    }
+
+    /// Gets the latest solar flux, sunspot number, and planetary A and K indices.
+    pub async fn get_solar_indices(&self) -> anyhow::Result<SolarIndices> {
+        if self.mock {
+            return Ok(mock::solar_indices());
+        }
+
+        if let Some((fetched_at, indices)) = self.solar_cache.lock().unwrap().as_ref() {
+            if util::current_utc() - fetched_at < REPORT_TTL {
+                return Ok(indices.clone());
+            }
+        }
+
+        let (flux, sunspots, k_index) = tokio::try_join!(
+            self.get::<Vec<FluxReading>>("/json/f107_cm_flux.json"),
+            self.get::<Vec<SunspotReading>>("/json/solar-cycle/swpc_observed_ssn.json"),
+            self.get::<Value>("/products/noaa-planetary-k-index.json"),
+        )?;
+
+        let solar_flux = flux
+            .into_iter()
+            .filter_map(|reading| Some((parse_time(&reading.time_tag)?, reading.flux?)))
+            .max_by_key(|(time, _)| *time);
+        let sunspot_number = sunspots
+            .into_iter()
+            .filter_map(|reading| Some((parse_time(&reading.obsdate)?, reading.swpc_ssn?)))
+            .max_by_key(|(time, _)| *time);
+        let (a_index, k_index) = rows(&k_index)
+            .into_iter()
+            .filter_map(|row| {
+                let time = parse_time(row.get("time_tag")?.as_str()?)?;
+                Some((
+                    (time, number(row.get("a_running")?)?),
+                    (time, number(row.get("Kp")?)?),
+                ))
+            })
+            .max_by_key(|((time, _), _)| *time)
+            .unzip();
+
+        let indices = SolarIndices {
+            solar_flux,
+            sunspot_number,
+            a_index,
+            k_index,
+        };
+
+        *self.solar_cache.lock().unwrap() = Some((util::current_utc(), indices.clone()));
+        Ok(indices)
+    }
+
+    async fn get<T: serde::de::DeserializeOwned>(&self, path: &str) -> anyhow::Result<T> {
+        let url = format!("{}{}", ROOT, path);
+        info!("Sending request to {}", url);
+
+        Ok(self
+            .client
+            .get(&url)
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<T>()
+            .await?)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct KpReading {
+    time_tag: String,
+    estimated_kp: f64,
+}
+
+#[derive(Debug, Deserialize)]
+struct FluxReading {
+    time_tag: String,
+    flux: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SunspotReading {
+    #[serde(rename = "Obsdate")]
+    obsdate: String,
+    swpc_ssn: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OvationResponse {
+    /// Longitude, latitude, and aurora probability, on a one degree grid.
+    coordinates: Vec<[f64; 3]>,
+}
+
+/// The rows of an SWPC product, which are arrays whose first row names the columns.
+fn rows(product: &Value) -> Vec<HashMap<&str, &Value>> {
+    let mut rows = product.as_array().into_iter().flatten();
+
+    let header = match rows.next().and_then(Value::as_array) {
+        Some(header) => header.iter().filter_map(Value::as_str).collect::<Vec<_>>(),
+        None => return Vec::new(),
+    };
+
+    rows.filter_map(Value::as_array)
+        .map(|row| header.iter().copied().zip(row.iter()).collect())
+        .collect()
+}
+
+/// Reads a number from an SWPC product, which writes most of them as strings.
+fn number(value: &Value) -> Option<f64> {
+    match value {
+        Value::String(number) => number.parse().ok(),
+        number => number.as_f64(),
+    }
+}
+
+/// Parses SWPC's UTC times, which are written both with and without a `T`.
+fn parse_time(time: &str) -> Option<i64> {
+    ["%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%MZ"]
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(time, format).ok())
+        .map(|time| time.and_utc().timestamp())
+}
+
+/// Geomagnetic conditions and the aurora forecast.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpaceWeather {
+    /// The latest estimated planetary Kp index, with when it was measured.
+    pub current: (i64, f64),
+    /// Predicted planetary Kp indices over the next day, by the start of each three hour period.
+    pub predicted: Vec<(i64, f64)>,
+    pub aurora: AuroraForecast,
+}
+
+/// Indices describing the Sun's activity and the geomagnetic field, each with when it was
+/// measured, if SWPC reported it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SolarIndices {
+    /// The 10.7 cm solar flux index (SFI). Higher flux strengthens the ionosphere, opening up the
+    /// higher HF bands.
+    pub solar_flux: Option<(i64, f64)>,
+    pub sunspot_number: Option<(i64, f64)>,
+    /// The running planetary A index, the day's geomagnetic activity so far.
+    pub a_index: Option<(i64, f64)>,
+    /// The latest three hour planetary K index.
+    pub k_index: Option<(i64, f64)>,
+}
+
+/// The OVATION model's forecast of the chance of seeing an aurora overhead.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuroraForecast {
+    /// Percentage chances by whole degree of longitude (0 to 359) and latitude.
+    pub probabilities: HashMap<(i32, i32), u8>,
+}
+
+impl AuroraForecast {
+    /// The chance of an aurora overhead at a location, as a percentage.
+    pub fn probability_at(&self, location: &Location) -> u8 {
+        let longitude = (location.longitude.round() as i32).rem_euclid(360);
+        let latitude = location.latitude.round() as i32;
+
+        self.probabilities
+            .get(&(longitude, latitude))
+            .copied()
+            .unwrap_or(0)
+    }
+}
+
+/// The NOAA G-scale level of a geomagnetic storm at a Kp index, if it is a storm at all.
+pub fn storm_level(kp: f64) -> Option<u8> {
+    match kp {
+        kp if kp >= 9.0 => Some(5),
+        kp if kp >= 5.0 => Some(kp.floor() as u8 - 4),
+        _ => None,
+    }
+}
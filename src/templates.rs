@@ -0,0 +1,127 @@
+use crate::{error::BotError, i18n::tr};
+
+/// The placeholders notification templates can use, each replaced with a detail of the pass.
+pub const PLACEHOLDERS: &[&str] = &[
+    "name", "location", "start", "end", "duration", "max_el", "quality", "path", "id",
+];
+
+/// A piece of a template.
+#[derive(Debug, PartialEq)]
+enum Part<'a> {
+    Text(&'a str),
+    Placeholder(&'a str),
+}
+
+/// Splits a template into text and placeholders. Braces are written `{{` and `}}`. Returns `None`
+/// if a brace is left open or unmatched.
+fn parse(template: &str) -> Option<Vec<Part<'_>>> {
+    let mut parts = Vec::new();
+    let mut rest = template;
+
+    while let Some(index) = rest.find(['{', '}']) {
+        parts.push(Part::Text(&rest[..index]));
+        let brace = &rest[index..index + 1];
+        rest = &rest[index + 1..];
+
+        if rest.starts_with(brace) {
+            parts.push(Part::Text(brace));
+            rest = &rest[1..];
+        } else if brace == "{" {
+            let end = rest.find('}')?;
+            parts.push(Part::Placeholder(&rest[..end]));
+            rest = &rest[end + 1..];
+        } else {
+            return None;
+        }
+    }
+
+    parts.push(Part::Text(rest));
+    Some(parts)
+}
+
+/// Checks a template can be rendered, telling the user which placeholders they can use if not.
+pub fn check(locale: &str, template: &str, max_length: usize) -> anyhow::Result<()> {
+    if template.chars().count() > max_length {
+        return Err(
+            BotError::user(tr!(locale, "error-template-too-long", max = max_length)).into(),
+        );
+    }
+
+    let placeholders = PLACEHOLDERS
+        .iter()
+        .map(|placeholder| format!("`{{{}}}`", placeholder))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let parts = parse(template).ok_or_else(|| {
+        BotError::user(tr!(
+            locale,
+            "error-template-braces",
+            placeholders = placeholders.as_str()
+        ))
+    })?;
+
+    for part in parts {
+        if let Part::Placeholder(name) = part {
+            if !PLACEHOLDERS.contains(&name) {
+                return Err(BotError::user(tr!(
+                    locale,
+                    "error-unknown-placeholder",
+                    placeholder = name,
+                    placeholders = placeholders.as_str()
+                ))
+                .into());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Fills in a template's placeholders. Anything that cannot be filled in, which [`check`] stops
+/// being saved, is left as it was written.
+pub fn render(template: &str, value: impl Fn(&str) -> Option<String>) -> String {
+    let Some(parts) = parse(template) else {
+        return template.to_string();
+    };
+
+    parts
+        .into_iter()
+        .map(|part| match part {
+            Part::Text(text) => text.to_string(),
+            Part::Placeholder(name) => value(name).unwrap_or_else(|| format!("{{{}}}", name)),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn value(name: &str) -> Option<String> {
+        match name {
+            "name" => Some("NOAA 19".to_string()),
+            "max_el" => Some("81".to_string()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn fills_in_placeholders() {
+        assert_eq!(
+            render("{name} reaches {max_el}° {{wow}}", value),
+            "NOAA 19 reaches 81° {wow}"
+        );
+        assert_eq!(render("{other} pass", value), "{other} pass");
+        assert_eq!(render("{name", value), "{name");
+    }
+
+    #[test]
+    fn checks_templates() {
+        assert!(check("en-GB", "{name} at {location}, {start}", 256).is_ok());
+        assert!(check("en-GB", "{{}}", 256).is_ok());
+        assert!(check("en-GB", "{elevation}", 256).is_err());
+        assert!(check("en-GB", "{name", 256).is_err());
+        assert!(check("en-GB", "name}", 256).is_err());
+        assert!(check("en-GB", "{name}", 3).is_err());
+    }
+}
@@ -0,0 +1,131 @@
+//! Fakes and fixtures shared by the tests of the background tasks.
+
+use std::{collections::HashMap, sync::Mutex};
+
+use async_trait::async_trait;
+use serenity::json::Value;
+
+use crate::{
+    astro::Lighting,
+    database::{LocationName, SatelliteId, Snowflake, WatchedSatellite},
+    n2yo::{PassKind, SatellitePass},
+    notify::{DeliveryError, Notifier},
+};
+
+/// How the [`FakeNotifier`] fails to deliver to a channel.
+pub enum Failure {
+    Gone,
+    Transient,
+}
+
+/// Records everything sent, failing deliveries to the channels in `failures`.
+#[derive(Default)]
+pub struct FakeNotifier {
+    pub failures: HashMap<u64, Failure>,
+    pub sent: Mutex<Vec<(u64, Value)>>,
+    pub direct_messages: Mutex<Vec<u64>>,
+    pub ops_messages: Mutex<Vec<String>>,
+    pub threads: Mutex<Vec<(u64, u64, String)>>,
+    pub archived_threads: Mutex<Vec<u64>>,
+}
+
+impl FakeNotifier {
+    /// The number of embeds in each message sent, by channel.
+    pub fn sent_embeds(&self) -> Vec<(u64, usize)> {
+        self.sent
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(channel, message)| (*channel, message["embeds"].as_array().unwrap().len()))
+            .collect()
+    }
+}
+
+#[async_trait]
+impl Notifier for FakeNotifier {
+    async fn send_message(&self, channel: u64, message: &Value) -> Result<u64, DeliveryError> {
+        match self.failures.get(&channel) {
+            Some(Failure::Gone) => Err(DeliveryError::ChannelGone(anyhow::anyhow!("gone"))),
+            Some(Failure::Transient) => Err(DeliveryError::Failed(anyhow::anyhow!("unavailable"))),
+            None => {
+                let mut sent = self.sent.lock().unwrap();
+                sent.push((channel, message.clone()));
+                Ok(sent.len() as u64)
+            }
+        }
+    }
+
+    async fn direct_message(&self, user: u64, _message: &str) -> anyhow::Result<()> {
+        self.direct_messages.lock().unwrap().push(user);
+        Ok(())
+    }
+
+    async fn post_to_ops_channel(&self, message: &str) {
+        self.ops_messages.lock().unwrap().push(message.to_string());
+    }
+
+    async fn create_thread(&self, channel: u64, message: u64, name: &str) -> anyhow::Result<u64> {
+        let mut threads = self.threads.lock().unwrap();
+        threads.push((channel, message, name.to_string()));
+        Ok(1000 + threads.len() as u64)
+    }
+
+    async fn archive_thread(&self, thread: u64) -> anyhow::Result<()> {
+        self.archived_threads.lock().unwrap().push(thread);
+        Ok(())
+    }
+}
+
+/// A ten minute pass starting at `start`, peaking halfway through.
+pub fn pass(start: i64, max_elevation: f64) -> SatellitePass {
+    SatellitePass {
+        start_azimuth: 0.0,
+        start_azimuth_compass: "N".to_string(),
+        start_utc: start as usize,
+        max_azimuth: 90.0,
+        max_azimuth_compass: "E".to_string(),
+        max_elevation,
+        max_utc: (start + 300) as usize,
+        end_azimuth: 180.0,
+        end_azimuth_compass: "S".to_string(),
+        end_utc: (start + 600) as usize,
+        visible_fraction: None,
+        cloud_cover: None,
+        moon_separation: None,
+        magnitude: None,
+    }
+}
+
+/// A radio watch from the location `home`, set up by user `1000 + channel`.
+pub fn watch(satellite_id: usize, channel: u64) -> WatchedSatellite {
+    WatchedSatellite {
+        satellite_id: SatelliteId(satellite_id),
+        name: format!("SAT {}", satellite_id),
+        location: LocationName("home".to_string()),
+        channel: Snowflake(channel),
+        watcher: Snowflake(1000 + channel),
+        locale: "en-GB".to_string(),
+        min_max_elevation: 20.0,
+        previous_notifications: Vec::new(),
+        days_ahead: 1,
+        notify_within_hours: None,
+        pending_passes: Vec::new(),
+        broken: None,
+        guild: None,
+        timezone: None,
+        lighting: Lighting::Any,
+        min_quality: None,
+        show_transmitter: false,
+        reentry_alerted: false,
+        stale_tle_alerted: None,
+        new_tle_alerted: None,
+        kind: PassKind::Radio,
+        max_magnitude: None,
+        threads: false,
+        announced_passes: Vec::new(),
+        title_template: None,
+        description_template: None,
+        tags: Vec::new(),
+        paused: false,
+    }
+}
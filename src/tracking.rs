@@ -0,0 +1,337 @@
+use std::{collections::HashSet, sync::Arc, time::Duration};
+
+use serenity::{
+    builder::CreateEmbed,
+    http::Http,
+    model::application::interaction::{
+        message_component::MessageComponentInteraction, InteractionResponseType,
+    },
+};
+use tokio::sync::Mutex;
+use tracing::{info, warn};
+
+use crate::{
+    database::Location,
+    i18n::tr,
+    n2yo::SatellitePass,
+    propagation::{self, Orbit},
+    util::{self, TimeStyle},
+    ApplicationContext,
+};
+
+/// The start of the custom ID of "Track live" buttons.
+pub const BUTTON_PREFIX: &str = "track:";
+/// How often live tracking messages are updated.
+const UPDATE_INTERVAL: Duration = Duration::from_secs(15);
+/// How long before a pass tracking can be started, in seconds.
+const EARLIEST_START: i64 = 10 * 60;
+/// The number of characters in the progress bar.
+const PROGRESS_BAR_LENGTH: usize = 20;
+/// The longest custom ID Discord allows.
+const MAX_CUSTOM_ID_LENGTH: usize = 100;
+
+/// The pass a "Track live" button was pressed for.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct TrackedPass {
+    satellite_id: usize,
+    start: i64,
+    end: i64,
+    location: String,
+}
+
+impl TrackedPass {
+    fn parse(custom_id: &str) -> Option<Self> {
+        let mut parts = custom_id.strip_prefix(BUTTON_PREFIX)?.splitn(4, ':');
+
+        Some(Self {
+            satellite_id: parts.next()?.parse().ok()?,
+            start: parts.next()?.parse().ok()?,
+            end: parts.next()?.parse().ok()?,
+            location: parts.next()?.to_string(),
+        })
+    }
+}
+
+/// The custom ID of a "Track live" button for a pass, if it fits in one. Location names can be
+/// long enough that it does not.
+pub fn button_id(satellite_id: usize, pass: &SatellitePass, location: &str) -> Option<String> {
+    let id = format!(
+        "{}{}:{}:{}:{}",
+        BUTTON_PREFIX, satellite_id, pass.start_utc, pass.end_utc, location
+    );
+
+    (id.len() <= MAX_CUSTOM_ID_LENGTH).then_some(id)
+}
+
+/// Keeps track of the passes being tracked live, so that pressing the button again does not post
+/// a second message.
+#[derive(Default)]
+pub struct LiveTracker {
+    active: Mutex<HashSet<(u64, TrackedPass)>>,
+}
+
+/// Replies to a "Track live" button with a message that follows the satellite through its pass,
+/// updated until loss of signal.
+pub async fn start(
+    http: Arc<Http>,
+    data: &ApplicationContext,
+    component: &MessageComponentInteraction,
+) -> anyhow::Result<()> {
+    let locale = component.locale.clone();
+    let now = util::current_utc();
+
+    let Some(pass) = TrackedPass::parse(&component.data.custom_id) else {
+        return reply_ephemeral(&http, component, tr!(&locale, "error-unknown-pass")).await;
+    };
+
+    if pass.end < now {
+        return reply_ephemeral(&http, component, tr!(&locale, "error-pass-over")).await;
+    }
+
+    if pass.start - EARLIEST_START > now {
+        let refusal = tr!(
+            &locale,
+            "error-pass-too-early",
+            minutes = EARLIEST_START / 60
+        );
+        return reply_ephemeral(&http, component, refusal).await;
+    }
+
+    let channel = component.channel_id.0;
+
+    if !data
+        .live_tracker
+        .active
+        .lock()
+        .await
+        .insert((channel, pass.clone()))
+    {
+        return reply_ephemeral(&http, component, tr!(&locale, "error-already-tracking")).await;
+    }
+
+    let setup = async {
+        let location = data
+            .database
+            .read()
+            .await
+            .contents
+            .locations
+            .iter()
+            .find(|location| location.name.0 == pass.location)
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("no such location {}", pass.location))?;
+        let (orbit, name) = data.pass_provider.orbit(pass.satellite_id).await?;
+        anyhow::Ok((location, orbit, name))
+    };
+
+    let (location, orbit, name) = match setup.await {
+        Ok(setup) => setup,
+        Err(e) => {
+            data.live_tracker
+                .active
+                .lock()
+                .await
+                .remove(&(channel, pass.clone()));
+            warn!("Failed to start tracking {:?}: {}", pass, e);
+            return reply_ephemeral(&http, component, tr!(&locale, "error-tracking-unavailable"))
+                .await;
+        }
+    };
+
+    let tracking = LiveTrack {
+        pass: pass.clone(),
+        name,
+        location,
+        orbit,
+        locale,
+    };
+
+    component
+        .create_interaction_response(&http, |r| {
+            r.kind(InteractionResponseType::ChannelMessageWithSource)
+                .interaction_response_data(|d| {
+                    d.embed(|e| {
+                        tracking.embed(e, util::current_utc());
+                        e
+                    })
+                })
+        })
+        .await?;
+    let mut message = component.get_interaction_response(&http).await?;
+    info!(
+        "Tracking {} live in channel {} until {}",
+        tracking.name, channel, pass.end
+    );
+
+    let live_tracker = data.live_tracker.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(UPDATE_INTERVAL);
+        interval.tick().await;
+
+        loop {
+            interval.tick().await;
+            let now = util::current_utc();
+
+            if let Err(e) = message
+                .edit(&http, |m| {
+                    m.embed(|e| {
+                        tracking.embed(e, now);
+                        e
+                    })
+                })
+                .await
+            {
+                warn!("Failed to update live tracking of {}: {}", tracking.name, e);
+                break;
+            }
+
+            if now > tracking.pass.end {
+                break;
+            }
+        }
+
+        live_tracker
+            .active
+            .lock()
+            .await
+            .remove(&(channel, tracking.pass));
+    });
+
+    Ok(())
+}
+
+async fn reply_ephemeral(
+    http: &Http,
+    component: &MessageComponentInteraction,
+    content: String,
+) -> anyhow::Result<()> {
+    component
+        .create_interaction_response(http, |r| {
+            r.kind(InteractionResponseType::ChannelMessageWithSource)
+                .interaction_response_data(|d| d.content(content).ephemeral(true))
+        })
+        .await?;
+    Ok(())
+}
+
+/// A pass being followed live.
+struct LiveTrack {
+    pass: TrackedPass,
+    name: String,
+    location: Location,
+    orbit: Orbit,
+    locale: String,
+}
+
+impl LiveTrack {
+    fn embed(&self, e: &mut CreateEmbed, now: i64) {
+        let locale = &self.locale;
+        e.title(tr!(
+            locale,
+            "tracking-title",
+            satellite = self.name.as_str(),
+            location = self.location.name.0.as_str()
+        ));
+
+        if now > self.pass.end {
+            e.description(tr!(
+                locale,
+                "tracking-over",
+                time = format!("<t:{}:t>", self.pass.end)
+            ));
+            return;
+        }
+
+        let mut description = progress_bar(self.pass.start, self.pass.end, now);
+        description.push('\n');
+        description.push_str(&if now < self.pass.start {
+            tr!(
+                locale,
+                "tracking-rises",
+                time = util::format_time(self.pass.start, TimeStyle::Discord)
+            )
+        } else {
+            tr!(
+                locale,
+                "tracking-sets",
+                time = util::format_time(self.pass.end, TimeStyle::Discord)
+            )
+        });
+        e.description(description);
+
+        match self.orbit.look_angles(&self.location, now) {
+            Ok(look_angles) => {
+                e.field(
+                    tr!(locale, "tracking-azimuth"),
+                    format!(
+                        "{:.0}° ({})",
+                        look_angles.azimuth,
+                        propagation::compass(look_angles.azimuth)
+                    ),
+                    true,
+                );
+                e.field(
+                    tr!(locale, "tracking-elevation"),
+                    format!("{:.0}°", look_angles.elevation),
+                    true,
+                );
+                e.field(
+                    tr!(locale, "tracking-range"),
+                    format!("{:.0} km", look_angles.range),
+                    true,
+                );
+            }
+            Err(e) => warn!("Failed to propagate {}: {}", self.name, e),
+        }
+    }
+}
+
+/// A bar showing how far through a pass a time is, with the percentage.
+fn progress_bar(start: i64, end: i64, now: i64) -> String {
+    let progress = ((now - start) as f64 / (end - start).max(1) as f64).clamp(0.0, 1.0);
+    let filled = (progress * PROGRESS_BAR_LENGTH as f64).round() as usize;
+
+    format!(
+        "{}{} {:.0}%",
+        "█".repeat(filled),
+        "░".repeat(PROGRESS_BAR_LENGTH - filled),
+        progress * 100.0
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing;
+
+    #[test]
+    fn reads_button_ids() {
+        let pass = testing::pass(1000, 45.0);
+        let id = button_id(33591, &pass, "home: garden").unwrap();
+
+        assert_eq!(
+            TrackedPass::parse(&id),
+            Some(TrackedPass {
+                satellite_id: 33591,
+                start: 1000,
+                end: 1600,
+                location: "home: garden".to_string(),
+            })
+        );
+        assert_eq!(button_id(33591, &pass, &"x".repeat(100)), None);
+        assert_eq!(TrackedPass::parse("help-1-0"), None);
+    }
+
+    #[test]
+    fn draws_progress() {
+        assert_eq!(progress_bar(0, 100, -10), format!("{} 0%", "░".repeat(20)));
+        assert_eq!(
+            progress_bar(0, 100, 25),
+            format!("{}{} 25%", "█".repeat(5), "░".repeat(15))
+        );
+        assert_eq!(
+            progress_bar(0, 100, 200),
+            format!("{} 100%", "█".repeat(20))
+        );
+    }
+}
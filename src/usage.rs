@@ -0,0 +1,216 @@
+use std::{cmp::Reverse, collections::HashMap, hash::Hash, sync::Arc, time::Duration};
+
+use serde::{Deserialize, Serialize};
+use serenity::http::Http;
+use tokio::{sync::RwLock, task::JoinHandle, time::interval};
+use tracing::info;
+
+use crate::{
+    database::{Database, SatelliteId, Snowflake},
+    util,
+};
+
+const WEEK: i64 = 7 * 24 * 60 * 60;
+/// How many weeks of totals are kept for trends.
+const KEPT_WEEKS: usize = 12;
+
+/// Long-term usage counters, kept in the database so that they survive restarts.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UsageStats {
+    pub notifications_per_satellite: HashMap<SatelliteId, usize>,
+    pub notifications_per_channel: HashMap<Snowflake, usize>,
+    pub command_invocations: HashMap<String, usize>,
+    /// Totals for each week, oldest first.
+    pub weeks: Vec<WeeklyUsage>,
+    /// When the last weekly summary was posted.
+    pub last_summary_at: Option<i64>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WeeklyUsage {
+    /// The start of the week, at midnight UTC on Monday.
+    pub week_start: i64,
+    pub notifications: usize,
+    pub command_invocations: usize,
+}
+
+impl UsageStats {
+    pub fn record_notifications(
+        &mut self,
+        satellite_id: &SatelliteId,
+        channel: &Snowflake,
+        count: usize,
+    ) {
+        *self
+            .notifications_per_satellite
+            .entry(satellite_id.clone())
+            .or_default() += count;
+        *self
+            .notifications_per_channel
+            .entry(channel.clone())
+            .or_default() += count;
+        self.current_week().notifications += count;
+    }
+
+    pub fn record_command(&mut self, name: &str) {
+        *self
+            .command_invocations
+            .entry(name.to_string())
+            .or_default() += 1;
+        self.current_week().command_invocations += 1;
+    }
+
+    /// The totals for the current week so far.
+    pub fn this_week(&self) -> WeeklyUsage {
+        self.week(week_start(util::current_utc()))
+    }
+
+    /// The totals for the week before the current one.
+    pub fn last_week(&self) -> WeeklyUsage {
+        self.week(week_start(util::current_utc()) - WEEK)
+    }
+
+    fn week(&self, start: i64) -> WeeklyUsage {
+        self.weeks
+            .iter()
+            .find(|week| week.week_start == start)
+            .cloned()
+            .unwrap_or(WeeklyUsage {
+                week_start: start,
+                ..Default::default()
+            })
+    }
+
+    fn current_week(&mut self) -> &mut WeeklyUsage {
+        let start = week_start(util::current_utc());
+
+        if self.weeks.last().map(|week| week.week_start) != Some(start) {
+            self.weeks.push(WeeklyUsage {
+                week_start: start,
+                ..Default::default()
+            });
+
+            if self.weeks.len() > KEPT_WEEKS {
+                self.weeks.remove(0);
+            }
+        }
+
+        self.weeks.last_mut().unwrap()
+    }
+}
+
+/// The start of the week containing a unix timestamp, at midnight UTC on Monday.
+pub fn week_start(time: i64) -> i64 {
+    // The unix epoch was a Thursday, so the first Monday was four days later
+    const FIRST_MONDAY: i64 = 4 * 24 * 60 * 60;
+    (time - FIRST_MONDAY).div_euclid(WEEK) * WEEK + FIRST_MONDAY
+}
+
+/// The entries with the highest counts, highest first.
+fn top<K: Clone + Eq + Hash>(counts: &HashMap<K, usize>, limit: usize) -> Vec<(K, usize)> {
+    let mut entries = counts
+        .iter()
+        .map(|(key, count)| (key.clone(), *count))
+        .collect::<Vec<_>>();
+    entries.sort_by_key(|(_, count)| Reverse(*count));
+    entries.truncate(limit);
+    entries
+}
+
+/// The most notified satellites, by name where known, with their notification counts.
+pub fn top_satellites(database: &Database, limit: usize) -> Vec<String> {
+    top(&database.contents.usage.notifications_per_satellite, limit)
+        .into_iter()
+        .map(|(id, count)| {
+            let name = database
+                .contents
+                .satellites
+                .get(&id)
+                .map(|metadata| metadata.name.clone())
+                .unwrap_or(id.0.to_string());
+            format!("{} ({})", name, count)
+        })
+        .collect()
+}
+
+/// The most used commands, with their invocation counts.
+pub fn top_commands(usage: &UsageStats, limit: usize) -> Vec<String> {
+    top(&usage.command_invocations, limit)
+        .into_iter()
+        .map(|(name, count)| format!("/{} ({})", name, count))
+        .collect()
+}
+
+/// Describes the change between two totals, e.g. `12 (+50% on last week)`.
+pub fn trend(current: usize, previous: usize) -> String {
+    if previous == 0 {
+        current.to_string()
+    } else {
+        let change = (current as f64 - previous as f64) / previous as f64 * 100.0;
+        format!("{} ({:+.0}% on last week)", current, change)
+    }
+}
+
+/// Spawns the task that posts a summary of the past week's usage to the ops channel.
+pub fn spawn_summary_task(http: Arc<Http>, database: Arc<RwLock<Database>>) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = interval(Duration::from_secs(60 * 60));
+
+        loop {
+            interval.tick().await;
+
+            if util::ops_channel().is_none() {
+                continue;
+            }
+
+            let summary = {
+                let mut database = database.write().await;
+                let now = util::current_utc();
+
+                match database.contents.usage.last_summary_at {
+                    Some(last) if now - last < WEEK => continue,
+                    Some(_) => {}
+                    None => {
+                        // Start counting from now rather than posting straight away
+                        database.contents.usage.last_summary_at = Some(now);
+                        database.mark_dirty();
+                        continue;
+                    }
+                }
+
+                database.contents.usage.last_summary_at = Some(now);
+                database.mark_dirty();
+                summarise(&database)
+            };
+
+            info!("Posting weekly usage summary");
+            util::post_to_ops_channel(&http, &summary).await;
+        }
+    })
+}
+
+fn summarise(database: &Database) -> String {
+    let usage = &database.contents.usage;
+    // The summary is posted during the following week, so compare the last two full weeks
+    let previous = usage.week(week_start(util::current_utc()) - 2 * WEEK);
+    let last = usage.last_week();
+
+    let satellites = top_satellites(database, 5);
+    let commands = top_commands(usage, 5);
+
+    format!(
+        "**Weekly summary**\nNotifications: {}\nCommands: {}\nMost notified satellites: {}\nMost used commands: {}",
+        trend(last.notifications, previous.notifications),
+        trend(last.command_invocations, previous.command_invocations),
+        if satellites.is_empty() {
+            "none".to_string()
+        } else {
+            satellites.join(", ")
+        },
+        if commands.is_empty() {
+            "none".to_string()
+        } else {
+            commands.join(", ")
+        }
+    )
+}
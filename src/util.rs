@@ -1,39 +1,78 @@
-use std::path::PathBuf;
+use serenity::{
+    http::{Http, HttpError},
+    model::id::ChannelId,
+};
+use tracing::error;
 
-use anyhow::Context as _;
-use tracing::info;
+use crate::{config, preferences::TimeFormat};
 
-pub fn load_env_file() -> anyhow::Result<()> {
-    info!("Loading .env file");
-    let path = PathBuf::from(".env");
+/// Identifies the bot to the APIs it uses, with a way to get in touch about its requests.
+pub const USER_AGENT: &str = "sat-bot (james@jamalam.tech)";
 
-    if path.exists() {
-        for line in std::fs::read_to_string(&path)?.lines() {
-            if line.trim().starts_with('#') || line.trim().is_empty() {
-                continue;
-            }
+/// The channel operational messages are posted to, if configured.
+pub fn ops_channel() -> Option<ChannelId> {
+    config::get().discord.ops_channel_id.map(ChannelId)
+}
 
-            let mut parts = line.splitn(2, '=');
-            let name = parts.next().unwrap().trim();
-            let value = parts.next().unwrap().trim();
-            std::env::set_var(name, value);
+/// Posts a message to the ops channel, if one is configured.
+pub async fn post_to_ops_channel(http: &Http, message: &str) {
+    if let Some(channel) = ops_channel() {
+        if let Err(e) = channel.say(http, message).await {
+            error!("Failed to post to ops channel: {}", e);
         }
     }
+}
+
+/// Whether an error from Discord means a channel has been deleted or can no longer be accessed.
+pub fn is_dead_channel_error(error: &serenity::Error) -> bool {
+    const UNKNOWN_CHANNEL: isize = 10003;
+    const MISSING_ACCESS: isize = 50001;
 
-    Ok(())
+    match error {
+        serenity::Error::Http(error) => match error.as_ref() {
+            HttpError::UnsuccessfulRequest(response) => {
+                matches!(response.error.code, UNKNOWN_CHANNEL | MISSING_ACCESS)
+            }
+            _ => false,
+        },
+        _ => false,
+    }
 }
 
-pub fn env(name: &str) -> anyhow::Result<String> {
-    std::env::var(name).with_context(|| format!("missing environment variable {}", name))
+/// How times are written in messages.
+#[derive(Debug, Clone, Copy)]
+pub enum TimeStyle<'a> {
+    /// Discord timestamp markup, shown in each viewer's own timezone and clock format.
+    Discord,
+    /// Plain text, for embed titles, field names and footers, where Discord does not render
+    /// timestamps, and for anything read outside of Discord.
+    Plain(&'a TimeFormat),
 }
 
-pub fn format_pass_time(start: i64, end: i64) -> String {
-    format!(
-        "<t:{}> - <t:{}:t> ({})",
-        start,
-        end,
-        duration_between(start, end)
-    )
+/// Writes when a pass starts and ends, and how long it lasts.
+pub fn format_pass_time(start: i64, end: i64, style: TimeStyle) -> String {
+    match style {
+        TimeStyle::Discord => format!(
+            "<t:{}> - <t:{}:t> ({})",
+            start,
+            end,
+            duration_between(start, end)
+        ),
+        TimeStyle::Plain(format) => format!(
+            "{} - {} ({})",
+            format.date_time(start),
+            format.time(end),
+            duration_between(start, end)
+        ),
+    }
+}
+
+/// Writes a point in time, relative to now where Discord can render it.
+pub fn format_time(time: i64, style: TimeStyle) -> String {
+    match style {
+        TimeStyle::Discord => format!("<t:{}:R>", time),
+        TimeStyle::Plain(format) => format.date_time(time),
+    }
 }
 
 pub fn duration_between(a: i64, b: i64) -> String {
@@ -46,11 +85,93 @@ pub fn duration_between(a: i64, b: i64) -> String {
     format!("{}m {}s", minutes, seconds)
 }
 
+/// Shortens text to at most a number of characters, ending it with an ellipsis if cut.
+pub fn truncate(text: &str, length: usize) -> String {
+    if text.chars().count() <= length {
+        text.to_string()
+    } else {
+        let mut truncated = text.chars().take(length - 1).collect::<String>();
+        truncated.push('…');
+        truncated
+    }
+}
+
 pub fn are_within_10_seconds(a: i64, b: i64) -> bool {
     let duration = chrono::Duration::seconds(b - a);
     duration.num_seconds().abs() < 10
 }
 
+/// A pseudo-random number in `[0, 1)`, good enough for spreading out retries.
+pub fn jitter() -> f64 {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|duration| duration.subsec_nanos())
+        .unwrap_or_default();
+    (nanos % 1_000_000) as f64 / 1_000_000.0
+}
+
+/// Writes a unix timestamp in ISO 8601 UTC, such as `2024-01-01T12:00:00Z`.
+pub fn iso_time(time: i64) -> anyhow::Result<String> {
+    Ok(chrono::DateTime::from_timestamp(time, 0)
+        .ok_or_else(|| anyhow::anyhow!("invalid timestamp"))?
+        .format("%Y-%m-%dT%H:%M:%SZ")
+        .to_string())
+}
+
+/// Writes a unix timestamp in the basic UTC format of iCalendar, such as `20240101T120000Z`.
+pub fn calendar_time(time: i64) -> anyhow::Result<String> {
+    Ok(chrono::DateTime::from_timestamp(time, 0)
+        .ok_or_else(|| anyhow::anyhow!("invalid timestamp"))?
+        .format("%Y%m%dT%H%M%SZ")
+        .to_string())
+}
+
+/// Reads a duration such as `90m`, `2h`, or `1d12h`, in seconds. Units are `m`, `h`, `d`, and `w`.
+pub fn parse_duration(text: &str) -> Option<i64> {
+    let mut total = 0i64;
+    let mut number = String::new();
+
+    for c in text
+        .trim()
+        .to_lowercase()
+        .chars()
+        .filter(|c| !c.is_whitespace())
+    {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+
+        let unit = match c {
+            'm' => 60,
+            'h' => 60 * 60,
+            'd' => 24 * 60 * 60,
+            'w' => 7 * 24 * 60 * 60,
+            _ => return None,
+        };
+        total = total.checked_add(number.parse::<i64>().ok()?.checked_mul(unit)?)?;
+        number.clear();
+    }
+
+    (number.is_empty() && total > 0).then_some(total)
+}
+
 pub fn current_utc() -> i64 {
     chrono::Utc::now().timestamp()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_duration("90m"), Some(90 * 60));
+        assert_eq!(parse_duration("1d 12h"), Some(36 * 60 * 60));
+        assert_eq!(parse_duration("2W"), Some(14 * 24 * 60 * 60));
+        assert_eq!(parse_duration("12"), None);
+        assert_eq!(parse_duration("h"), None);
+        assert_eq!(parse_duration("0h"), None);
+        assert_eq!(parse_duration("soon"), None);
+    }
+}
@@ -0,0 +1,118 @@
+use std::{collections::HashMap, sync::Mutex};
+
+use serde::Deserialize;
+use tracing::info;
+
+use crate::{database::Location, mock, util};
+
+/// How long a forecast is reused for the same location, in seconds.
+const FORECAST_TTL: i64 = 30 * 60;
+/// The furthest ahead Open-Meteo forecasts, in days.
+const MAX_FORECAST_DAYS: usize = 16;
+/// How far from a forecast hour a time may be and still use it, in seconds.
+const MAX_FORECAST_DISTANCE: i64 = 60 * 60;
+
+/// A client for the Open-Meteo forecast API, which needs no key.
+pub struct WeatherAPI {
+    client: reqwest::Client,
+    /// Forecasts by location, with when they were fetched.
+    cache: Mutex<HashMap<String, (i64, CloudCoverForecast)>>,
+    /// Whether canned forecasts are returned instead of calling Open-Meteo.
+    mock: bool,
+}
+
+impl WeatherAPI {
+    pub fn new() -> anyhow::Result<Self> {
+        info!("Creating Open-Meteo API client");
+        Ok(Self {
+            client: reqwest::ClientBuilder::new()
+                .user_agent(util::USER_AGENT)
+                .build()?,
+            cache: Mutex::new(HashMap::new()),
+            mock: mock::is_enabled(),
+        })
+    }
+
+    /// Gets the hourly cloud cover forecast at a location for the next few days.
+    pub async fn get_cloud_cover(
+        &self,
+        location: &Location,
+        days: usize,
+    ) -> anyhow::Result<CloudCoverForecast> {
+        if self.mock {
+            return Ok(mock::cloud_cover(days));
+        }
+
+        let key = format!(
+            "{:.2},{:.2},{}",
+            location.latitude, location.longitude, days
+        );
+
+        if let Some((fetched_at, forecast)) = self.cache.lock().unwrap().get(&key) {
+            if util::current_utc() - fetched_at < FORECAST_TTL {
+                return Ok(forecast.clone());
+            }
+        }
+
+        let url = format!(
+            "https://api.open-meteo.com/v1/forecast?latitude={}&longitude={}&hourly=cloud_cover&timeformat=unixtime&forecast_days={}",
+            location.latitude,
+            location.longitude,
+            (days + 1).min(MAX_FORECAST_DAYS)
+        );
+
+        info!("Sending request to {}", url);
+
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<ForecastResponse>()
+            .await?;
+        let forecast = CloudCoverForecast {
+            hours: response
+                .hourly
+                .time
+                .into_iter()
+                .zip(response.hourly.cloud_cover)
+                .filter_map(|(time, cover)| Some((time, cover?)))
+                .collect(),
+        };
+
+        self.cache
+            .lock()
+            .unwrap()
+            .insert(key, (util::current_utc(), forecast.clone()));
+        Ok(forecast)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ForecastResponse {
+    hourly: HourlyForecast,
+}
+
+#[derive(Debug, Deserialize)]
+struct HourlyForecast {
+    time: Vec<i64>,
+    cloud_cover: Vec<Option<f64>>,
+}
+
+/// Forecast total cloud cover, as percentages at the start of each hour.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CloudCoverForecast {
+    pub hours: Vec<(i64, f64)>,
+}
+
+impl CloudCoverForecast {
+    /// The forecast cloud cover closest to a unix timestamp, if the forecast covers it.
+    pub fn at(&self, time: i64) -> Option<u8> {
+        self.hours
+            .iter()
+            .min_by_key(|(hour, _)| (hour - time).abs())
+            .filter(|(hour, _)| (hour - time).abs() <= MAX_FORECAST_DISTANCE)
+            .map(|(_, cover)| cover.round().clamp(0.0, 100.0) as u8)
+    }
+}